@@ -0,0 +1,4 @@
+-- Add down migration script here
+
+DROP TABLE IF EXISTS services.farm_member_invitations;
+DROP TABLE IF EXISTS services.farm_members;
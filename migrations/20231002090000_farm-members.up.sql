@@ -0,0 +1,31 @@
+-- Add up migration script here
+
+-- Farm members table, users allowed to manage the farm
+-- role must be either owner, manager or listing_editor.
+CREATE TABLE IF NOT EXISTS services.farm_members(
+    id uuid PRIMARY KEY,
+    farm_id uuid REFERENCES services.farms (id) ON DELETE CASCADE NOT NULL,
+    user_id uuid REFERENCES accounts.users (id) ON DELETE CASCADE NOT NULL,
+    role text NOT NULL CHECK (role IN ('owner', 'manager', 'listing_editor')),
+    joined_at timestamptz NOT NULL,
+    UNIQUE(farm_id, user_id)
+);
+
+-- Pending invitations sent by email to join a farm
+CREATE TABLE IF NOT EXISTS services.farm_member_invitations(
+    id uuid PRIMARY KEY,
+    farm_id uuid REFERENCES services.farms (id) ON DELETE CASCADE NOT NULL,
+    invited_by uuid REFERENCES accounts.users (id) ON DELETE CASCADE NOT NULL,
+    email text NOT NULL,
+    role text NOT NULL CHECK (role IN ('manager', 'listing_editor')),
+    token bytea UNIQUE NOT NULL, -- token hash
+    created_at timestamptz NOT NULL,
+    UNIQUE(farm_id, email)
+);
+
+-- Existing farm owners become the owner members of their farms
+INSERT INTO services.farm_members(id, farm_id, user_id, role, joined_at)
+SELECT gen_random_uuid(), farm.id, farm.owner_id, 'owner', farm.registered_on::timestamptz
+FROM services.farms farm
+WHERE farm.owner_id IS NOT NULL
+ON CONFLICT DO NOTHING;
@@ -0,0 +1,4 @@
+-- Add down migration script here
+
+DROP TABLE IF EXISTS services.farm_ownership_transfer_logs;
+DROP TABLE IF EXISTS services.farm_ownership_transfers;
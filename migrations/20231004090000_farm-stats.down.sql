@@ -0,0 +1,15 @@
+-- Add down migration script here
+
+DROP TRIGGER IF EXISTS locations_stats ON services.locations;
+DROP TRIGGER IF EXISTS harvests_stats ON services.harvests;
+DROP TRIGGER IF EXISTS farm_ratings_stats ON services.farm_ratings;
+DROP TRIGGER IF EXISTS farms_stats ON services.farms;
+
+DROP FUNCTION IF EXISTS services.locations_stats_trigger();
+DROP FUNCTION IF EXISTS services.harvests_stats_trigger();
+DROP FUNCTION IF EXISTS services.farm_ratings_stats_trigger();
+DROP FUNCTION IF EXISTS services.farms_stats_trigger();
+DROP FUNCTION IF EXISTS services.refresh_farm_harvest_stats(uuid);
+DROP FUNCTION IF EXISTS services.refresh_farm_rating_stats(uuid);
+
+DROP TABLE IF EXISTS services.farm_stats;
@@ -0,0 +1,119 @@
+-- Add up migration script here
+
+-- Farm aggregate stats, kept up to date by the triggers below
+-- so farm responses don't have to aggregate ratings and harvests.
+CREATE TABLE IF NOT EXISTS services.farm_stats(
+    farm_id uuid PRIMARY KEY REFERENCES services.farms (id) ON DELETE CASCADE,
+    rating_average double precision, -- null when the farm has no ratings
+    rating_count bigint NOT NULL DEFAULT 0,
+    harvest_count bigint NOT NULL DEFAULT 0, -- active harvests count
+    updated_at timestamptz NOT NULL DEFAULT now()
+);
+
+-- ===== FUNCTIONS =====
+
+-- Recompute the farm rating average and count
+CREATE OR REPLACE FUNCTION services.refresh_farm_rating_stats(target_farm_id uuid)
+RETURNS void AS $$
+    UPDATE services.farm_stats stat
+    SET rating_average = rating_stat.average,
+        rating_count = rating_stat.count,
+        updated_at = now()
+    FROM (
+        SELECT AVG(rating.grade)::double precision AS average,
+            COUNT(rating.id) AS count
+        FROM services.farm_ratings rating
+        WHERE rating.farm_id = target_farm_id
+            AND rating.grade IS NOT NULL
+    ) rating_stat
+    WHERE stat.farm_id = target_farm_id;
+$$ LANGUAGE sql;
+
+-- Recompute the farm active harvests count
+CREATE OR REPLACE FUNCTION services.refresh_farm_harvest_stats(target_farm_id uuid)
+RETURNS void AS $$
+    UPDATE services.farm_stats stat
+    SET harvest_count = (
+            SELECT COUNT(harvest.id)
+            FROM services.active_harvests harvest
+            INNER JOIN services.active_locations location_
+                ON harvest.location_id = location_.id
+            WHERE location_.farm_id = target_farm_id
+        ),
+        updated_at = now()
+    WHERE stat.farm_id = target_farm_id;
+$$ LANGUAGE sql;
+
+CREATE OR REPLACE FUNCTION services.farms_stats_trigger()
+RETURNS trigger AS $$
+BEGIN
+    INSERT INTO services.farm_stats(farm_id) VALUES(NEW.id)
+    ON CONFLICT DO NOTHING;
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE OR REPLACE FUNCTION services.farm_ratings_stats_trigger()
+RETURNS trigger AS $$
+BEGIN
+    IF TG_OP IN ('UPDATE', 'DELETE') THEN
+        PERFORM services.refresh_farm_rating_stats(OLD.farm_id);
+    END IF;
+    IF TG_OP IN ('INSERT', 'UPDATE') THEN
+        PERFORM services.refresh_farm_rating_stats(NEW.farm_id);
+    END IF;
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE OR REPLACE FUNCTION services.harvests_stats_trigger()
+RETURNS trigger AS $$
+BEGIN
+    IF TG_OP IN ('UPDATE', 'DELETE') THEN
+        PERFORM services.refresh_farm_harvest_stats(location_.farm_id)
+        FROM services.locations location_
+        WHERE location_.id = OLD.location_id;
+    END IF;
+    IF TG_OP IN ('INSERT', 'UPDATE') THEN
+        PERFORM services.refresh_farm_harvest_stats(location_.farm_id)
+        FROM services.locations location_
+        WHERE location_.id = NEW.location_id;
+    END IF;
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE OR REPLACE FUNCTION services.locations_stats_trigger()
+RETURNS trigger AS $$
+BEGIN
+    PERFORM services.refresh_farm_harvest_stats(OLD.farm_id);
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+-- ===== TRIGGERS =====
+
+CREATE TRIGGER farms_stats
+AFTER INSERT ON services.farms
+FOR EACH ROW EXECUTE FUNCTION services.farms_stats_trigger();
+
+CREATE TRIGGER farm_ratings_stats
+AFTER INSERT OR UPDATE OF grade, farm_id OR DELETE ON services.farm_ratings
+FOR EACH ROW EXECUTE FUNCTION services.farm_ratings_stats_trigger();
+
+CREATE TRIGGER harvests_stats
+AFTER INSERT OR UPDATE OF finished, location_id OR DELETE ON services.harvests
+FOR EACH ROW EXECUTE FUNCTION services.harvests_stats_trigger();
+
+CREATE TRIGGER locations_stats
+AFTER UPDATE OF deleted OR DELETE ON services.locations
+FOR EACH ROW EXECUTE FUNCTION services.locations_stats_trigger();
+
+-- Backfill existing farms stats
+INSERT INTO services.farm_stats(farm_id)
+SELECT farm.id FROM services.farms farm
+ON CONFLICT DO NOTHING;
+
+SELECT services.refresh_farm_rating_stats(farm.id),
+    services.refresh_farm_harvest_stats(farm.id)
+FROM services.farms farm;
@@ -0,0 +1,23 @@
+-- Add down migration script here
+
+DROP FUNCTION IF EXISTS services.location_schedule(uuid);
+DROP FUNCTION IF EXISTS services.location_open_today(uuid);
+DROP FUNCTION IF EXISTS services.location_open_now(uuid);
+DROP FUNCTION IF EXISTS services.location_open_on(uuid, date);
+DROP FUNCTION IF EXISTS services.location_open_at(uuid, timestamp);
+DROP FUNCTION IF EXISTS services.location_hours_on(uuid, date);
+DROP FUNCTION IF EXISTS services.location_local_now(uuid);
+
+DROP TABLE IF EXISTS services.location_holidays;
+DROP TABLE IF EXISTS services.location_opening_hours;
+
+DROP VIEW IF EXISTS services.active_locations;
+ALTER TABLE services.locations
+    DROP COLUMN IF EXISTS pickup_instructions,
+    DROP COLUMN IF EXISTS timezone;
+
+CREATE VIEW services.active_locations AS (
+	SELECT *
+	FROM services.locations location_
+	WHERE location_.deleted = false
+);
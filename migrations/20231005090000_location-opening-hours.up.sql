@@ -0,0 +1,168 @@
+-- Add up migration script here
+
+-- Location timezone (IANA name) used to compute the local opening hours
+ALTER TABLE services.locations
+    ADD COLUMN IF NOT EXISTS timezone text NOT NULL DEFAULT 'UTC',
+    ADD COLUMN IF NOT EXISTS pickup_instructions text;
+
+CREATE OR REPLACE VIEW services.active_locations AS (
+	SELECT *
+	FROM services.locations location_
+	WHERE location_.deleted = false
+);
+
+-- Location weekly opening hours,
+-- weekday is the ISO day of the week: 1 = Monday ... 7 = Sunday.
+-- Opening hours closing before they open wrap past midnight
+-- into the next day, e.g. 22:00 - 02:00.
+CREATE TABLE IF NOT EXISTS services.location_opening_hours(
+    id uuid PRIMARY KEY,
+    location_id uuid REFERENCES services.locations (id) ON DELETE CASCADE NOT NULL,
+    weekday smallint NOT NULL CHECK (weekday >= 1 AND weekday <= 7),
+    opens_at time NOT NULL,
+    closes_at time NOT NULL CHECK (closes_at <> opens_at)
+);
+
+CREATE INDEX IF NOT EXISTS location_opening_hours_location_id_idx
+    ON services.location_opening_hours (location_id);
+
+-- Location holiday exceptions, the opening hours on the date
+-- replace the weekly opening hours, null opening hours means closed.
+CREATE TABLE IF NOT EXISTS services.location_holidays(
+    id uuid PRIMARY KEY,
+    location_id uuid REFERENCES services.locations (id) ON DELETE CASCADE NOT NULL,
+    date date NOT NULL,
+    name text,
+    opens_at time,
+    closes_at time,
+    CHECK (
+        (opens_at IS NULL AND closes_at IS NULL)
+        OR (opens_at IS NOT NULL AND closes_at <> opens_at)
+    ),
+    UNIQUE(location_id, date)
+);
+
+-- ===== FUNCTIONS =====
+
+-- Current local time at the location
+CREATE OR REPLACE FUNCTION services.location_local_now(target_location_id uuid)
+RETURNS timestamp AS $$
+    SELECT now() AT TIME ZONE location_.timezone
+    FROM services.locations location_
+    WHERE location_.id = target_location_id;
+$$ LANGUAGE sql STABLE;
+
+-- Location opening hours on the local date, the holiday
+-- opening hours replace the weekly opening hours.
+CREATE OR REPLACE FUNCTION services.location_hours_on(target_location_id uuid, local_date date)
+RETURNS TABLE(opens_at time, closes_at time) AS $$
+    SELECT holiday.opens_at, holiday.closes_at
+    FROM services.location_holidays holiday
+    WHERE holiday.location_id = target_location_id
+        AND holiday.date = local_date
+        AND holiday.opens_at IS NOT NULL
+    UNION ALL
+    SELECT hours.opens_at, hours.closes_at
+    FROM services.location_opening_hours hours
+    WHERE hours.location_id = target_location_id
+        AND hours.weekday = EXTRACT(ISODOW FROM local_date)
+        AND NOT EXISTS(
+            SELECT 1
+            FROM services.location_holidays holiday
+            WHERE holiday.location_id = target_location_id
+                AND holiday.date = local_date
+        );
+$$ LANGUAGE sql STABLE;
+
+-- Checks if the location is open at the local time,
+-- including the previous day's hours open past midnight.
+CREATE OR REPLACE FUNCTION services.location_open_at(target_location_id uuid, local_at timestamp)
+RETURNS boolean AS $$
+    SELECT EXISTS(
+        SELECT 1
+        FROM services.location_hours_on(target_location_id, local_at::date) hours
+        WHERE local_at::time >= hours.opens_at
+            AND (local_at::time < hours.closes_at OR hours.closes_at < hours.opens_at)
+    ) OR EXISTS(
+        SELECT 1
+        FROM services.location_hours_on(target_location_id, (local_at - interval '1 day')::date) hours
+        WHERE hours.closes_at < hours.opens_at
+            AND local_at::time < hours.closes_at
+    );
+$$ LANGUAGE sql STABLE;
+
+-- Checks if the location opens on the local date
+CREATE OR REPLACE FUNCTION services.location_open_on(target_location_id uuid, local_date date)
+RETURNS boolean AS $$
+    SELECT CASE
+        WHEN holiday.id IS NOT NULL THEN holiday.opens_at IS NOT NULL
+        ELSE EXISTS(
+            SELECT 1
+            FROM services.location_opening_hours hours
+            WHERE hours.location_id = target_location_id
+                AND hours.weekday = EXTRACT(ISODOW FROM local_date)
+        )
+    END
+    FROM (SELECT 1) AS one
+    LEFT JOIN services.location_holidays holiday
+        ON holiday.location_id = target_location_id
+        AND holiday.date = local_date;
+$$ LANGUAGE sql STABLE;
+
+CREATE OR REPLACE FUNCTION services.location_open_now(target_location_id uuid)
+RETURNS boolean AS $$
+    SELECT services.location_open_at(
+        target_location_id,
+        services.location_local_now(target_location_id)
+    );
+$$ LANGUAGE sql STABLE;
+
+CREATE OR REPLACE FUNCTION services.location_open_today(target_location_id uuid)
+RETURNS boolean AS $$
+    SELECT services.location_open_on(
+        target_location_id,
+        services.location_local_now(target_location_id)::date
+    );
+$$ LANGUAGE sql STABLE;
+
+-- Location schedule: timezone, pickup instructions, weekly opening hours
+-- and upcoming holidays as a json object.
+CREATE OR REPLACE FUNCTION services.location_schedule(target_location_id uuid)
+RETURNS jsonb AS $$
+    SELECT jsonb_build_object(
+        'timezone', location_.timezone,
+        'pickupInstructions', location_.pickup_instructions,
+        'openNow', services.location_open_now(location_.id),
+        'openToday', services.location_open_today(location_.id),
+        'openingHours', COALESCE((
+            SELECT jsonb_agg(
+                jsonb_build_object(
+                    'id', hours.id,
+                    'weekday', hours.weekday,
+                    'opensAt', to_char(hours.opens_at, 'HH24:MI'),
+                    'closesAt', to_char(hours.closes_at, 'HH24:MI')
+                )
+                ORDER BY hours.weekday, hours.opens_at
+            )
+            FROM services.location_opening_hours hours
+            WHERE hours.location_id = location_.id
+        ), '[]'::jsonb),
+        'holidays', COALESCE((
+            SELECT jsonb_agg(
+                jsonb_build_object(
+                    'id', holiday.id,
+                    'date', holiday.date,
+                    'name', holiday.name,
+                    'opensAt', to_char(holiday.opens_at, 'HH24:MI'),
+                    'closesAt', to_char(holiday.closes_at, 'HH24:MI')
+                )
+                ORDER BY holiday.date
+            )
+            FROM services.location_holidays holiday
+            WHERE holiday.location_id = location_.id
+                AND holiday.date >= services.location_local_now(location_.id)::date
+        ), '[]'::jsonb)
+    )
+    FROM services.locations location_
+    WHERE location_.id = target_location_id;
+$$ LANGUAGE sql STABLE;
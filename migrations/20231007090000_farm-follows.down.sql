@@ -0,0 +1,10 @@
+-- Add down migration script here
+
+DROP TRIGGER IF EXISTS farm_follows_stats ON accounts.farm_follows;
+
+DROP FUNCTION IF EXISTS services.farm_follows_stats_trigger();
+DROP FUNCTION IF EXISTS services.refresh_farm_follower_stats(uuid);
+
+ALTER TABLE services.farm_stats DROP COLUMN IF EXISTS follower_count;
+
+DROP TABLE IF EXISTS accounts.farm_follows;
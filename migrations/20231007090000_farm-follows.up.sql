@@ -0,0 +1,49 @@
+-- Add up migration script here
+
+-- Farms followed by users
+CREATE TABLE IF NOT EXISTS accounts.farm_follows(
+    user_id uuid REFERENCES accounts.users (id) ON DELETE CASCADE,
+    farm_id uuid REFERENCES services.farms (id) ON DELETE CASCADE,
+    notify boolean NOT NULL, -- email the user when the farm lists a new harvest
+    created_at timestamptz NOT NULL,
+    PRIMARY KEY(user_id, farm_id)
+);
+
+CREATE INDEX IF NOT EXISTS farm_follows_farm_id_idx
+    ON accounts.farm_follows (farm_id);
+
+ALTER TABLE services.farm_stats
+    ADD COLUMN IF NOT EXISTS follower_count bigint NOT NULL DEFAULT 0;
+
+-- ===== FUNCTIONS =====
+
+-- Recompute the farm followers count
+CREATE OR REPLACE FUNCTION services.refresh_farm_follower_stats(target_farm_id uuid)
+RETURNS void AS $$
+    UPDATE services.farm_stats stat
+    SET follower_count = (
+            SELECT COUNT(follow.user_id)
+            FROM accounts.farm_follows follow
+            WHERE follow.farm_id = target_farm_id
+        ),
+        updated_at = now()
+    WHERE stat.farm_id = target_farm_id;
+$$ LANGUAGE sql;
+
+CREATE OR REPLACE FUNCTION services.farm_follows_stats_trigger()
+RETURNS trigger AS $$
+BEGIN
+    IF TG_OP = 'DELETE' THEN
+        PERFORM services.refresh_farm_follower_stats(OLD.farm_id);
+    ELSE
+        PERFORM services.refresh_farm_follower_stats(NEW.farm_id);
+    END IF;
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+-- ===== TRIGGERS =====
+
+CREATE TRIGGER farm_follows_stats
+AFTER INSERT OR DELETE ON accounts.farm_follows
+FOR EACH ROW EXECUTE FUNCTION services.farm_follows_stats_trigger();
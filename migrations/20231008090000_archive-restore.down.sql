@@ -0,0 +1,32 @@
+-- Add down migration script here
+
+DROP VIEW IF EXISTS services.active_harvests;
+DROP VIEW IF EXISTS services.active_locations;
+DROP VIEW IF EXISTS services.active_farms;
+
+DROP INDEX IF EXISTS services.harvests_archived_at_idx;
+DROP INDEX IF EXISTS services.locations_archived_at_idx;
+DROP INDEX IF EXISTS services.farms_archived_at_idx;
+
+ALTER TABLE services.harvests DROP COLUMN IF EXISTS archived_at;
+ALTER TABLE services.locations DROP COLUMN IF EXISTS archived_at;
+ALTER TABLE services.farms DROP COLUMN IF EXISTS archived_at;
+
+CREATE VIEW services.active_farms AS (
+	SELECT *
+	FROM services.farms farm
+	WHERE farm.deleted = false
+    	AND farm.owner_id IS NOT NULL
+);
+
+CREATE VIEW services.active_locations AS (
+	SELECT *
+	FROM services.locations location_
+	WHERE location_.deleted = false
+);
+
+CREATE VIEW services.active_harvests AS (
+	SELECT *
+	FROM services.harvests harvest
+	WHERE harvest.finished = false
+);
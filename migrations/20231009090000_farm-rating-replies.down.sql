@@ -0,0 +1,9 @@
+-- Add down migration script here
+
+DROP INDEX IF EXISTS services.farm_ratings_reply_to_idx;
+
+ALTER TABLE services.farm_ratings
+    DROP CONSTRAINT IF EXISTS farm_ratings_reply_grade_check,
+    DROP CONSTRAINT IF EXISTS farm_ratings_reply_to_fkey,
+    ADD CONSTRAINT farm_ratings_reply_to_fkey
+        FOREIGN KEY (reply_to) REFERENCES services.farm_ratings (id);
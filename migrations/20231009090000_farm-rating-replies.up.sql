@@ -0,0 +1,13 @@
+-- Add up migration script here
+
+-- Replies are rows in farm_ratings with `reply_to` set, they have no grade
+-- and are deleted together with the rating or reply they respond to.
+ALTER TABLE services.farm_ratings
+    DROP CONSTRAINT IF EXISTS farm_ratings_reply_to_fkey,
+    ADD CONSTRAINT farm_ratings_reply_to_fkey
+        FOREIGN KEY (reply_to) REFERENCES services.farm_ratings (id) ON DELETE CASCADE,
+    ADD CONSTRAINT farm_ratings_reply_grade_check
+        CHECK (reply_to IS NULL OR grade IS NULL);
+
+CREATE INDEX IF NOT EXISTS farm_ratings_reply_to_idx
+    ON services.farm_ratings (reply_to) WHERE reply_to IS NOT NULL;
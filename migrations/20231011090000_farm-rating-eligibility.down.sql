@@ -0,0 +1,8 @@
+-- Add down migration script here
+
+DROP FUNCTION IF EXISTS services.farm_verified_buyer(uuid, uuid);
+
+DROP INDEX IF EXISTS features.direct_messages_sender_id_receiver_id_idx;
+DROP INDEX IF EXISTS services.farm_ratings_author_id_farm_id_key;
+
+ALTER TABLE services.farm_ratings DROP COLUMN IF EXISTS verified_buyer;
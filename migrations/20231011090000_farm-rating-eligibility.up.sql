@@ -0,0 +1,68 @@
+-- Add up migration script here
+
+-- Set when the rating author had a conversation with the farm owner
+ALTER TABLE services.farm_ratings
+    ADD COLUMN IF NOT EXISTS verified_buyer boolean NOT NULL DEFAULT false;
+
+-- A user can only rate a farm once, keep the latest rating of every
+-- author and farm and move the replies of the older ratings to it.
+-- Rating votes, reports and images are added by later migrations,
+-- the older ratings have none of them yet.
+CREATE TEMPORARY TABLE farm_rating_duplicates AS
+SELECT ranked.id, ranked.keep_id
+FROM (
+    SELECT rating.id,
+        first_value(rating.id) OVER (
+            PARTITION BY rating.author_id, rating.farm_id
+            ORDER BY rating.created_at DESC, rating.id DESC
+        ) AS keep_id
+    FROM services.farm_ratings rating
+    WHERE rating.reply_to IS NULL
+        AND rating.author_id IS NOT NULL
+        AND rating.farm_id IS NOT NULL
+) ranked
+WHERE ranked.id <> ranked.keep_id;
+
+UPDATE services.farm_ratings reply
+SET reply_to = duplicate.keep_id
+FROM farm_rating_duplicates duplicate
+WHERE reply.reply_to = duplicate.id;
+
+DELETE FROM services.farm_ratings rating
+USING farm_rating_duplicates duplicate
+WHERE rating.id = duplicate.id;
+
+DROP TABLE farm_rating_duplicates;
+
+CREATE UNIQUE INDEX IF NOT EXISTS farm_ratings_author_id_farm_id_key
+    ON services.farm_ratings (author_id, farm_id)
+    WHERE reply_to IS NULL;
+
+CREATE INDEX IF NOT EXISTS direct_messages_sender_id_receiver_id_idx
+    ON features.direct_messages (sender_id, receiver_id);
+
+-- A user is a verified buyer of the farm when they exchanged
+-- direct messages with the farm owner in both directions.
+CREATE OR REPLACE FUNCTION services.farm_verified_buyer(target_user_id uuid, target_farm_id uuid)
+RETURNS boolean AS $$
+    SELECT EXISTS(
+        SELECT 1
+        FROM services.farms farm
+        WHERE farm.id = target_farm_id
+            AND farm.owner_id <> target_user_id
+            AND EXISTS(
+                SELECT 1 FROM features.direct_messages message
+                WHERE message.sender_id = target_user_id
+                    AND message.receiver_id = farm.owner_id
+            )
+            AND EXISTS(
+                SELECT 1 FROM features.direct_messages message
+                WHERE message.sender_id = farm.owner_id
+                    AND message.receiver_id = target_user_id
+            )
+    );
+$$ LANGUAGE sql STABLE;
+
+UPDATE services.farm_ratings rating
+SET verified_buyer = services.farm_verified_buyer(rating.author_id, rating.farm_id)
+WHERE rating.reply_to IS NULL;
@@ -0,0 +1,7 @@
+-- Add down migration script here
+
+ALTER TABLE services.farm_ratings
+    DROP CONSTRAINT IF EXISTS farm_ratings_reply_images_check;
+
+ALTER TABLE services.farm_ratings
+    DROP COLUMN IF EXISTS images;
@@ -0,0 +1,13 @@
+-- Add down migration script here
+
+DROP VIEW IF EXISTS services.active_locations;
+
+ALTER TABLE services.locations
+    DROP COLUMN IF EXISTS images,
+    DROP COLUMN IF EXISTS access_notes;
+
+CREATE VIEW services.active_locations AS (
+	SELECT *
+	FROM services.locations location_
+	WHERE location_.deleted = false
+);
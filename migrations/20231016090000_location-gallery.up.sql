@@ -0,0 +1,13 @@
+-- Add up migration script here
+
+-- Location photo gallery and access notes such as gate code or road conditions.
+ALTER TABLE services.locations
+    ADD COLUMN IF NOT EXISTS images text[],
+    ADD COLUMN IF NOT EXISTS access_notes text;
+
+-- Expose the new columns in the view
+CREATE OR REPLACE VIEW services.active_locations AS (
+	SELECT *
+	FROM services.locations location_
+	WHERE location_.deleted = false
+);
@@ -0,0 +1,15 @@
+-- Add up migration script here
+
+-- Personal data export archives requested by users,
+-- the download token is set once the archive is ready.
+CREATE TABLE IF NOT EXISTS accounts.data_exports(
+    id uuid PRIMARY KEY,
+    user_id uuid NOT NULL REFERENCES accounts.users (id) ON DELETE CASCADE,
+    token bytea UNIQUE, -- token hash
+    requested_at timestamptz NOT NULL,
+    completed_at timestamptz,
+    expires_at timestamptz
+);
+
+CREATE INDEX IF NOT EXISTS data_exports_user_id_idx
+    ON accounts.data_exports (user_id, requested_at);
@@ -0,0 +1,24 @@
+-- Add down migration script here
+
+DROP TABLE IF EXISTS services.farm_slug_history;
+
+DROP TABLE IF EXISTS accounts.user_handle_history;
+
+DROP VIEW IF EXISTS services.active_farms;
+
+ALTER TABLE services.farms
+    DROP COLUMN IF EXISTS slug;
+
+CREATE VIEW services.active_farms AS (
+	SELECT *
+	FROM services.farms farm
+	WHERE farm.deleted = false
+    	AND farm.owner_id IS NOT NULL
+		AND NOT EXISTS(
+			SELECT 1 FROM accounts.account_delete_requests delete_request
+			WHERE delete_request.user_id = farm.owner_id
+		)
+);
+
+ALTER TABLE accounts.users
+    DROP COLUMN IF EXISTS handle;
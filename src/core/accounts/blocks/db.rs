@@ -0,0 +1,214 @@
+//! User blocking database impls
+
+use time::OffsetDateTime;
+
+use crate::{
+    accounts::user::models::UserIndex,
+    endpoint::EndpointRejection,
+    error::{ServerError, ServerResult},
+    server::state::DatabaseConnection,
+    types::{ModelID, Pagination},
+};
+
+use super::{BlockedUser, BlockedUserList, UserBlock};
+
+impl UserBlock {
+    /// Fetches the users blocked by the user from the database
+    #[tracing::instrument(skip(db))]
+    pub async fn records(
+        user_id: ModelID,
+        pg: Pagination,
+        db: DatabaseConnection,
+    ) -> ServerResult<BlockedUserList> {
+        let (offset, limit) = pg.offset_limit();
+        match sqlx::query!(
+            r#"
+                SELECT user_.id AS user_id,
+                    user_.first_name AS user_first_name,
+                    user_.last_name AS user_last_name,
+                    user_.identity_verified AS user_identity_verified,
+                    user_profile.photo AS "user_photo?",
+                    block.blocked_at
+                FROM accounts.user_blocks block
+                LEFT JOIN accounts.users user_
+                    ON block.blocked_id = user_.id
+                LEFT JOIN accounts.user_profiles user_profile
+                    ON block.blocked_id = user_profile.user_id
+
+                WHERE block.user_id = $1
+                ORDER BY block.blocked_at DESC
+                LIMIT $2
+                OFFSET $3
+            "#,
+            user_id.0,
+            limit,
+            offset
+        )
+        .fetch_all(&db.pool)
+        .await
+        {
+            Ok(records) => {
+                let blocked = records
+                    .into_iter()
+                    .map(|rec| BlockedUser {
+                        user: UserIndex::from_row(
+                            rec.user_id.into(),
+                            rec.user_first_name,
+                            rec.user_last_name,
+                            rec.user_photo,
+                            rec.user_identity_verified,
+                        ),
+                        blocked_at: rec.blocked_at,
+                    })
+                    .collect();
+                Ok(blocked)
+            }
+            Err(err) => {
+                tracing::error!("Database error, failed to fetch blocked users: {}", err);
+                Err(err.into())
+            }
+        }
+    }
+
+    /// Checks if the user blocked the other user
+    #[tracing::instrument(skip(db))]
+    pub async fn exists(
+        user_id: ModelID,
+        blocked_id: ModelID,
+        db: DatabaseConnection,
+    ) -> ServerResult<bool> {
+        match sqlx::query!(
+            r#"
+                SELECT EXISTS(
+                    SELECT 1 FROM accounts.user_blocks block
+                    WHERE block.user_id = $1
+                        AND block.blocked_id = $2
+                ) AS "exists!"
+            "#,
+            user_id.0,
+            blocked_id.0
+        )
+        .fetch_one(&db.pool)
+        .await
+        {
+            Ok(rec) => Ok(rec.exists),
+            Err(err) => {
+                tracing::error!("Database error, failed to check user block: {}", err);
+                Err(err.into())
+            }
+        }
+    }
+
+    /// Checks if either of the users blocked the other
+    #[tracing::instrument(skip(db))]
+    pub async fn exists_between(
+        user_id: ModelID,
+        other_id: ModelID,
+        db: DatabaseConnection,
+    ) -> ServerResult<bool> {
+        match sqlx::query!(
+            r#"
+                SELECT EXISTS(
+                    SELECT 1 FROM accounts.user_blocks block
+                    WHERE (block.user_id = $1 AND block.blocked_id = $2)
+                        OR (block.user_id = $2 AND block.blocked_id = $1)
+                ) AS "exists!"
+            "#,
+            user_id.0,
+            other_id.0
+        )
+        .fetch_one(&db.pool)
+        .await
+        {
+            Ok(rec) => Ok(rec.exists),
+            Err(err) => {
+                tracing::error!("Database error, failed to check user block: {}", err);
+                Err(err.into())
+            }
+        }
+    }
+
+    /// Inserts the user block into the database,
+    /// blocking an already blocked user does nothing.
+    #[tracing::instrument(skip(db))]
+    pub async fn insert(
+        user_id: ModelID,
+        blocked_id: ModelID,
+        db: DatabaseConnection,
+    ) -> ServerResult<()> {
+        match sqlx::query!(
+            r#"
+                INSERT INTO accounts.user_blocks(
+                    user_id,
+                    blocked_id,
+                    blocked_at
+                )
+                VALUES($1, $2, $3)
+                ON CONFLICT (user_id, blocked_id) DO NOTHING
+            "#,
+            user_id.0,
+            blocked_id.0,
+            OffsetDateTime::now_utc()
+        )
+        .execute(&db.pool)
+        .await
+        {
+            Ok(result) => {
+                tracing::debug!("User block inserted successfully: {:?}", result);
+                Ok(())
+            }
+            Err(err) => {
+                // Handle database constraint error
+                handle_user_block_database_error(&err)?;
+
+                tracing::error!("Database error, failed to block user: {}", err);
+                Err(err.into())
+            }
+        }
+    }
+
+    /// Deletes the user block from the database
+    #[tracing::instrument(skip(db))]
+    pub async fn delete(
+        user_id: ModelID,
+        blocked_id: ModelID,
+        db: DatabaseConnection,
+    ) -> ServerResult<()> {
+        match sqlx::query!(
+            r#"
+                DELETE FROM accounts.user_blocks block
+                WHERE block.user_id = $1
+                    AND block.blocked_id = $2
+            "#,
+            user_id.0,
+            blocked_id.0
+        )
+        .execute(&db.pool)
+        .await
+        {
+            Ok(result) => {
+                tracing::debug!("User block deleted successfully: {:?}", result);
+                Ok(())
+            }
+            Err(err) => {
+                tracing::error!("Database error, failed to unblock user: {}", err);
+                Err(err.into())
+            }
+        }
+    }
+}
+
+/// Handle user blocks database constraints errors
+pub fn handle_user_block_database_error(err: &sqlx::Error) -> ServerResult<()> {
+    if let sqlx::Error::Database(db_err) = err {
+        // Handle db foreign key constraints
+        if db_err.is_foreign_key_violation() {
+            tracing::error!("Database error, user not found. {:?}", err);
+            return Err(ServerError::rejection(EndpointRejection::NotFound(
+                "User not found.".into(),
+            )));
+        }
+    }
+
+    Ok(())
+}
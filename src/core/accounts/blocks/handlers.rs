@@ -0,0 +1,60 @@
+//! User blocking http handlers impls
+
+use axum::{
+    extract::{Query, State},
+    http::StatusCode,
+    Json,
+};
+
+use crate::{
+    auth::CurrentUser,
+    endpoint::{EndpointRejection, EndpointResult},
+    server::state::DatabaseConnection,
+    types::{ModelID, Pagination},
+};
+
+use super::{BlockedUserList, UserBlock};
+
+/// Handles the `GET /account/users/blocked` route.
+///
+/// Returns the users blocked by the current user, recently blocked first
+#[tracing::instrument(skip(db))]
+pub async fn user_blocked_list(
+    user: CurrentUser,
+    pg: Option<Query<Pagination>>,
+    State(db): State<DatabaseConnection>,
+) -> EndpointResult<Json<BlockedUserList>> {
+    let pagination = pg.unwrap_or_default().0;
+    let blocked = UserBlock::records(user.id, pagination, db).await?;
+    Ok(Json(blocked))
+}
+
+/// Handles the `POST /account/users/:user_id/block` route.
+///
+/// The blocked user cannot message the current user, rate their farm
+/// and the current user no longer sees their profile and conversations.
+#[tracing::instrument(skip(db))]
+pub async fn user_block(
+    user: CurrentUser,
+    blocked_id: ModelID,
+    State(db): State<DatabaseConnection>,
+) -> EndpointResult<StatusCode> {
+    if user.id == blocked_id {
+        return Err(EndpointRejection::BadRequest(
+            "You cannot block yourself.".into(),
+        ));
+    }
+    UserBlock::insert(user.id, blocked_id, db).await?;
+    Ok(StatusCode::OK)
+}
+
+/// Handles the `DELETE /account/users/:user_id/block` route.
+#[tracing::instrument(skip(db))]
+pub async fn user_unblock(
+    user: CurrentUser,
+    blocked_id: ModelID,
+    State(db): State<DatabaseConnection>,
+) -> EndpointResult<StatusCode> {
+    UserBlock::delete(user.id, blocked_id, db).await?;
+    Ok(StatusCode::OK)
+}
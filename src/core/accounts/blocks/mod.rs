@@ -0,0 +1,24 @@
+//! User blocking impls
+
+pub mod db;
+pub mod handlers;
+
+use serde::Serialize;
+use time::OffsetDateTime;
+
+use crate::accounts::user::models::UserIndex;
+
+/// A `Vec` of users blocked by a user
+pub type BlockedUserList = Vec<BlockedUser>;
+
+/// The model representing a row in the `user_blocks` database table.
+#[derive(Debug, Clone, Copy)]
+pub struct UserBlock;
+
+/// A user blocked by the current user
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BlockedUser {
+    pub user: UserIndex,
+    pub blocked_at: OffsetDateTime,
+}
@@ -0,0 +1,100 @@
+//! Data export archive impls
+
+use std::{
+    fs::File,
+    io::{self, Write},
+    path::{Path, PathBuf},
+};
+
+use zip::{write::FileOptions, CompressionMethod, ZipWriter};
+
+use crate::{
+    error::{ServerError, ServerResult},
+    settings::{
+        FARM_LOGO_UPLOAD_DIR, HARVEST_UPLOAD_DIR, LOCATION_UPLOAD_DIR, RATING_UPLOAD_DIR,
+        USER_UPLOAD_DIR,
+    },
+};
+
+/// The user personal data written into the export archive
+#[derive(Debug, Clone, Default)]
+pub struct DataArchive {
+    /// JSON documents written as `<name>.json`
+    pub documents: Vec<(&'static str, serde_json::Value)>,
+    /// Images uploaded by the user written under `images/<kind>/`
+    pub images: Vec<ArchiveImage>,
+}
+
+/// An image uploaded by the user
+#[derive(Debug, Clone)]
+pub struct ArchiveImage {
+    pub kind: String,
+    pub file: String,
+}
+
+impl ArchiveImage {
+    /// Returns the path the image is saved at on the file system,
+    /// or `None` if the image kind is unknown.
+    fn saved_path(&self) -> Option<PathBuf> {
+        let upload_dir = match self.kind.as_str() {
+            "profile" => USER_UPLOAD_DIR,
+            "farm" => FARM_LOGO_UPLOAD_DIR,
+            "location" => LOCATION_UPLOAD_DIR,
+            "harvest" => HARVEST_UPLOAD_DIR,
+            "rating" => RATING_UPLOAD_DIR,
+            _ => return None,
+        };
+        // Only keep the file name so the path cannot escape the upload directory
+        let file = Path::new(&self.file).file_name()?;
+        Some(PathBuf::from(upload_dir).join(file))
+    }
+}
+
+impl DataArchive {
+    /// Writes the archive as a zip file at `path`
+    ///
+    /// Images no longer found on the file system are skipped.
+    ///
+    /// # Errors
+    ///
+    /// Return an error if failed to write the archive
+    pub async fn write(self, path: PathBuf) -> ServerResult<()> {
+        tokio::task::spawn_blocking(move || self.write_blocking(&path)).await?
+    }
+
+    fn write_blocking(self, path: &Path) -> ServerResult<()> {
+        let mut zip = ZipWriter::new(File::create(path)?);
+        let deflated = FileOptions::default().compression_method(CompressionMethod::Deflated);
+        // Images are already compressed
+        let stored = FileOptions::default().compression_method(CompressionMethod::Stored);
+
+        for (name, document) in self.documents {
+            zip.start_file(format!("{name}.json"), deflated)
+                .map_err(zip_error)?;
+            serde_json::to_writer_pretty(&mut zip, &document)?;
+        }
+
+        for image in self.images {
+            let Some(saved_path) = image.saved_path() else {
+                continue;
+            };
+            let Ok(mut file) = File::open(&saved_path) else {
+                tracing::warn!("Data export image not found: {:?}", saved_path);
+                continue;
+            };
+            let file_name = saved_path.file_name().unwrap().to_string_lossy();
+            zip.start_file(format!("images/{}/{file_name}", image.kind), stored)
+                .map_err(zip_error)?;
+            io::copy(&mut file, &mut zip)?;
+        }
+
+        zip.finish().map_err(zip_error)?.flush()?;
+        Ok(())
+    }
+}
+
+/// Convert zip error into server error
+fn zip_error(err: zip::result::ZipError) -> ServerError {
+    tracing::error!("Zip error, failed to write data export: {}", err);
+    ServerError::internal(Box::new(err))
+}
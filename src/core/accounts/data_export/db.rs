@@ -0,0 +1,596 @@
+//! Data export database impls
+
+use time::{Duration, OffsetDateTime};
+use uuid::Uuid;
+
+use crate::{
+    auth::TokenHash, error::ServerResult, server::state::DatabaseConnection,
+    settings::DATA_EXPORT_DIR, types::ModelID,
+};
+
+use super::{
+    archive::{ArchiveImage, DataArchive},
+    DataExport,
+};
+
+impl DataExport {
+    /// Inserts a new data export request into the database
+    ///
+    /// Returns `None` if the user already requested an export
+    /// in the last `DATA_EXPORT_REQUEST_INTERVAL` hours.
+    #[tracing::instrument(skip(db))]
+    pub async fn insert(user_id: ModelID, db: DatabaseConnection) -> ServerResult<Option<ModelID>> {
+        let now = OffsetDateTime::now_utc();
+        match sqlx::query!(
+            r#"
+                INSERT INTO accounts.data_exports(id, user_id, requested_at)
+                SELECT $1, $2, $3
+                WHERE NOT EXISTS(
+                    SELECT 1 FROM accounts.data_exports data_export
+                    WHERE data_export.user_id = $2
+                        AND data_export.requested_at > $4
+                )
+                RETURNING id
+            "#,
+            ModelID::new().0,
+            user_id.0,
+            now,
+            now - Duration::hours(crate::DATA_EXPORT_REQUEST_INTERVAL),
+        )
+        .fetch_optional(&db.pool)
+        .await
+        {
+            Ok(rec) => Ok(rec.map(|rec| rec.id.into())),
+            Err(err) => {
+                tracing::error!("Database error, failed to insert data export: {}", err);
+                Err(err.into())
+            }
+        }
+    }
+
+    /// Marks the data export archive ready to be downloaded with the `token`
+    #[tracing::instrument(skip(db, token))]
+    pub async fn complete(
+        id: ModelID,
+        token: TokenHash,
+        db: DatabaseConnection,
+    ) -> ServerResult<()> {
+        let now = OffsetDateTime::now_utc();
+        match sqlx::query!(
+            r#"
+                UPDATE accounts.data_exports data_export
+                SET token = $1,
+                    completed_at = $2,
+                    expires_at = $3
+                WHERE data_export.id = $4
+            "#,
+            &token[..],
+            now,
+            now + Duration::days(crate::DATA_EXPORT_EXPIRY_DAYS),
+            id.0
+        )
+        .execute(&db.pool)
+        .await
+        {
+            Ok(result) => {
+                tracing::debug!("Data export completed: {:?}", result);
+                Ok(())
+            }
+            Err(err) => {
+                tracing::error!("Database error, failed to complete data export: {}", err);
+                Err(err.into())
+            }
+        }
+    }
+
+    /// Finds the id of the data export that can be downloaded with the `token`
+    #[tracing::instrument(skip(db, token))]
+    pub async fn find_by_token(
+        token: TokenHash,
+        db: DatabaseConnection,
+    ) -> ServerResult<Option<ModelID>> {
+        match sqlx::query!(
+            r#"
+                SELECT data_export.id
+                FROM accounts.data_exports data_export
+                WHERE data_export.token = $1
+                    AND data_export.expires_at > $2
+            "#,
+            &token[..],
+            OffsetDateTime::now_utc(),
+        )
+        .fetch_optional(&db.pool)
+        .await
+        {
+            Ok(rec) => Ok(rec.map(|rec| rec.id.into())),
+            Err(err) => {
+                tracing::error!("Database error, failed to find data export: {}", err);
+                Err(err.into())
+            }
+        }
+    }
+
+    /// Deletes expired data exports and exports that failed to complete
+    /// from the database.
+    ///
+    /// Returns the ids of the exports still available.
+    #[tracing::instrument(skip(db))]
+    pub async fn delete_expired(db: DatabaseConnection) -> ServerResult<Vec<ModelID>> {
+        let now = OffsetDateTime::now_utc();
+        let mut tx = db.pool.begin().await?;
+
+        if let Err(err) = sqlx::query!(
+            r#"
+                DELETE FROM accounts.data_exports data_export
+                WHERE data_export.expires_at <= $1
+                    OR (
+                        data_export.completed_at IS NULL
+                        AND data_export.requested_at <= $2
+                    )
+            "#,
+            now,
+            now - Duration::hours(crate::DATA_EXPORT_REQUEST_INTERVAL),
+        )
+        .execute(&mut *tx)
+        .await
+        {
+            tracing::error!(
+                "Database error, failed to delete expired data exports: {}",
+                err
+            );
+            return Err(err.into());
+        }
+
+        match sqlx::query!(
+            r#"
+                SELECT data_export.id
+                FROM accounts.data_exports data_export
+            "#,
+        )
+        .fetch_all(&mut *tx)
+        .await
+        {
+            Ok(records) => {
+                tx.commit().await?;
+                Ok(records.into_iter().map(|rec| rec.id.into()).collect())
+            }
+            Err(err) => {
+                tracing::error!("Database error, failed to fetch data exports: {}", err);
+                Err(err.into())
+            }
+        }
+    }
+
+    /// Deletes the data export from the database
+    #[tracing::instrument(skip(db))]
+    pub async fn delete(id: ModelID, db: DatabaseConnection) -> ServerResult<()> {
+        match sqlx::query!(
+            r#"
+                DELETE FROM accounts.data_exports data_export
+                WHERE data_export.id = $1
+            "#,
+            id.0
+        )
+        .execute(&db.pool)
+        .await
+        {
+            Ok(result) => {
+                tracing::debug!("Data export deleted successfully: {:?}", result);
+                Ok(())
+            }
+            Err(err) => {
+                tracing::error!("Database error, failed to delete data export: {}", err);
+                Err(err.into())
+            }
+        }
+    }
+
+    /// Deletes expired data exports and their archives
+    ///
+    /// Archives without a data export, like ones of deleted accounts,
+    /// are deleted too.
+    #[tracing::instrument(skip(db))]
+    pub async fn purge_expired(db: DatabaseConnection) -> ServerResult<()> {
+        let available = Self::delete_expired(db).await?;
+
+        let Ok(mut entries) = tokio::fs::read_dir(DATA_EXPORT_DIR).await else {
+            return Ok(());
+        };
+        while let Some(entry) = entries.next_entry().await? {
+            let path = entry.path();
+            let is_available = path
+                .file_stem()
+                .and_then(|stem| stem.to_str())
+                .and_then(|stem| Uuid::parse_str(stem).ok())
+                .is_some_and(|id| available.contains(&ModelID::from(id)));
+            if !is_available {
+                if let Err(err) = tokio::fs::remove_file(&path).await {
+                    tracing::error!("Failed to delete data export archive: {}", err);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Fetches the user personal data from the database
+    #[tracing::instrument(skip(db))]
+    pub async fn collect(user_id: ModelID, db: DatabaseConnection) -> ServerResult<DataArchive> {
+        let (profile, personal_info) = Self::collect_profile(user_id, db.clone()).await?;
+        let (ratings_written, ratings_received) =
+            Self::collect_ratings(user_id, db.clone()).await?;
+        let documents = vec![
+            ("profile", profile),
+            ("personal_info", personal_info),
+            ("farms", Self::collect_farms(user_id, db.clone()).await?),
+            (
+                "locations",
+                Self::collect_locations(user_id, db.clone()).await?,
+            ),
+            (
+                "harvests",
+                Self::collect_harvests(user_id, db.clone()).await?,
+            ),
+            ("ratings_written", ratings_written),
+            ("ratings_received", ratings_received),
+            (
+                "direct_messages",
+                Self::collect_messages(user_id, db.clone()).await?,
+            ),
+            (
+                "sessions",
+                Self::collect_sessions(user_id, db.clone()).await?,
+            ),
+        ];
+        let images = Self::collect_images(user_id, db).await?;
+
+        Ok(DataArchive { documents, images })
+    }
+
+    /// Fetches the user profile and personal info documents
+    async fn collect_profile(
+        user_id: ModelID,
+        db: DatabaseConnection,
+    ) -> ServerResult<(serde_json::Value, serde_json::Value)> {
+        match sqlx::query!(
+            r#"
+                SELECT json_build_object(
+                        'id', user_.id,
+                        'firstName', user_.first_name,
+                        'lastName', user_.last_name,
+                        'about', profile.about,
+                        'livesAt', profile.lives_at,
+                        'photo', profile.photo,
+                        'isFarmer', user_.is_farmer,
+                        'dateJoined', user_.date_joined,
+                        'lastLogin', user_.last_login
+                    ) AS "profile!",
+                    json_build_object(
+                        'email', address.email,
+                        'phone', phone.phone,
+                        'phoneVerified', phone.verified,
+                        'gender', user_.gender,
+                        'dateOfBirth', user_.date_of_birth
+                    ) AS "personal_info!"
+                FROM accounts.users user_
+                LEFT JOIN accounts.user_profiles profile
+                    ON user_.id = profile.user_id
+                LEFT JOIN accounts.emails address
+                    ON user_.id = address.user_id
+                LEFT JOIN accounts.phones phone
+                    ON user_.id = phone.user_id
+
+                WHERE user_.id = $1
+            "#,
+            user_id.0
+        )
+        .fetch_one(&db.pool)
+        .await
+        {
+            Ok(rec) => Ok((rec.profile, rec.personal_info)),
+            Err(err) => {
+                tracing::error!("Database error, failed to fetch user profile data: {}", err);
+                Err(err.into())
+            }
+        }
+    }
+
+    /// Fetches the farms owned by the user
+    async fn collect_farms(
+        user_id: ModelID,
+        db: DatabaseConnection,
+    ) -> ServerResult<serde_json::Value> {
+        match sqlx::query!(
+            r#"
+                SELECT COALESCE(json_agg(json_build_object(
+                    'id', farm.id,
+                    'name', farm.name,
+                    'logo', farm.logo,
+                    'contactNumber', farm.contact_number,
+                    'contactEmail', farm.contact_email,
+                    'foundedAt', farm.founded_at,
+                    'registeredOn', farm.registered_on,
+                    'deleted', farm.deleted
+                ) ORDER BY farm.registered_on), '[]') AS "farms!"
+                FROM services.farms farm
+                WHERE farm.owner_id = $1
+            "#,
+            user_id.0
+        )
+        .fetch_one(&db.pool)
+        .await
+        {
+            Ok(rec) => Ok(rec.farms),
+            Err(err) => {
+                tracing::error!("Database error, failed to fetch user farms data: {}", err);
+                Err(err.into())
+            }
+        }
+    }
+
+    /// Fetches the locations of the farms owned by the user
+    async fn collect_locations(
+        user_id: ModelID,
+        db: DatabaseConnection,
+    ) -> ServerResult<serde_json::Value> {
+        match sqlx::query!(
+            r#"
+                SELECT COALESCE(json_agg(json_build_object(
+                    'id', location_.id,
+                    'farmId', location_.farm_id,
+                    'placeName', location_.place_name,
+                    'region', region.name,
+                    'country', country.name,
+                    'coords', location_.coords,
+                    'description', location_.description,
+                    'accessNotes', location_.access_notes,
+                    'images', location_.images,
+                    'deleted', location_.deleted
+                ) ORDER BY location_.place_name), '[]') AS "locations!"
+                FROM services.locations location_
+                LEFT JOIN services.farms farm
+                    ON location_.farm_id = farm.id
+                LEFT JOIN services.countries country
+                    ON location_.country_id = country.id
+                LEFT JOIN services.regions region
+                    ON location_.region_id = region.id
+                WHERE farm.owner_id = $1
+            "#,
+            user_id.0
+        )
+        .fetch_one(&db.pool)
+        .await
+        {
+            Ok(rec) => Ok(rec.locations),
+            Err(err) => {
+                tracing::error!(
+                    "Database error, failed to fetch user locations data: {}",
+                    err
+                );
+                Err(err.into())
+            }
+        }
+    }
+
+    /// Fetches the harvests of the farms owned by the user
+    async fn collect_harvests(
+        user_id: ModelID,
+        db: DatabaseConnection,
+    ) -> ServerResult<serde_json::Value> {
+        match sqlx::query!(
+            r#"
+                SELECT COALESCE(json_agg(json_build_object(
+                    'id', harvest.id,
+                    'locationId', harvest.location_id,
+                    'cultivar', cultivar.name,
+                    'price', harvest.price,
+                    'type', harvest.type,
+                    'description', harvest.description,
+                    'harvestDate', harvest.harvest_date,
+                    'images', harvest.images,
+                    'finished', harvest.finished,
+                    'createdAt', harvest.created_at
+                ) ORDER BY harvest.created_at), '[]') AS "harvests!"
+                FROM services.harvests harvest
+                LEFT JOIN services.cultivars cultivar
+                    ON harvest.cultivar_id = cultivar.id
+                LEFT JOIN services.locations location_
+                    ON harvest.location_id = location_.id
+                LEFT JOIN services.farms farm
+                    ON location_.farm_id = farm.id
+                WHERE farm.owner_id = $1
+            "#,
+            user_id.0
+        )
+        .fetch_one(&db.pool)
+        .await
+        {
+            Ok(rec) => Ok(rec.harvests),
+            Err(err) => {
+                tracing::error!(
+                    "Database error, failed to fetch user harvests data: {}",
+                    err
+                );
+                Err(err.into())
+            }
+        }
+    }
+
+    /// Fetches the ratings written by the user
+    /// and the ratings received by the farms the user owns
+    async fn collect_ratings(
+        user_id: ModelID,
+        db: DatabaseConnection,
+    ) -> ServerResult<(serde_json::Value, serde_json::Value)> {
+        match sqlx::query!(
+            r#"
+                SELECT (
+                        SELECT COALESCE(json_agg(json_build_object(
+                            'id', farm_rating.id,
+                            'farmId', farm_rating.farm_id,
+                            'farm', farm.name,
+                            'grade', farm_rating.grade,
+                            'comment', farm_rating.comment,
+                            'replyTo', farm_rating.reply_to,
+                            'images', farm_rating.images,
+                            'createdAt', farm_rating.created_at,
+                            'updatedAt', farm_rating.updated_at
+                        ) ORDER BY farm_rating.created_at), '[]')
+                        FROM services.farm_ratings farm_rating
+                        LEFT JOIN services.farms farm
+                            ON farm_rating.farm_id = farm.id
+                        WHERE farm_rating.author_id = $1
+                    ) AS "ratings_written!",
+                    (
+                        SELECT COALESCE(json_agg(json_build_object(
+                            'id', farm_rating.id,
+                            'farmId', farm_rating.farm_id,
+                            'farm', farm.name,
+                            'grade', farm_rating.grade,
+                            'comment', farm_rating.comment,
+                            'replyTo', farm_rating.reply_to,
+                            'verifiedBuyer', farm_rating.verified_buyer,
+                            'createdAt', farm_rating.created_at,
+                            'updatedAt', farm_rating.updated_at
+                        ) ORDER BY farm_rating.created_at), '[]')
+                        FROM services.farm_ratings farm_rating
+                        LEFT JOIN services.farms farm
+                            ON farm_rating.farm_id = farm.id
+                        WHERE farm.owner_id = $1
+                    ) AS "ratings_received!"
+            "#,
+            user_id.0
+        )
+        .fetch_one(&db.pool)
+        .await
+        {
+            Ok(rec) => Ok((rec.ratings_written, rec.ratings_received)),
+            Err(err) => {
+                tracing::error!("Database error, failed to fetch user ratings data: {}", err);
+                Err(err.into())
+            }
+        }
+    }
+
+    /// Fetches the direct messages sent or received by the user
+    async fn collect_messages(
+        user_id: ModelID,
+        db: DatabaseConnection,
+    ) -> ServerResult<serde_json::Value> {
+        match sqlx::query!(
+            r#"
+                SELECT COALESCE(json_agg(json_build_object(
+                    'id', message.id,
+                    'senderId', message.sender_id,
+                    'receiverId', message.receiver_id,
+                    'content', message.content,
+                    'sentAt', message.sent_at
+                ) ORDER BY message.sent_at), '[]') AS "direct_messages!"
+                FROM features.direct_messages message
+                WHERE message.sender_id = $1
+                    OR message.receiver_id = $1
+            "#,
+            user_id.0
+        )
+        .fetch_one(&db.pool)
+        .await
+        {
+            Ok(rec) => Ok(rec.direct_messages),
+            Err(err) => {
+                tracing::error!(
+                    "Database error, failed to fetch user messages data: {}",
+                    err
+                );
+                Err(err.into())
+            }
+        }
+    }
+
+    /// Fetches the user login sessions
+    async fn collect_sessions(
+        user_id: ModelID,
+        db: DatabaseConnection,
+    ) -> ServerResult<serde_json::Value> {
+        match sqlx::query!(
+            r#"
+                SELECT COALESCE(json_agg(json_build_object(
+                    'userAgent', session.user_agent,
+                    'createdAt', session.created_at,
+                    'lastUsedAt', session.last_used_at
+                ) ORDER BY session.created_at), '[]') AS "sessions!"
+                FROM auth.sessions session
+                WHERE session.user_id = $1
+            "#,
+            user_id.0
+        )
+        .fetch_one(&db.pool)
+        .await
+        {
+            Ok(rec) => Ok(rec.sessions),
+            Err(err) => {
+                tracing::error!(
+                    "Database error, failed to fetch user sessions data: {}",
+                    err
+                );
+                Err(err.into())
+            }
+        }
+    }
+
+    /// Fetches the images uploaded by the user
+    async fn collect_images(
+        user_id: ModelID,
+        db: DatabaseConnection,
+    ) -> ServerResult<Vec<ArchiveImage>> {
+        match sqlx::query!(
+            r#"
+                SELECT 'profile' AS "kind!", profile.photo AS "file!"
+                FROM accounts.user_profiles profile
+                WHERE profile.user_id = $1
+                    AND profile.photo IS NOT NULL
+
+                UNION ALL
+                SELECT 'farm', farm.logo
+                FROM services.farms farm
+                WHERE farm.owner_id = $1
+                    AND farm.logo IS NOT NULL
+
+                UNION ALL
+                SELECT 'location', unnest(location_.images)
+                FROM services.locations location_
+                LEFT JOIN services.farms farm
+                    ON location_.farm_id = farm.id
+                WHERE farm.owner_id = $1
+
+                UNION ALL
+                SELECT 'harvest', unnest(harvest.images)
+                FROM services.harvests harvest
+                LEFT JOIN services.locations location_
+                    ON harvest.location_id = location_.id
+                LEFT JOIN services.farms farm
+                    ON location_.farm_id = farm.id
+                WHERE farm.owner_id = $1
+
+                UNION ALL
+                SELECT 'rating', unnest(farm_rating.images)
+                FROM services.farm_ratings farm_rating
+                WHERE farm_rating.author_id = $1
+            "#,
+            user_id.0
+        )
+        .fetch_all(&db.pool)
+        .await
+        {
+            Ok(records) => Ok(records
+                .into_iter()
+                .map(|rec| ArchiveImage {
+                    kind: rec.kind,
+                    file: rec.file,
+                })
+                .collect()),
+            Err(err) => {
+                tracing::error!("Database error, failed to fetch user images: {}", err);
+                Err(err.into())
+            }
+        }
+    }
+}
@@ -0,0 +1,125 @@
+//! Data export http handlers impls
+
+use axum::{
+    body::Body,
+    extract::{Query, State},
+    http::{header, HeaderValue, Request, StatusCode},
+    response::{IntoResponse, Response},
+};
+use tower::ServiceExt;
+use tower_http::services::ServeFile;
+
+use crate::{
+    accounts::emails::EmailModel,
+    auth::{hash_token, CurrentUser, Token, TokenConfirm},
+    endpoint::{EndpointRejection, EndpointResult},
+    error::ServerResult,
+    mail::Mail,
+    server::state::DatabaseConnection,
+    settings::DATA_EXPORT_DIR,
+    types::ModelID,
+    SERVER_DOMAIN_NAME,
+};
+
+use super::{archive_path, DataExport};
+
+/// Handles the `POST /account/data-export` route.
+///
+/// Assembles the user data export archive in the background
+/// and emails the user a download link when it's ready.
+#[tracing::instrument(skip(user, db, outlook))]
+pub async fn data_export_request(
+    user: CurrentUser,
+    State(db): State<DatabaseConnection>,
+    State(outlook): State<Mail>,
+) -> EndpointResult<(StatusCode, &'static str)> {
+    let Some(export_id) = DataExport::insert(user.id, db.clone()).await? else {
+        return Err(EndpointRejection::TooManyRequests(
+            "You already requested a copy of your data, please try again later.".into(),
+        ));
+    };
+
+    tokio::spawn(async move {
+        if let Err(err) = build_data_export(user.id, export_id, db.clone(), outlook.clone()).await {
+            tracing::error!("Failed to build data export {}: {}", export_id, err);
+            // Let the user request a new export
+            _ = DataExport::delete(export_id, db.clone()).await;
+            if let Err(err) = notify_data_export_failed(user.id, db, outlook).await {
+                tracing::error!("Failed to send data export failed email: {}", err);
+            }
+        }
+    });
+
+    Ok((
+        StatusCode::ACCEPTED,
+        "We are preparing a copy of your data, we will email you a download link when it's ready",
+    ))
+}
+
+/// Handles the `GET /account/data-export/download?token=...` route.
+#[tracing::instrument(skip(db))]
+pub async fn data_export_download(
+    confirm_token: Option<Query<TokenConfirm>>,
+    State(db): State<DatabaseConnection>,
+) -> EndpointResult<Response> {
+    let Some(Query(confirm_token)) = confirm_token else {
+        return Err(EndpointRejection::BadRequest(
+            "Download token required!".into(),
+        ));
+    };
+
+    let token_hash = hash_token(confirm_token.token.as_bytes());
+    let Some(export_id) = DataExport::find_by_token(token_hash, db).await? else {
+        return Err(EndpointRejection::BadRequest(
+            "Your data export download link is no longer valid.".into(),
+        ));
+    };
+
+    let Ok(response) = ServeFile::new(archive_path(export_id))
+        .oneshot(Request::new(Body::empty()))
+        .await;
+    if response.status() != StatusCode::OK {
+        return Err(EndpointRejection::NotFound(
+            "Data export archive not found.".into(),
+        ));
+    }
+
+    let mut response = response.map(Body::new).into_response();
+    response.headers_mut().insert(
+        header::CONTENT_DISPOSITION,
+        HeaderValue::from_static("attachment; filename=\"reapears-data-export.zip\""),
+    );
+    Ok(response)
+}
+
+/// Assembles the user data export archive and emails the download link
+async fn build_data_export(
+    user_id: ModelID,
+    export_id: ModelID,
+    db: DatabaseConnection,
+    outlook: Mail,
+) -> ServerResult<()> {
+    let archive = DataExport::collect(user_id, db.clone()).await?;
+    tokio::fs::create_dir_all(DATA_EXPORT_DIR).await?;
+    archive.write(archive_path(export_id)).await?;
+
+    let (token, hash) = Token::default().into_parts();
+    DataExport::complete(export_id, hash, db.clone()).await?;
+
+    let (first_name, email_address) = EmailModel::find_user(user_id, db).await?;
+    let domain = SERVER_DOMAIN_NAME.get().unwrap();
+    let link = format!("{domain}/account/data-export/download?token={token}");
+    let email = outlook.data_export(&first_name, &email_address, &link)?;
+    outlook.send(email).await
+}
+
+/// Emails the user that their data export could not be prepared
+async fn notify_data_export_failed(
+    user_id: ModelID,
+    db: DatabaseConnection,
+    outlook: Mail,
+) -> ServerResult<()> {
+    let (first_name, email_address) = EmailModel::find_user(user_id, db).await?;
+    let email = outlook.data_export_failed(&first_name, &email_address)?;
+    outlook.send(email).await
+}
@@ -0,0 +1,21 @@
+//! Personal data export impls
+
+mod archive;
+pub mod db;
+pub mod handlers;
+
+use std::path::PathBuf;
+
+use crate::{settings::DATA_EXPORT_DIR, types::ModelID};
+
+pub use archive::{ArchiveImage, DataArchive};
+
+/// Personal data export archive requested by a user
+#[derive(Debug, Clone, Copy)]
+pub struct DataExport;
+
+/// Returns the path the data export archive is saved at
+#[must_use]
+pub fn archive_path(export_id: ModelID) -> PathBuf {
+    PathBuf::from(DATA_EXPORT_DIR).join(format!("{}.zip", export_id.0))
+}
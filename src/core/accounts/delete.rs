@@ -4,8 +4,8 @@ use time::{Duration, OffsetDateTime};
 use tokio::task::JoinSet;
 
 use crate::{
-    accounts::user::models::User, error::ServerResult, server::state::DatabaseConnection,
-    types::ModelID,
+    accounts::user::models::User, auth::TokenHash, error::ServerResult,
+    server::state::DatabaseConnection, types::ModelID,
 };
 
 /// A list of user ids that requested for account deletion.
@@ -59,9 +59,9 @@ impl AccountDelete {
                     // Filter only account that can be deleted;
                     // such that the MAX_DAYS_TO_DELETE_ACCOUNT has been reached
                     .filter(|rec| {
-                        let request_date = rec.requested_at
-                            - Duration::days(i64::from(crate::MAX_DAYS_TO_DELETE_ACCOUNT));
-                        request_date.date() <= now
+                        let delete_date = rec.requested_at
+                            + Duration::days(i64::from(crate::MAX_DAYS_TO_DELETE_ACCOUNT));
+                        delete_date.date() <= now
                     })
                     .map(|rec| ModelID::from(rec.user_id))
                     .collect();
@@ -79,34 +79,63 @@ impl AccountDelete {
     }
 
     /// Insert user account delete request into the database
-    #[tracing::instrument(skip(db), name = "Insert account delete request")]
-    pub async fn insert(user_id: ModelID, db: DatabaseConnection) -> ServerResult<()> {
-        match sqlx::query!(
+    /// and logs the user out of all their sessions.
+    ///
+    /// Returns `false` if the user already requested account deletion.
+    #[tracing::instrument(skip(db, token), name = "Insert account delete request")]
+    pub async fn insert(
+        user_id: ModelID,
+        token: TokenHash,
+        db: DatabaseConnection,
+    ) -> ServerResult<bool> {
+        let mut tx = db.pool.begin().await?;
+
+        let inserted = match sqlx::query!(
             r#"
                 INSERT INTO accounts.account_delete_requests(
                     user_id, 
-                    requested_at
+                    requested_at,
+                    token
                 )
-                 VALUES($1, $2);
-              "#,
+                VALUES($1, $2, $3)
+                ON CONFLICT (user_id) DO NOTHING;
+            "#,
             user_id.0,
             OffsetDateTime::now_utc(),
+            &token[..],
         )
-        .execute(&db.pool)
+        .execute(&mut *tx)
         .await
         {
             Ok(result) => {
                 tracing::debug!("Account delete request inserted successfully: {:?}", result);
-                Ok(())
+                result.rows_affected() == 1
             }
             Err(err) => {
                 tracing::error!(
                     "Database error, failed to insert account delete request: {}",
                     err
                 );
-                Err(err.into())
+                return Err(err.into());
             }
+        };
+
+        if let Err(err) = sqlx::query!(
+            r#"
+                DELETE FROM auth.sessions session
+                WHERE session.user_id = $1
+            "#,
+            user_id.0
+        )
+        .execute(&mut *tx)
+        .await
+        {
+            tracing::error!("Database error, failed to delete user sessions: {}", err);
+            return Err(err.into());
         }
+
+        tx.commit().await?;
+        Ok(inserted)
     }
 
     /// Deletes account delete request from the database.
@@ -138,4 +167,36 @@ impl AccountDelete {
             }
         }
     }
+
+    /// Deletes the account delete request matching the cancel `token` from the database.
+    ///
+    /// Returns `false` if no account delete request matched the token.
+    #[tracing::instrument(name = "Cancel account delete request", skip(db, token))]
+    pub async fn cancel(token: TokenHash, db: DatabaseConnection) -> ServerResult<bool> {
+        match sqlx::query!(
+            r#"
+                 DELETE FROM accounts.account_delete_requests
+                 WHERE token = $1
+            "#,
+            &token[..]
+        )
+        .execute(&db.pool)
+        .await
+        {
+            Ok(result) => {
+                tracing::debug!(
+                    "Account delete request cancelled successfully: {:?}",
+                    result
+                );
+                Ok(result.rows_affected() == 1)
+            }
+            Err(err) => {
+                tracing::error!(
+                    "Database error, failed to cancel account delete request: {}",
+                    err
+                );
+                Err(err.into())
+            }
+        }
+    }
 }
@@ -0,0 +1,164 @@
+//! User handle database impls
+
+use time::OffsetDateTime;
+
+use crate::{
+    endpoint::EndpointRejection,
+    error::{ServerError, ServerResult},
+    server::state::DatabaseConnection,
+    types::ModelID,
+};
+
+use super::{HandleLookup, UserHandle};
+
+impl UserHandle {
+    /// Fetches the user with the current or a previous handle from the database,
+    /// handles are matched case-insensitively.
+    #[tracing::instrument(skip(db))]
+    pub async fn find(handle: &str, db: DatabaseConnection) -> ServerResult<Option<HandleLookup>> {
+        match sqlx::query!(
+            r#"
+                SELECT user_.id,
+                    user_.handle AS "handle!",
+                    lower(user_.handle) = lower($1) AS "is_current!"
+                FROM accounts.users user_
+                WHERE lower(user_.handle) = lower($1)
+                    OR user_.id = (
+                        SELECT history.user_id
+                        FROM accounts.user_handle_history history
+                        WHERE history.handle = lower($1)
+                    )
+                ORDER BY 3 DESC
+                LIMIT 1
+            "#,
+            handle
+        )
+        .fetch_optional(&db.pool)
+        .await
+        {
+            Ok(rec) => Ok(rec.map(|rec| {
+                if rec.is_current {
+                    HandleLookup::Current(rec.id.into())
+                } else {
+                    HandleLookup::Renamed(rec.handle)
+                }
+            })),
+            Err(err) => {
+                tracing::error!("Database error, failed to fetch user handle: {}", err);
+                Err(err.into())
+            }
+        }
+    }
+
+    /// Updates the user handle in the database,
+    /// the previous handle is kept to redirect to the new one.
+    #[tracing::instrument(skip(db))]
+    pub async fn update(
+        user_id: ModelID,
+        handle: String,
+        db: DatabaseConnection,
+    ) -> ServerResult<()> {
+        let mut tx = db.pool.begin().await?;
+
+        let previous = match sqlx::query!(
+            r#"
+                SELECT user_.handle
+                FROM accounts.users user_
+                WHERE user_.id = $1
+                FOR UPDATE
+            "#,
+            user_id.0
+        )
+        .fetch_one(&mut *tx)
+        .await
+        {
+            Ok(rec) => rec.handle,
+            Err(err) => {
+                tracing::error!("Database error, failed to fetch user handle: {}", err);
+                return Err(err.into());
+            }
+        };
+
+        if let Err(err) = sqlx::query!(
+            r#"
+                UPDATE accounts.users user_
+                SET handle = $1
+                WHERE user_.id = $2
+            "#,
+            handle,
+            user_id.0
+        )
+        .execute(&mut *tx)
+        .await
+        {
+            // Handle database constraint error
+            handle_handle_database_error(&err)?;
+
+            tracing::error!("Database error, failed to update user handle: {}", err);
+            return Err(err.into());
+        }
+
+        // The handle no longer redirects to its previous owner
+        if let Err(err) = sqlx::query!(
+            r#"
+                DELETE FROM accounts.user_handle_history history
+                WHERE history.handle = lower($1)
+            "#,
+            handle
+        )
+        .execute(&mut *tx)
+        .await
+        {
+            tracing::error!("Database error, failed to release user handle: {}", err);
+            return Err(err.into());
+        }
+
+        if let Some(previous) = previous.filter(|previous| !previous.eq_ignore_ascii_case(&handle))
+        {
+            if let Err(err) = sqlx::query!(
+                r#"
+                    INSERT INTO accounts.user_handle_history(
+                        handle,
+                        user_id,
+                        changed_at
+                    )
+                    VALUES(lower($1), $2, $3)
+                    ON CONFLICT (handle) DO UPDATE
+                        SET user_id = EXCLUDED.user_id,
+                            changed_at = EXCLUDED.changed_at
+                "#,
+                previous,
+                user_id.0,
+                OffsetDateTime::now_utc()
+            )
+            .execute(&mut *tx)
+            .await
+            {
+                tracing::error!(
+                    "Database error, failed to insert user handle history: {}",
+                    err
+                );
+                return Err(err.into());
+            }
+        }
+
+        tx.commit().await?;
+        tracing::debug!("User handle updated successfully");
+        Ok(())
+    }
+}
+
+/// Handle user handles database constraints errors
+pub fn handle_handle_database_error(err: &sqlx::Error) -> ServerResult<()> {
+    if let sqlx::Error::Database(db_err) = err {
+        // Handle db unique constraints
+        if db_err.is_unique_violation() && db_err.constraint() == Some("users_handle_key") {
+            tracing::error!("Database error, user handle already taken. {:?}", err);
+            return Err(ServerError::rejection(EndpointRejection::Conflict(
+                "This handle is already taken.".into(),
+            )));
+        }
+    }
+
+    Ok(())
+}
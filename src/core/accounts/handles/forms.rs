@@ -0,0 +1,43 @@
+//! User handle forms impls
+
+use axum::{
+    async_trait,
+    extract::{rejection::JsonRejection, FromRequest, Request},
+    Json,
+};
+use serde::Deserialize;
+
+use crate::{
+    endpoint::{validators::ValidateString, EndpointRejection, EndpointResult},
+    server::state::ServerState,
+};
+
+/// User handle update form
+#[derive(Debug, Clone, Deserialize)]
+pub struct HandleUpdateForm {
+    pub handle: String,
+}
+
+impl HandleUpdateForm {
+    /// Validates handle update form inputs
+    fn validate(&mut self) -> EndpointResult<()> {
+        // Clean the data
+        self.handle = self.handle.trim().trim_start_matches('@').to_owned();
+
+        self.handle.validate_handle()
+    }
+}
+
+#[async_trait]
+impl FromRequest<ServerState> for HandleUpdateForm
+where
+    Json<Self>: FromRequest<ServerState, Rejection = JsonRejection>,
+{
+    type Rejection = EndpointRejection;
+
+    async fn from_request(req: Request, state: &ServerState) -> Result<Self, Self::Rejection> {
+        let Json(mut handle) = Json::<Self>::from_request(req, state).await?;
+        handle.validate()?;
+        Ok(handle)
+    }
+}
@@ -0,0 +1,46 @@
+//! User handle http handlers impls
+
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::{IntoResponse, Redirect, Response},
+};
+
+use crate::{
+    accounts::user_profile::handlers::user_profile,
+    auth::CurrentUser,
+    endpoint::{EndpointRejection, EndpointResult},
+    server::state::DatabaseConnection,
+};
+
+use super::{forms::HandleUpdateForm, HandleLookup, UserHandle};
+
+/// Handles the `PUT /account/settings/handle` route.
+#[tracing::instrument(skip(db))]
+pub async fn user_handle_update(
+    user: CurrentUser,
+    State(db): State<DatabaseConnection>,
+    form: HandleUpdateForm,
+) -> EndpointResult<StatusCode> {
+    UserHandle::update(user.id, form.handle, db).await?;
+    Ok(StatusCode::OK)
+}
+
+/// Handles the `GET /account/users/handle/:handle` route.
+///
+/// Returns the user profile, previous handles redirect to the current one.
+#[tracing::instrument(skip(db))]
+pub async fn user_profile_by_handle(
+    user: Option<CurrentUser>,
+    Path(handle): Path<String>,
+    State(db): State<DatabaseConnection>,
+) -> EndpointResult<Response> {
+    match UserHandle::find(&handle, db.clone()).await? {
+        Some(HandleLookup::Current(user_id)) => Ok(user_profile(user, user_id, State(db))
+            .await?
+            .into_response()),
+        // Relative to the requested handle path
+        Some(HandleLookup::Renamed(current)) => Ok(Redirect::temporary(&current).into_response()),
+        None => Err(EndpointRejection::NotFound("User profile not found".into())),
+    }
+}
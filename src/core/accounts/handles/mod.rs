@@ -0,0 +1,21 @@
+//! User handle impls
+
+pub mod db;
+pub mod forms;
+pub mod handlers;
+
+use crate::types::ModelID;
+
+/// The model representing the `handle` column in the `users` database table
+/// and its rename history.
+#[derive(Debug, Clone, Copy)]
+pub struct UserHandle;
+
+/// The user found by a handle
+#[derive(Debug, Clone)]
+pub enum HandleLookup {
+    /// The handle is the current handle of the user
+    Current(ModelID),
+    /// The handle was renamed, holds the user current handle
+    Renamed(String),
+}
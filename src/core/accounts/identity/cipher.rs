@@ -0,0 +1,88 @@
+//! Identity files encryption impls
+
+use std::{env, fmt};
+
+use aes_gcm::{
+    aead::{Aead, AeadCore, KeyInit, OsRng},
+    Aes256Gcm, Nonce,
+};
+
+use crate::error::{ServerError, ServerResult};
+
+/// Context used to derive the encryption key from the server secret
+const KEY_DERIVATION_CONTEXT: &str = "reapears 2023-10-21 identity verification files";
+/// AES-GCM nonce length in bytes
+const NONCE_LENGTH: usize = 12;
+
+/// Server key used to encrypt identity verification files at rest
+///
+/// Encrypted files are laid out as `nonce || ciphertext`.
+#[derive(Clone)]
+pub struct IdentityKey(Aes256Gcm);
+
+impl fmt::Debug for IdentityKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("IdentityKey{..}").finish()
+    }
+}
+
+impl IdentityKey {
+    /// Derives the encryption key from the server `secret`
+    #[must_use]
+    pub fn new(secret: &[u8]) -> Self {
+        let key = blake3::derive_key(KEY_DERIVATION_CONTEXT, secret);
+        Self(Aes256Gcm::new(&key.into()))
+    }
+
+    /// Loads the encryption key from the `IDENTITY_KEY` environment variable
+    ///
+    /// # Panics
+    ///
+    /// Panics if the environment variable is not set or is too short
+    #[must_use]
+    pub fn from_env() -> Self {
+        let secret = env::var("IDENTITY_KEY").expect("IDENTITY_KEY environment variable not set.");
+        assert!(
+            secret.len() >= 32,
+            "Key too short, identity key must be at least 32 bytes"
+        );
+        Self::new(secret.as_bytes())
+    }
+
+    /// Encrypts the file `content`
+    ///
+    /// # Errors
+    ///
+    /// Return an error if the encryption failed
+    pub fn encrypt(&self, content: &[u8]) -> ServerResult<Vec<u8>> {
+        let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+        let ciphertext = self.0.encrypt(&nonce, content).map_err(|err| {
+            tracing::error!("Encryption error, failed to encrypt identity file: {}", err);
+            ServerError::new("Failed to encrypt identity file")
+        })?;
+
+        let mut encrypted = Vec::with_capacity(NONCE_LENGTH + ciphertext.len());
+        encrypted.extend_from_slice(&nonce);
+        encrypted.extend_from_slice(&ciphertext);
+        Ok(encrypted)
+    }
+
+    /// Decrypts a file encrypted with `encrypt`
+    ///
+    /// # Errors
+    ///
+    /// Return an error if the file is malformed or was not encrypted with this key
+    pub fn decrypt(&self, encrypted: &[u8]) -> ServerResult<Vec<u8>> {
+        if encrypted.len() < NONCE_LENGTH {
+            tracing::error!("Encryption error, identity file too short");
+            return Err(ServerError::new("Malformed identity file"));
+        }
+        let (nonce, ciphertext) = encrypted.split_at(NONCE_LENGTH);
+        self.0
+            .decrypt(Nonce::from_slice(nonce), ciphertext)
+            .map_err(|err| {
+                tracing::error!("Encryption error, failed to decrypt identity file: {}", err);
+                ServerError::new("Failed to decrypt identity file")
+            })
+    }
+}
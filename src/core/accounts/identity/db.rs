@@ -0,0 +1,363 @@
+//! Identity verification database impls
+
+use time::{Duration, OffsetDateTime};
+
+use crate::{
+    accounts::user::models::UserIndex,
+    endpoint::EndpointRejection,
+    error::{ServerError, ServerResult},
+    server::state::DatabaseConnection,
+    settings::IDENTITY_UPLOAD_DIR,
+    types::{ModelID, Pagination},
+};
+
+use super::{
+    IdentityFile, IdentityReview, IdentityReviewList, IdentityStatus, IdentityVerification,
+};
+
+impl IdentityVerification {
+    /// Fetches the user latest identity verification from the database
+    #[tracing::instrument(skip(db))]
+    pub async fn find_latest(
+        user_id: ModelID,
+        db: DatabaseConnection,
+    ) -> ServerResult<Option<Self>> {
+        match sqlx::query!(
+            r#"
+                SELECT verification.id,
+                    verification.status,
+                    verification.reject_reason,
+                    verification.submitted_at,
+                    verification.reviewed_at
+                FROM accounts.identity_verifications verification
+                WHERE verification.user_id = $1
+                ORDER BY verification.submitted_at DESC
+                LIMIT 1
+            "#,
+            user_id.0
+        )
+        .fetch_optional(&db.pool)
+        .await
+        {
+            Ok(rec) => Ok(rec.map(|rec| {
+                Self::from_row(
+                    rec.id.into(),
+                    &rec.status,
+                    rec.reject_reason,
+                    rec.submitted_at,
+                    rec.reviewed_at,
+                )
+            })),
+            Err(err) => {
+                tracing::error!(
+                    "Database error, failed to fetch identity verification: {}",
+                    err
+                );
+                Err(err.into())
+            }
+        }
+    }
+
+    /// Inserts the user identity verification into the database
+    #[tracing::instrument(skip(db, document, selfie))]
+    pub async fn insert(
+        user_id: ModelID,
+        document: IdentityFile,
+        selfie: IdentityFile,
+        db: DatabaseConnection,
+    ) -> ServerResult<ModelID> {
+        match sqlx::query!(
+            r#"
+                INSERT INTO accounts.identity_verifications(
+                    id,
+                    user_id,
+                    document,
+                    document_type,
+                    selfie,
+                    selfie_type,
+                    status,
+                    submitted_at
+                )
+                VALUES($1, $2, $3, $4, $5, $6, $7, $8)
+                RETURNING id
+            "#,
+            ModelID::new().0,
+            user_id.0,
+            document.file,
+            document.content_type,
+            selfie.file,
+            selfie.content_type,
+            IdentityStatus::Pending.as_str(),
+            OffsetDateTime::now_utc(),
+        )
+        .fetch_one(&db.pool)
+        .await
+        {
+            Ok(rec) => Ok(rec.id.into()),
+            Err(err) => {
+                // Handle database constraint error
+                handle_identity_database_error(&err)?;
+
+                tracing::error!(
+                    "Database error, failed to insert identity verification: {}",
+                    err
+                );
+                Err(err.into())
+            }
+        }
+    }
+
+    /// Fetches identity verifications waiting for review from the database,
+    /// oldest first.
+    #[tracing::instrument(skip(db))]
+    pub async fn pending_records(
+        pg: Pagination,
+        db: DatabaseConnection,
+    ) -> ServerResult<IdentityReviewList> {
+        let (offset, limit) = pg.offset_limit();
+        match sqlx::query!(
+            r#"
+                SELECT verification.id,
+                    verification.submitted_at,
+                    user_.id AS user_id,
+                    user_.first_name AS user_first_name,
+                    user_.last_name AS user_last_name,
+                    user_.identity_verified AS user_identity_verified,
+                    user_profile.photo AS "user_photo?"
+                FROM accounts.identity_verifications verification
+                LEFT JOIN accounts.users user_
+                    ON verification.user_id = user_.id
+                LEFT JOIN accounts.user_profiles user_profile
+                    ON verification.user_id = user_profile.user_id
+
+                WHERE verification.status = 'pending'
+                ORDER BY verification.submitted_at
+                LIMIT $1
+                OFFSET $2
+            "#,
+            limit,
+            offset
+        )
+        .fetch_all(&db.pool)
+        .await
+        {
+            Ok(records) => {
+                let reviews = records
+                    .into_iter()
+                    .map(|rec| IdentityReview {
+                        id: rec.id.into(),
+                        user: UserIndex::from_row(
+                            rec.user_id.into(),
+                            rec.user_first_name,
+                            rec.user_last_name,
+                            rec.user_photo,
+                            rec.user_identity_verified,
+                        ),
+                        submitted_at: rec.submitted_at,
+                    })
+                    .collect();
+                Ok(reviews)
+            }
+            Err(err) => {
+                tracing::error!(
+                    "Database error, failed to fetch pending identity verifications: {}",
+                    err
+                );
+                Err(err.into())
+            }
+        }
+    }
+
+    /// Fetches the document and selfie of an identity verification
+    /// waiting for review from the database
+    #[tracing::instrument(skip(db))]
+    pub async fn find_files(
+        id: ModelID,
+        db: DatabaseConnection,
+    ) -> ServerResult<Option<(IdentityFile, IdentityFile)>> {
+        match sqlx::query!(
+            r#"
+                SELECT verification.document AS "document!",
+                    verification.document_type AS "document_type!",
+                    verification.selfie AS "selfie!",
+                    verification.selfie_type AS "selfie_type!"
+                FROM accounts.identity_verifications verification
+                WHERE verification.id = $1
+                    AND verification.status = 'pending'
+                    AND verification.document IS NOT NULL
+                    AND verification.selfie IS NOT NULL
+            "#,
+            id.0
+        )
+        .fetch_optional(&db.pool)
+        .await
+        {
+            Ok(rec) => Ok(rec.map(|rec| {
+                (
+                    IdentityFile {
+                        file: rec.document,
+                        content_type: rec.document_type,
+                    },
+                    IdentityFile {
+                        file: rec.selfie,
+                        content_type: rec.selfie_type,
+                    },
+                )
+            })),
+            Err(err) => {
+                tracing::error!("Database error, failed to fetch identity files: {}", err);
+                Err(err.into())
+            }
+        }
+    }
+
+    /// Records the staff decision on the identity verification in the database,
+    /// and marks the user identity as verified if approved.
+    ///
+    /// Returns the submitted files to be deleted,
+    /// or `None` if the verification is not waiting for review.
+    #[tracing::instrument(skip(db))]
+    pub async fn review(
+        id: ModelID,
+        reviewer_id: ModelID,
+        status: IdentityStatus,
+        reject_reason: Option<String>,
+        db: DatabaseConnection,
+    ) -> ServerResult<Option<Vec<String>>> {
+        let mut tx = db.pool.begin().await?;
+
+        let rec = match sqlx::query!(
+            r#"
+                UPDATE accounts.identity_verifications verification
+                SET status = $1,
+                    reject_reason = $2,
+                    reviewed_at = $3,
+                    reviewer_id = $4,
+                    document = NULL,
+                    document_type = NULL,
+                    selfie = NULL,
+                    selfie_type = NULL
+                FROM (
+                    SELECT id, document, selfie
+                    FROM accounts.identity_verifications
+                    WHERE id = $5
+                        AND status = 'pending'
+                    FOR UPDATE
+                ) submitted
+                WHERE verification.id = submitted.id
+                RETURNING verification.user_id,
+                    submitted.document,
+                    submitted.selfie
+            "#,
+            status.as_str(),
+            reject_reason,
+            OffsetDateTime::now_utc(),
+            reviewer_id.0,
+            id.0
+        )
+        .fetch_optional(&mut *tx)
+        .await
+        {
+            Ok(Some(rec)) => rec,
+            Ok(None) => return Ok(None),
+            Err(err) => {
+                tracing::error!(
+                    "Database error, failed to review identity verification: {}",
+                    err
+                );
+                return Err(err.into());
+            }
+        };
+
+        if status == IdentityStatus::Approved {
+            if let Err(err) = sqlx::query!(
+                r#"
+                    UPDATE accounts.users user_
+                    SET identity_verified = true
+                    WHERE user_.id = $1
+                "#,
+                rec.user_id
+            )
+            .execute(&mut *tx)
+            .await
+            {
+                tracing::error!("Database error, failed to verify user identity: {}", err);
+                return Err(err.into());
+            }
+        }
+
+        tx.commit().await?;
+        tracing::debug!("Identity verification reviewed successfully");
+        Ok(Some(rec.document.into_iter().chain(rec.selfie).collect()))
+    }
+
+    /// Fetches the identity files still waiting for review from the database
+    #[tracing::instrument(skip(db))]
+    pub async fn pending_files(db: DatabaseConnection) -> ServerResult<Vec<String>> {
+        match sqlx::query!(
+            r#"
+                SELECT unnest(ARRAY[verification.document, verification.selfie]) AS file
+                FROM accounts.identity_verifications verification
+                WHERE verification.status = 'pending'
+            "#,
+        )
+        .fetch_all(&db.pool)
+        .await
+        {
+            Ok(records) => Ok(records.into_iter().filter_map(|rec| rec.file).collect()),
+            Err(err) => {
+                tracing::error!("Database error, failed to fetch identity files: {}", err);
+                Err(err.into())
+            }
+        }
+    }
+
+    /// Deletes identity files no longer waiting for review from the file system,
+    /// like ones left behind by a failed review or a deleted account.
+    #[tracing::instrument(skip(db))]
+    pub async fn purge_files(db: DatabaseConnection) -> ServerResult<()> {
+        let pending = Self::pending_files(db).await?;
+        let recent = OffsetDateTime::now_utc() - Duration::hours(1);
+
+        let Ok(mut entries) = tokio::fs::read_dir(IDENTITY_UPLOAD_DIR).await else {
+            return Ok(());
+        };
+        while let Some(entry) = entries.next_entry().await? {
+            // Skip files still being submitted
+            let is_recent = entry
+                .metadata()
+                .await
+                .and_then(|metadata| metadata.modified())
+                .is_ok_and(|modified| OffsetDateTime::from(modified) > recent);
+            let is_pending = pending
+                .iter()
+                .any(|file| entry.file_name().to_str() == Some(file.as_str()));
+            if !is_recent && !is_pending {
+                if let Err(err) = tokio::fs::remove_file(entry.path()).await {
+                    tracing::error!("Failed to delete identity file: {}", err);
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Handle identity verifications database constraints errors
+pub fn handle_identity_database_error(err: &sqlx::Error) -> ServerResult<()> {
+    if let sqlx::Error::Database(db_err) = err {
+        // Handle db unique constraints
+        if db_err.is_unique_violation()
+            && db_err.constraint() == Some("identity_verifications_pending_idx")
+        {
+            tracing::error!(
+                "Database error, identity verification already pending. {:?}",
+                err
+            );
+            return Err(ServerError::rejection(EndpointRejection::Conflict(
+                "Your identity verification is already waiting for review.".into(),
+            )));
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,94 @@
+//! Identity verification forms impls
+
+use axum::{
+    async_trait,
+    extract::{rejection::JsonRejection, FromRequest, Request},
+    Json,
+};
+use image::ImageFormat;
+use serde::Deserialize;
+
+use crate::{
+    endpoint::{
+        validators::{TransformString, ValidateString},
+        EndpointRejection, EndpointResult,
+    },
+    files::UploadedFile,
+    server::state::ServerState,
+};
+
+use super::{IdentityFile, IdentityKey};
+
+/// Identity verification reject form
+#[derive(Debug, Clone, Deserialize)]
+pub struct IdentityRejectForm {
+    pub reason: String,
+}
+
+impl IdentityRejectForm {
+    /// Validates identity reject form inputs
+    fn validate(&mut self) -> EndpointResult<()> {
+        // Clean the data
+        self.reason = self.reason.clean();
+
+        self.reason
+            .validate_len(1, 512, "Reject reason must be between 1 and 512 characters")
+    }
+}
+
+#[async_trait]
+impl FromRequest<ServerState> for IdentityRejectForm
+where
+    Json<Self>: FromRequest<ServerState, Rejection = JsonRejection>,
+{
+    type Rejection = EndpointRejection;
+
+    async fn from_request(req: Request, state: &ServerState) -> Result<Self, Self::Rejection> {
+        let Json(mut reject) = Json::<Self>::from_request(req, state).await?;
+        reject.validate()?;
+        Ok(reject)
+    }
+}
+
+/// An uploaded identity file ready to be saved to the file system
+#[derive(Debug, Clone)]
+pub struct EncryptedFile {
+    pub file: IdentityFile,
+    pub content: Vec<u8>,
+}
+
+impl EncryptedFile {
+    /// Validates the uploaded file is an image and encrypts it
+    pub async fn try_from_upload(upload: UploadedFile, key: IdentityKey) -> EndpointResult<Self> {
+        if !crate::SUPPORTED_UPLOAD_IMAGE_FORMATS.contains(&upload.file_ext.to_lowercase().as_str())
+        {
+            return Err(EndpointRejection::BadRequest(
+                format!(
+                    "Unsupported image format: {:?}. Supported formats: jpg, png, webp.",
+                    upload.file_ext
+                )
+                .into(),
+            ));
+        }
+        let content_type = match image::guess_format(&upload.content) {
+            Ok(format @ (ImageFormat::Jpeg | ImageFormat::Png | ImageFormat::WebP)) => {
+                format.to_mime_type()
+            }
+            _ => {
+                return Err(EndpointRejection::BadRequest(
+                    "The uploaded file is not a valid image.".into(),
+                ))
+            }
+        };
+
+        let content = tokio::task::spawn_blocking(move || key.encrypt(&upload.content)).await??;
+
+        Ok(Self {
+            file: IdentityFile {
+                file: format!("{}.enc", upload.id),
+                content_type: content_type.to_owned(),
+            },
+            content,
+        })
+    }
+}
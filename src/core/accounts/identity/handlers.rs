@@ -0,0 +1,238 @@
+//! Identity verification http handlers impls
+
+use axum::{
+    extract::{Multipart, Query, State},
+    http::{header, HeaderValue, StatusCode},
+    response::{IntoResponse, Response},
+    Json,
+};
+
+use crate::{
+    auth::{AdminUser, CurrentUser},
+    endpoint::{EndpointRejection, EndpointResult},
+    files,
+    server::state::DatabaseConnection,
+    settings::IDENTITY_UPLOAD_DIR,
+    types::{ModelID, Pagination},
+};
+
+use super::{
+    delete_identity_files,
+    forms::{EncryptedFile, IdentityRejectForm},
+    identity_file_path, IdentityFile, IdentityKey, IdentityReviewList, IdentityStatus,
+    IdentityVerification,
+};
+
+/// Handles the `GET /account/settings/identity` route.
+///
+/// Returns the user latest identity verification
+#[tracing::instrument(skip(user, db))]
+pub async fn identity_status(
+    user: CurrentUser,
+    State(db): State<DatabaseConnection>,
+) -> EndpointResult<Json<Option<IdentityVerification>>> {
+    let verification = IdentityVerification::find_latest(user.id, db).await?;
+    Ok(Json(verification))
+}
+
+/// Handles the `POST /account/settings/identity` route.
+///
+/// Receives the user ID document and selfie in the `document`
+/// and `selfie` multipart fields and saves them encrypted for staff review.
+#[tracing::instrument(skip(user, db, key, multipart))]
+pub async fn identity_submit(
+    user: CurrentUser,
+    State(db): State<DatabaseConnection>,
+    State(key): State<IdentityKey>,
+    multipart: Multipart,
+) -> EndpointResult<(StatusCode, &'static str)> {
+    if let Some(verification) = IdentityVerification::find_latest(user.id, db.clone()).await? {
+        match verification.status {
+            IdentityStatus::Approved => {
+                return Err(EndpointRejection::Conflict(
+                    "Your identity is already verified.".into(),
+                ))
+            }
+            IdentityStatus::Pending => {
+                return Err(EndpointRejection::Conflict(
+                    "Your identity verification is already waiting for review.".into(),
+                ))
+            }
+            IdentityStatus::Rejected => {}
+        }
+    }
+
+    let (handler, mut uploads) =
+        files::accept_uploads(multipart, crate::IDENTITY_VERIFICATION_FILES);
+    handler.accept().await?; // Receive files from the client
+
+    let (mut document, mut selfie) = (None, None);
+    while let Some(file) = uploads.files().await {
+        match file.field_name.as_deref() {
+            Some("document") => document = Some(file),
+            Some("selfie") => selfie = Some(file),
+            _ => {}
+        }
+    }
+    let (Some(document), Some(selfie)) = (document, selfie) else {
+        return Err(EndpointRejection::BadRequest(
+            "An ID document and a selfie are required".into(),
+        ));
+    };
+
+    let document = EncryptedFile::try_from_upload(document, key.clone()).await?;
+    let selfie = EncryptedFile::try_from_upload(selfie, key).await?;
+
+    // Save the encrypted files to the file system
+    tokio::fs::create_dir_all(IDENTITY_UPLOAD_DIR).await?;
+    let saved = [document.file.file.clone(), selfie.file.file.clone()];
+    for file in [&document, &selfie] {
+        if let Err(err) =
+            files::save_file(&identity_file_path(&file.file.file), &file.content).await
+        {
+            delete_identity_files(saved.into_iter()).await;
+            return Err(err.into());
+        }
+    }
+
+    if let Err(err) = IdentityVerification::insert(user.id, document.file, selfie.file, db).await {
+        delete_identity_files(saved.into_iter()).await;
+        return Err(err.into());
+    }
+
+    Ok((
+        StatusCode::CREATED,
+        "Your identity verification was submitted for review",
+    ))
+}
+
+/// Handles the `GET /account/identity-verifications` route.
+///
+/// Returns the identity verifications waiting for review, oldest first
+#[tracing::instrument(skip(db))]
+pub async fn identity_review_list(
+    _: AdminUser,
+    pg: Option<Query<Pagination>>,
+    State(db): State<DatabaseConnection>,
+) -> EndpointResult<Json<IdentityReviewList>> {
+    let pagination = pg.unwrap_or_default().0;
+    let pending = IdentityVerification::pending_records(pagination, db).await?;
+    Ok(Json(pending))
+}
+
+/// Handles the `GET /account/identity-verifications/:verification_id/document` route.
+#[tracing::instrument(skip(db, key))]
+pub async fn identity_document(
+    _: AdminUser,
+    verification_id: ModelID,
+    State(db): State<DatabaseConnection>,
+    State(key): State<IdentityKey>,
+) -> EndpointResult<Response> {
+    let (document, _) = find_files(verification_id, db).await?;
+    decrypted_response(document, key).await
+}
+
+/// Handles the `GET /account/identity-verifications/:verification_id/selfie` route.
+#[tracing::instrument(skip(db, key))]
+pub async fn identity_selfie(
+    _: AdminUser,
+    verification_id: ModelID,
+    State(db): State<DatabaseConnection>,
+    State(key): State<IdentityKey>,
+) -> EndpointResult<Response> {
+    let (_, selfie) = find_files(verification_id, db).await?;
+    decrypted_response(selfie, key).await
+}
+
+/// Handles the `POST /account/identity-verifications/:verification_id/approve` route.
+///
+/// Marks the user identity as verified and deletes the submitted files
+#[tracing::instrument(skip(db))]
+pub async fn identity_approve(
+    user: AdminUser,
+    verification_id: ModelID,
+    State(db): State<DatabaseConnection>,
+) -> EndpointResult<StatusCode> {
+    review(
+        verification_id,
+        user.id(),
+        IdentityStatus::Approved,
+        None,
+        db,
+    )
+    .await?;
+    Ok(StatusCode::OK)
+}
+
+/// Handles the `POST /account/identity-verifications/:verification_id/reject` route.
+///
+/// Rejects the identity verification with a reason and deletes the submitted files
+#[tracing::instrument(skip(db, form))]
+pub async fn identity_reject(
+    user: AdminUser,
+    verification_id: ModelID,
+    State(db): State<DatabaseConnection>,
+    form: IdentityRejectForm,
+) -> EndpointResult<StatusCode> {
+    review(
+        verification_id,
+        user.id(),
+        IdentityStatus::Rejected,
+        Some(form.reason),
+        db,
+    )
+    .await?;
+    Ok(StatusCode::OK)
+}
+
+/// Records the staff decision and deletes the submitted files
+async fn review(
+    verification_id: ModelID,
+    reviewer_id: ModelID,
+    status: IdentityStatus,
+    reject_reason: Option<String>,
+    db: DatabaseConnection,
+) -> EndpointResult<()> {
+    let Some(files) =
+        IdentityVerification::review(verification_id, reviewer_id, status, reject_reason, db)
+            .await?
+    else {
+        return Err(EndpointRejection::NotFound(
+            "Identity verification not found or already reviewed.".into(),
+        ));
+    };
+
+    tokio::spawn(async move { delete_identity_files(files.into_iter()).await });
+    Ok(())
+}
+
+/// Finds the files of the identity verification waiting for review
+async fn find_files(
+    verification_id: ModelID,
+    db: DatabaseConnection,
+) -> EndpointResult<(IdentityFile, IdentityFile)> {
+    IdentityVerification::find_files(verification_id, db)
+        .await?
+        .ok_or_else(|| {
+            EndpointRejection::NotFound(
+                "Identity verification not found or already reviewed.".into(),
+            )
+        })
+}
+
+/// Decrypts the identity file into a response that is not cached
+async fn decrypted_response(file: IdentityFile, key: IdentityKey) -> EndpointResult<Response> {
+    let encrypted = tokio::fs::read(identity_file_path(&file.file)).await?;
+    let content = tokio::task::spawn_blocking(move || key.decrypt(&encrypted)).await??;
+
+    let content_type = HeaderValue::from_str(&file.content_type)
+        .unwrap_or_else(|_| HeaderValue::from_static("application/octet-stream"));
+    Ok((
+        [
+            (header::CONTENT_TYPE, content_type),
+            (header::CACHE_CONTROL, HeaderValue::from_static("no-store")),
+        ],
+        content,
+    )
+        .into_response())
+}
@@ -0,0 +1,115 @@
+//! User identity verification impls
+
+mod cipher;
+pub mod db;
+pub mod forms;
+pub mod handlers;
+
+use std::path::PathBuf;
+
+use serde::Serialize;
+use time::OffsetDateTime;
+
+use crate::{
+    accounts::user::models::UserIndex, files, settings::IDENTITY_UPLOAD_DIR, types::ModelID,
+};
+
+pub use cipher::IdentityKey;
+
+/// A `Vec` of identity verifications waiting for staff review
+pub type IdentityReviewList = Vec<IdentityReview>;
+
+/// The model representing a row in the `identity_verifications` database table.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IdentityVerification {
+    pub id: ModelID,
+    pub status: IdentityStatus,
+    pub reject_reason: Option<String>,
+    pub submitted_at: OffsetDateTime,
+    pub reviewed_at: Option<OffsetDateTime>,
+}
+
+impl IdentityVerification {
+    /// Creates a new `IdentityVerification` from the database row
+    #[must_use]
+    pub fn from_row(
+        id: ModelID,
+        status: &str,
+        reject_reason: Option<String>,
+        submitted_at: OffsetDateTime,
+        reviewed_at: Option<OffsetDateTime>,
+    ) -> Self {
+        Self {
+            id,
+            status: IdentityStatus::from_row(status),
+            reject_reason,
+            submitted_at,
+            reviewed_at,
+        }
+    }
+}
+
+/// An identity verification waiting for staff review
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IdentityReview {
+    pub id: ModelID,
+    pub user: UserIndex,
+    pub submitted_at: OffsetDateTime,
+}
+
+/// The review status of an identity verification
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum IdentityStatus {
+    /// Waiting for staff review
+    Pending,
+    /// The user identity is verified
+    Approved,
+    /// The submission was rejected, the user may submit again
+    Rejected,
+}
+
+impl IdentityStatus {
+    /// Returns the status name as it stored in the database
+    #[must_use]
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Self::Pending => "pending",
+            Self::Approved => "approved",
+            Self::Rejected => "rejected",
+        }
+    }
+
+    /// Creates a new `IdentityStatus` from the database column
+    #[must_use]
+    pub fn from_row(status: &str) -> Self {
+        match status {
+            "approved" => Self::Approved,
+            "rejected" => Self::Rejected,
+            _ => Self::Pending,
+        }
+    }
+}
+
+/// An encrypted identity verification file
+#[derive(Debug, Clone)]
+pub struct IdentityFile {
+    /// File name on the file system
+    pub file: String,
+    /// Media type of the decrypted file
+    pub content_type: String,
+}
+
+/// Returns the path the encrypted identity file is saved at
+#[must_use]
+pub fn identity_file_path(file: &str) -> PathBuf {
+    PathBuf::from(IDENTITY_UPLOAD_DIR).join(file)
+}
+
+/// Deletes identity verification files from the file system
+pub async fn delete_identity_files(files: impl Iterator<Item = String>) {
+    let paths = files.map(|file| identity_file_path(&file)).collect();
+    let _ = files::delete_files(paths).await;
+}
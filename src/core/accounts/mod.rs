@@ -1,9 +1,14 @@
 //! User account impls
 
+pub mod blocks;
+pub mod data_export;
 mod delete;
 pub mod emails;
+pub mod handles;
+pub mod identity;
 pub mod passwords;
 pub mod personal_info;
+pub mod phones;
 pub mod user;
 pub mod user_profile;
 
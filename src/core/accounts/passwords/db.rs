@@ -1,6 +1,6 @@
 //! Password database impls
 
-use time::OffsetDateTime;
+use time::{Duration, OffsetDateTime};
 
 use crate::{
     accounts::user::db::handle_user_database_error, auth::TokenHash, error::ServerResult,
@@ -137,4 +137,125 @@ impl PasswordModel {
             }
         }
     }
+
+    /// Inserts password reset code into the database
+    ///
+    /// Returns false if the previous code was sent
+    /// less than `PHONE_CODE_RESEND_INTERVAL` seconds ago.
+    pub async fn insert_code(
+        user_id: ModelID,
+        code: TokenHash,
+        db: DatabaseConnection,
+    ) -> ServerResult<bool> {
+        let now = OffsetDateTime::now_utc();
+        match sqlx::query!(
+            r#"
+                INSERT INTO auth.password_reset_codes(
+                    user_id,
+                    code,
+                    code_generated_at,
+                    code_attempts
+                )
+                VALUES($1, $2, $3, 0)
+
+                ON CONFLICT ON CONSTRAINT password_reset_codes_pkey
+                DO UPDATE SET code = EXCLUDED.code,
+                            code_generated_at = EXCLUDED.code_generated_at,
+                            code_attempts = 0
+                WHERE password_reset_codes.code_generated_at < $4;
+            "#,
+            user_id.0,
+            &code[..],
+            now,
+            now - Duration::seconds(crate::PHONE_CODE_RESEND_INTERVAL),
+        )
+        .execute(&db.pool)
+        .await
+        {
+            Ok(result) => {
+                tracing::debug!("Password reset code inserted: {:?}", result);
+                Ok(result.rows_affected() > 0)
+            }
+            Err(err) => {
+                tracing::error!(
+                    "Database error, failed to insert password reset code: {}",
+                    err
+                );
+                Err(err.into())
+            }
+        }
+    }
+
+    /// Records a password reset attempt with the code sent to the verified `phone`
+    ///
+    /// Returns the `user_id`, whether the `code` matches, when the code was generated
+    /// and the number of attempts made incl this one,
+    /// or `None` if there is no code sent to the phone.
+    pub async fn verify_code_attempt(
+        phone: String,
+        code: TokenHash,
+        db: DatabaseConnection,
+    ) -> ServerResult<Option<(ModelID, bool, OffsetDateTime, i16)>> {
+        match sqlx::query!(
+            r#"
+                UPDATE auth.password_reset_codes reset_code
+                SET code_attempts = reset_code.code_attempts + 1
+                FROM accounts.phones phone
+                WHERE reset_code.user_id = phone.user_id
+                    AND phone.phone = $1
+                    AND phone.verified = true
+                RETURNING reset_code.user_id,
+                    reset_code.code = $2 AS "matches!",
+                    reset_code.code_generated_at,
+                    reset_code.code_attempts
+            "#,
+            phone,
+            &code[..],
+        )
+        .fetch_optional(&db.pool)
+        .await
+        {
+            Ok(rec) => Ok(rec.map(|rec| {
+                (
+                    rec.user_id.into(),
+                    rec.matches,
+                    rec.code_generated_at,
+                    rec.code_attempts,
+                )
+            })),
+            Err(err) => {
+                tracing::error!(
+                    "Database error, failed to record password reset code attempt: {}",
+                    err
+                );
+                Err(err.into())
+            }
+        }
+    }
+
+    /// Deletes the user password reset code from the database
+    pub async fn delete_code(user_id: ModelID, db: DatabaseConnection) -> ServerResult<()> {
+        match sqlx::query!(
+            r#"
+                DELETE FROM auth.password_reset_codes reset_code
+                WHERE reset_code.user_id = $1
+            "#,
+            user_id.0
+        )
+        .execute(&db.pool)
+        .await
+        {
+            Ok(result) => {
+                tracing::debug!("Password reset code deleted: {:?}", result);
+                Ok(())
+            }
+            Err(err) => {
+                tracing::error!(
+                    "Database error, failed to delete password reset code: {}",
+                    err
+                );
+                Err(err.into())
+            }
+        }
+    }
 }
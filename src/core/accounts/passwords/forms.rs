@@ -112,11 +112,15 @@ where
 
 // ===== Password ForgotForm impls =====
 
-// User password forgot form
+// User password forgot form, the reset link is sent to the email
+// or a reset code is sent to the verified phone number
 #[derive(Debug, Clone, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct PasswordForgotForm {
-    pub email: String,
+    #[serde(default)]
+    pub email: Option<String>,
+    #[serde(default)]
+    pub phone: Option<String>,
 }
 
 impl PasswordForgotForm {
@@ -125,14 +129,26 @@ impl PasswordForgotForm {
         // Clean form fields
         self.clean_data();
 
-        self.email.validate_email()?;
+        match (&self.email, &self.phone) {
+            (Some(email), None) => email.validate_email()?,
+            (None, Some(phone)) => self.phone = Some(phone.validate_phone()?),
+            _ => {
+                return Err(EndpointRejection::BadRequest(
+                    "Enter either your email or your phone number.".into(),
+                ))
+            }
+        }
 
         Ok(())
     }
 
     /// Clean form data
     fn clean_data(&mut self) {
-        self.email = self.email.clean().to_ascii_lowercase();
+        self.email = self
+            .email
+            .as_ref()
+            .map(|email| email.clean().to_ascii_lowercase());
+        self.phone = self.phone.as_ref().map(|phone| phone.clean());
     }
 }
 
@@ -156,17 +172,32 @@ where
 
 // ===== Password ResetForm impls =====
 
-// User password reset form
+// User password reset form, `phone` and `code` are set
+// when resetting the password with the code sent to the phone
 #[derive(Debug, Clone, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct PasswordResetForm {
     pub new: String,
     pub confirm: String,
+    #[serde(default)]
+    pub phone: Option<String>,
+    #[serde(default)]
+    pub code: Option<String>,
 }
 
 impl PasswordResetForm {
     /// Validates password reset form inputs
-    fn validate(&self) -> EndpointResult<()> {
+    fn validate(&mut self) -> EndpointResult<()> {
+        // Clean form fields
+        self.clean_data();
+
+        if let Some(phone) = &self.phone {
+            self.phone = Some(phone.validate_phone()?);
+        }
+        if let Some(code) = &self.code {
+            code.validate_len(6, 6, "Invalid code")?;
+        }
+
         self.new.validate_len(
             6,
             24,
@@ -182,6 +213,18 @@ impl PasswordResetForm {
         Ok(())
     }
 
+    /// Clean form data
+    fn clean_data(&mut self) {
+        self.phone = self.phone.as_ref().map(|phone| phone.clean());
+        self.code = self.code.as_ref().map(|code| code.clean());
+    }
+
+    /// Returns the (`phone`, `code`) the password is reset with, if both are set
+    #[must_use]
+    pub fn phone_code(&self) -> Option<(String, String)> {
+        self.phone.clone().zip(self.code.clone())
+    }
+
     /// Hash new password and return `phc_string`
     pub async fn try_phc(self) -> ServerResult<String> {
         hash_password(self.new).await
@@ -196,7 +239,7 @@ where
     type Rejection = EndpointRejection;
 
     async fn from_request(req: Request, state: &ServerState) -> Result<Self, Self::Rejection> {
-        let Json(password_reset) = Json::<Self>::from_request(req, state).await?;
+        let Json(mut password_reset) = Json::<Self>::from_request(req, state).await?;
 
         // Validate form fields
         password_reset.validate()?;
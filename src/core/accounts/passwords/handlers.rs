@@ -7,11 +7,13 @@ use axum::{
 use axum_extra::extract::PrivateCookieJar;
 
 use crate::{
-    accounts::user::models::User,
+    accounts::{phones::phone_code_expired, user::models::User},
     auth::{hash_token, CurrentUser, Token, TokenConfirm},
     endpoint::{EndpointRejection, EndpointResult},
     mail::Mail,
     server::state::DatabaseConnection,
+    sms::Sms,
+    types::ModelID,
     SERVER_DOMAIN_NAME,
 };
 
@@ -53,8 +55,10 @@ pub async fn password_change(
 
 /// Handles the `POST /account/reset-password` route.
 ///
-/// Used for when a user want to change their forgotten password
-#[tracing::instrument(skip(db))]
+/// Used for when a user want to change their forgotten password,
+/// either with the token from the email link
+/// or with the code sent to their phone.
+#[tracing::instrument(skip(db, form))]
 pub async fn password_reset(
     confirm_token: Option<Query<TokenConfirm>>,
     State(db): State<DatabaseConnection>,
@@ -62,40 +66,84 @@ pub async fn password_reset(
 ) -> EndpointResult<&'static str> {
     static ERR_MSG: &str = "Your password rest link is no longer valid.";
 
-    let Some(Query(confirm_token)) = confirm_token else {
-        return Err(EndpointRejection::BadRequest(
-            "Password reset token required!".into(),
-        ));
+    let user_id = match (confirm_token, form.phone_code()) {
+        (Some(Query(confirm_token)), _) => {
+            let token = confirm_token.token;
+            // Verify token
+            let token_hash = hash_token(token.as_bytes());
+            let Some((user_id, token_created_at)) =
+                PasswordModel::find_token(token_hash, db.clone()).await?
+            else {
+                return Err(EndpointRejection::BadRequest(ERR_MSG.into()));
+            };
+            if password_reset_token_expired(token_created_at) {
+                return Err(EndpointRejection::BadRequest(ERR_MSG.into()));
+            }
+            user_id
+        }
+        (None, Some((phone, code))) => {
+            let user_id = verify_password_reset_code(phone, &code, db.clone()).await?;
+            PasswordModel::delete_code(user_id, db.clone()).await?;
+            user_id
+        }
+        (None, None) => {
+            return Err(EndpointRejection::BadRequest(
+                "Password reset token required!".into(),
+            ));
+        }
     };
 
-    let token = confirm_token.token;
-    // Verify token
-    let token_hash = hash_token(token.as_bytes());
-    let Some((user_id, token_created_at)) =
-        PasswordModel::find_token(token_hash, db.clone()).await?
+    // Update password
+    let phc_string = form.try_phc().await?;
+    PasswordModel::update(user_id, phc_string, db).await?;
+
+    Ok("Your password has been reset successfully")
+}
+
+/// Verify the password reset `code` sent to the `phone`
+///
+/// Returns the id of the user the code was sent to
+async fn verify_password_reset_code(
+    phone: String,
+    code: &str,
+    db: DatabaseConnection,
+) -> EndpointResult<ModelID> {
+    static ERR_MSG: &str = "Your password reset code is incorrect or expired.";
+
+    let code_hash = hash_token(code.as_bytes());
+    let Some((user_id, matches, generated_at, attempts)) =
+        PasswordModel::verify_code_attempt(phone, code_hash, db).await?
     else {
         return Err(EndpointRejection::BadRequest(ERR_MSG.into()));
     };
-    if password_reset_token_expired(token_created_at) {
+
+    if attempts > crate::PHONE_CODE_MAX_ATTEMPTS {
+        return Err(EndpointRejection::TooManyRequests(
+            "Too many incorrect attempts, request a new code.".into(),
+        ));
+    }
+    if !matches || phone_code_expired(generated_at) {
         return Err(EndpointRejection::BadRequest(ERR_MSG.into()));
     }
 
-    // Update password
-    let phc_string = form.try_phc().await?;
-    PasswordModel::update(user_id, phc_string, db).await?;
-
-    Ok("Your password has been reset successfully")
+    Ok(user_id)
 }
 
 /// Handles the `POST /account/password-forgot` route.
-#[tracing::instrument(skip(db, form))]
+#[tracing::instrument(skip(db, sms, form))]
 pub async fn password_forgot(
     State(db): State<DatabaseConnection>,
     State(outlook): State<Mail>,
+    State(sms): State<Sms>,
     form: PasswordForgotForm,
 ) -> EndpointResult<&'static str> {
+    // The form has either the email or the phone set
+    let email_address = match (form.email, form.phone) {
+        (Some(email), _) => email,
+        (None, phone) => return password_forgot_sms(phone.unwrap_or_default(), sms, db).await,
+    };
+
     let (plaintext, hash) = Token::default().into_parts();
-    let email_address = form.email;
     let Some((user_id, first_name)) =
         User::find_by_email(email_address.clone(), db.clone()).await?
     else {
@@ -113,3 +161,27 @@ pub async fn password_forgot(
 
     Ok("Your password reset link was sent to your email ")
 }
+
+/// Sends a password reset code to the verified `phone`
+async fn password_forgot_sms(
+    phone: String,
+    sms: Sms,
+    db: DatabaseConnection,
+) -> EndpointResult<&'static str> {
+    let Some((user_id, _)) = User::find_by_phone(phone.clone(), db.clone()).await? else {
+        return Err(EndpointRejection::BadRequest(
+            "Sorry, we could not find your account.".into(),
+        ));
+    };
+
+    let (code, hash) = Token::new_code().into_parts();
+    if !PasswordModel::insert_code(user_id, hash, db).await? {
+        return Err(EndpointRejection::TooManyRequests(
+            "Please wait a minute before requesting a new code.".into(),
+        ));
+    }
+
+    sms.password_reset(&phone, &code).await?;
+
+    Ok("Your password reset code was sent to your phone")
+}
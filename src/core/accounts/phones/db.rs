@@ -0,0 +1,259 @@
+//! Phone database impls
+
+use time::OffsetDateTime;
+
+use crate::{
+    auth::TokenHash,
+    endpoint::EndpointRejection,
+    error::{ServerError, ServerResult},
+    server::state::DatabaseConnection,
+    types::ModelID,
+};
+
+use super::{forms::PhoneInsertData, Phone, PhoneModel};
+
+impl PhoneModel {
+    /// Fetches the user phone from the database
+    #[tracing::instrument(skip(db))]
+    pub async fn find(user_id: ModelID, db: DatabaseConnection) -> ServerResult<Option<Phone>> {
+        match sqlx::query!(
+            r#"
+                SELECT phone.phone,
+                    phone.verified,
+                    phone.token_generated_at
+                FROM accounts.phones phone
+                WHERE phone.user_id = $1
+            "#,
+            user_id.0
+        )
+        .fetch_optional(&db.pool)
+        .await
+        {
+            Ok(rec) => Ok(rec.map(|rec| Phone {
+                phone: rec.phone,
+                verified: rec.verified,
+                token_generated_at: rec.token_generated_at,
+            })),
+            Err(err) => {
+                tracing::error!("Database error, failed to find user phone: {}", err);
+                Err(err.into())
+            }
+        }
+    }
+
+    /// Inserts the user phone and its verification code into the database,
+    /// replaces the user previous phone.
+    ///
+    /// Unverified phones of other users with the same number are removed.
+    #[tracing::instrument(skip(db, values))]
+    pub async fn insert(
+        user_id: ModelID,
+        values: PhoneInsertData,
+        db: DatabaseConnection,
+    ) -> ServerResult<()> {
+        let mut tx = db.pool.begin().await?;
+
+        if let Err(err) = sqlx::query!(
+            r#"
+                DELETE FROM accounts.phones phone
+                WHERE phone.phone = $1
+                    AND phone.verified = false
+                    AND phone.user_id <> $2
+            "#,
+            values.phone,
+            user_id.0,
+        )
+        .execute(&mut *tx)
+        .await
+        {
+            tracing::error!(
+                "Database error, failed to delete unverified phones: {}",
+                err
+            );
+            return Err(err.into());
+        }
+
+        match sqlx::query!(
+            r#"
+                INSERT INTO accounts.phones(
+                    user_id,
+                    phone,
+                    verified,
+                    token,
+                    token_generated_at,
+                    token_attempts
+                )
+                VALUES($1, $2, false, $3, $4, 0)
+                ON CONFLICT (user_id) DO UPDATE
+                SET phone = EXCLUDED.phone,
+                    verified = false,
+                    token = EXCLUDED.token,
+                    token_generated_at = EXCLUDED.token_generated_at,
+                    token_attempts = 0
+            "#,
+            user_id.0,
+            values.phone,
+            &values.token[..],
+            values.token_generated_at,
+        )
+        .execute(&mut *tx)
+        .await
+        {
+            Ok(result) => {
+                tx.commit().await?;
+                tracing::debug!("Phone inserted successfully: {:?}", result);
+                Ok(())
+            }
+            Err(err) => {
+                // Handle database constraint error
+                handle_phone_database_error(&err)?;
+
+                tracing::error!("Database error, failed to insert phone: {}", err);
+                Err(err.into())
+            }
+        }
+    }
+
+    /// Replaces the pending phone verification code in the database
+    #[tracing::instrument(skip(db, token))]
+    pub async fn insert_code(
+        user_id: ModelID,
+        token: TokenHash,
+        db: DatabaseConnection,
+    ) -> ServerResult<()> {
+        match sqlx::query!(
+            r#"
+                UPDATE accounts.phones phone
+                SET token = $1,
+                    token_generated_at = $2,
+                    token_attempts = 0
+                WHERE phone.user_id = $3
+                    AND phone.verified = false
+            "#,
+            &token[..],
+            OffsetDateTime::now_utc(),
+            user_id.0
+        )
+        .execute(&db.pool)
+        .await
+        {
+            Ok(result) => {
+                tracing::debug!("Phone verification code inserted: {:?}", result);
+                Ok(())
+            }
+            Err(err) => {
+                tracing::error!(
+                    "Database error, failed to insert phone verification code: {}",
+                    err
+                );
+                Err(err.into())
+            }
+        }
+    }
+
+    /// Records a verification attempt of the pending phone code
+    ///
+    /// Returns whether the `token` matches, when the code was generated
+    /// and the number of attempts made incl this one,
+    /// or `None` if the user has no pending code.
+    #[tracing::instrument(skip(db, token))]
+    pub async fn verify_attempt(
+        user_id: ModelID,
+        token: TokenHash,
+        db: DatabaseConnection,
+    ) -> ServerResult<Option<(bool, OffsetDateTime, i16)>> {
+        match sqlx::query!(
+            r#"
+                UPDATE accounts.phones phone
+                SET token_attempts = phone.token_attempts + 1
+                WHERE phone.user_id = $1
+                    AND phone.verified = false
+                    AND phone.token IS NOT NULL
+                RETURNING phone.token = $2 AS "matches!",
+                    phone.token_generated_at AS "token_generated_at!",
+                    phone.token_attempts
+            "#,
+            user_id.0,
+            &token[..],
+        )
+        .fetch_optional(&db.pool)
+        .await
+        {
+            Ok(rec) => Ok(rec.map(|rec| (rec.matches, rec.token_generated_at, rec.token_attempts))),
+            Err(err) => {
+                tracing::error!(
+                    "Database error, failed to record phone verification attempt: {}",
+                    err
+                );
+                Err(err.into())
+            }
+        }
+    }
+
+    /// Marks the user phone as verified in the database
+    #[tracing::instrument(skip(db))]
+    pub async fn verify(user_id: ModelID, db: DatabaseConnection) -> ServerResult<()> {
+        match sqlx::query!(
+            r#"
+                UPDATE accounts.phones phone
+                SET verified = true,
+                    token = NULL,
+                    token_generated_at = NULL,
+                    token_attempts = 0
+                WHERE phone.user_id = $1
+            "#,
+            user_id.0
+        )
+        .execute(&db.pool)
+        .await
+        {
+            Ok(result) => {
+                tracing::debug!("Phone verified successfully: {:?}", result);
+                Ok(())
+            }
+            Err(err) => {
+                tracing::error!("Database error, failed to verify phone: {}", err);
+                Err(err.into())
+            }
+        }
+    }
+
+    /// Deletes the user phone from the database
+    #[tracing::instrument(skip(db))]
+    pub async fn delete(user_id: ModelID, db: DatabaseConnection) -> ServerResult<()> {
+        match sqlx::query!(
+            r#"
+                DELETE FROM accounts.phones phone
+                WHERE phone.user_id = $1
+            "#,
+            user_id.0
+        )
+        .execute(&db.pool)
+        .await
+        {
+            Ok(result) => {
+                tracing::debug!("Phone deleted successfully: {:?}", result);
+                Ok(())
+            }
+            Err(err) => {
+                tracing::error!("Database error, failed to delete phone: {}", err);
+                Err(err.into())
+            }
+        }
+    }
+}
+
+/// Handle phones database constraints errors
+pub fn handle_phone_database_error(err: &sqlx::Error) -> ServerResult<()> {
+    if let sqlx::Error::Database(db_err) = err {
+        // Handle db unique constraints
+        if db_err.is_unique_violation() && db_err.constraint() == Some("phones_phone_key") {
+            tracing::error!("Database error, phone already in use. {:?}", err);
+            return Err(ServerError::rejection(EndpointRejection::Conflict(
+                "Phone number is already in use.".into(),
+            )));
+        }
+    }
+
+    Ok(())
+}
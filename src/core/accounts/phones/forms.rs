@@ -0,0 +1,85 @@
+//! Phone forms impls
+
+use axum::{
+    async_trait,
+    extract::{rejection::JsonRejection, FromRequest, Json, Request},
+};
+use serde::Deserialize;
+use time::OffsetDateTime;
+
+use crate::{
+    auth::{Token, TokenHash},
+    endpoint::{
+        validators::{TransformString, ValidateString},
+        EndpointRejection, EndpointResult,
+    },
+    server::state::ServerState,
+};
+
+/// Phone create form
+#[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PhoneForm {
+    pub phone: String,
+}
+
+/// Phone insert cleaned data
+#[derive(Debug, Clone)]
+pub struct PhoneInsertData {
+    pub phone: String,
+    pub token: TokenHash,
+    pub token_generated_at: OffsetDateTime,
+}
+
+impl PhoneInsertData {
+    #[must_use]
+    pub fn new(phone: String, token: TokenHash) -> Self {
+        Self {
+            phone,
+            token,
+            token_generated_at: OffsetDateTime::now_utc(),
+        }
+    }
+}
+
+impl PhoneForm {
+    /// Validates phone form inputs
+    fn validate(&mut self) -> EndpointResult<()> {
+        // Clean the data
+        self.clean_data();
+
+        self.phone = self.phone.validate_phone()?;
+
+        Ok(())
+    }
+
+    /// Clean form data
+    fn clean_data(&mut self) {
+        self.phone = self.phone.clean();
+    }
+
+    // Return (`PhoneInsertData`, code_plaintext)
+    #[must_use]
+    pub fn data(self) -> (PhoneInsertData, String) {
+        let (code, hash) = Token::new_code().into_parts();
+        (PhoneInsertData::new(self.phone, hash), code)
+    }
+}
+
+#[async_trait]
+impl FromRequest<ServerState> for PhoneForm
+where
+    Json<Self>: FromRequest<ServerState, Rejection = JsonRejection>,
+{
+    type Rejection = EndpointRejection;
+
+    async fn from_request(req: Request, state: &ServerState) -> Result<Self, Self::Rejection> {
+        // Extract data
+        let Json(mut phone) = Json::<Self>::from_request(req, state).await?;
+
+        // Validate phone form
+        phone.validate()?;
+
+        Ok(phone)
+    }
+}
@@ -0,0 +1,140 @@
+//! Phone http handlers impls
+
+use axum::{extract::State, http::StatusCode};
+use axum_extra::extract::PrivateCookieJar;
+
+use crate::{
+    accounts::{
+        emails::forms::CodeConfirmForm,
+        passwords::{get_password_verified, remove_password_verified_cookie},
+    },
+    auth::{hash_token, CurrentUser, Token},
+    endpoint::{EndpointRejection, EndpointResult},
+    server::state::DatabaseConnection,
+    sms::Sms,
+};
+
+use super::{forms::PhoneForm, phone_code_expired, phone_code_resend_allowed, PhoneModel};
+
+/// Handles the `PUT /account/settings/phones` route.
+///
+/// Adds or replaces the user phone number and sends a verification code
+#[tracing::instrument(skip(db, sms, cookie_jar, user, form))]
+pub async fn phone_update(
+    user: CurrentUser,
+    cookie_jar: PrivateCookieJar,
+    State(db): State<DatabaseConnection>,
+    State(sms): State<Sms>,
+    form: PhoneForm,
+) -> EndpointResult<(PrivateCookieJar, &'static str)> {
+    // If the password is not verified, don't permit phone update
+    if get_password_verified(&cookie_jar).is_none() {
+        return Err(EndpointRejection::unauthorized());
+    }
+
+    if let Some(phone) = PhoneModel::find(user.id, db.clone()).await? {
+        if phone.verified && phone.phone == form.phone {
+            return Err(EndpointRejection::Conflict(
+                "This phone number is already verified.".into(),
+            ));
+        }
+        if !phone_code_resend_allowed(phone.token_generated_at) {
+            return Err(EndpointRejection::TooManyRequests(
+                "Please wait a minute before requesting a new code.".into(),
+            ));
+        }
+    }
+
+    let (values, code) = form.data();
+    let phone = values.phone.clone();
+    PhoneModel::insert(user.id, values, db).await?;
+
+    sms.verify_phone(&phone, &code).await?;
+
+    Ok((
+        remove_password_verified_cookie(cookie_jar),
+        "Verify your phone number by entering the code we just sent you",
+    ))
+}
+
+/// Handles the `POST /account/settings/phones/resend-code` route.
+///
+/// Sends a new verification code to the unverified phone number
+#[tracing::instrument(skip(db, sms, user))]
+pub async fn phone_code_resend(
+    user: CurrentUser,
+    State(db): State<DatabaseConnection>,
+    State(sms): State<Sms>,
+) -> EndpointResult<&'static str> {
+    let Some(phone) = PhoneModel::find(user.id, db.clone()).await? else {
+        return Err(EndpointRejection::NotFound(
+            "Phone number not found.".into(),
+        ));
+    };
+    if phone.verified {
+        return Err(EndpointRejection::Conflict(
+            "This phone number is already verified.".into(),
+        ));
+    }
+    if !phone_code_resend_allowed(phone.token_generated_at) {
+        return Err(EndpointRejection::TooManyRequests(
+            "Please wait a minute before requesting a new code.".into(),
+        ));
+    }
+
+    let (code, hash) = Token::new_code().into_parts();
+    PhoneModel::insert_code(user.id, hash, db).await?;
+
+    sms.verify_phone(&phone.phone, &code).await?;
+
+    Ok("We sent you a new verification code")
+}
+
+/// Handles the `POST /account/settings/phones/verify` route.
+#[tracing::instrument(skip(db, user, form))]
+pub async fn phone_verify(
+    user: CurrentUser,
+    State(db): State<DatabaseConnection>,
+    form: CodeConfirmForm,
+) -> EndpointResult<(StatusCode, &'static str)> {
+    let code = hash_token(form.code.as_bytes());
+    let Some((matches, generated_at, attempts)) =
+        PhoneModel::verify_attempt(user.id, code, db.clone()).await?
+    else {
+        return Err(EndpointRejection::BadRequest(
+            "There is no phone number waiting to be verified.".into(),
+        ));
+    };
+
+    if attempts > crate::PHONE_CODE_MAX_ATTEMPTS {
+        return Err(EndpointRejection::TooManyRequests(
+            "Too many incorrect attempts, request a new code.".into(),
+        ));
+    }
+    if phone_code_expired(generated_at) {
+        return Err(EndpointRejection::BadRequest(
+            "Your verification code expired, request a new code.".into(),
+        ));
+    }
+    if !matches {
+        return Err(EndpointRejection::BadRequest(
+            "Your verification code is incorrect".into(),
+        ));
+    }
+
+    PhoneModel::verify(user.id, db).await?;
+    Ok((
+        StatusCode::OK,
+        "Your phone number was verified successfully",
+    ))
+}
+
+/// Handles the `DELETE /account/settings/phones` route.
+#[tracing::instrument(skip(db, user))]
+pub async fn phone_delete(
+    user: CurrentUser,
+    State(db): State<DatabaseConnection>,
+) -> EndpointResult<StatusCode> {
+    PhoneModel::delete(user.id, db).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
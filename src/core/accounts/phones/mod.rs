@@ -0,0 +1,35 @@
+//! Phone related utilities impls
+
+pub mod db;
+pub mod forms;
+pub mod handlers;
+
+use time::{Duration, OffsetDateTime};
+
+/// User phone model
+#[derive(Debug)]
+pub struct PhoneModel;
+
+/// User phone number and its verification state
+#[derive(Debug, Clone)]
+pub struct Phone {
+    pub phone: String,
+    pub verified: bool,
+    pub token_generated_at: Option<OffsetDateTime>,
+}
+
+/// Checks if the phone verification code generated at `generated_at` expired
+#[must_use]
+pub fn phone_code_expired(generated_at: OffsetDateTime) -> bool {
+    generated_at < OffsetDateTime::now_utc() - Duration::minutes(crate::PHONE_CODE_EXPIRY)
+}
+
+/// Checks if a new phone verification code can be sent,
+/// the previous code was generated at `generated_at`.
+#[must_use]
+pub fn phone_code_resend_allowed(generated_at: Option<OffsetDateTime>) -> bool {
+    generated_at.is_none_or(|generated_at| {
+        generated_at
+            < OffsetDateTime::now_utc() - Duration::seconds(crate::PHONE_CODE_RESEND_INTERVAL)
+    })
+}
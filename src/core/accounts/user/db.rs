@@ -10,7 +10,10 @@ use crate::{
     endpoint::EndpointRejection,
     error::{ServerError, ServerResult},
     server::state::DatabaseConnection,
-    services::produce::harvest::delete_harvest_photos,
+    services::{
+        farmers::{location::delete_location_photos, rating::delete_rating_photos},
+        produce::harvest::delete_harvest_photos,
+    },
     types::ModelID,
     types::Pagination,
 };
@@ -21,7 +24,7 @@ use super::{
     utils::{
         archive_user_farms, archive_user_harvests, archive_user_locations, delete_user_farms,
         delete_user_harvests, delete_user_locations, get_user_photo, session_delete, user_delete,
-        user_harvest_photos, user_is_farmer,
+        user_harvest_photos, user_is_farmer, user_location_photos, user_rating_photos,
     },
 };
 
@@ -35,6 +38,7 @@ impl User {
                 SELECT user_.id AS user_id,
                     user_.first_name AS user_first_name,
                     user_.last_name AS user_last_name,
+                    user_.identity_verified AS user_identity_verified,
                     profile.photo AS user_photo
                 FROM accounts.users user_
                 LEFT JOIN accounts.user_profiles profile
@@ -58,6 +62,7 @@ impl User {
                             rec.user_first_name,
                             rec.user_last_name,
                             rec.user_photo,
+                            rec.user_identity_verified,
                         )
                     })
                     .collect();
@@ -156,6 +161,34 @@ impl User {
         }
     }
 
+    /// Fetches `user_id` and `first_name` by verified phone number from the database
+    pub async fn find_by_phone(
+        phone: String,
+        db: DatabaseConnection,
+    ) -> ServerResult<Option<(ModelID, String)>> {
+        match sqlx::query!(
+            r#"
+                SELECT user_.id AS user_id,
+                    user_.first_name
+                FROM accounts.phones phone
+                LEFT JOIN accounts.users user_
+                    ON phone.user_id = user_.id
+                WHERE phone.phone = $1
+                    AND phone.verified = true;
+            "#,
+            phone
+        )
+        .fetch_optional(&db.pool)
+        .await
+        {
+            Ok(rec) => Ok(rec.map(|rec| (rec.user_id.into(), rec.first_name))),
+            Err(err) => {
+                tracing::error!("Database error, failed to fetch user by phone: {}", err);
+                Err(err.into())
+            }
+        }
+    }
+
     /// Add or remove user is superuser
     pub async fn set_superuser(
         user_id: ModelID,
@@ -295,11 +328,13 @@ impl User {
     pub async fn delete(id: ModelID, db: DatabaseConnection) -> ServerResult<()> {
         let pool = db.clone();
         let profile_photo = get_user_photo(id, pool.clone()).await?;
+        let mut rating_photos = user_rating_photos(id, pool.clone()).await?;
         let mut tx = db.pool.begin().await?;
 
         // Clean up user's farms-location-harvests
         if user_is_farmer(id, pool.clone()).await? {
-            let image_paths = user_harvest_photos(id, pool).await?;
+            let image_paths = user_harvest_photos(id, pool.clone()).await?;
+            let location_paths = user_location_photos(id, pool).await?;
 
             let deleted_at = OffsetDateTime::now_utc();
 
@@ -313,12 +348,17 @@ impl User {
 
             //Cleanup farms
             archive_user_farms(id, deleted_at, &mut tx).await?;
-            delete_user_farms(id, &mut tx).await?;
+            rating_photos.push(delete_user_farms(id, &mut tx).await?);
 
             // Cleanup active harvest images
             tokio::spawn(
                 async move { delete_harvest_photos(image_paths.into_iter().flatten()).await },
             );
+
+            // Cleanup active location images
+            tokio::spawn(async move {
+                delete_location_photos(location_paths.into_iter().flatten()).await
+            });
         }
 
         user_delete(id, &mut tx).await?;
@@ -330,6 +370,14 @@ impl User {
             tokio::spawn(async move { delete_user_photo(&photo).await });
         }
 
+        // Delete the user ratings photos and the photos of the ratings on the deleted farms,
+        // the ratings are deleted together with the user and the farms
+        if !rating_photos.is_empty() {
+            tokio::spawn(
+                async move { delete_rating_photos(rating_photos.into_iter().flatten()).await },
+            );
+        }
+
         Ok(())
     }
 
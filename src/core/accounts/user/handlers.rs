@@ -5,10 +5,14 @@ use axum::{
     http::StatusCode,
     Json,
 };
+use axum_extra::extract::PrivateCookieJar;
 
 use crate::{
-    accounts::emails::EmailModel,
-    auth::{hash_token, AdminUser, CurrentUser, SuperUser, Token, TokenConfirm},
+    accounts::{emails::EmailModel, AccountDelete},
+    auth::{
+        hash_token, sessions::remove_session_cookie, AdminUser, CurrentUser, SuperUser, Token,
+        TokenConfirm,
+    },
     endpoint::{EndpointRejection, EndpointResult},
     mail::Mail,
     server::state::DatabaseConnection,
@@ -124,14 +128,60 @@ pub async fn account_unlock(
 
 /// Handles the `DELETE /account/deactivate` route.
 ///
-/// Permanently deletes the user from the platform
-#[tracing::instrument(skip(user, db))]
+/// Deactivates the user account and logs the user out, the account is
+/// permanently deleted after `MAX_DAYS_TO_DELETE_ACCOUNT` days unless
+/// the user logs in or cancels the deletion.
+#[tracing::instrument(skip(user, cookie_jar, db, outlook))]
 pub async fn account_deactivate(
     user: CurrentUser,
+    cookie_jar: PrivateCookieJar,
     State(db): State<DatabaseConnection>,
-) -> EndpointResult<StatusCode> {
-    User::delete(user.id, db).await?;
-    Ok(StatusCode::NO_CONTENT)
+    State(outlook): State<Mail>,
+) -> EndpointResult<(PrivateCookieJar, &'static str)> {
+    let (plaintext, hash) = Token::default().into_parts();
+    if !AccountDelete::insert(user.id, hash, db.clone()).await? {
+        return Err(EndpointRejection::Conflict(
+            "Your account is already deactivated.".into(),
+        ));
+    }
+
+    // Send the deletion cancel email
+    let (first_name, email_address) = EmailModel::find_user(user.id, db).await?;
+    let domain = SERVER_DOMAIN_NAME.get().unwrap();
+    let link = format!("{domain}/account/deactivate/cancel?token={plaintext}");
+    let email = outlook.account_deactivated(&first_name, &email_address, &link)?;
+    outlook.send(email).await?;
+
+    Ok((
+        remove_session_cookie(cookie_jar),
+        "Your account has been deactivated",
+    ))
+}
+
+/// Handles the `POST /account/deactivate/cancel?token=...` route.
+///
+/// Cancels the account deletion and reactivates the account
+#[tracing::instrument(skip(confirm_token, db))]
+pub async fn account_deactivate_cancel(
+    confirm_token: Option<Query<TokenConfirm>>,
+    State(db): State<DatabaseConnection>,
+) -> EndpointResult<&'static str> {
+    let Some(Query(confirm_token)) = confirm_token else {
+        return Err(EndpointRejection::BadRequest(
+            "Cancel token required!".into(),
+        ));
+    };
+
+    let token = hash_token(confirm_token.token.as_bytes());
+    if !AccountDelete::cancel(token, db).await? {
+        return Err(EndpointRejection::BadRequest(
+            "Your cancel link is no longer valid. \
+Your account may already be reactivated or deleted."
+                .into(),
+        ));
+    }
+
+    Ok("Your account has been reactivated")
 }
 
 /// Handles the `POST /account/unlock` route.
@@ -24,6 +24,8 @@ pub struct UserIndex {
     pub id: ModelID,
     pub full_name: String,
     pub photo: Option<String>,
+    /// Whether staff verified the user identity
+    pub identity_verified: bool,
 }
 
 impl UserIndex {
@@ -34,12 +36,14 @@ impl UserIndex {
         first_name: String,
         last_name: Option<String>,
         photo: Option<String>,
+        identity_verified: bool,
     ) -> Self {
         let full_name = concat_names(first_name, last_name);
         Self {
             id,
             full_name,
             photo,
+            identity_verified,
         }
     }
 }
@@ -127,13 +127,16 @@ pub async fn get_user_photo(
 
 /// Delete farm from the database
 ///
+/// Returns the images of the ratings deleted together with the farms,
+/// except the ratings written by the user.
+///
 /// # Errors
 ///
 /// Return database error
 pub async fn delete_user_farms(
     user_id: ModelID,
     tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
-) -> ServerResult<()> {
+) -> ServerResult<Vec<String>> {
     match sqlx::query!(
         r#"
             WITH farm_stats AS(
@@ -146,27 +149,39 @@ pub async fn delete_user_farms(
 
                 WHERE farm.owner_id = $1
                 GROUP BY farm.id
-            )
+            ),
+            deleted_farms AS(
+                DELETE FROM services.farms farm
 
-            DELETE FROM services.farms farm
+                WHERE farm.id IN(
+                    SELECT stat.farm_id
+                    FROM farm_stats stat
+                    WHERE stat.count = 0
+                )
+                RETURNING farm.id
+            )
 
-            WHERE farm.id IN(
-                SELECT stat.farm_id
-                FROM farm_stats stat
-                WHERE stat.count = 0
-            );
+            SELECT farm_rating.images
+            FROM services.farm_ratings farm_rating
+            WHERE farm_rating.farm_id IN (SELECT id FROM deleted_farms)
+                AND farm_rating.author_id <> $1
+                AND farm_rating.images IS NOT NULL
         "#,
         user_id.0
     )
-    .execute(&mut **tx)
+    .fetch_all(&mut **tx)
     .await
     {
-        Ok(result) => {
+        Ok(records) => {
             tracing::trace!(
-                "User farms deleted,  but transaction not committed: {:?}",
-                result
+                "User farms deleted,  but transaction not committed: {} rating images",
+                records.len()
             );
-            Ok(())
+            Ok(records
+                .into_iter()
+                .filter_map(|rec| rec.images)
+                .flatten()
+                .collect())
         }
         Err(err) => {
             tracing::error!("Database error, failed to delete user farms: {}", err);
@@ -474,3 +489,69 @@ pub async fn user_harvest_photos(
         }
     }
 }
+
+/// Fetch user active locations images
+///
+/// # Errors
+///
+/// Return database error
+pub async fn user_location_photos(
+    user_id: ModelID,
+    db: DatabaseConnection,
+) -> ServerResult<Vec<Vec<String>>> {
+    match sqlx::query!(
+        r#"
+            SELECT location_.images
+            FROM services.active_locations location_
+            LEFT JOIN services.active_farms farm
+                ON location_.farm_id = farm.id
+            WHERE farm.owner_id = $1
+                AND location_.images IS NOT NULL
+        "#,
+        user_id.0
+    )
+    .fetch_all(&db.pool)
+    .await
+    {
+        Ok(records) => Ok(records.into_iter().filter_map(|rec| rec.images).collect()),
+        Err(err) => {
+            tracing::error!(
+                "Database error, failed to fetch user location images: {}",
+                err
+            );
+            Err(err.into())
+        }
+    }
+}
+
+/// Fetch the images of the ratings written by the user
+///
+/// # Errors
+///
+/// Return database error
+pub async fn user_rating_photos(
+    user_id: ModelID,
+    db: DatabaseConnection,
+) -> ServerResult<Vec<Vec<String>>> {
+    match sqlx::query!(
+        r#"
+            SELECT farm_rating.images
+            FROM services.farm_ratings farm_rating
+            WHERE farm_rating.author_id = $1
+                AND farm_rating.images IS NOT NULL
+        "#,
+        user_id.0
+    )
+    .fetch_all(&db.pool)
+    .await
+    {
+        Ok(records) => Ok(records.into_iter().filter_map(|rec| rec.images).collect()),
+        Err(err) => {
+            tracing::error!(
+                "Database error, failed to fetch user rating images: {}",
+                err
+            );
+            Err(err.into())
+        }
+    }
+}
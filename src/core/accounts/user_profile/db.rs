@@ -10,7 +10,11 @@ use crate::{
     files,
     server::state::DatabaseConnection,
     services::{
-        farmers::{farm::models::Farm, location::models::Location},
+        farmers::{
+            farm::models::{Farm, FarmStats},
+            location::{models::Location, schedule::LocationSchedule},
+            rating::models::RatingSummary,
+        },
         produce::harvest::models::HarvestIndex,
     },
     types::ModelID,
@@ -29,19 +33,38 @@ impl UserProfile {
                 user_.first_name AS user_first_name,
                 user_.last_name AS user_last_name,
                 user_.date_joined AS user_date_joined,
+                user_.identity_verified AS user_identity_verified,
+                user_.handle AS user_handle,
                 profile.about AS "user_about?",
                 profile.photo AS user_photo,
                 profile.lives_at AS user_lives_at,
                 farm.id AS "farm_id?",
                 farm.name AS "farm_name?",
+                farm.slug AS farm_slug,
                 farm.logo AS "farm_logo",
                 farm.contact_email AS "farm_contact_email",
                 farm.contact_number AS "farm_contact_number",
                 farm.registered_on AS "farm_registered_on?",
+                farm.founded_at AS farm_founded_at,
+                farm.verified AS "farm_verified?",
+                stat.rating_average AS "farm_rating_average?",
+                stat.rating_count AS "farm_rating_count?",
+                stat.harvest_count AS "farm_harvest_count?",
+                stat.follower_count AS "farm_follower_count?",
+                stat.grade_1_count AS "farm_grade_1_count?",
+                stat.grade_2_count AS "farm_grade_2_count?",
+                stat.grade_3_count AS "farm_grade_3_count?",
+                stat.grade_4_count AS "farm_grade_4_count?",
+                stat.grade_5_count AS "farm_grade_5_count?",
                 location_.id AS "location_id?",
                 location_.place_name AS "location_place_name?",
                 location_.coords AS location_coords,
+                services.location_schedule(location_.id) AS location_schedule,
+                services.location_open_now(location_.id) AS location_open_now,
+                services.location_open_today(location_.id) AS location_open_today,
                 location_.description AS location_description,
+                location_.access_notes AS location_access_notes,
+                location_.images AS location_images,
                 country.name AS "location_country?",
                 region.name AS "location_region?",
                 harvest.id AS "harvest_id?",
@@ -56,6 +79,8 @@ impl UserProfile {
                 ON user_.id = profile.user_id
             LEFT JOIN services.active_farms farm
                 ON user_.id = farm.owner_id
+            LEFT JOIN services.farm_stats stat
+                ON farm.id = stat.farm_id
             LEFT JOIN services.active_locations location_
                 ON farm.id = location_.farm_id
             LEFT JOIN services.countries country
@@ -84,8 +109,10 @@ impl UserProfile {
                 let user_id = first_rec.user_id.into();
                 let first_name = first_rec.user_first_name.clone();
                 let last_name = first_rec.user_last_name.clone();
+                let handle = first_rec.user_handle.clone();
                 let about = first_rec.user_about.clone().unwrap_or_default();
                 let photo = first_rec.user_photo.clone();
+                let identity_verified = first_rec.user_identity_verified;
                 let lives_at = first_rec.user_lives_at.clone();
                 let date_joined = first_rec.user_date_joined.date();
 
@@ -101,10 +128,28 @@ impl UserProfile {
                     let first_rec = &farm_group[0];
 
                     let farm_name = first_rec.farm_name.clone().unwrap();
+                    let farm_slug = first_rec.farm_slug.clone();
                     let farm_logo = first_rec.farm_logo.clone();
                     let farm_contact_email = first_rec.farm_contact_email.clone();
                     let farm_contact_number = first_rec.farm_contact_number.clone();
                     let registered_on = first_rec.farm_registered_on.unwrap();
+                    let farm_stats = FarmStats::from_row(
+                        first_rec.farm_harvest_count,
+                        first_rec.farm_follower_count,
+                        first_rec.farm_founded_at,
+                        first_rec.farm_verified.unwrap_or_default(),
+                    );
+                    let farm_ratings = RatingSummary::from_row(
+                        first_rec.farm_rating_average,
+                        first_rec.farm_rating_count,
+                        [
+                            first_rec.farm_grade_1_count,
+                            first_rec.farm_grade_2_count,
+                            first_rec.farm_grade_3_count,
+                            first_rec.farm_grade_4_count,
+                            first_rec.farm_grade_5_count,
+                        ],
+                    );
 
                     // Create farm locations
                     let farm_locations = {
@@ -121,6 +166,10 @@ impl UserProfile {
                             let country = first_rec.location_country.clone().unwrap();
                             let coords = first_rec.location_coords.clone();
                             let description = first_rec.location_description.clone();
+                            let access_notes = first_rec.location_access_notes.clone();
+                            let images = first_rec.location_images.clone();
+                            let schedule =
+                                LocationSchedule::from_row(first_rec.location_schedule.clone());
 
                             // Create harvests if there is some available at the location.
                             let harvests: Vec<_> = location_group
@@ -139,6 +188,9 @@ impl UserProfile {
                                         rec.location_region,
                                         rec.location_country.unwrap(),
                                         rec.location_coords,
+                                        rec.location_open_now,
+                                        rec.location_open_today,
+                                        rec.farm_id.unwrap().into(),
                                         rec.farm_name.unwrap(),
                                         rec.farm_logo,
                                         0.into(), // boost amount not important
@@ -155,6 +207,9 @@ impl UserProfile {
                                 country,
                                 coords,
                                 description,
+                                access_notes,
+                                images,
+                                schedule,
                                 farm_id.into(),
                                 farm_name.clone(),
                                 harvests,
@@ -166,15 +221,19 @@ impl UserProfile {
                     farms.push(Farm::from_row(
                         farm_id.into(),
                         farm_name,
+                        farm_slug,
                         farm_logo,
                         farm_contact_email,
                         farm_contact_number,
                         farm_locations,
                         registered_on,
+                        farm_stats,
+                        farm_ratings,
                         user_id,
                         first_name.clone(),
                         last_name.clone(),
                         photo.clone(),
+                        identity_verified,
                     ));
                 }
 
@@ -184,9 +243,11 @@ impl UserProfile {
                     user_id,
                     first_name,
                     last_name,
+                    handle,
                     about,
                     lives_at,
                     photo,
+                    identity_verified,
                     date_joined,
                     farms,
                 );
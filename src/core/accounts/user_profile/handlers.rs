@@ -1,15 +1,16 @@
 //! `UserProfile` http handlers impls
 
 use axum::{
-    extract::{Multipart, State},
+    extract::{Multipart, Query, State},
     http::StatusCode,
     Json,
 };
 
 use crate::{
+    accounts::blocks::UserBlock,
     auth::CurrentUser,
     endpoint::{EndpointRejection, EndpointResult},
-    files,
+    files::{self, CropQuery, ImageVariants},
     server::state::DatabaseConnection,
     settings::USER_UPLOAD_DIR,
     types::ModelID,
@@ -18,11 +19,19 @@ use crate::{
 use super::{forms::UserProfileUpdateForm, models::UserProfile, utils::delete_user_photo};
 
 /// Handles the `GET account/users/:id/profile` route.
+///
+/// Profiles of users blocked by the current user are not found.
 #[tracing::instrument(skip(db))]
 pub async fn user_profile(
+    user: Option<CurrentUser>,
     id: ModelID,
     State(db): State<DatabaseConnection>,
 ) -> EndpointResult<Json<UserProfile>> {
+    if let Some(user) = user {
+        if UserBlock::exists(user.id, id, db.clone()).await? {
+            return Err(EndpointRejection::NotFound("User profile not found".into()));
+        }
+    }
     UserProfile::find(id, db).await?.map_or_else(
         || Err(EndpointRejection::NotFound("User profile not found".into())),
         |profile| Ok(Json(profile)),
@@ -34,7 +43,7 @@ pub async fn user_my_profile(
     user: CurrentUser,
     db: State<DatabaseConnection>,
 ) -> EndpointResult<Json<UserProfile>> {
-    user_profile(user.id, db).await
+    user_profile(None, user.id, db).await
 }
 
 /// Handles the `PUT /account/users/profile` route.
@@ -49,17 +58,24 @@ pub async fn user_profile_update(
 }
 
 /// Handles the `POST /account/users/profile/photo` route.
+///
+/// The photo can be cropped with the `x`, `y`, `width` and `height` query params,
+/// returns the urls of the photo and its square thumbnails.
 #[tracing::instrument(skip(db))]
 pub async fn user_photo_upload(
     user: CurrentUser,
+    Query(crop): Query<CropQuery>,
     State(db): State<DatabaseConnection>,
     multipart: Multipart,
-) -> EndpointResult<Json<String>> {
+) -> EndpointResult<Json<ImageVariants>> {
+    let crop = crop.image_crop()?;
     let (handler, mut uploads) = files::accept_uploads(multipart, crate::USER_MAX_PROFILE_PHOTO);
     handler.accept().await?; // Receive photo from the client
     if let Some(file) = uploads.files().await {
-        // Save an image to the file system
-        let saved_to = file.save_image(USER_UPLOAD_DIR).await?;
+        // Save an image and its thumbnails to the file system
+        let saved_to = file
+            .save_image_with_thumbnails(USER_UPLOAD_DIR, crop)
+            .await?;
 
         // Save image path to the database
         let (new_photo, old_photo) = UserProfile::insert_photo(user.id, saved_to, db).await?;
@@ -69,7 +85,7 @@ pub async fn user_photo_upload(
             tokio::spawn(async move { delete_user_photo(&old_photo).await });
         }
 
-        Ok(Json(new_photo))
+        Ok(Json(ImageVariants::new(crate::USER_PHOTO_URL, &new_photo)))
     } else {
         Err(EndpointRejection::BadRequest(
             "User profile photo not received".into(),
@@ -14,6 +14,7 @@ use crate::{
 #[serde(rename_all = "camelCase")]
 pub struct UserProfile {
     pub user: UserIndex,
+    pub handle: Option<String>,
     pub about: String,
     pub lives_at: Option<String>,
     pub date_joined: Date,
@@ -28,14 +29,17 @@ impl UserProfile {
         user_id: ModelID,
         first_name: String,
         last_name: Option<String>,
+        handle: Option<String>,
         about: String,
         lives_at: Option<String>,
         photo: Option<String>,
+        identity_verified: bool,
         date_joined: Date,
         farms: Option<Vec<Farm>>,
     ) -> Self {
         Self {
-            user: UserIndex::from_row(user_id, first_name, last_name, photo),
+            user: UserIndex::from_row(user_id, first_name, last_name, photo, identity_verified),
+            handle,
             about,
             lives_at,
             date_joined,
@@ -59,6 +59,57 @@ impl Session {
         }
     }
 
+    /// Find the user associated with the verified phone number from the database
+    pub async fn find_user_by_phone(
+        phone: String,
+        db: DatabaseConnection,
+    ) -> ServerResult<Option<LoginUser>> {
+        match sqlx::query!(
+            r#"
+                SELECT user_.id AS user_id,
+                    user_.phc_string,
+                    user_.account_locked,
+                    user_.account_locked_reason,
+                    user_.account_locked_until,
+                    address.verified AS email_verified,
+                    delete_request.requested_at AS "delete_requested_at?"
+                FROM accounts.phones phone
+                LEFT JOIN accounts.users user_
+                    ON phone.user_id = user_.id
+                LEFT JOIN accounts.emails address
+                    ON phone.user_id = address.user_id
+                LEFT JOIN accounts.account_delete_requests delete_request
+                    ON phone.user_id = delete_request.user_id
+
+                WHERE phone.phone = $1
+                    AND phone.verified = true
+            "#,
+            phone
+        )
+        .fetch_optional(&db.pool)
+        .await
+        {
+            Ok(rec) => {
+                let user = rec.map(|rec| {
+                    LoginUser::from_row(
+                        rec.user_id.into(),
+                        rec.phc_string,
+                        rec.account_locked,
+                        rec.account_locked_reason,
+                        rec.account_locked_until,
+                        rec.email_verified,
+                        rec.delete_requested_at.is_some(),
+                    )
+                });
+                Ok(user)
+            }
+            Err(err) => {
+                tracing::error!("Database error, failed to fetch LoginUser: {}", err);
+                Err(err.into())
+            }
+        }
+    }
+
     /// Insert a new session into the database.
     #[tracing::instrument(name = "Insert Session", skip(db, session))]
     pub async fn insert(session: SessionInsert, db: DatabaseConnection) -> ServerResult<ModelID> {
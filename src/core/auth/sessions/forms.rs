@@ -20,10 +20,14 @@ use crate::{
 
 use super::models::Session;
 
-/// User login form
+/// User login form, the user logs-in
+/// with either their email or verified phone number
 #[derive(Debug, Clone, Deserialize)]
 pub struct LoginForm {
-    pub email: String,
+    #[serde(default)]
+    pub email: Option<String>,
+    #[serde(default)]
+    pub phone: Option<String>,
     pub password: String,
     /// User id is set if user login completed successfully
     #[serde(skip_deserializing)]
@@ -47,8 +51,24 @@ impl LoginForm {
         // Clean the data
         self.clean_data();
 
-        self.email
-            .validate_len(1, 255, crate::INVALID_CREDENTIALS_ERR_MSG)?;
+        match (&self.email, &self.phone) {
+            (Some(email), None) => {
+                email.validate_len(1, 255, crate::INVALID_CREDENTIALS_ERR_MSG)?;
+            }
+            (None, Some(phone)) => {
+                let Ok(phone) = phone.validate_phone() else {
+                    return Err(EndpointRejection::BadRequest(
+                        crate::INVALID_CREDENTIALS_ERR_MSG.into(),
+                    ));
+                };
+                self.phone = Some(phone);
+            }
+            _ => {
+                return Err(EndpointRejection::BadRequest(
+                    "Enter either your email or your phone number.".into(),
+                ))
+            }
+        }
         self.password
             .validate_len(0, 25, crate::INVALID_CREDENTIALS_ERR_MSG)?;
 
@@ -57,7 +77,11 @@ impl LoginForm {
 
     /// Clean form data
     fn clean_data(&mut self) {
-        self.email = self.email.clean().to_ascii_lowercase();
+        self.email = self
+            .email
+            .as_ref()
+            .map(|email| email.clean().to_ascii_lowercase());
+        self.phone = self.phone.as_ref().map(|phone| phone.clean());
     }
 
     /// Creates new `SessionInsert` data and returns (`SessionInsert`, token:String)
@@ -106,9 +130,13 @@ where
         login.validate()?;
 
         let db = state.database();
-        let email = login.email.clone();
+        let user = match (login.email.clone(), login.phone.clone()) {
+            (Some(email), _) => Session::find_user_by_email(email, db.clone()).await?,
+            (None, Some(phone)) => Session::find_user_by_phone(phone, db.clone()).await?,
+            (None, None) => None,
+        };
 
-        let Some(user) = Session::find_user_by_email(email.clone(), db.clone()).await? else {
+        let Some(user) = user else {
             return Err(EndpointRejection::BadRequest(
                 crate::INVALID_CREDENTIALS_ERR_MSG.into(),
             ));
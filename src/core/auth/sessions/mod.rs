@@ -31,7 +31,7 @@ fn add_session_cookie(jar: PrivateCookieJar, token: String) -> PrivateCookieJar
 
 /// Removes session cookie from cookie jar
 #[must_use]
-fn remove_session_cookie(jar: PrivateCookieJar) -> PrivateCookieJar {
+pub fn remove_session_cookie(jar: PrivateCookieJar) -> PrivateCookieJar {
     let jar = jar.remove(Cookie::build(SESSION_TOKEN));
     jar
 }
@@ -28,20 +28,70 @@ pub const PASSWORD_TOKEN_EXPIRY_TIME: i64 = 30; // 30 minutes
 pub const ACCOUNT_CONFIRM_TOKEN_EXPIRY: i64 = 30; // minutes
 /// Number of profile photos allowed per user
 pub const USER_MAX_PROFILE_PHOTO: u8 = 1;
+/// Number of files submitted for identity verification; an ID document and a selfie
+pub const IDENTITY_VERIFICATION_FILES: u8 = 2;
 /// Max numbers of days a user has before their account deleted permanently.
 pub const MAX_DAYS_TO_DELETE_ACCOUNT: u8 = 90;
+/// Phone verification code expiry time
+pub const PHONE_CODE_EXPIRY: i64 = 10; // minutes
+/// Number of times a phone verification code can be entered before it's invalidated
+pub const PHONE_CODE_MAX_ATTEMPTS: i16 = 5;
+/// Minimum time between two phone verification codes sent to a user
+pub const PHONE_CODE_RESEND_INTERVAL: i64 = 60; // seconds
+/// Number of days a personal data export can be downloaded before it's deleted
+pub const DATA_EXPORT_EXPIRY_DAYS: i64 = 7;
+/// Minimum time between two personal data exports requested by a user
+pub const DATA_EXPORT_REQUEST_INTERVAL: i64 = 24; // hours
+/// Words that cannot be claimed as user handles or farm slugs
+pub const RESERVED_HANDLES: [&str; 30] = [
+    "about",
+    "account",
+    "accounts",
+    "admin",
+    "administrator",
+    "api",
+    "app",
+    "blocked",
+    "directory",
+    "farm",
+    "farms",
+    "handle",
+    "harvest",
+    "harvests",
+    "help",
+    "home",
+    "invitations",
+    "login",
+    "logout",
+    "new",
+    "profile",
+    "ratings",
+    "reapears",
+    "root",
+    "settings",
+    "signup",
+    "slug",
+    "staff",
+    "support",
+    "transfer",
+];
 
 // ===== AUTH =====
 
 /// Endpoints that are not protected with an API key;
-pub const UNAUTHENTICATED_ENDPOINTS: [&str; 6] = [
+pub const UNAUTHENTICATED_ENDPOINTS: [&str; 11] = [
     // "/account/signup" ??
     "/account/confirm",
     "/health-check",
     "/account/reset-password",
+    "/account/data-export/download",
+    "/account/deactivate/cancel",
     // Media endpoints
     "/harvests/p",
     "/cultivars/p",
+    "/farms/ratings/p",
+    "/locations/p",
+    "/farms/logos/p",
     "/account/users/photo",
 ];
 /// An error message for when a user entered a wrong password of username
@@ -55,6 +105,12 @@ pub const SUPPORTED_UPLOAD_IMAGE_FORMATS: [&str; 4] = ["jpeg", "jpg", "png", "we
 pub const IMAGE_OUTPUT_FORMATS: [ImageFormat; 2] = [ImageFormat::Jpeg, ImageFormat::WebP];
 /// Image maximum size allowed on the server
 pub const IMAGE_MAX_SIZE: usize = 20 * 1024 * 1024; // 20 * 1024 * 1024 = 20mb
+/// Square thumbnail sizes generated for profile photos and farm logos
+pub const THUMBNAIL_SIZES: [u32; 3] = [64, 256, 512]; // pixels
+/// Url users profile photos are served at
+pub const USER_PHOTO_URL: &str = "/api/v1/account/users/photo";
+/// Url farm logos are served at
+pub const FARM_LOGO_URL: &str = "/api/v1/farms/logos/p";
 
 // ===== MAIL =====
 
@@ -92,3 +148,30 @@ pub const CULTIVAR_MAX_IMAGE: u8 = 1;
 pub const HARVEST_MAX_AGE_TO_ARCHIVE: i64 = 4; // days
 /// Number of images allowed to be uploaded per harvest
 pub const HARVEST_MAX_IMAGE: u8 = 5;
+/// Default number of days a deleted farm, location or harvest can be restored,
+/// the archive is finalized and its images deleted afterwards.
+///
+/// Overridden by the `ARCHIVE_RESTORE_GRACE_DAYS` environmental variable.
+pub const ARCHIVE_RESTORE_GRACE_DAYS: i64 = 30;
+/// Number of images allowed to be uploaded per farm location
+pub const LOCATION_MAX_IMAGE: u8 = 6;
+/// Number of images allowed to be uploaded per farm rating
+pub const RATING_MAX_IMAGE: u8 = 3;
+/// Number of reports after which a farm rating is hidden until staff review it
+pub const RATING_REPORTS_TO_HIDE: i64 = 3;
+/// Region boundary `GeoJSON` maximum size allowed on the server
+pub const REGION_BOUNDARY_MAX_SIZE: usize = 10 * 1024 * 1024; // 10mb
+/// Countries and regions import file maximum size allowed on the server
+pub const LOCATION_IMPORT_MAX_SIZE: usize = 5 * 1024 * 1024; // 5mb
+/// Number of days a farm member invitation is valid for
+pub const FARM_INVITATION_EXPIRY_DAYS: i64 = 7;
+/// Number of days a farm ownership transfer is valid for
+pub const FARM_TRANSFER_EXPIRY_DAYS: i64 = 7;
+/// Map zoom level from which harvests and locations are no longer clustered
+pub const MAP_CLUSTER_MAX_ZOOM: u8 = 14;
+/// Size in pixels of a map cluster cell
+pub const MAP_CLUSTER_RADIUS: f64 = 60.0;
+/// Maximum number of harvests returned on the map
+pub const MAP_HARVESTS_MAX: i64 = 2000;
+/// Maximum number of locations returned on the map
+pub const MAP_LOCATIONS_MAX: i64 = 2000;
@@ -16,6 +16,12 @@ pub trait ValidateString {
 
     /// Validates string is valid phone number
     fn validate_phone(&self) -> EndpointResult<String>;
+
+    /// Validates the string is a valid user handle
+    fn validate_handle(&self) -> EndpointResult<()>;
+
+    /// Validates the string is a valid farm slug
+    fn validate_slug(&self) -> EndpointResult<()>;
 }
 
 impl ValidateString for str {
@@ -66,6 +72,49 @@ impl ValidateString for str {
             }
         }
     }
+
+    /// Validates the string is 3 to 30 letters, digits or underscores
+    /// and not a reserved word
+    fn validate_handle(&self) -> EndpointResult<()> {
+        self.validate_len(3, 30, "Handle must be between 3 and 30 characters")?;
+        if !self.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+            tracing::error!("Validation handle error: invalid characters.");
+            return Err(EndpointRejection::BadRequest(
+                "Handle can only contain letters, digits and underscores".into(),
+            ));
+        }
+        validate_not_reserved(self)
+    }
+
+    /// Validates the string is 3 to 50 lowercase letters, digits or hyphens
+    /// not starting or ending with a hyphen, and not a reserved word
+    fn validate_slug(&self) -> EndpointResult<()> {
+        self.validate_len(3, 50, "Slug must be between 3 and 50 characters")?;
+        if !self
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-')
+            || self.starts_with('-')
+            || self.ends_with('-')
+            || self.contains("--")
+        {
+            tracing::error!("Validation slug error: invalid characters.");
+            return Err(EndpointRejection::BadRequest(
+                "Slug can only contain lowercase letters, digits and single hyphens".into(),
+            ));
+        }
+        validate_not_reserved(self)
+    }
+}
+
+/// Validates the handle or slug is not a reserved word
+fn validate_not_reserved(name: &str) -> EndpointResult<()> {
+    if crate::RESERVED_HANDLES.contains(&name.to_ascii_lowercase().as_str()) {
+        tracing::error!("Validation handle error: reserved word.");
+        return Err(EndpointRejection::BadRequest(
+            format!("`{name}` is reserved and cannot be used").into(),
+        ));
+    }
+    Ok(())
 }
 
 // ====== String Transformation =====
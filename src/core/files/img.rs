@@ -6,7 +6,8 @@ use std::{
     sync::Arc,
 };
 
-use image::{io::Reader as ImageReader, DynamicImage, ImageFormat};
+use image::{imageops::FilterType, io::Reader as ImageReader, DynamicImage, ImageFormat};
+use serde::{Deserialize, Serialize};
 use tokio::task::{self, JoinSet};
 
 use crate::{
@@ -103,6 +104,45 @@ impl UploadedImage {
 
         Ok(paths)
     }
+
+    /// Crops the image to the `crop` rectangle.
+    pub async fn crop(self, crop: ImageCrop) -> EndpointResult<Self> {
+        let cropped = task::spawn_blocking(move || self.0.crop(crop))
+            .await
+            .map_err(|_join_err| EndpointRejection::internal_server_error())??;
+        Ok(Self(Arc::new(cropped)))
+    }
+
+    /// Saves square thumbnails of the image in all supported output formats,
+    /// named `<id>-<size>` for every size in `THUMBNAIL_SIZES`.
+    pub async fn save_thumbnails<P>(self, upload_dir: P) -> ServerResult<Vec<PathBuf>>
+    where
+        P: AsRef<Path> + Send + 'static,
+    {
+        let upload_dir = upload_dir.as_ref().to_owned();
+        task::spawn_blocking(move || {
+            let mut paths = Vec::new();
+            for size in crate::THUMBNAIL_SIZES {
+                let thumbnail = self
+                    .0
+                    .image
+                    .resize_to_fill(size, size, FilterType::Lanczos3);
+                let file_stem = thumbnail_stem(&self.0.id.to_string(), size);
+                for ext in crate::IMAGE_OUTPUT_FORMATS {
+                    match save_dynamic_image(&thumbnail, &file_stem, ext, &upload_dir) {
+                        Ok(path) => paths.push(path),
+                        Err(err) => {
+                            // Could not complete delete other saved thumbnails.
+                            tokio::spawn(async move { super::delete_files(paths).await });
+                            return Err(err);
+                        }
+                    }
+                }
+            }
+            Ok(paths)
+        })
+        .await?
+    }
 }
 
 impl UploadedFile {
@@ -115,6 +155,32 @@ impl UploadedFile {
         Ok(paths)
     }
 
+    /// Saves as an image to the file system in all server image output formats,
+    /// cropped to `crop` if given, together with its square thumbnails.
+    ///
+    /// Returns the paths of the full size image.
+    pub async fn save_image_with_thumbnails<P>(
+        self,
+        upload_dir: P,
+        crop: Option<ImageCrop>,
+    ) -> EndpointResult<Vec<PathBuf>>
+    where
+        P: AsRef<Path> + Clone + Send + 'static,
+    {
+        let mut image = self.try_into_image().await?;
+        if let Some(crop) = crop {
+            image = image.crop(crop).await?;
+        }
+
+        let paths = image.clone().save_all(upload_dir.clone()).await?;
+        if let Err(err) = image.save_thumbnails(upload_dir).await {
+            let saved = paths.clone();
+            tokio::spawn(async move { super::delete_files(saved).await });
+            return Err(err.into());
+        }
+        Ok(paths)
+    }
+
     /// Saves as an image to the file system in it's original format.
     pub async fn save_image_original<P>(self, upload_dir: P) -> EndpointResult<PathBuf>
     where
@@ -197,16 +263,161 @@ impl InnerImage {
     where
         P: AsRef<Path> + Send + 'static,
     {
-        let path = upload_dir
-            .as_ref()
-            .join(self.id.to_string())
-            .with_extension(fmt.extensions_str()[0]);
-
-        self.image.save(&path).map_err(|err| {
-            tracing::error!("Image error, failed to save an image: {}", err);
-            <image::ImageError as std::convert::Into<ServerError>>::into(err)
-        })?;
+        save_dynamic_image(&self.image, &self.id.to_string(), fmt, upload_dir)
+    }
 
-        Ok(path)
+    /// Crops the image to the `crop` rectangle,
+    /// fails if the rectangle is empty or outside the image.
+    fn crop(&self, crop: ImageCrop) -> ServerResult<Self> {
+        let fits = |start: u32, len: u32, bound: u32| {
+            len > 0 && start.checked_add(len).is_some_and(|end| end <= bound)
+        };
+        if !fits(crop.x, crop.width, self.image.width())
+            || !fits(crop.y, crop.height, self.image.height())
+        {
+            tracing::error!("Image error, crop rectangle outside the image: {:?}", crop);
+            return Err(ServerError::bad_request(
+                "Crop rectangle must be within the image",
+            ));
+        }
+
+        Ok(Self {
+            id: self.id,
+            file_name: self.file_name.clone(),
+            file_ext: self.file_ext,
+            image: self.image.crop_imm(crop.x, crop.y, crop.width, crop.height),
+        })
+    }
+}
+
+/// Saves `image` named `file_stem` in given format(`fmt`) to the `upload dir`.
+fn save_dynamic_image<P>(
+    image: &DynamicImage,
+    file_stem: &str,
+    fmt: ImageFormat,
+    upload_dir: P,
+) -> ServerResult<PathBuf>
+where
+    P: AsRef<Path>,
+{
+    let path = upload_dir
+        .as_ref()
+        .join(file_stem)
+        .with_extension(fmt.extensions_str()[0]);
+
+    image.save(&path).map_err(|err| {
+        tracing::error!("Image error, failed to save an image: {}", err);
+        <image::ImageError as std::convert::Into<ServerError>>::into(err)
+    })?;
+
+    Ok(path)
+}
+
+/// Returns the file name of the image thumbnail without its extension
+fn thumbnail_stem(file_stem: &str, size: u32) -> String {
+    format!("{file_stem}-{size}")
+}
+
+/// Crop rectangle applied to an uploaded image, in pixels
+#[derive(Debug, Clone, Copy)]
+pub struct ImageCrop {
+    pub x: u32,
+    pub y: u32,
+    pub width: u32,
+    pub height: u32,
+}
+
+/// Image upload crop query parameters, in pixels
+#[derive(Debug, Clone, Copy, Default, Deserialize)]
+pub struct CropQuery {
+    #[serde(default)]
+    pub x: Option<u32>,
+    #[serde(default)]
+    pub y: Option<u32>,
+    #[serde(default)]
+    pub width: Option<u32>,
+    #[serde(default)]
+    pub height: Option<u32>,
+}
+
+impl CropQuery {
+    /// Crop rectangle, `None` if no crop params were given
+    ///
+    /// # Errors
+    ///
+    /// Return bad request if only some of the crop params were given
+    pub fn image_crop(&self) -> EndpointResult<Option<ImageCrop>> {
+        match (self.x, self.y, self.width, self.height) {
+            (Some(x), Some(y), Some(width), Some(height)) => Ok(Some(ImageCrop {
+                x,
+                y,
+                width,
+                height,
+            })),
+            (None, None, None, None) => Ok(None),
+            _ => Err(EndpointRejection::BadRequest(
+                "Crop requires all of the x, y, width and height params".into(),
+            )),
+        }
+    }
+}
+
+/// Urls of a saved image and its square thumbnails
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ImageVariants {
+    /// The full size image, in all server image output formats
+    pub original: Vec<String>,
+    pub thumbnails: Vec<ImageThumbnail>,
+}
+
+/// Urls of an image thumbnail in all server image output formats
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ImageThumbnail {
+    pub size: u32,
+    pub urls: Vec<String>,
+}
+
+impl ImageVariants {
+    /// Creates the variant urls of the image `file` served at `base_url`
+    #[must_use]
+    pub fn new(base_url: &str, file: &str) -> Self {
+        let file_stem = Path::new(file).file_stem().map_or_else(
+            || file.to_owned(),
+            |stem| stem.to_string_lossy().to_string(),
+        );
+        let urls = |stem: &str| {
+            crate::IMAGE_OUTPUT_FORMATS
+                .into_iter()
+                .map(|ext| format!("{base_url}/{stem}.{}", ext.extensions_str()[0]))
+                .collect()
+        };
+
+        Self {
+            original: urls(&file_stem),
+            thumbnails: crate::THUMBNAIL_SIZES
+                .into_iter()
+                .map(|size| ImageThumbnail {
+                    size,
+                    urls: urls(&thumbnail_stem(&file_stem, size)),
+                })
+                .collect(),
+        }
+    }
+}
+
+/// Get all image formats and thumbnails paths saved on the server
+#[must_use]
+pub fn saved_paths_with_thumbnails(upload_dir: &str, file: &str) -> Vec<PathBuf> {
+    let mut paths = super::saved_paths(upload_dir, file);
+    let file_stem = Path::new(file).file_stem().map_or_else(
+        || file.to_owned(),
+        |stem| stem.to_string_lossy().to_string(),
+    );
+    for size in crate::THUMBNAIL_SIZES {
+        let thumbnail = thumbnail_stem(&file_stem, size);
+        paths.extend(super::saved_paths(upload_dir, &thumbnail));
     }
+    paths
 }
@@ -10,7 +10,9 @@ mod img;
 mod uploaded;
 
 pub use handler::accept_uploads;
-pub use img::UploadedImage;
+pub use img::{
+    saved_paths_with_thumbnails, CropQuery, ImageCrop, ImageThumbnail, ImageVariants, UploadedImage,
+};
 pub use uploaded::UploadedFile;
 
 /// Saves file on the filesystem
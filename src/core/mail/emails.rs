@@ -50,6 +50,72 @@ const VERIFY_NEW_EMAIL_CHANGE_EMAIL_TEXT: &str = include_str!(concat!(
     "/static/templates/emails/verify_new_email.txt"
 ));
 
+/// An email to invite a user to join a farm.
+const FARM_INVITATION_EMAIL_HTML: &str = include_str!(concat!(
+    env!("CARGO_MANIFEST_DIR"),
+    "/static/templates/emails/farm_invitation.html"
+));
+/// An email to invite a user to join a farm.
+const FARM_INVITATION_EMAIL_TEXT: &str = include_str!(concat!(
+    env!("CARGO_MANIFEST_DIR"),
+    "/static/templates/emails/farm_invitation.txt"
+));
+
+/// An email to a user to confirm a farm ownership transfer.
+const FARM_TRANSFER_EMAIL_HTML: &str = include_str!(concat!(
+    env!("CARGO_MANIFEST_DIR"),
+    "/static/templates/emails/farm_transfer.html"
+));
+/// An email to a user to confirm a farm ownership transfer.
+const FARM_TRANSFER_EMAIL_TEXT: &str = include_str!(concat!(
+    env!("CARGO_MANIFEST_DIR"),
+    "/static/templates/emails/farm_transfer.txt"
+));
+
+/// An email to a farm follower about the farm new harvest.
+const FARM_NEW_HARVEST_EMAIL_HTML: &str = include_str!(concat!(
+    env!("CARGO_MANIFEST_DIR"),
+    "/static/templates/emails/farm_new_harvest.html"
+));
+/// An email to a farm follower about the farm new harvest.
+const FARM_NEW_HARVEST_EMAIL_TEXT: &str = include_str!(concat!(
+    env!("CARGO_MANIFEST_DIR"),
+    "/static/templates/emails/farm_new_harvest.txt"
+));
+
+/// An email to user with the link to download their data export.
+const DATA_EXPORT_EMAIL_HTML: &str = include_str!(concat!(
+    env!("CARGO_MANIFEST_DIR"),
+    "/static/templates/emails/data_export.html"
+));
+/// An email to user with the link to download their data export.
+const DATA_EXPORT_EMAIL_TEXT: &str = include_str!(concat!(
+    env!("CARGO_MANIFEST_DIR"),
+    "/static/templates/emails/data_export.txt"
+));
+
+/// An email to user that their data export could not be prepared.
+const DATA_EXPORT_FAILED_EMAIL_HTML: &str = include_str!(concat!(
+    env!("CARGO_MANIFEST_DIR"),
+    "/static/templates/emails/data_export_failed.html"
+));
+/// An email to user that their data export could not be prepared.
+const DATA_EXPORT_FAILED_EMAIL_TEXT: &str = include_str!(concat!(
+    env!("CARGO_MANIFEST_DIR"),
+    "/static/templates/emails/data_export_failed.txt"
+));
+
+/// An email to user to cancel their account deletion.
+const ACCOUNT_DEACTIVATED_EMAIL_HTML: &str = include_str!(concat!(
+    env!("CARGO_MANIFEST_DIR"),
+    "/static/templates/emails/account_deactivated.html"
+));
+/// An email to user to cancel their account deletion.
+const ACCOUNT_DEACTIVATED_EMAIL_TEXT: &str = include_str!(concat!(
+    env!("CARGO_MANIFEST_DIR"),
+    "/static/templates/emails/account_deactivated.txt"
+));
+
 // ===== Email Template Names =====
 // Names used to identify templates in the email template container
 
@@ -65,6 +131,24 @@ const NAME_PASSWORD_RESET_EMAIL_TEXT: &str = "password_reset_txt";
 const NAME_VERIFY_NEW_EMAIL_CHANGE_EMAIL_HTML: &str = "verify_new_email_html";
 const NAME_VERIFY_NEW_EMAIL_CHANGE_EMAIL_TEXT: &str = "verify_new_email_txt";
 
+const NAME_FARM_INVITATION_EMAIL_HTML: &str = "farm_invitation_html";
+const NAME_FARM_INVITATION_EMAIL_TEXT: &str = "farm_invitation_txt";
+
+const NAME_FARM_TRANSFER_EMAIL_HTML: &str = "farm_transfer_html";
+const NAME_FARM_TRANSFER_EMAIL_TEXT: &str = "farm_transfer_txt";
+
+const NAME_FARM_NEW_HARVEST_EMAIL_HTML: &str = "farm_new_harvest_html";
+const NAME_FARM_NEW_HARVEST_EMAIL_TEXT: &str = "farm_new_harvest_txt";
+
+const NAME_DATA_EXPORT_EMAIL_HTML: &str = "data_export_html";
+const NAME_DATA_EXPORT_EMAIL_TEXT: &str = "data_export_txt";
+
+const NAME_DATA_EXPORT_FAILED_EMAIL_HTML: &str = "data_export_failed_html";
+const NAME_DATA_EXPORT_FAILED_EMAIL_TEXT: &str = "data_export_failed_txt";
+
+const NAME_ACCOUNT_DEACTIVATED_EMAIL_HTML: &str = "account_deactivated_html";
+const NAME_ACCOUNT_DEACTIVATED_EMAIL_TEXT: &str = "account_deactivated_txt";
+
 /// A container for email templates
 #[derive(Debug, Clone)]
 pub struct EmailTemplates(minijinja::Environment<'static>);
@@ -119,6 +203,54 @@ impl EmailTemplates {
         )
         .unwrap();
 
+        env.add_template(NAME_FARM_INVITATION_EMAIL_HTML, FARM_INVITATION_EMAIL_HTML)
+            .unwrap();
+        env.add_template(NAME_FARM_INVITATION_EMAIL_TEXT, FARM_INVITATION_EMAIL_TEXT)
+            .unwrap();
+
+        env.add_template(NAME_FARM_TRANSFER_EMAIL_HTML, FARM_TRANSFER_EMAIL_HTML)
+            .unwrap();
+        env.add_template(NAME_FARM_TRANSFER_EMAIL_TEXT, FARM_TRANSFER_EMAIL_TEXT)
+            .unwrap();
+
+        env.add_template(
+            NAME_FARM_NEW_HARVEST_EMAIL_HTML,
+            FARM_NEW_HARVEST_EMAIL_HTML,
+        )
+        .unwrap();
+        env.add_template(
+            NAME_FARM_NEW_HARVEST_EMAIL_TEXT,
+            FARM_NEW_HARVEST_EMAIL_TEXT,
+        )
+        .unwrap();
+
+        env.add_template(NAME_DATA_EXPORT_EMAIL_HTML, DATA_EXPORT_EMAIL_HTML)
+            .unwrap();
+        env.add_template(NAME_DATA_EXPORT_EMAIL_TEXT, DATA_EXPORT_EMAIL_TEXT)
+            .unwrap();
+
+        env.add_template(
+            NAME_DATA_EXPORT_FAILED_EMAIL_HTML,
+            DATA_EXPORT_FAILED_EMAIL_HTML,
+        )
+        .unwrap();
+        env.add_template(
+            NAME_DATA_EXPORT_FAILED_EMAIL_TEXT,
+            DATA_EXPORT_FAILED_EMAIL_TEXT,
+        )
+        .unwrap();
+
+        env.add_template(
+            NAME_ACCOUNT_DEACTIVATED_EMAIL_HTML,
+            ACCOUNT_DEACTIVATED_EMAIL_HTML,
+        )
+        .unwrap();
+        env.add_template(
+            NAME_ACCOUNT_DEACTIVATED_EMAIL_TEXT,
+            ACCOUNT_DEACTIVATED_EMAIL_TEXT,
+        )
+        .unwrap();
+
         Self(env)
     }
 
@@ -226,4 +358,196 @@ impl EmailTemplates {
 
         EmailMessage::from_server(server_email, new_email, &subject, text, html)
     }
+
+    /// Return farm invitation email
+    pub fn farm_invitation(
+        &self,
+        server_email: &str,
+        inviter_name: &str,
+        farm_name: &str,
+        role: &str,
+        user_email: &str,
+        link: &str,
+    ) -> ServerResult<EmailMessage> {
+        let ctx = context! {
+            inviter_name => inviter_name,
+            farm_name => farm_name,
+            role => role,
+            link => link,
+            expiry_days => crate::FARM_INVITATION_EXPIRY_DAYS
+        };
+        let text = self
+            .0
+            .get_template(NAME_FARM_INVITATION_EMAIL_TEXT)
+            .unwrap()
+            .render(&ctx)
+            .unwrap();
+        let html = self
+            .0
+            .get_template(NAME_FARM_INVITATION_EMAIL_HTML)
+            .unwrap()
+            .render(&ctx)
+            .unwrap();
+
+        let subject = format!("[{APP_NAME}] You have been invited to join {farm_name}.");
+
+        EmailMessage::from_server(server_email, user_email, &subject, text, html)
+    }
+
+    /// Return farm ownership transfer email
+    pub fn farm_transfer(
+        &self,
+        server_email: &str,
+        owner_name: &str,
+        farm_name: &str,
+        user_email: &str,
+        link: &str,
+    ) -> ServerResult<EmailMessage> {
+        let ctx = context! {
+            owner_name => owner_name,
+            farm_name => farm_name,
+            link => link,
+            expiry_days => crate::FARM_TRANSFER_EXPIRY_DAYS
+        };
+        let text = self
+            .0
+            .get_template(NAME_FARM_TRANSFER_EMAIL_TEXT)
+            .unwrap()
+            .render(&ctx)
+            .unwrap();
+        let html = self
+            .0
+            .get_template(NAME_FARM_TRANSFER_EMAIL_HTML)
+            .unwrap()
+            .render(&ctx)
+            .unwrap();
+
+        let subject = format!("[{APP_NAME}] Confirm the ownership transfer of {farm_name}.");
+
+        EmailMessage::from_server(server_email, user_email, &subject, text, html)
+    }
+
+    /// Return farm new harvest email
+    pub fn farm_new_harvest(
+        &self,
+        server_email: &str,
+        first_name: &str,
+        farm_name: &str,
+        harvest_name: &str,
+        user_email: &str,
+        link: &str,
+    ) -> ServerResult<EmailMessage> {
+        let ctx = context! {
+            first_name => first_name,
+            farm_name => farm_name,
+            harvest_name => harvest_name,
+            link => link,
+        };
+        let text = self
+            .0
+            .get_template(NAME_FARM_NEW_HARVEST_EMAIL_TEXT)
+            .unwrap()
+            .render(&ctx)
+            .unwrap();
+        let html = self
+            .0
+            .get_template(NAME_FARM_NEW_HARVEST_EMAIL_HTML)
+            .unwrap()
+            .render(&ctx)
+            .unwrap();
+
+        let subject = format!("[{APP_NAME}] {farm_name} listed a new harvest: {harvest_name}.");
+
+        EmailMessage::from_server(server_email, user_email, &subject, text, html)
+    }
+
+    /// Return data export ready email
+    pub fn data_export(
+        &self,
+        server_email: &str,
+        first_name: &str,
+        user_email: &str,
+        link: &str,
+    ) -> ServerResult<EmailMessage> {
+        let ctx = context! {
+            first_name => first_name,
+            link => link,
+            expiry_days => crate::DATA_EXPORT_EXPIRY_DAYS
+        };
+        let text = self
+            .0
+            .get_template(NAME_DATA_EXPORT_EMAIL_TEXT)
+            .unwrap()
+            .render(&ctx)
+            .unwrap();
+        let html = self
+            .0
+            .get_template(NAME_DATA_EXPORT_EMAIL_HTML)
+            .unwrap()
+            .render(&ctx)
+            .unwrap();
+
+        let subject = format!("[{APP_NAME}] Your data export is ready.");
+
+        EmailMessage::from_server(server_email, user_email, &subject, text, html)
+    }
+
+    /// Return data export failed email
+    pub fn data_export_failed(
+        &self,
+        server_email: &str,
+        first_name: &str,
+        user_email: &str,
+    ) -> ServerResult<EmailMessage> {
+        let ctx = context! {
+            first_name => first_name,
+        };
+        let text = self
+            .0
+            .get_template(NAME_DATA_EXPORT_FAILED_EMAIL_TEXT)
+            .unwrap()
+            .render(&ctx)
+            .unwrap();
+        let html = self
+            .0
+            .get_template(NAME_DATA_EXPORT_FAILED_EMAIL_HTML)
+            .unwrap()
+            .render(&ctx)
+            .unwrap();
+
+        let subject = format!("[{APP_NAME}] Your data export could not be prepared.");
+
+        EmailMessage::from_server(server_email, user_email, &subject, text, html)
+    }
+
+    /// Return account deactivated email
+    pub fn account_deactivated(
+        &self,
+        server_email: &str,
+        first_name: &str,
+        user_email: &str,
+        link: &str,
+    ) -> ServerResult<EmailMessage> {
+        let ctx = context! {
+            first_name => first_name,
+            link => link,
+            days => crate::MAX_DAYS_TO_DELETE_ACCOUNT
+        };
+        let text = self
+            .0
+            .get_template(NAME_ACCOUNT_DEACTIVATED_EMAIL_TEXT)
+            .unwrap()
+            .render(&ctx)
+            .unwrap();
+        let html = self
+            .0
+            .get_template(NAME_ACCOUNT_DEACTIVATED_EMAIL_HTML)
+            .unwrap()
+            .render(&ctx)
+            .unwrap();
+
+        let subject = format!("[{APP_NAME}] Your account has been deactivated.");
+
+        EmailMessage::from_server(server_email, user_email, &subject, text, html)
+    }
 }
@@ -107,4 +107,91 @@ impl Mail {
         self.emails
             .verify_new_email(self.address.as_str(), first_name, new_email, code)
     }
+
+    /// Return farm invitation email
+    pub fn farm_invitation(
+        &self,
+        inviter_name: &str,
+        farm_name: &str,
+        role: &str,
+        user_email: &str,
+        link: &str,
+    ) -> ServerResult<EmailMessage> {
+        self.emails.farm_invitation(
+            self.address.as_str(),
+            inviter_name,
+            farm_name,
+            role,
+            user_email,
+            link,
+        )
+    }
+
+    /// Return farm ownership transfer email
+    pub fn farm_transfer(
+        &self,
+        owner_name: &str,
+        farm_name: &str,
+        user_email: &str,
+        link: &str,
+    ) -> ServerResult<EmailMessage> {
+        self.emails.farm_transfer(
+            self.address.as_str(),
+            owner_name,
+            farm_name,
+            user_email,
+            link,
+        )
+    }
+
+    /// Return farm new harvest email
+    pub fn farm_new_harvest(
+        &self,
+        first_name: &str,
+        farm_name: &str,
+        harvest_name: &str,
+        user_email: &str,
+        link: &str,
+    ) -> ServerResult<EmailMessage> {
+        self.emails.farm_new_harvest(
+            self.address.as_str(),
+            first_name,
+            farm_name,
+            harvest_name,
+            user_email,
+            link,
+        )
+    }
+
+    /// Return data export ready email
+    pub fn data_export(
+        &self,
+        first_name: &str,
+        user_email: &str,
+        link: &str,
+    ) -> ServerResult<EmailMessage> {
+        self.emails
+            .data_export(self.address.as_str(), first_name, user_email, link)
+    }
+
+    /// Return data export failed email
+    pub fn data_export_failed(
+        &self,
+        first_name: &str,
+        user_email: &str,
+    ) -> ServerResult<EmailMessage> {
+        self.emails
+            .data_export_failed(self.address.as_str(), first_name, user_email)
+    }
+
+    /// Return account deactivated email
+    pub fn account_deactivated(
+        &self,
+        first_name: &str,
+        user_email: &str,
+        link: &str,
+    ) -> ServerResult<EmailMessage> {
+        self.emails
+            .account_deactivated(self.address.as_str(), first_name, user_email, link)
+    }
 }
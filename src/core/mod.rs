@@ -9,4 +9,5 @@ pub mod files;
 pub mod mail;
 pub mod server;
 pub mod settings;
+pub mod sms;
 pub mod types;
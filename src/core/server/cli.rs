@@ -1,5 +1,7 @@
 //! Server cli impls.
 
+use std::path::PathBuf;
+
 use clap::{Parser, Subcommand};
 
 /// Collect config values from cli
@@ -20,4 +22,9 @@ pub enum Commands {
         #[arg(short, long)]
         password: String,
     },
+    /// Imports countries and regions from a CSV or JSON file.
+    ImportLocations {
+        #[arg(short, long)]
+        file: PathBuf,
+    },
 }
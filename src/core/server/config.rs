@@ -4,7 +4,12 @@ use std::{env, fmt, net::SocketAddr};
 
 use axum_extra::extract::cookie::Key;
 
-use crate::{APP_DOMAIN_NAME, DEFAULT_SERVER_ADDR, DEFAULT_SERVER_PORT, SERVER_DOMAIN_NAME};
+use crate::{
+    accounts::identity::IdentityKey,
+    services::{farmers::rating::policy::RatingPolicy, produce::harvest::ArchiveGraceDays},
+    sms::Sms,
+    APP_DOMAIN_NAME, DEFAULT_SERVER_ADDR, DEFAULT_SERVER_PORT, SERVER_DOMAIN_NAME,
+};
 
 /// Server config values
 #[derive(Clone)]
@@ -22,8 +27,20 @@ pub struct Config {
     /// Outlook smtp password
     pub mail_password: String,
 
+    /// SMS provider
+    pub sms: Sms,
+
     /// Cookie encryption key
     pub cookie_key: Key,
+
+    /// Identity verification files encryption key
+    pub identity_key: IdentityKey,
+
+    /// Who is allowed to rate farms
+    pub rating_policy: RatingPolicy,
+
+    /// Number of days a deleted farm, location or harvest can be restored
+    pub archive_grace_days: ArchiveGraceDays,
 }
 
 impl fmt::Debug for Config {
@@ -64,8 +81,16 @@ impl Config {
             mail_password: env::var("MAIL_PASSWORD")
                 .expect("MAIL_PASSWORD environment variable not set."),
 
+            sms: Sms::from_env(),
+
             cookie_key: Key::try_from(cookie_key.as_bytes())
                 .expect("Key too short, cookie key must be at least 64 bytes"),
+
+            identity_key: IdentityKey::from_env(),
+
+            rating_policy: RatingPolicy::from_env(),
+
+            archive_grace_days: ArchiveGraceDays::from_env(),
         }
     }
 }
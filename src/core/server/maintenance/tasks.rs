@@ -4,7 +4,17 @@ use std::time::Duration;
 
 use time::{OffsetDateTime, Time};
 
-use crate::{accounts::AccountDelete, server::state::ServerState};
+use crate::{
+    accounts::{data_export::DataExport, identity::IdentityVerification, AccountDelete},
+    server::state::ServerState,
+    services::{
+        farmers::{
+            farm::{models::Farm, transfer::FarmTransfer},
+            location::models::Location,
+        },
+        produce::harvest::models::Harvest,
+    },
+};
 
 /// Server maintenance tasks runner
 pub async fn server_maintenance(state: ServerState) {
@@ -22,6 +32,22 @@ pub async fn server_maintenance(state: ServerState) {
 
         let db = state.database();
         // Delete user accounts the requested for account deletion
-        AccountDelete::permanently_delete_accounts(db).await;
+        AccountDelete::permanently_delete_accounts(db.clone()).await;
+
+        // Delete expired personal data export archives
+        _ = DataExport::purge_expired(db.clone()).await;
+
+        // Delete identity files left behind after review
+        _ = IdentityVerification::purge_files(db.clone()).await;
+
+        // Delete farm ownership transfers no longer accepted
+        _ = FarmTransfer::purge_expired(db.clone()).await;
+
+        // Finalize farms, locations and harvests that can no longer be restored;
+        // harvests first, locations and farms are only deleted when empty.
+        let grace_days = state.archive_grace_days();
+        _ = Harvest::purge_archived(grace_days, db.clone()).await;
+        _ = Location::purge_archived(grace_days, db.clone()).await;
+        _ = Farm::purge_archived(grace_days, db).await;
     }
 }
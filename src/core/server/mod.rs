@@ -1,5 +1,7 @@
 //! Server setup impls
 
+use std::path::Path;
+
 use axum::{
     error_handling::HandleErrorLayer,
     http::{header, Request},
@@ -19,6 +21,7 @@ use crate::{
     accounts::user::models::create_unsecure_superuser,
     auth::{api_key::ApiToken, ApiAuthentication},
     endpoint::{EndpointRejection, EndpointResult},
+    services::farmers::location::import::{ImportSummary, LocationImport},
     types::ModelID,
     CONCURRENCY_LIMIT, ONE_SECOND, REQUEST_PER_SEC, SENSITIVE_HEADERS, TIMEOUT_SECS,
 };
@@ -76,11 +79,25 @@ pub async fn run() {
 
     // Create superuser if values given.
     let cli = ConfigCli::parse();
-    if let Some(Commands::WithSuperuser { email, password }) = cli.command {
-        let id = create_unsecure_superuser(email, password, db.clone()).await;
-        let (token, key) = ApiToken::new_for_user(id);
-        let _ = token.insert(db.clone()).await.unwrap();
-        println!("API_KEY: {key}");
+    match cli.command {
+        Some(Commands::WithSuperuser { email, password }) => {
+            let id = create_unsecure_superuser(email, password, db.clone()).await;
+            let (token, key) = ApiToken::new_for_user(id);
+            let _ = token.insert(db.clone()).await.unwrap();
+            println!("API_KEY: {key}");
+        }
+        // Import countries and regions if a file is given.
+        Some(Commands::ImportLocations { file }) => {
+            match import_locations(&file, db.clone()).await {
+                Ok(summary) => println!("LOCATIONS IMPORTED: {summary:?}"),
+                Err(err) => {
+                    eprintln!("Failed to import locations from {}: {err}", file.display());
+                    #[allow(clippy::exit)]
+                    std::process::exit(1);
+                }
+            }
+        }
+        None => {}
     }
 
     // RUN MAINTENANCE TASK
@@ -96,6 +113,12 @@ pub async fn run() {
 
 // =====
 
+/// Imports countries and regions from the file at `path`
+async fn import_locations(path: &Path, db: DatabaseConnection) -> EndpointResult<ImportSummary> {
+    let import = LocationImport::from_file(path, db.clone()).await?;
+    Ok(import.save(db).await?)
+}
+
 // RUN MIGRATIONS
 #[cfg(not(feature = "dev"))]
 async fn run_migration(db: DatabaseConnection) {
@@ -5,7 +5,8 @@
 //! Endpoints                                                                          Method(s) Allowed                Login Required            Admin
 //!
 //! [::]/api/v1/account/signup                                                         POST
-//! [::]/api/v1/account/deactivate                                                     POST
+//! [::]/api/v1/account/deactivate                                                     DELETE
+//! [::]/api/v1/account/deactivate/cancel?token=...                                    POST
 //! [::]/api/v1/account/login                                                          POST
 //! [::]/api/v1/account/logout                                                         DELETE
 //! [::]/api/v1/account/lock                                                           POST
@@ -14,17 +15,33 @@
 //! [::]/api/v1/account/email-exists                                                   POST
 //! [::]/api/v1/account/forgot-password                                                POST
 //! [::]/api/v1/account/reset-password?token=...                                       POST
+//! [::]/api/v1/account/data-export                                                    POST
+//! [::]/api/v1/account/data-export/download?token=...                                 GET
 //!
 //! [::]/api/v1/account/users                                                          GET
 //! [::]/api/v1/account/users/:user_id/profile                                         GET
+//! [::]/api/v1/account/users/handle/:handle                                           GET
 //! [::]/api/v1/account/users/profile                                                  GET, PUT
 //! [::]/api/v1/account/users/profile/photo                                            POST, DELETE
+//! [::]/api/v1/account/users/blocked                                                  GET
+//! [::]/api/v1/account/users/:user_id/block                                           POST, DELETE
 //!
 //! [::]/api/v1/account/settings/personal-info                                         GET, PUT,
+//! [::]/api/v1/account/settings/handle                                                PUT
 //! [::]/api/v1/account/settings/change-email                                          POST
 //! [::]/api/v1/account/settings/verify-email                                          POST
 //! [::]/api/v1/account/settings/change-password                                       POST
 //! [::]/api/v1/account/settings/verify-password                                       POST
+//! [::]/api/v1/account/settings/phones                                                PUT, DELETE
+//! [::]/api/v1/account/settings/phones/verify                                         POST
+//! [::]/api/v1/account/settings/phones/resend-code                                    POST
+//! [::]/api/v1/account/settings/identity                                              GET, POST
+//!
+//! [::]/api/v1/account/identity-verifications                                         GET
+//! [::]/api/v1/account/identity-verifications/:verification_id/document               GET
+//! [::]/api/v1/account/identity-verifications/:verification_id/selfie                 GET
+//! [::]/api/v1/account/identity-verifications/:verification_id/approve                POST
+//! [::]/api/v1/account/identity-verifications/:verification_id/reject                 POST
 //!
 //! [::]/api/v1/cultivars                                                               GET, POST
 //! [::]/api/v1/cultivars/:cultivar_id                                                  GET, PUT, DELETE
@@ -33,22 +50,58 @@
 //! [::]/api/v1/cultivars/categories/:category_id                                       PUT, DELETE
 //! [::]/api/v1/cultivars/:cultivar_id/photo                                            POST, DELETE
 //!
+//! [::]/api/v1/produce                                                                 GET
+//! [::]/api/v1/produce/following                                                       GET
+//! [::]/api/v1/produce/geojson                                                         GET
+//!
 //! [::]/api/v1/harvests                                                                GET POST
 //! [::]/api/v1/harvests/:harvest_id                                                    GET, PUT, DELETE
 //! [::]/api/v1/harvests/:harvest_id/photos                                             POST, DELETE
+//! [::]/api/v1/harvests/:harvest_id/restore                                            POST
 //!
 //! [::]/api/v1/farms                                                                   GET POST
+//! [::]/api/v1/farms/directory                                                         GET
+//! [::]/api/v1/farms/slug/:slug                                                       GET
 //! [::]/api/v1/farms/:farm_id                                                          GET, PUT, DELETE
+//! [::]/api/v1/farms/:farm_id/restore                                                  POST
+//! [::]/api/v1/farms/:farm_id/slug                                                    PUT
 //! [::]/api/v1/farms/:farm_id/locations                                                GET, POST
 //! [::]/api/v1/farms/:farm_id/ratings                                                  GET, POST
+//! [::]/api/v1/farms/:farm_id/ratings/summary                                          GET
 //! [::]/api/v1/farms/ratings/:rating_id                                                GET, PUT, DELETE
+//! [::]/api/v1/farms/ratings/:rating_id/replies                                        POST
+//! [::]/api/v1/farms/ratings/replies/:reply_id                                         PUT, DELETE
+//! [::]/api/v1/farms/ratings/:rating_id/helpful                                        POST, DELETE
+//! [::]/api/v1/farms/ratings/:rating_id/reports                                        POST
+//! [::]/api/v1/farms/ratings/:rating_id/review                                         POST
+//! [::]/api/v1/farms/ratings/reports                                                   GET
+//! [::]/api/v1/farms/:farm_id/members                                                  GET, POST
+//! [::]/api/v1/farms/members/:member_id                                                PUT, DELETE
+//! [::]/api/v1/farms/invitations/accept?token=...                                      POST
+//! [::]/api/v1/farms/invitations/decline?token=...                                     POST
+//! [::]/api/v1/farms/:farm_id/transfer                                                 POST, DELETE
+//! [::]/api/v1/farms/transfer/accept?token=...                                         POST
+//! [::]/api/v1/farms/transfer/decline?token=...                                        POST
+//! [::]/api/v1/farms/:farm_id/follow                                                   POST, DELETE
+//! [::]/api/v1/farms/:farm_id/delivery                                                 GET, POST
+//! [::]/api/v1/farms/delivery/:option_id                                               PUT, DELETE
 //!
 //! [::]/api/v1/locations                                                               GET
+//! [::]/api/v1/locations/geojson                                                       GET
 //! [::]/api/v1/locations/:location_id                                                  GET, PUT, DELETE
+//! [::]/api/v1/locations/:location_id/restore                                          POST
+//! [::]/api/v1/locations/:location_id/photos                                           POST, DELETE
+//! [::]/api/v1/locations/:location_id/schedule                                         GET
+//! [::]/api/v1/locations/:location_id/hours                                            PUT
+//! [::]/api/v1/locations/:location_id/holidays                                         POST
+//! [::]/api/v1/locations/holidays/:holiday_id                                          DELETE
 //! [::]/api/v1/locations/countries                                                     GET, POST
 //! [::]/api/v1/locations/countries/country_id                                          PUT, DELETE
+//! [::]/api/v1/locations/countries/import                                              POST
 //! [::]/api/v1/locations/countries/:country_id/regions                                 GET, POST
 //! [::]/api/v1/locations/countries/regions/region_id                                   PUT DELETE
+//! [::]/api/v1/locations/countries/regions/:region_id/boundary                         PUT, DELETE
+//! [::]/api/v1/locations/countries/regions/suggest                                     GET
 //!
 //!
 //! --------------------------------------------------------------
@@ -96,7 +149,8 @@ use tower_http::{
 use crate::{
     endpoint::EndpointResult,
     settings::{
-        CULTIVAR_UPLOAD_DIR, HARVEST_UPLOAD_DIR, USER_UPLOAD_DIR, WEB_APP_BUILD_DIR, WEB_APP_ROOT,
+        CULTIVAR_UPLOAD_DIR, FARM_LOGO_UPLOAD_DIR, HARVEST_UPLOAD_DIR, LOCATION_UPLOAD_DIR,
+        RATING_UPLOAD_DIR, USER_UPLOAD_DIR, WEB_APP_BUILD_DIR, WEB_APP_ROOT,
     },
 };
 
@@ -165,6 +219,18 @@ fn pictures_router() -> Router<ServerState> {
             "/harvests/p",
             get_service(serve_dir(HARVEST_UPLOAD_DIR, MAX_AGE_ONE_DAY)),
         )
+        .nest_service(
+            "/locations/p",
+            get_service(serve_dir(LOCATION_UPLOAD_DIR, MAX_AGE_ONE_DAY)),
+        )
+        .nest_service(
+            "/farms/logos/p",
+            get_service(serve_dir(FARM_LOGO_UPLOAD_DIR, MAX_AGE_ONE_DAY)),
+        )
+        .nest_service(
+            "/farms/ratings/p",
+            get_service(serve_dir(RATING_UPLOAD_DIR, MAX_AGE_ONE_DAY)),
+        )
         .nest_service(
             "/account/users/photo",
             get_service(serve_dir(USER_UPLOAD_DIR, MAX_AGE_ONE_DAY)),
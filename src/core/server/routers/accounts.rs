@@ -2,20 +2,29 @@
 
 use axum::{
     extract::DefaultBodyLimit,
-    routing::{delete, get, post},
+    routing::{delete, get, post, put},
     Router,
 };
 
 use crate::{
     accounts::{
+        blocks::handlers::{user_block, user_blocked_list, user_unblock},
+        data_export::handlers::{data_export_download, data_export_request},
         emails::handlers::{
             email_change_approve, email_exists, email_update, new_email_change_verify,
         },
+        handles::handlers::{user_handle_update, user_profile_by_handle},
+        identity::handlers::{
+            identity_approve, identity_document, identity_reject, identity_review_list,
+            identity_selfie, identity_status, identity_submit,
+        },
         passwords::handlers::{password_change, password_forgot, password_reset, password_verify},
         personal_info::handlers::{user_personal_info, user_personal_info_update},
+        phones::handlers::{phone_code_resend, phone_delete, phone_update, phone_verify},
         user::handlers::{
-            account_confirm, account_deactivate, account_lock, account_unlock, signup, user_list,
-            user_make_staff, user_make_superuser, user_revoke_staff, user_revoke_superuser,
+            account_confirm, account_deactivate, account_deactivate_cancel, account_lock,
+            account_unlock, signup, user_list, user_make_staff, user_make_superuser,
+            user_revoke_staff, user_revoke_superuser,
         },
         user_profile::handlers::{
             user_my_profile, user_photo_upload, user_profile, user_profile_update,
@@ -34,10 +43,21 @@ use crate::{
 
 /// Accounts routers
 pub fn routers() -> Router<ServerState> {
+    Router::new()
+        .merge(account_routers())
+        .merge(identity_routers())
+}
+
+/// Accounts, users and settings routers
+fn account_routers() -> Router<ServerState> {
     Router::new()
         // Accounts
         .route("/account/signup", post(signup))
         .route("/account/deactivate", delete(account_deactivate))
+        .route(
+            "/account/deactivate/cancel",
+            post(account_deactivate_cancel),
+        )
         .route("/account/login", post(login))
         .route("/account/logout", delete(logout))
         .route("/account/lock", post(account_lock))
@@ -46,9 +66,12 @@ pub fn routers() -> Router<ServerState> {
         .route("/account/confirm", get(account_confirm))
         .route("/account/forgot-password", post(password_forgot))
         .route("/account/reset-password", post(password_reset))
+        .route("/account/data-export", post(data_export_request))
+        .route("/account/data-export/download", get(data_export_download))
         // Users
         .route("/account/users", get(user_list))
         .route("/account/users/:user_id/profile", get(user_profile))
+        .route("/account/users/handle/:handle", get(user_profile_by_handle))
         .route(
             "/account/users/profile",
             get(user_my_profile).put(user_profile_update),
@@ -57,6 +80,11 @@ pub fn routers() -> Router<ServerState> {
             "/account/users/profile/photo",
             post(user_photo_upload).layer(DefaultBodyLimit::max(crate::IMAGE_MAX_SIZE)),
         )
+        .route("/account/users/blocked", get(user_blocked_list))
+        .route(
+            "/account/users/:user_id/block",
+            post(user_block).delete(user_unblock),
+        )
         // DirectMessage
         .route("/account/users/chat", get(direct_message_websocket))
         .route(
@@ -68,6 +96,7 @@ pub fn routers() -> Router<ServerState> {
             "/account/settings/personal-info",
             get(user_personal_info).put(user_personal_info_update),
         )
+        .route("/account/settings/handle", put(user_handle_update))
         .route("/account/settings/change-email", post(email_update))
         .route(
             "/account/settings/approve-email-change",
@@ -79,7 +108,23 @@ pub fn routers() -> Router<ServerState> {
         )
         .route("/account/settings/change-password", post(password_change))
         .route("/account/settings/verify-password", post(password_verify))
-        // .route("/account/settings/phones", put(phone_update))
+        .route(
+            "/account/settings/phones",
+            put(phone_update).delete(phone_delete),
+        )
+        .route("/account/settings/phones/verify", post(phone_verify))
+        .route(
+            "/account/settings/phones/resend-code",
+            post(phone_code_resend),
+        )
+        .route(
+            "/account/settings/identity",
+            get(identity_status)
+                .post(identity_submit)
+                .layer(DefaultBodyLimit::max(
+                    crate::IMAGE_MAX_SIZE * crate::IDENTITY_VERIFICATION_FILES as usize,
+                )),
+        )
         .route("/account/settings/add-superuser", post(user_make_superuser))
         .route(
             "/account/settings/revoke-superuser",
@@ -104,3 +149,25 @@ pub fn routers() -> Router<ServerState> {
             get(generate_api_key_for_app),
         )
 }
+
+/// Identity verifications staff review routers
+fn identity_routers() -> Router<ServerState> {
+    Router::new()
+        .route("/account/identity-verifications", get(identity_review_list))
+        .route(
+            "/account/identity-verifications/:verification_id/document",
+            get(identity_document),
+        )
+        .route(
+            "/account/identity-verifications/:verification_id/selfie",
+            get(identity_selfie),
+        )
+        .route(
+            "/account/identity-verifications/:verification_id/approve",
+            post(identity_approve),
+        )
+        .route(
+            "/account/identity-verifications/:verification_id/reject",
+            post(identity_reject),
+        )
+}
@@ -2,7 +2,7 @@
 
 use axum::{
     extract::DefaultBodyLimit,
-    routing::{get, post, put},
+    routing::{delete, get, post, put},
     Router,
 };
 
@@ -13,20 +13,47 @@ use crate::{
     },
     server::state::ServerState,
     services::{
-        farmers::farm::handlers::{
-            farm_create, farm_delete, farm_detail, farm_list, farm_location_index,
-            farm_logo_delete, farm_logo_upload, farm_update,
+        farmers::farm::{
+            delivery::handlers::{
+                delivery_option_create, delivery_option_delete, delivery_option_list,
+                delivery_option_update,
+            },
+            follow::handlers::{farm_follow, farm_unfollow},
+            handlers::{
+                farm_create, farm_delete, farm_detail, farm_directory, farm_list,
+                farm_location_index, farm_logo_delete, farm_logo_upload, farm_restore, farm_update,
+            },
+            member::handlers::{
+                farm_invitation_accept, farm_invitation_decline, farm_member_delete,
+                farm_member_invite, farm_member_list, farm_member_update,
+            },
+            slug::handlers::{farm_detail_by_slug, farm_slug_update},
+            transfer::handlers::{
+                farm_transfer_accept, farm_transfer_cancel, farm_transfer_create,
+                farm_transfer_decline,
+            },
         },
         farmers::location::{
             country::handlers::{country_create, country_delete, country_list, country_update},
             handlers::{
-                location_create, location_delete, location_detail, location_list, location_update,
+                location_create, location_delete, location_detail, location_image_delete,
+                location_image_uploads, location_list, location_restore, location_update,
+            },
+            import::handlers::location_import,
+            region::handlers::{
+                region_boundary_delete, region_boundary_update, region_create, region_delete,
+                region_list, region_suggest, region_update,
+            },
+            schedule::handlers::{
+                location_holiday_create, location_holiday_delete, location_hours_update,
+                location_schedule,
             },
-            region::handlers::{region_create, region_delete, region_list, region_update},
         },
         farmers::rating::handlers::{
             farm_rating_create, farm_rating_delete, farm_rating_detail, farm_rating_list,
-            farm_rating_update, farm_ratings,
+            farm_rating_report, farm_rating_reported_list, farm_rating_review, farm_rating_summary,
+            farm_rating_unvote, farm_rating_update, farm_rating_vote, farm_ratings,
+            rating_reply_create, rating_reply_delete, rating_reply_update,
         },
         produce::cultivar::{
             category::handlers::{
@@ -40,17 +67,30 @@ use crate::{
         },
         produce::harvest::handlers::{
             harvest_create, harvest_delete, harvest_detail, harvest_image_delete,
-            harvest_image_uploads, harvest_list, harvest_update,
+            harvest_image_uploads, harvest_list, harvest_restore, harvest_update,
+        },
+        produce::{
+            harvest_feed, harvest_following_feed,
+            map::handlers::{harvest_geojson, location_geojson},
         },
-        produce::harvest_feed,
     },
 };
 
 /// Services routers
 pub fn routers() -> Router<ServerState> {
+    Router::new()
+        .merge(produce_routers())
+        .merge(farm_routers())
+        .merge(location_routers())
+}
+
+/// Produce, cultivars and harvests routers
+fn produce_routers() -> Router<ServerState> {
     Router::new()
         //Produce
         .route("/produce", get(harvest_feed))
+        .route("/produce/following", get(harvest_following_feed))
+        .route("/produce/geojson", get(harvest_geojson))
         // Cultivar
         .route("/cultivars", get(cultivar_list).post(cultivar_create))
         .route(
@@ -82,6 +122,7 @@ pub fn routers() -> Router<ServerState> {
                 .put(harvest_update)
                 .delete(harvest_delete),
         )
+        .route("/harvests/:harvest_id/restore", post(harvest_restore))
         .route(
             "/harvests/:harvest_id/photos",
             post(harvest_image_uploads)
@@ -98,12 +139,20 @@ pub fn routers() -> Router<ServerState> {
             "/harvests/subscription/:subscription_id",
             put(harvest_subscription_update).delete(harvest_subscription_delete),
         )
-        // Farms
+}
+
+/// Farms routers
+fn farm_routers() -> Router<ServerState> {
+    Router::new()
         .route("/farms", get(farm_list).post(farm_create))
+        .route("/farms/directory", get(farm_directory))
+        .route("/farms/slug/:slug", get(farm_detail_by_slug))
         .route(
             "/farms/:farm_id",
             get(farm_detail).put(farm_update).delete(farm_delete),
         )
+        .route("/farms/:farm_id/restore", post(farm_restore))
+        .route("/farms/:farm_id/slug", put(farm_slug_update))
         .route(
             "/farms/:farm_id/logo",
             post(farm_logo_upload)
@@ -116,23 +165,98 @@ pub fn routers() -> Router<ServerState> {
         )
         .route(
             "/farms/:farm_id/ratings",
-            get(farm_ratings).post(farm_rating_create),
+            post(farm_rating_create)
+                .layer(DefaultBodyLimit::max(
+                    usize::from(crate::RATING_MAX_IMAGE) * crate::IMAGE_MAX_SIZE,
+                ))
+                .get(farm_ratings),
         )
+        .route("/farms/:farm_id/ratings/summary", get(farm_rating_summary))
         .route(
             "/farms/ratings/:rating_id",
             get(farm_rating_detail)
                 .put(farm_rating_update)
                 .delete(farm_rating_delete),
         )
+        .route(
+            "/farms/ratings/:rating_id/replies",
+            post(rating_reply_create),
+        )
+        .route(
+            "/farms/ratings/replies/:reply_id",
+            put(rating_reply_update).delete(rating_reply_delete),
+        )
+        .route(
+            "/farms/ratings/:rating_id/helpful",
+            post(farm_rating_vote).delete(farm_rating_unvote),
+        )
+        .route(
+            "/farms/ratings/:rating_id/reports",
+            post(farm_rating_report),
+        )
+        .route("/farms/ratings/:rating_id/review", post(farm_rating_review))
+        .route("/farms/ratings/reports", get(farm_rating_reported_list))
         .route("/farms/ratings", get(farm_rating_list))
-        // Locations
+        .route(
+            "/farms/:farm_id/members",
+            get(farm_member_list).post(farm_member_invite),
+        )
+        .route(
+            "/farms/members/:member_id",
+            put(farm_member_update).delete(farm_member_delete),
+        )
+        .route("/farms/invitations/accept", post(farm_invitation_accept))
+        .route("/farms/invitations/decline", post(farm_invitation_decline))
+        .route(
+            "/farms/:farm_id/transfer",
+            post(farm_transfer_create).delete(farm_transfer_cancel),
+        )
+        .route("/farms/transfer/accept", post(farm_transfer_accept))
+        .route("/farms/transfer/decline", post(farm_transfer_decline))
+        .route(
+            "/farms/:farm_id/follow",
+            post(farm_follow).delete(farm_unfollow),
+        )
+        .route(
+            "/farms/:farm_id/delivery",
+            get(delivery_option_list).post(delivery_option_create),
+        )
+        .route(
+            "/farms/delivery/:option_id",
+            put(delivery_option_update).delete(delivery_option_delete),
+        )
+}
+
+/// Locations routers
+fn location_routers() -> Router<ServerState> {
+    Router::new()
         .route("/locations", get(location_list))
+        .route("/locations/geojson", get(location_geojson))
         .route(
             "/locations/:location_id",
             get(location_detail)
                 .put(location_update)
                 .delete(location_delete),
         )
+        .route("/locations/:location_id/restore", post(location_restore))
+        .route(
+            "/locations/:location_id/photos",
+            post(location_image_uploads)
+                .layer(DefaultBodyLimit::max(
+                    crate::IMAGE_MAX_SIZE * crate::LOCATION_MAX_IMAGE as usize,
+                ))
+                .delete(location_image_delete),
+        )
+        .route("/locations/:location_id/schedule", get(location_schedule))
+        .route("/locations/:location_id/hours", put(location_hours_update))
+        .route(
+            "/locations/:location_id/holidays",
+            post(location_holiday_create),
+        )
+        .route(
+            "/locations/holidays/:holiday_id",
+            delete(location_holiday_delete),
+        )
         .route(
             "/locations/countries",
             get(country_list).post(country_create),
@@ -141,6 +265,10 @@ pub fn routers() -> Router<ServerState> {
             "/locations/countries/:country_id",
             put(country_update).delete(country_delete),
         )
+        .route(
+            "/locations/countries/import",
+            post(location_import).layer(DefaultBodyLimit::max(crate::LOCATION_IMPORT_MAX_SIZE)),
+        )
         .route(
             "/locations/countries/:country_id/regions",
             get(region_list).post(region_create),
@@ -149,4 +277,11 @@ pub fn routers() -> Router<ServerState> {
             "/locations/countries/regions/:region_id",
             put(region_update).delete(region_delete),
         )
+        .route(
+            "/locations/countries/regions/:region_id/boundary",
+            put(region_boundary_update)
+                .layer(DefaultBodyLimit::max(crate::REGION_BOUNDARY_MAX_SIZE))
+                .delete(region_boundary_delete),
+        )
+        .route("/locations/countries/regions/suggest", get(region_suggest))
 }
@@ -6,7 +6,13 @@ use axum::extract::FromRef;
 use axum_extra::extract::cookie::Key;
 use sqlx::{postgres::PgPoolOptions, PgPool};
 
-use crate::{features::direct_message::ChatFeed, mail::Mail};
+use crate::{
+    accounts::identity::IdentityKey,
+    features::direct_message::ChatFeed,
+    mail::Mail,
+    services::{farmers::rating::policy::RatingPolicy, produce::harvest::ArchiveGraceDays},
+    sms::Sms,
+};
 
 use super::config::Config;
 
@@ -18,8 +24,12 @@ pub struct ServerState(Arc<StateInner>);
 struct StateInner {
     database: DatabaseConnection,
     outlook_client: Mail,
+    sms: Sms,
     chat: ChatFeed,
     cookie_key: Key,
+    identity_key: IdentityKey,
+    rating_policy: RatingPolicy,
+    archive_grace_days: ArchiveGraceDays,
 }
 
 impl ServerState {
@@ -28,8 +38,12 @@ impl ServerState {
         Self(Arc::new(StateInner {
             database: DatabaseConnection::new(&config.database_url).await,
             outlook_client: Mail::outlook(&config.mail_email, config.mail_password),
+            sms: config.sms,
             chat: ChatFeed::new(),
             cookie_key: config.cookie_key,
+            identity_key: config.identity_key,
+            rating_policy: config.rating_policy,
+            archive_grace_days: config.archive_grace_days,
         }))
     }
 
@@ -47,6 +61,13 @@ impl ServerState {
         self.0.outlook_client.clone()
     }
 
+    /// Clone and returns SMS client
+    #[must_use]
+    #[inline]
+    pub fn sms(&self) -> Sms {
+        self.0.sms.clone()
+    }
+
     /// Clone and returns chat feed instance
     #[must_use]
     #[inline]
@@ -67,6 +88,27 @@ impl ServerState {
     pub fn cookie_key(&self) -> Key {
         self.0.cookie_key.clone()
     }
+
+    /// Clone and returns identity files encryption key
+    #[must_use]
+    #[inline]
+    pub fn identity_key(&self) -> IdentityKey {
+        self.0.identity_key.clone()
+    }
+
+    /// Returns farm rating policy
+    #[must_use]
+    #[inline]
+    pub fn rating_policy(&self) -> RatingPolicy {
+        self.0.rating_policy
+    }
+
+    /// Returns the archive restore grace period
+    #[must_use]
+    #[inline]
+    pub fn archive_grace_days(&self) -> ArchiveGraceDays {
+        self.0.archive_grace_days
+    }
 }
 
 impl fmt::Debug for ServerState {
@@ -93,6 +135,24 @@ impl FromRef<ServerState> for Mail {
     }
 }
 
+impl FromRef<ServerState> for IdentityKey {
+    fn from_ref(state: &ServerState) -> Self {
+        state.identity_key()
+    }
+}
+
+impl FromRef<ServerState> for ArchiveGraceDays {
+    fn from_ref(state: &ServerState) -> Self {
+        state.archive_grace_days()
+    }
+}
+
+impl FromRef<ServerState> for Sms {
+    fn from_ref(state: &ServerState) -> Self {
+        state.sms()
+    }
+}
+
 impl FromRef<ServerState> for Key {
     fn from_ref(state: &ServerState) -> Self {
         state.cookie_key()
@@ -33,3 +33,17 @@ pub const CULTIVAR_UPLOAD_DIR: &str =
 /// Harvests image file uploads directory
 pub const HARVEST_UPLOAD_DIR: &str =
     concat!(env!("CARGO_MANIFEST_DIR"), "/static/media/uploads/harvest");
+
+/// Farm ratings image file uploads directory
+pub const RATING_UPLOAD_DIR: &str =
+    concat!(env!("CARGO_MANIFEST_DIR"), "/static/media/uploads/rating");
+
+/// Farm locations image file uploads directory
+pub const LOCATION_UPLOAD_DIR: &str =
+    concat!(env!("CARGO_MANIFEST_DIR"), "/static/media/uploads/location");
+
+/// Users personal data export archives directory, not served publicly
+pub const DATA_EXPORT_DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/static/exports");
+
+/// Encrypted identity verification files directory, not served publicly
+pub const IDENTITY_UPLOAD_DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/static/identity");
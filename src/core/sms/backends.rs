@@ -0,0 +1,80 @@
+//! SMS development senders
+
+use std::path::PathBuf;
+
+use axum::async_trait;
+use time::OffsetDateTime;
+use tokio::io::AsyncWriteExt;
+
+use crate::error::ServerResult;
+
+use super::SmsSender;
+
+/// Writes text messages to the logs instead of sending them,
+/// for development only.
+///
+/// Verification codes in the messages are redacted.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct LogSmsSender;
+
+#[async_trait]
+impl SmsSender for LogSmsSender {
+    async fn send(&self, phone: &str, message: &str) -> ServerResult<()> {
+        tracing::debug!("SMS to {}: {}", phone, redact_codes(message));
+        Ok(())
+    }
+}
+
+/// Replaces every run of four or more digits in the `message` with `*`
+fn redact_codes(message: &str) -> String {
+    let mut redacted = String::with_capacity(message.len());
+    let mut digits = String::new();
+    for c in message.chars().chain(std::iter::once(' ')) {
+        if c.is_ascii_digit() {
+            digits.push(c);
+            continue;
+        }
+        if digits.len() >= 4 {
+            redacted.push_str(&"*".repeat(digits.len()));
+        } else {
+            redacted.push_str(&digits);
+        }
+        digits.clear();
+        redacted.push(c);
+    }
+    redacted.pop(); // The trailing space pushed to flush the digits
+    redacted
+}
+
+/// Appends text messages to an outbox file instead of sending them,
+/// for development and tests.
+#[derive(Debug, Clone)]
+pub struct FileSmsSender {
+    outbox: PathBuf,
+}
+
+impl FileSmsSender {
+    /// Creates a new sender writing to the `outbox` file
+    #[must_use]
+    pub fn new(outbox: impl Into<PathBuf>) -> Self {
+        Self {
+            outbox: outbox.into(),
+        }
+    }
+}
+
+#[async_trait]
+impl SmsSender for FileSmsSender {
+    async fn send(&self, phone: &str, message: &str) -> ServerResult<()> {
+        let line = format!("{}\t{phone}\t{message}\n", OffsetDateTime::now_utc());
+
+        let mut outbox = tokio::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.outbox)
+            .await?;
+        outbox.write_all(line.as_bytes()).await?;
+
+        Ok(())
+    }
+}
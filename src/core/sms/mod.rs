@@ -0,0 +1,7 @@
+//! SMS sender impls
+
+mod backends;
+mod sender;
+
+pub use backends::{FileSmsSender, LogSmsSender};
+pub use sender::{Sms, SmsSender};
@@ -0,0 +1,81 @@
+//! SMS sender
+
+use std::{env, fmt, sync::Arc};
+
+use axum::async_trait;
+
+use crate::error::ServerResult;
+
+use super::backends::{FileSmsSender, LogSmsSender};
+
+/// SMS provider the text messages are sent through
+#[async_trait]
+pub trait SmsSender: fmt::Debug + Send + Sync {
+    /// Sends the text `message` to the `phone` number
+    async fn send(&self, phone: &str, message: &str) -> ServerResult<()>;
+}
+
+/// SMS client
+#[derive(Debug, Clone)]
+pub struct Sms {
+    sender: Arc<dyn SmsSender>,
+}
+
+impl Sms {
+    /// Creates a new SMS client sending messages through `sender`
+    #[must_use]
+    pub fn new(sender: impl SmsSender + 'static) -> Self {
+        Self {
+            sender: Arc::new(sender),
+        }
+    }
+
+    /// Loads the SMS provider from environmental variables
+    ///
+    /// `SMS_SENDER` is either `file` or `log`, the file sender writes
+    /// messages to `SMS_OUTBOX`. The log sender is only allowed in debug builds.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `SMS_SENDER` is not set or is not a known sender
+    #[must_use]
+    pub fn from_env() -> Self {
+        let sender = env::var("SMS_SENDER").expect("SMS_SENDER environment variable not set.");
+        match sender.as_str() {
+            "log" if cfg!(debug_assertions) => Self::new(LogSmsSender),
+            "log" => panic!("SMS_SENDER `log` is only allowed in debug builds."),
+            "file" => {
+                let outbox =
+                    env::var("SMS_OUTBOX").expect("SMS_OUTBOX environment variable not set.");
+                Self::new(FileSmsSender::new(outbox))
+            }
+            sender => panic!("Unknown SMS_SENDER: {sender}, expected `log` or `file`."),
+        }
+    }
+
+    /// Sends a text message
+    pub async fn send(&self, phone: &str, message: &str) -> ServerResult<()> {
+        self.sender.send(phone, message).await
+    }
+
+    /// Sends phone number verification code
+    pub async fn verify_phone(&self, phone: &str, code: &str) -> ServerResult<()> {
+        let message = format!(
+            "Your {} verification code is {code}. It expires in {} minutes.",
+            crate::APP_NAME,
+            crate::PHONE_CODE_EXPIRY
+        );
+        self.send(phone, &message).await
+    }
+
+    /// Sends password reset code
+    pub async fn password_reset(&self, phone: &str, code: &str) -> ServerResult<()> {
+        let message = format!(
+            "Your {} password reset code is {code}. It expires in {} minutes, \
+            if you did not request it ignore this message.",
+            crate::APP_NAME,
+            crate::PHONE_CODE_EXPIRY
+        );
+        self.send(phone, &message).await
+    }
+}
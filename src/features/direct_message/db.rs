@@ -34,7 +34,15 @@ impl Conversations {
             LEFT JOIN features.message_status status
                 ON message.id = status.message_id
 
-            WHERE message.sender_id = $1 OR message.receiver_id = $1;
+            WHERE (message.sender_id = $1 OR message.receiver_id = $1)
+                -- Hide conversations with blocked users
+                AND NOT EXISTS(
+                    SELECT 1 FROM accounts.user_blocks block
+                    WHERE (block.user_id = message.sender_id
+                            AND block.blocked_id = message.receiver_id)
+                        OR (block.user_id = message.receiver_id
+                            AND block.blocked_id = message.sender_id)
+                );
         "#,
             user_id.0,
         )
@@ -115,8 +123,14 @@ impl Conversation {
                 LEFT JOIN features.message_status status
                     ON message.id = status.message_id
 
-                WHERE (message.sender_id = $1 AND message.receiver_id = $2) OR
-                        (message.sender_id = $2 AND message.receiver_id = $1)
+                WHERE ((message.sender_id = $1 AND message.receiver_id = $2) OR
+                        (message.sender_id = $2 AND message.receiver_id = $1))
+                    -- Hide conversations with blocked users
+                    AND NOT EXISTS(
+                        SELECT 1 FROM accounts.user_blocks block
+                        WHERE (block.user_id = $1 AND block.blocked_id = $2)
+                            OR (block.user_id = $2 AND block.blocked_id = $1)
+                    )
             "#,
             user_id.0,
             other_id.0
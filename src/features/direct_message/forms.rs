@@ -127,6 +127,8 @@ pub enum IncomingMessageError {
     UnprocessableEntity,
     /// Server error occurs while working with an `IncomingMessage`.
     InternalServerError,
+    /// The receiver blocked the user or was blocked by them.
+    Blocked,
     // NotFound,
     // Forbidden,
     // BadRequest(String),
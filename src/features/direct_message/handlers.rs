@@ -14,6 +14,7 @@ use futures_util::{
 };
 
 use crate::{
+    accounts::blocks::UserBlock,
     auth::CurrentUser,
     endpoint::{EndpointRejection, EndpointResult},
     server::state::{DatabaseConnection, ServerState},
@@ -133,6 +134,23 @@ async fn process_incoming_message(
     match msg {
         IncomingMessage::NewMessage(new_msg) => {
             let insert_data = new_msg.insert_data(user.id);
+            match UserBlock::exists_between(user.id, insert_data.receiver_id, db.clone()).await {
+                Ok(false) => {}
+                Ok(true) => {
+                    chat.broadcast(BroadcastMessage::message_error(
+                        user.id,
+                        IncomingMessageError::Blocked,
+                    ));
+                    return;
+                }
+                Err(_err) => {
+                    chat.broadcast(BroadcastMessage::message_error(
+                        user.id,
+                        IncomingMessageError::InternalServerError,
+                    ));
+                    return;
+                }
+            }
             let direct_msg = insert_data.direct_message();
             match Conversation::insert(insert_data, db).await {
                 Ok(_) => chat.broadcast(BroadcastMessage::direct_message(
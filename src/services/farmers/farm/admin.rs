@@ -30,13 +30,20 @@ impl FarmAdmin {
         owner_first_name: String,
         owner_last_name: Option<String>,
         owner_photo: Option<String>,
+        owner_identity_verified: bool,
         locations: Vec<LocationAdmin>,
         registered_on: Date,
     ) -> Self {
         Self {
             id,
             name,
-            owner: UserIndex::from_row(owner_id, owner_first_name, owner_last_name, owner_photo),
+            owner: UserIndex::from_row(
+                owner_id,
+                owner_first_name,
+                owner_last_name,
+                owner_photo,
+                owner_identity_verified,
+            ),
             locations,
             registered_on,
         }
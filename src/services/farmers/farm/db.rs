@@ -11,20 +11,26 @@ use crate::{
     files,
     server::state::DatabaseConnection,
     services::{
-        farmers::location::models::{Location, LocationIndex},
-        produce::harvest::{delete_harvest_photos, models::HarvestIndex},
+        farmers::{
+            location::models::{Location, LocationIndex},
+            rating::delete_rating_photos,
+        },
+        produce::harvest::{archive_restore_cutoff, ArchiveGraceDays},
     },
     types::ModelID,
     types::{ModelIdentifier, ModelIndex, Pagination},
 };
 
 use super::{
-    forms::{FarmInsertData, FarmUpdateData},
-    models::{Farm, FarmIndex, FarmList},
+    forms::{FarmDirectoryFilter, FarmInsertData, FarmUpdateData},
+    member::{forms::FarmMemberInsertData, FarmMember, FarmRole},
+    models::{Farm, FarmCard, FarmCardList, FarmIndex, FarmList},
     utils::{
-        archive_farm, archive_farm_harvests, archive_farm_locations, delete_farm,
-        delete_farm_harvests, delete_farm_locations, farm_archived_harvest_count,
-        farm_harvest_images, location_insert, update_user_is_farmer, user_farm_count,
+        archive_farm, archive_farm_harvests, archive_farm_locations, delete_expired_farms,
+        farm_member_ids, farm_stats, finalize_archived_farms, location_galleries,
+        location_harvests, location_insert, location_schedules, restore_farm,
+        restore_farm_harvests, restore_farm_locations, update_user_is_farmer,
+        update_users_is_farmer,
     },
 };
 
@@ -49,6 +55,7 @@ impl Farm {
                     farm.logo AS "farm_logo",
                     user_.first_name AS "farm_owner_first_name!",
                     user_.last_name AS farm_owner_last_name,
+                    user_.identity_verified AS "farm_owner_identity_verified!",
                     profile.photo AS farm_owner_photo,
                     location_.id AS "location_id!",
                     location_.place_name AS "location_place_name!",
@@ -81,29 +88,26 @@ impl Farm {
         .await
         {
             Ok(records) => {
+                let farm_ids: Vec<_> = records.iter().map(|rec| rec.farm_id.into()).collect();
+                let mut stats = farm_stats(&farm_ids, db).await?;
+
                 let mut farms = Vec::new();
                 for (farm_id, farm_group) in &records.into_iter().group_by(|rec| rec.farm_id) {
                     let farm_group: Vec<_> = farm_group.collect();
                     let first_rec = &farm_group[0];
-
-                    let farm_name = first_rec.farm_name.clone();
-                    let farm_logo = first_rec.farm_logo.clone();
-
-                    let owner_id = first_rec.farm_owner_id.into();
-                    let owner_first_name = first_rec.farm_owner_first_name.clone();
-                    let owner_last_name = first_rec.farm_owner_last_name.clone();
-                    let owner_photo = first_rec.farm_owner_photo.clone();
+                    let (farm_stats, farm_ratings) =
+                        stats.remove(&farm_id.into()).unwrap_or_default();
 
                     let locations = farm_group
-                        .into_iter()
+                        .iter()
                         .map(|rec| {
                             LocationIndex::from_row(
                                 rec.location_id.into(),
-                                rec.location_place_name,
-                                rec.location_region,
-                                rec.location_country,
-                                rec.location_coords,
-                                rec.farm_name,
+                                rec.location_place_name.clone(),
+                                rec.location_region.clone(),
+                                rec.location_country.clone(),
+                                rec.location_coords.clone(),
+                                rec.farm_name.clone(),
                                 rec.harvests_count,
                             )
                         })
@@ -111,13 +115,16 @@ impl Farm {
 
                     farms.push(FarmIndex::from_row(
                         farm_id.into(),
-                        farm_name,
-                        farm_logo,
+                        first_rec.farm_name.clone(),
+                        first_rec.farm_logo.clone(),
                         locations,
-                        owner_id,
-                        owner_first_name,
-                        owner_last_name,
-                        owner_photo,
+                        farm_stats,
+                        farm_ratings,
+                        first_rec.farm_owner_id.into(),
+                        first_rec.farm_owner_first_name.clone(),
+                        first_rec.farm_owner_last_name.clone(),
+                        first_rec.farm_owner_photo.clone(),
+                        first_rec.farm_owner_identity_verified,
                     ));
                 }
 
@@ -130,6 +137,89 @@ impl Farm {
         }
     }
 
+    /// Fetches the public farm directory from the database
+    #[tracing::instrument(name = "Fetch FarmCardList", skip(db))]
+    pub async fn directory(
+        filter: FarmDirectoryFilter,
+        db: DatabaseConnection,
+    ) -> ServerResult<FarmCardList> {
+        let (offset, limit) = filter.pagination().offset_limit();
+        match sqlx::query!(
+            r#"
+                SELECT farm.id AS "farm_id!",
+                    farm.name AS "farm_name!",
+                    farm.logo AS farm_logo,
+                    farm.verified AS "farm_verified!",
+                    stat.rating_average AS "farm_rating_average?",
+                    stat.rating_count AS "farm_rating_count?",
+                    (SELECT COUNT(location_.id)
+                        FROM services.active_locations location_
+                        WHERE location_.farm_id = farm.id
+                    ) AS "location_count!"
+                FROM services.active_farms farm
+                LEFT JOIN services.farm_stats stat
+                    ON farm.id = stat.farm_id
+
+                WHERE ($1::text IS NULL OR farm.name ILIKE $1)
+                    AND ($2::text IS NULL OR EXISTS(
+                        SELECT 1 FROM services.active_locations location_
+                        INNER JOIN services.regions region
+                            ON location_.region_id = region.id
+                        WHERE location_.farm_id = farm.id
+                            AND region.name = $2
+                    ))
+                    AND ($3::text IS NULL OR EXISTS(
+                        SELECT 1 FROM services.active_harvests harvest
+                        INNER JOIN services.active_locations location_
+                            ON harvest.location_id = location_.id
+                        INNER JOIN services.cultivars cultivar
+                            ON harvest.cultivar_id = cultivar.id
+                        WHERE location_.farm_id = farm.id
+                            AND cultivar.name = $3
+                    ))
+                    AND ($4::boolean IS NULL OR farm.verified = $4)
+                    AND ($5::double precision IS NULL OR stat.rating_average >= $5)
+
+                ORDER BY farm.name, farm.id
+                LIMIT $6
+                OFFSET $7;
+            "#,
+            filter.search_pattern(),
+            filter.region(),
+            filter.cultivar(),
+            filter.verified,
+            filter.min_rating,
+            limit,
+            offset
+        )
+        .fetch_all(&db.pool)
+        .await
+        {
+            Ok(records) => {
+                let farms = records
+                    .into_iter()
+                    .map(|rec| {
+                        FarmCard::from_row(
+                            rec.farm_id.into(),
+                            rec.farm_name,
+                            rec.farm_logo,
+                            rec.farm_verified,
+                            rec.location_count,
+                            rec.farm_rating_average,
+                            rec.farm_rating_count,
+                        )
+                    })
+                    .collect();
+
+                Ok(farms)
+            }
+            Err(err) => {
+                tracing::error!("Database error, failed to fetch farm directory: {}", err);
+                Err(err.into())
+            }
+        }
+    }
+
     /// Fetches farm detail from the database
     #[tracing::instrument(name = "Find Farm", skip(db))]
     pub async fn find(id: ModelID, db: DatabaseConnection) -> ServerResult<Option<Self>> {
@@ -138,26 +228,22 @@ impl Farm {
                 SELECT farm.id AS "farm_id!",
                     farm.owner_id as "farm_owner_id!",
                     farm.name AS "farm_name!",
+                    farm.slug AS farm_slug,
                     farm.logo AS "farm_logo",
                     farm.contact_email AS "farm_contact_email",
                     farm.contact_number AS "farm_contact_number",
                     farm.registered_on AS "farm_registered_on!",
                     user_.first_name AS farm_owner_first_name,
                     user_.last_name AS farm_owner_last_name,
+                    user_.identity_verified AS farm_owner_identity_verified,
                     profile.photo AS farm_owner_photo,
                     location_.id AS "location_id!",
                     location_.place_name AS "location_place_name!",
                     location_.coords AS location_coords,
                     location_.description AS location_description,
+                    location_.access_notes AS location_access_notes,
                     country.name AS location_country,
-                    region.name AS "location_region?",
-                    harvest.id AS "harvest_id?",
-                    harvest.price AS "harvest_price?",
-                    harvest.images AS harvest_images,
-                    harvest.harvest_date AS "harvest_harvest_date?",
-                    cultivar.name AS "cultivar_name?",
-                    cultivar_category.name AS "cultivar_category?",
-                    cultivar.image AS cultivar_image
+                    region.name AS "location_region?"
                 FROM services.active_farms farm
                 LEFT JOIN accounts.users user_
                     ON farm.owner_id = user_.id
@@ -169,12 +255,6 @@ impl Farm {
                     ON location_.country_id = country.id
                 LEFT JOIN services.regions region
                     ON location_.region_id = region.id
-                LEFT JOIN services.active_harvests harvest
-                    ON location_.id = harvest.location_id
-                LEFT JOIN services.cultivars cultivar
-                    ON harvest.cultivar_id = cultivar.id
-                LEFT JOIN services.cultivar_categories cultivar_category
-                    ON cultivar.category_id = cultivar_category.id
 
                 WHERE farm.id = $1
             "#,
@@ -186,86 +266,57 @@ impl Farm {
             Ok(records) if records.is_empty() => Ok(None),
             Ok(records) => {
                 let first_rec = &records[0];
-
                 let farm_id = first_rec.farm_id.into();
-                let farm_name = first_rec.farm_name.clone();
-                let farm_logo = first_rec.farm_logo.clone();
-                let farm_contact_email = first_rec.farm_contact_email.clone();
-                let farm_contact_number = first_rec.farm_contact_number.clone();
-
-                let registered_on = first_rec.farm_registered_on;
-                let owner_id = first_rec.farm_owner_id.into();
-                let owner_first_name = first_rec.farm_owner_first_name.clone();
-                let owner_last_name = first_rec.farm_owner_last_name.clone();
-                let owner_photo = first_rec.farm_owner_photo.clone();
-
-                let mut locations = Vec::new();
-
-                for (location_id, location_group) in
-                    &records.into_iter().group_by(|rec| rec.location_id)
-                {
-                    let location_group: Vec<_> = location_group.collect();
-                    let first_rec = &location_group[0];
-
-                    let place_name = first_rec.location_place_name.clone();
-                    let region = first_rec.location_region.clone();
-                    let country = first_rec.location_country.clone();
-                    let coords = first_rec.location_coords.clone();
-                    let description = first_rec.location_description.clone();
-                    let farm_name = first_rec.farm_name.clone();
-
-                    // Create harvests available at the location
-                    let harvests: Vec<_> = location_group
-                        .into_iter()
-                        .filter(|rec| rec.harvest_id.is_some())
-                        .map(|rec| {
-                            HarvestIndex::from_row(
-                                rec.harvest_id.unwrap().into(),
-                                rec.harvest_price.unwrap(),
-                                rec.harvest_harvest_date.unwrap(),
-                                rec.harvest_images,
-                                rec.cultivar_name.unwrap(),
-                                rec.cultivar_category.unwrap(),
-                                rec.cultivar_image,
-                                rec.location_place_name,
-                                rec.location_region,
-                                rec.location_country,
-                                rec.location_coords,
-                                rec.farm_name,
-                                rec.farm_logo,
-                                0.into(), // boost amount not important
-                            )
-                        })
-                        .collect();
+                let location_ids: Vec<_> =
+                    records.iter().map(|rec| rec.location_id.into()).collect();
 
-                    let harvests = (!harvests.is_empty()).then_some(harvests);
-
-                    locations.push(Location::from_row(
-                        location_id.into(),
-                        place_name,
-                        region,
-                        country,
-                        coords,
-                        description,
-                        farm_id,
-                        farm_name,
-                        harvests,
-                    ));
-                }
+                let (farm_stats, farm_ratings) = farm_stats(&[farm_id], db.clone())
+                    .await?
+                    .remove(&farm_id)
+                    .unwrap_or_default();
+                let mut schedules = location_schedules(&location_ids, db.clone()).await?;
+                let mut galleries = location_galleries(&location_ids, db.clone()).await?;
+                let mut harvests = location_harvests(&location_ids, db.clone()).await?;
+
+                let locations = records
+                    .iter()
+                    .map(|rec| {
+                        let location_id = rec.location_id.into();
+                        Location::from_row(
+                            location_id,
+                            rec.location_place_name.clone(),
+                            rec.location_region.clone(),
+                            rec.location_country.clone(),
+                            rec.location_coords.clone(),
+                            rec.location_description.clone(),
+                            rec.location_access_notes.clone(),
+                            galleries.remove(&location_id),
+                            schedules.remove(&location_id).unwrap_or_default(),
+                            farm_id,
+                            rec.farm_name.clone(),
+                            harvests.remove(&location_id),
+                        )
+                    })
+                    .collect();
 
-                let farm = Self::from_row(
+                let mut farm = Self::from_row(
                     farm_id,
-                    farm_name,
-                    farm_logo,
-                    farm_contact_email,
-                    farm_contact_number,
+                    first_rec.farm_name.clone(),
+                    first_rec.farm_slug.clone(),
+                    first_rec.farm_logo.clone(),
+                    first_rec.farm_contact_email.clone(),
+                    first_rec.farm_contact_number.clone(),
                     locations,
-                    registered_on,
-                    owner_id,
-                    owner_first_name,
-                    owner_last_name,
-                    owner_photo,
+                    first_rec.farm_registered_on,
+                    farm_stats,
+                    farm_ratings,
+                    first_rec.farm_owner_id.into(),
+                    first_rec.farm_owner_first_name.clone(),
+                    first_rec.farm_owner_last_name.clone(),
+                    first_rec.farm_owner_photo.clone(),
+                    first_rec.farm_owner_identity_verified,
                 );
+                farm.ratings.load_highlights(farm_id, db).await?;
                 Ok(Some(farm))
             }
             Err(err) => {
@@ -312,6 +363,10 @@ impl Farm {
                 // Insert farm location
                 location_insert(farm.location, &mut tx).await?;
 
+                // Insert farm owner member
+                let owner = FarmMemberInsertData::new(farm.id, farm.owner_id, FarmRole::Owner);
+                FarmMember::insert(owner, &mut tx).await?;
+
                 //Update user
                 update_user_is_farmer(true, farm.owner_id, &mut tx).await?;
 
@@ -416,59 +471,95 @@ impl Farm {
 
     /// Deletes farm from the database
     ///
-    // Farm will only be `deleted` if it has no location
-    // that has harvests associated with it.
+    /// Farm, its locations and harvests are archived and can be restored
+    /// within the restore grace period, afterwards the farm
+    /// will only be deleted if it has no location left.
     #[tracing::instrument(name = "Delete Farm", skip(db))]
-    #[allow(clippy::cast_sign_loss)]
     pub async fn delete(id: ModelID, db: DatabaseConnection) -> ServerResult<()> {
+        // Fetch farm members, including the owner
         let conn = db.clone();
-        let old_archived_harvest_count =
-            tokio::spawn(async move { farm_archived_harvest_count(id, conn).await }).await??;
+        let member_ids = tokio::spawn(async move { farm_member_ids(id, conn).await }).await??;
 
-        // Fetch farm active harvests images
-        let conn = db.clone();
-        let image_paths =
-            tokio::spawn(async move { farm_harvest_images(id, conn).await }).await??;
+        // initialize transaction
+        let mut tx = db.pool.begin().await?;
+
+        let archived_at = OffsetDateTime::now_utc();
+
+        // Archive harvests, locations and the farm
+        archive_farm_harvests(id, archived_at, &mut tx).await?;
+        archive_farm_locations(id, archived_at, &mut tx).await?;
+        archive_farm(id, archived_at, &mut tx).await?;
 
-        // Fetch farm count belonging to the user of the current farm
+        // Members of no other active farm are no longer farmers
+        update_users_is_farmer(&member_ids, &mut tx).await?;
+
+        tx.commit().await?;
+        tracing::debug!("Farm::delete, transaction committed successfully.");
+
+        Ok(())
+    }
+
+    /// Restores the deleted farm and the locations and harvests
+    /// deleted together with it in the database
+    ///
+    /// Farm can only be restored within the restore grace period
+    /// of being deleted.
+    #[tracing::instrument(name = "Restore Farm", skip(db))]
+    pub async fn restore(
+        id: ModelID,
+        grace_days: ArchiveGraceDays,
+        db: DatabaseConnection,
+    ) -> ServerResult<()> {
+        let cutoff = archive_restore_cutoff(OffsetDateTime::now_utc(), grace_days)?;
+
+        // Fetch farm members, including the owner
         let conn = db.clone();
-        let (user_id, farm_count) =
-            tokio::spawn(async move { user_farm_count(id, conn).await }).await??;
-        let user_id = user_id
-            .ok_or_else(|| ServerError::new("Database error, failed to fetch farm owner."))?;
+        let member_ids = tokio::spawn(async move { farm_member_ids(id, conn).await }).await??;
 
         // initialize transaction
         let mut tx = db.pool.begin().await?;
 
-        let deleted_at = OffsetDateTime::now_utc();
-
-        // Cleanup harvests
-        let new_archived_harvest_count = archive_farm_harvests(id, deleted_at, &mut tx).await?;
-        delete_farm_harvests(id, deleted_at, &mut tx).await?;
+        let Some(archived_at) = restore_farm(id, cutoff, &mut tx).await? else {
+            return Err(ServerError::rejection(EndpointRejection::NotFound(
+                "Farm not found or can no longer be restored.".into(),
+            )));
+        };
+        restore_farm_locations(id, archived_at, &mut tx).await?;
+        restore_farm_harvests(id, archived_at, &mut tx).await?;
 
-        // Cleanup locations
-        archive_farm_locations(id, deleted_at, &mut tx).await?;
-        delete_farm_locations(id, &mut tx).await?;
+        // Members of the farm are farmers again
+        update_users_is_farmer(&member_ids, &mut tx).await?;
 
-        let archived_count = old_archived_harvest_count as u64 + new_archived_harvest_count;
+        tx.commit().await?;
+        tracing::debug!("Farm::restore, transaction committed successfully.");
 
-        // Delete or archive farm
-        if archived_count == 0 {
-            delete_farm(id, &mut tx).await?;
-        } else {
-            archive_farm(id, deleted_at, &mut tx).await?;
-        }
+        Ok(())
+    }
 
-        // If this was the only user's farm, set user is no longer a farmer
-        if farm_count == 1 {
-            update_user_is_farmer(false, user_id, &mut tx).await?;
-        }
+    /// Finalizes farms archived for longer than the restore grace period
+    ///
+    /// Farms that have no location left are deleted.
+    /// Must run after the archived locations are purged.
+    #[tracing::instrument(name = "Purge archived Farms", skip(db))]
+    pub async fn purge_archived(
+        grace_days: ArchiveGraceDays,
+        db: DatabaseConnection,
+    ) -> ServerResult<()> {
+        let cutoff = archive_restore_cutoff(OffsetDateTime::now_utc(), grace_days)?;
 
+        let mut tx = db.pool.begin().await?;
+        let rating_images = delete_expired_farms(cutoff, &mut tx).await?;
+        finalize_archived_farms(cutoff, &mut tx).await?;
         tx.commit().await?;
-        tracing::debug!("Farm::delete, transaction committed successfully.");
+        tracing::debug!("Farm::purge_archived, transaction committed successfully.");
 
-        // Delete active harvest images
-        tokio::spawn(async move { delete_harvest_photos(image_paths.into_iter().flatten()).await });
+        // Delete the images of the ratings deleted together with the farms
+        if delete_rating_photos(rating_images.into_iter())
+            .await
+            .is_err()
+        {
+            tracing::error!("Io error, failed to delete deleted farms rating images, but farms were purged successfully.");
+        }
 
         Ok(())
     }
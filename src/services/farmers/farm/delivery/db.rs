@@ -0,0 +1,335 @@
+//! Farm delivery option database impl
+
+use std::collections::HashSet;
+
+use crate::{
+    endpoint::EndpointRejection,
+    error::{ServerError, ServerResult},
+    server::state::DatabaseConnection,
+    types::ModelID,
+};
+
+use super::{forms::DeliveryOptionInsertData, DeliveryOption, DeliveryOptionList, DeliveryTarget};
+
+impl DeliveryOption {
+    /// Fetches farm delivery options from the database
+    #[tracing::instrument(name = "Fetch DeliveryOptionList", skip(db))]
+    pub async fn records(
+        farm_id: ModelID,
+        db: DatabaseConnection,
+    ) -> ServerResult<DeliveryOptionList> {
+        match sqlx::query!(
+            r#"
+                SELECT option_.id,
+                    option_.farm_id,
+                    option_.kind,
+                    option_.location_id,
+                    option_.radius_km,
+                    option_.region_ids,
+                    option_.fee_type,
+                    option_.fee,
+                    option_.minimum_order,
+                    location_.coords AS "location_coords?"
+                FROM services.farm_delivery_options option_
+                LEFT JOIN services.active_locations location_
+                    ON option_.location_id = location_.id
+
+                WHERE option_.farm_id = $1
+                ORDER BY option_.created_at
+            "#,
+            farm_id.0
+        )
+        .fetch_all(&db.pool)
+        .await
+        {
+            Ok(records) => {
+                let options = records
+                    .into_iter()
+                    .map(|rec| {
+                        Self::from_row(
+                            rec.id.into(),
+                            rec.farm_id.into(),
+                            &rec.kind,
+                            rec.location_id.map(Into::into),
+                            rec.radius_km,
+                            rec.region_ids,
+                            &rec.fee_type,
+                            rec.fee,
+                            rec.minimum_order,
+                            rec.location_coords,
+                        )
+                    })
+                    .collect();
+
+                Ok(options)
+            }
+            Err(err) => {
+                tracing::error!("Database error, failed to fetch delivery options: {}", err);
+                Err(err.into())
+            }
+        }
+    }
+
+    /// Fetches the ids of the farms delivering to the buyer location,
+    /// the radius distance is the great-circle distance in kilometres.
+    #[tracing::instrument(skip(db))]
+    pub async fn farms_delivering_to(
+        target: &DeliveryTarget,
+        db: DatabaseConnection,
+    ) -> ServerResult<HashSet<ModelID>> {
+        match sqlx::query!(
+            r#"
+                SELECT DISTINCT option_.farm_id
+                FROM services.farm_delivery_options option_
+                INNER JOIN services.active_farms farm
+                    ON option_.farm_id = farm.id
+                LEFT JOIN services.active_locations location_
+                    ON option_.location_id = location_.id
+
+                WHERE (option_.kind = 'regions' AND $3 = ANY(option_.region_ids))
+                    OR (option_.kind = 'radius' AND 6371.0088 * 2 * asin(sqrt(
+                        power(sin(radians(($1 - (location_.coords->>'y')::double precision) / 2)), 2)
+                        + cos(radians((location_.coords->>'y')::double precision)) * cos(radians($1))
+                        * power(sin(radians(($2 - (location_.coords->>'x')::double precision) / 2)), 2)
+                    )) <= option_.radius_km)
+            "#,
+            target.lat,
+            target.lng,
+            target.delivery_region.map(|id| id.0),
+        )
+        .fetch_all(&db.pool)
+        .await
+        {
+            Ok(records) => Ok(records.into_iter().map(|rec| rec.farm_id.into()).collect()),
+            Err(err) => {
+                tracing::error!("Database error, failed to fetch delivering farms: {}", err);
+                Err(err.into())
+            }
+        }
+    }
+
+    /// Inserts farm delivery option into the database
+    #[tracing::instrument(name = "Insert DeliveryOption", skip(db, values))]
+    pub async fn insert(
+        values: DeliveryOptionInsertData,
+        db: DatabaseConnection,
+    ) -> ServerResult<ModelID> {
+        let region_ids: Vec<_> = values.region_ids.iter().map(|id| id.0).collect();
+        match sqlx::query!(
+            r#"
+                INSERT INTO services.farm_delivery_options(
+                    id,
+                    farm_id,
+                    kind,
+                    location_id,
+                    radius_km,
+                    region_ids,
+                    fee_type,
+                    fee,
+                    minimum_order,
+                    created_at
+                )
+                VALUES($1, $2, $3, $4, $5, $6, $7, $8, $9, $10);
+            "#,
+            values.id.0,
+            values.farm_id.0,
+            values.kind.as_str(),
+            values.location_id.map(|id| id.0),
+            values.radius_km,
+            &region_ids[..],
+            values.fee_type.as_str(),
+            values.fee,
+            values.minimum_order,
+            values.created_at,
+        )
+        .execute(&db.pool)
+        .await
+        {
+            Ok(result) => {
+                tracing::debug!("Delivery option inserted successfully: {:?}", result);
+                Ok(values.id)
+            }
+            Err(err) => {
+                // Handle database constraint error
+                handle_delivery_database_error(&err)?;
+
+                tracing::error!("Database error, failed to insert delivery option: {}", err);
+                Err(err.into())
+            }
+        }
+    }
+
+    /// Updates farm delivery option in the database
+    #[tracing::instrument(name = "Update DeliveryOption", skip(db, values))]
+    pub async fn update(
+        id: ModelID,
+        values: DeliveryOptionInsertData,
+        db: DatabaseConnection,
+    ) -> ServerResult<()> {
+        let region_ids: Vec<_> = values.region_ids.iter().map(|id| id.0).collect();
+        match sqlx::query!(
+            r#"
+                UPDATE services.farm_delivery_options option_
+                SET kind = $1,
+                    location_id = $2,
+                    radius_km = $3,
+                    region_ids = $4,
+                    fee_type = $5,
+                    fee = $6,
+                    minimum_order = $7
+                WHERE option_.id = $8
+            "#,
+            values.kind.as_str(),
+            values.location_id.map(|id| id.0),
+            values.radius_km,
+            &region_ids[..],
+            values.fee_type.as_str(),
+            values.fee,
+            values.minimum_order,
+            id.0
+        )
+        .execute(&db.pool)
+        .await
+        {
+            Ok(result) => {
+                tracing::debug!("Delivery option updated successfully: {:?}", result);
+                Ok(())
+            }
+            Err(err) => {
+                // Handle database constraint error
+                handle_delivery_database_error(&err)?;
+
+                tracing::error!("Database error, failed to update delivery option: {}", err);
+                Err(err.into())
+            }
+        }
+    }
+
+    /// Deletes farm delivery option from the database
+    #[tracing::instrument(name = "Delete DeliveryOption", skip(db))]
+    pub async fn delete(id: ModelID, db: DatabaseConnection) -> ServerResult<()> {
+        match sqlx::query!(
+            r#"
+                DELETE FROM services.farm_delivery_options option_
+                WHERE option_.id = $1
+            "#,
+            id.0
+        )
+        .execute(&db.pool)
+        .await
+        {
+            Ok(result) => {
+                tracing::debug!("Delivery option deleted successfully: {:?}", result);
+                Ok(())
+            }
+            Err(err) => {
+                tracing::error!("Database error, failed to delete delivery option: {}", err);
+                Err(err.into())
+            }
+        }
+    }
+
+    /// Fetches the farm id the delivery option belongs to
+    #[tracing::instrument(skip(db))]
+    pub async fn farm_id(id: ModelID, db: DatabaseConnection) -> ServerResult<Option<ModelID>> {
+        match sqlx::query!(
+            r#"
+                SELECT option_.farm_id
+                FROM services.farm_delivery_options option_
+                WHERE option_.id = $1
+            "#,
+            id.0
+        )
+        .fetch_optional(&db.pool)
+        .await
+        {
+            Ok(rec) => Ok(rec.map(|rec| rec.farm_id.into())),
+            Err(err) => {
+                tracing::error!(
+                    "Database error, failed to fetch delivery option farm: {}",
+                    err
+                );
+                Err(err.into())
+            }
+        }
+    }
+
+    /// Checks if the location is an active location of the farm
+    #[tracing::instrument(skip(db))]
+    pub async fn location_in_farm(
+        location_id: ModelID,
+        farm_id: ModelID,
+        db: DatabaseConnection,
+    ) -> ServerResult<bool> {
+        match sqlx::query!(
+            r#"
+                SELECT EXISTS(
+                    SELECT 1 FROM services.active_locations location_
+                    WHERE location_.id = $1
+                        AND location_.farm_id = $2
+                ) AS "exists!"
+            "#,
+            location_id.0,
+            farm_id.0
+        )
+        .fetch_one(&db.pool)
+        .await
+        {
+            Ok(rec) => Ok(rec.exists),
+            Err(err) => {
+                tracing::error!("Database error, failed to check farm location: {}", err);
+                Err(err.into())
+            }
+        }
+    }
+
+    /// Checks if all the regions exist
+    #[tracing::instrument(skip(db))]
+    pub async fn regions_exist(
+        region_ids: &[ModelID],
+        db: DatabaseConnection,
+    ) -> ServerResult<bool> {
+        let ids: Vec<_> = region_ids.iter().map(|id| id.0).collect();
+        match sqlx::query!(
+            r#"
+                SELECT COUNT(region.id) AS "count!"
+                FROM services.regions region
+                WHERE region.id = ANY($1)
+            "#,
+            &ids[..]
+        )
+        .fetch_one(&db.pool)
+        .await
+        {
+            Ok(rec) => Ok(usize::try_from(rec.count).is_ok_and(|count| count == ids.len())),
+            Err(err) => {
+                tracing::error!("Database error, failed to check regions exist: {}", err);
+                Err(err.into())
+            }
+        }
+    }
+}
+
+/// Handle farm delivery option database constraints errors
+#[allow(clippy::cognitive_complexity)]
+pub fn handle_delivery_database_error(err: &sqlx::Error) -> ServerResult<()> {
+    if let sqlx::Error::Database(db_err) = err {
+        // Handle db foreign key constraints
+        if db_err.is_foreign_key_violation() {
+            tracing::error!("Database error, farm or location not found. {:?}", err);
+            return Err(ServerError::rejection(EndpointRejection::BadRequest(
+                "Farm or location not found.".into(),
+            )));
+        }
+
+        // Handle db check constraints
+        if db_err.is_check_violation() {
+            tracing::error!("Database error, invalid delivery option. {:?}", err);
+            return Err(ServerError::rejection(EndpointRejection::BadRequest(
+                "Invalid delivery option.".into(),
+            )));
+        }
+    }
+
+    Ok(())
+}
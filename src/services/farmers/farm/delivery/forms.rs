@@ -0,0 +1,250 @@
+//! Farm delivery option forms impls
+
+use axum::{
+    async_trait,
+    extract::{rejection::JsonRejection, FromRequest, FromRequestParts, Json, Request},
+};
+use rust_decimal::Decimal;
+use serde::Deserialize;
+use time::OffsetDateTime;
+
+use crate::{
+    auth::FarmerUser,
+    endpoint::{EndpointRejection, EndpointResult},
+    server::state::ServerState,
+    services::farmers::farm::permissions::check_user_can_manage_farm,
+    types::ModelID,
+};
+
+use super::{DeliveryFeeType, DeliveryKind, DeliveryOption};
+
+/// Maximum delivery radius in kilometres
+const DELIVERY_MAX_RADIUS_KM: f64 = 500.0;
+
+/// Maximum number of regions a delivery option can deliver into
+const DELIVERY_MAX_REGIONS: usize = 50;
+
+/// Farm delivery option form
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DeliveryOptionForm {
+    pub kind: DeliveryKind,
+    pub location_id: Option<ModelID>,
+    pub radius_km: Option<f64>,
+    #[serde(default)]
+    pub region_ids: Vec<ModelID>,
+    pub fee_type: DeliveryFeeType,
+    #[serde(default)]
+    pub fee: Decimal,
+    #[serde(default)]
+    pub minimum_order: Decimal,
+}
+
+/// Farm delivery option cleaned data
+#[derive(Debug, Clone)]
+pub struct DeliveryOptionInsertData {
+    pub id: ModelID,
+    pub farm_id: ModelID,
+    pub kind: DeliveryKind,
+    pub location_id: Option<ModelID>,
+    pub radius_km: Option<f64>,
+    pub region_ids: Vec<ModelID>,
+    pub fee_type: DeliveryFeeType,
+    pub fee: Decimal,
+    pub minimum_order: Decimal,
+    pub created_at: OffsetDateTime,
+}
+
+impl DeliveryOptionForm {
+    /// Validates delivery option form inputs
+    fn validate(&mut self) -> EndpointResult<()> {
+        // Clean the data
+        self.clean_data();
+
+        if self.fee < Decimal::ZERO {
+            return Err(EndpointRejection::BadRequest(
+                "Delivery fee cannot be negative.".into(),
+            ));
+        }
+
+        if self.minimum_order < Decimal::ZERO {
+            return Err(EndpointRejection::BadRequest(
+                "Delivery minimum order cannot be negative.".into(),
+            ));
+        }
+
+        match self.kind {
+            DeliveryKind::Pickup => Ok(()),
+            DeliveryKind::Radius => {
+                if self.location_id.is_none() {
+                    return Err(EndpointRejection::BadRequest(
+                        "Delivery radius requires a location to measure from.".into(),
+                    ));
+                }
+                match self.radius_km {
+                    Some(radius_km) if radius_km > 0.0 && radius_km <= DELIVERY_MAX_RADIUS_KM => {
+                        Ok(())
+                    }
+                    _ => Err(EndpointRejection::BadRequest(
+                        format!(
+                            "Delivery radius must be between 0 and {DELIVERY_MAX_RADIUS_KM} km."
+                        )
+                        .into(),
+                    )),
+                }
+            }
+            DeliveryKind::Regions => {
+                if self.region_ids.is_empty() || self.region_ids.len() > DELIVERY_MAX_REGIONS {
+                    return Err(EndpointRejection::BadRequest(
+                        format!("Delivery regions must be between 1 and {DELIVERY_MAX_REGIONS}.")
+                            .into(),
+                    ));
+                }
+                Ok(())
+            }
+        }
+    }
+
+    /// Clean form data, drops the fields the delivery kind does not use
+    fn clean_data(&mut self) {
+        self.region_ids.sort_unstable();
+        self.region_ids.dedup();
+
+        match self.kind {
+            DeliveryKind::Pickup => {
+                self.location_id = None;
+                self.radius_km = None;
+                self.region_ids.clear();
+                self.fee_type = DeliveryFeeType::Flat;
+                self.fee = Decimal::ZERO;
+            }
+            DeliveryKind::Radius => self.region_ids.clear(),
+            DeliveryKind::Regions => {
+                self.location_id = None;
+                self.radius_km = None;
+            }
+        }
+    }
+
+    /// Convert `Self` into `DeliveryOptionInsertData`
+    #[must_use]
+    pub fn data(self, farm_id: ModelID) -> DeliveryOptionInsertData {
+        DeliveryOptionInsertData {
+            id: ModelID::new(),
+            farm_id,
+            kind: self.kind,
+            location_id: self.location_id,
+            radius_km: self.radius_km,
+            region_ids: self.region_ids,
+            fee_type: self.fee_type,
+            fee: self.fee,
+            minimum_order: self.minimum_order,
+            created_at: OffsetDateTime::now_utc(),
+        }
+    }
+
+    /// Validates the delivery location belongs to the farm
+    /// and the delivery regions exist.
+    async fn validate_references(
+        &self,
+        farm_id: ModelID,
+        state: &ServerState,
+    ) -> EndpointResult<()> {
+        if let Some(location_id) = self.location_id {
+            if !DeliveryOption::location_in_farm(location_id, farm_id, state.database()).await? {
+                return Err(EndpointRejection::BadRequest(
+                    "Delivery location not found on the farm.".into(),
+                ));
+            }
+        }
+
+        if !self.region_ids.is_empty()
+            && !DeliveryOption::regions_exist(&self.region_ids, state.database()).await?
+        {
+            return Err(EndpointRejection::BadRequest(
+                "Delivery region not found.".into(),
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+// ===== Delivery Option Create form impls =====
+
+/// Farm delivery option create form
+#[derive(Debug, Clone)]
+pub struct DeliveryOptionCreateForm {
+    pub farm_id: ModelID,
+    pub option: DeliveryOptionForm,
+}
+
+#[async_trait]
+impl FromRequest<ServerState> for DeliveryOptionCreateForm
+where
+    Json<DeliveryOptionForm>: FromRequest<ServerState, Rejection = JsonRejection>,
+{
+    type Rejection = EndpointRejection;
+
+    async fn from_request(req: Request, state: &ServerState) -> Result<Self, Self::Rejection> {
+        // Extract data
+        let (mut parts, body) = req.into_parts();
+        let user = { FarmerUser::from_parts(&mut parts, state).await? };
+        let farm_id = { ModelID::from_request_parts(&mut parts, state).await? };
+        let Json(mut option) =
+            Json::<DeliveryOptionForm>::from_request(Request::from_parts(parts, body), state)
+                .await?;
+
+        // Validate form fields
+        option.validate()?;
+
+        // Authorize request
+        check_user_can_manage_farm(user.id(), farm_id, state.database()).await?;
+
+        option.validate_references(farm_id, state).await?;
+
+        Ok(Self { farm_id, option })
+    }
+}
+
+// ===== Delivery Option Update form impls =====
+
+/// Farm delivery option update form
+#[derive(Debug, Clone)]
+pub struct DeliveryOptionUpdateForm {
+    pub farm_id: ModelID,
+    pub option: DeliveryOptionForm,
+}
+
+#[async_trait]
+impl FromRequest<ServerState> for DeliveryOptionUpdateForm
+where
+    Json<DeliveryOptionForm>: FromRequest<ServerState, Rejection = JsonRejection>,
+{
+    type Rejection = EndpointRejection;
+
+    async fn from_request(req: Request, state: &ServerState) -> Result<Self, Self::Rejection> {
+        // Extract data
+        let (mut parts, body) = req.into_parts();
+        let user = { FarmerUser::from_parts(&mut parts, state).await? };
+        let id = { ModelID::from_request_parts(&mut parts, state).await? };
+        let Json(mut option) =
+            Json::<DeliveryOptionForm>::from_request(Request::from_parts(parts, body), state)
+                .await?;
+
+        // Validate form fields
+        option.validate()?;
+
+        // Authorize request
+        let Some(farm_id) = DeliveryOption::farm_id(id, state.database()).await? else {
+            return Err(EndpointRejection::NotFound(
+                "Delivery option not found.".into(),
+            ));
+        };
+        check_user_can_manage_farm(user.id(), farm_id, state.database()).await?;
+
+        option.validate_references(farm_id, state).await?;
+
+        Ok(Self { farm_id, option })
+    }
+}
@@ -0,0 +1,60 @@
+//! Farm delivery option http handlers impls
+
+use axum::{
+    extract::{Json, State},
+    http::StatusCode,
+};
+
+use crate::{
+    auth::FarmerUser, endpoint::EndpointResult, server::state::DatabaseConnection,
+    services::farmers::farm::permissions::DeliveryOptionPermission, types::ModelID,
+};
+
+use super::{
+    forms::{DeliveryOptionCreateForm, DeliveryOptionUpdateForm},
+    DeliveryOption, DeliveryOptionList,
+};
+
+/// Handles the `GET /farms/:farm_id/delivery` route.
+#[tracing::instrument(skip(db))]
+pub async fn delivery_option_list(
+    farm_id: ModelID,
+    State(db): State<DatabaseConnection>,
+) -> EndpointResult<Json<DeliveryOptionList>> {
+    let options = DeliveryOption::records(farm_id, db).await?;
+    Ok(Json(options))
+}
+
+/// Handles the `POST /farms/:farm_id/delivery` route.
+#[tracing::instrument(skip(db, form))]
+pub async fn delivery_option_create(
+    _: FarmerUser,
+    State(db): State<DatabaseConnection>,
+    form: DeliveryOptionCreateForm,
+) -> EndpointResult<StatusCode> {
+    DeliveryOption::insert(form.option.data(form.farm_id), db).await?;
+    Ok(StatusCode::CREATED)
+}
+
+/// Handles the `PUT /farms/delivery/:option_id` route.
+#[tracing::instrument(skip(db, form))]
+pub async fn delivery_option_update(
+    _: FarmerUser,
+    option_id: ModelID,
+    State(db): State<DatabaseConnection>,
+    form: DeliveryOptionUpdateForm,
+) -> EndpointResult<StatusCode> {
+    DeliveryOption::update(option_id, form.option.data(form.farm_id), db).await?;
+    Ok(StatusCode::OK)
+}
+
+/// Handles the `DELETE /farms/delivery/:option_id` route.
+#[tracing::instrument(skip(db))]
+pub async fn delivery_option_delete(
+    _: DeliveryOptionPermission,
+    option_id: ModelID,
+    State(db): State<DatabaseConnection>,
+) -> EndpointResult<StatusCode> {
+    DeliveryOption::delete(option_id, db).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
@@ -0,0 +1,248 @@
+//! Farm delivery option model impls
+
+pub mod db;
+pub mod forms;
+pub mod handlers;
+
+use std::fmt;
+
+use geo::{HaversineDistance, Point};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+use crate::{services::farmers::location, types::ModelID};
+
+/// A `Vec` of farm delivery options
+pub type DeliveryOptionList = Vec<DeliveryOption>;
+
+/// The model representing a row in the `farm_delivery_options` database table.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DeliveryOption {
+    pub id: ModelID,
+    pub farm_id: ModelID,
+    pub kind: DeliveryKind,
+    pub location_id: Option<ModelID>,
+    pub radius_km: Option<f64>,
+    pub region_ids: Vec<ModelID>,
+    pub fee_type: DeliveryFeeType,
+    pub fee: Decimal,
+    pub minimum_order: Decimal,
+
+    // This field is for internal use only; it is not sent to the users.
+    // The coordinates of the location the radius is measured from.
+    #[serde(skip_serializing)]
+    pub origin: Option<Point>,
+}
+
+impl DeliveryOption {
+    /// Creates a new `DeliveryOption` from the database row
+    #[allow(clippy::too_many_arguments)]
+    #[must_use]
+    pub fn from_row(
+        id: ModelID,
+        farm_id: ModelID,
+        kind: &str,
+        location_id: Option<ModelID>,
+        radius_km: Option<f64>,
+        region_ids: Vec<uuid::Uuid>,
+        fee_type: &str,
+        fee: Decimal,
+        minimum_order: Decimal,
+        location_coords: Option<serde_json::Value>,
+    ) -> Self {
+        Self {
+            id,
+            farm_id,
+            kind: DeliveryKind::from_row(kind),
+            location_id,
+            radius_km,
+            region_ids: region_ids.into_iter().map(ModelID).collect(),
+            fee_type: DeliveryFeeType::from_row(fee_type),
+            fee,
+            minimum_order,
+            origin: location::try_into_point(location_coords),
+        }
+    }
+
+    /// Checks if the option delivers to the buyer location
+    #[must_use]
+    pub fn delivers_to(&self, target: &DeliveryTarget) -> bool {
+        match self.kind {
+            DeliveryKind::Pickup => false,
+            DeliveryKind::Radius => match (self.origin, target.coords(), self.radius_km) {
+                (Some(origin), Some(coords), Some(radius_km)) => {
+                    distance_km(origin, coords) <= radius_km
+                }
+                _ => false,
+            },
+            DeliveryKind::Regions => target
+                .delivery_region
+                .is_some_and(|region_id| self.region_ids.contains(&region_id)),
+        }
+    }
+
+    /// Estimates the delivery fee to the buyer location,
+    /// `from` is the location the harvest is delivered from.
+    ///
+    /// Returns `None` if the option does not deliver to the buyer location
+    /// or the fee depends on a distance that cannot be calculated.
+    #[must_use]
+    pub fn estimate(
+        &self,
+        target: &DeliveryTarget,
+        from: Option<Point>,
+    ) -> Option<DeliveryEstimate> {
+        if !self.delivers_to(target) {
+            return None;
+        }
+
+        let distance_km = self
+            .origin
+            .or(from)
+            .zip(target.coords())
+            .map(|(origin, coords)| distance_km(origin, coords));
+
+        let fee = match self.fee_type {
+            DeliveryFeeType::Flat => self.fee,
+            DeliveryFeeType::PerKm => self.fee * Decimal::from_f64_retain(distance_km?)?,
+        };
+
+        Some(DeliveryEstimate {
+            option_id: self.id,
+            fee: fee.round_dp(2),
+            distance_km,
+            minimum_order: self.minimum_order,
+        })
+    }
+
+    /// Returns the cheapest delivery estimate to the buyer location
+    #[must_use]
+    pub fn cheapest_estimate(
+        options: &[Self],
+        target: &DeliveryTarget,
+        from: Option<Point>,
+    ) -> Option<DeliveryEstimate> {
+        options
+            .iter()
+            .filter_map(|option| option.estimate(target, from))
+            .min_by_key(|estimate| estimate.fee)
+    }
+}
+
+/// How the farm gets the harvest to the buyer
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum DeliveryKind {
+    /// Buyers collect the harvest at the farm's locations.
+    Pickup,
+    /// Delivery within a radius of a farm location.
+    Radius,
+    /// Delivery into named regions.
+    Regions,
+}
+
+impl DeliveryKind {
+    /// Returns the kind name as it stored in the database
+    #[must_use]
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Self::Pickup => "pickup",
+            Self::Radius => "radius",
+            Self::Regions => "regions",
+        }
+    }
+
+    /// Creates a new `DeliveryKind` from the database column
+    #[must_use]
+    pub fn from_row(kind: &str) -> Self {
+        match kind {
+            "radius" => Self::Radius,
+            "regions" => Self::Regions,
+            _ => Self::Pickup,
+        }
+    }
+}
+
+impl fmt::Display for DeliveryKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// How the delivery fee is charged
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum DeliveryFeeType {
+    /// The same fee regardless of the distance.
+    Flat,
+    /// The fee is charged per kilometre of the delivery distance.
+    PerKm,
+}
+
+impl DeliveryFeeType {
+    /// Returns the fee type name as it stored in the database
+    #[must_use]
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Self::Flat => "flat",
+            Self::PerKm => "per_km",
+        }
+    }
+
+    /// Creates a new `DeliveryFeeType` from the database column
+    #[must_use]
+    pub fn from_row(fee_type: &str) -> Self {
+        match fee_type {
+            "per_km" => Self::PerKm,
+            _ => Self::Flat,
+        }
+    }
+}
+
+impl fmt::Display for DeliveryFeeType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// The buyer location a delivery is estimated for
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DeliveryTarget {
+    /// Buyer latitude
+    pub lat: Option<f64>,
+    /// Buyer longitude
+    pub lng: Option<f64>,
+    /// Buyer region id
+    pub delivery_region: Option<ModelID>,
+}
+
+impl DeliveryTarget {
+    /// Buyer coordinates
+    #[must_use]
+    pub fn coords(&self) -> Option<Point> {
+        Some(Point::new(self.lng?, self.lat?))
+    }
+
+    /// Checks if the buyer location was not provided
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.coords().is_none() && self.delivery_region.is_none()
+    }
+}
+
+/// The estimated delivery fee to the buyer location
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DeliveryEstimate {
+    pub option_id: ModelID,
+    pub fee: Decimal,
+    pub distance_km: Option<f64>,
+    pub minimum_order: Decimal,
+}
+
+/// Returns the great-circle distance between two points in kilometres
+fn distance_km(from: Point, to: Point) -> f64 {
+    from.haversine_distance(&to) / 1000.0
+}
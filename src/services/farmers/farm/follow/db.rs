@@ -0,0 +1,145 @@
+//! Farm follow database impl
+
+use crate::{
+    endpoint::EndpointRejection,
+    error::{ServerError, ServerResult},
+    server::state::DatabaseConnection,
+    types::ModelID,
+};
+
+use super::{FarmFollow, NewHarvestNotice};
+
+impl FarmFollow {
+    /// Inserts farm follow into the database
+    ///
+    /// Updates the notification preference if the user already follows the farm
+    #[tracing::instrument(name = "Insert FarmFollow", skip(db))]
+    pub async fn insert(self, db: DatabaseConnection) -> ServerResult<()> {
+        match sqlx::query!(
+            r#"
+                INSERT INTO accounts.farm_follows(
+                    user_id,
+                    farm_id,
+                    notify,
+                    created_at
+                )
+                SELECT $1, farm.id, $3, $4
+                FROM services.active_farms farm
+                WHERE farm.id = $2
+
+                ON CONFLICT (user_id, farm_id)
+                DO UPDATE SET notify = EXCLUDED.notify;
+            "#,
+            self.user_id.0,
+            self.farm_id.0,
+            self.notify,
+            self.created_at,
+        )
+        .execute(&db.pool)
+        .await
+        {
+            Ok(result) if result.rows_affected() == 0 => Err(ServerError::rejection(
+                EndpointRejection::NotFound("Farm not found.".into()),
+            )),
+            Ok(result) => {
+                tracing::debug!("Farm follow inserted successfully: {:?}", result);
+                Ok(())
+            }
+            Err(err) => {
+                tracing::error!("Database error, failed to insert farm follow: {}", err);
+                Err(err.into())
+            }
+        }
+    }
+
+    /// Deletes farm follow from the database
+    #[tracing::instrument(name = "Delete FarmFollow", skip(db))]
+    pub async fn delete(
+        user_id: ModelID,
+        farm_id: ModelID,
+        db: DatabaseConnection,
+    ) -> ServerResult<()> {
+        match sqlx::query!(
+            r#"
+                DELETE FROM accounts.farm_follows follow
+                WHERE follow.user_id = $1
+                    AND follow.farm_id = $2
+            "#,
+            user_id.0,
+            farm_id.0
+        )
+        .execute(&db.pool)
+        .await
+        {
+            Ok(result) if result.rows_affected() == 0 => Err(ServerError::rejection(
+                EndpointRejection::NotFound("You are not following this farm.".into()),
+            )),
+            Ok(result) => {
+                tracing::debug!("Farm follow deleted successfully: {:?}", result);
+                Ok(())
+            }
+            Err(err) => {
+                tracing::error!("Database error, failed to delete farm follow: {}", err);
+                Err(err.into())
+            }
+        }
+    }
+}
+
+impl NewHarvestNotice {
+    /// Fetches the harvest and the farm followers to be notified
+    /// about the new harvest from the database
+    #[tracing::instrument(name = "Find NewHarvestNotice", skip(db))]
+    pub async fn find(harvest_id: ModelID, db: DatabaseConnection) -> ServerResult<Option<Self>> {
+        match sqlx::query!(
+            r#"
+                SELECT cultivar.name AS harvest_name,
+                    farm.name AS farm_name,
+                    user_.first_name AS "user_first_name?",
+                    address.email AS "user_email?"
+                FROM services.active_harvests harvest
+                INNER JOIN services.cultivars cultivar
+                    ON harvest.cultivar_id = cultivar.id
+                INNER JOIN services.locations location_
+                    ON harvest.location_id = location_.id
+                INNER JOIN services.farms farm
+                    ON location_.farm_id = farm.id
+                LEFT JOIN accounts.farm_follows follow
+                    ON farm.id = follow.farm_id
+                    AND follow.notify = true
+                LEFT JOIN accounts.users user_
+                    ON follow.user_id = user_.id
+                LEFT JOIN accounts.emails address
+                    ON user_.id = address.user_id
+                    AND address.verified = true
+
+                WHERE harvest.id = $1
+            "#,
+            harvest_id.0
+        )
+        .fetch_all(&db.pool)
+        .await
+        {
+            Ok(records) if records.is_empty() => Ok(None),
+            Ok(records) => {
+                let harvest_name = records[0].harvest_name.clone();
+                let farm_name = records[0].farm_name.clone();
+                let recipients = records
+                    .into_iter()
+                    .filter_map(|rec| rec.user_first_name.zip(rec.user_email))
+                    .collect();
+
+                Ok(Some(Self {
+                    harvest_id,
+                    harvest_name,
+                    farm_name,
+                    recipients,
+                }))
+            }
+            Err(err) => {
+                tracing::error!("Database error, failed to fetch harvest followers: {}", err);
+                Err(err.into())
+            }
+        }
+    }
+}
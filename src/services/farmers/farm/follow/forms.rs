@@ -0,0 +1,43 @@
+//! Farm follow forms impls
+
+use serde::Deserialize;
+use time::OffsetDateTime;
+
+use crate::types::ModelID;
+
+use super::FarmFollow;
+
+/// Farm follow form
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FarmFollowForm {
+    /// Email the user when the farm lists a new harvest
+    #[serde(default = "default_notify")]
+    pub notify: bool,
+}
+
+impl Default for FarmFollowForm {
+    fn default() -> Self {
+        Self {
+            notify: default_notify(),
+        }
+    }
+}
+
+impl FarmFollowForm {
+    /// Convert `Self` into `FarmFollow`
+    #[must_use]
+    pub fn data(self, user_id: ModelID, farm_id: ModelID) -> FarmFollow {
+        FarmFollow {
+            user_id,
+            farm_id,
+            notify: self.notify,
+            created_at: OffsetDateTime::now_utc(),
+        }
+    }
+}
+
+/// Followers are notified of new harvests by default
+const fn default_notify() -> bool {
+    true
+}
@@ -0,0 +1,36 @@
+//! Farm follow http handlers impls
+
+use axum::{
+    extract::{Json, State},
+    http::StatusCode,
+};
+
+use crate::{
+    auth::CurrentUser, endpoint::EndpointResult, server::state::DatabaseConnection, types::ModelID,
+};
+
+use super::{forms::FarmFollowForm, FarmFollow};
+
+/// Handles the `POST /farms/:farm_id/follow` route.
+#[tracing::instrument(skip(user, db, form))]
+pub async fn farm_follow(
+    user: CurrentUser,
+    farm_id: ModelID,
+    State(db): State<DatabaseConnection>,
+    form: Option<Json<FarmFollowForm>>,
+) -> EndpointResult<StatusCode> {
+    let Json(form) = form.unwrap_or_default();
+    form.data(user.id, farm_id).insert(db).await?;
+    Ok(StatusCode::CREATED)
+}
+
+/// Handles the `DELETE /farms/:farm_id/follow` route.
+#[tracing::instrument(skip(user, db))]
+pub async fn farm_unfollow(
+    user: CurrentUser,
+    farm_id: ModelID,
+    State(db): State<DatabaseConnection>,
+) -> EndpointResult<StatusCode> {
+    FarmFollow::delete(user.id, farm_id, db).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
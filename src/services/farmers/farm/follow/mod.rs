@@ -0,0 +1,32 @@
+//! Farm follow model impls
+
+pub mod db;
+pub mod forms;
+pub mod handlers;
+mod utils;
+
+pub use utils::notify_followers;
+
+use time::OffsetDateTime;
+
+use crate::types::ModelID;
+
+/// The model representing a row in the `farm_follows` database table.
+#[derive(Debug, Clone)]
+pub struct FarmFollow {
+    pub user_id: ModelID,
+    pub farm_id: ModelID,
+    pub notify: bool,
+    pub created_at: OffsetDateTime,
+}
+
+/// A new harvest listed by a followed farm,
+/// sent to the followers that opted in to notifications.
+#[derive(Debug, Clone)]
+pub struct NewHarvestNotice {
+    pub harvest_id: ModelID,
+    pub harvest_name: String,
+    pub farm_name: String,
+    /// Followers first name and email address
+    pub recipients: Vec<(String, String)>,
+}
@@ -0,0 +1,41 @@
+//! Farm follow helpers impls
+
+use crate::{
+    error::ServerResult, mail::Mail, server::state::DatabaseConnection, types::ModelID,
+    APP_DOMAIN_NAME,
+};
+
+use super::NewHarvestNotice;
+
+/// Emails the farm followers that opted in to notifications about the new harvest
+///
+/// # Errors
+///
+/// Return database error
+#[tracing::instrument(skip(db, outlook))]
+pub async fn notify_followers(
+    harvest_id: ModelID,
+    db: DatabaseConnection,
+    outlook: Mail,
+) -> ServerResult<()> {
+    let Some(notice) = NewHarvestNotice::find(harvest_id, db).await? else {
+        return Ok(());
+    };
+
+    let link = format!("{APP_DOMAIN_NAME}/harvests/{}", notice.harvest_id);
+    for (first_name, email_address) in &notice.recipients {
+        let email = outlook.farm_new_harvest(
+            first_name,
+            &notice.farm_name,
+            &notice.harvest_name,
+            email_address,
+            &link,
+        )?;
+        // A failed email should not stop the other followers from being notified
+        if let Err(err) = outlook.send(email).await {
+            tracing::error!("Failed to send new harvest email to a follower: {}", err);
+        }
+    }
+
+    Ok(())
+}
@@ -14,11 +14,14 @@ use crate::{
         EndpointRejection, EndpointResult,
     },
     server::state::ServerState,
-    services::farmers::location::forms::{LocationEmbeddedForm, LocationInsertData},
-    types::ModelID,
+    services::farmers::location::{
+        forms::{validate_location_area, LocationEmbeddedForm, LocationInsertData},
+        schedule::forms::validate_timezone,
+    },
+    types::{ModelID, Pagination},
 };
 
-use super::permissions::check_user_owns_farm;
+use super::permissions::check_user_can_manage_farm;
 
 /// Farm create form
 #[derive(Debug, Clone, Deserialize)]
@@ -105,6 +108,16 @@ where
         // Validate form fields
         farm.validate()?;
         farm.location.validate()?;
+        if let Some(ref timezone) = farm.location.timezone {
+            validate_timezone(timezone, state.database()).await?;
+        }
+        validate_location_area(
+            &farm.location.region_id,
+            &farm.location.country_id,
+            farm.location.coords,
+            state.database(),
+        )
+        .await?;
 
         Ok(farm)
     }
@@ -177,7 +190,7 @@ impl FarmUpdateForm {
         user: FarmerUser,
         farm_id: ModelID,
     ) -> EndpointResult<()> {
-        check_user_owns_farm(user.id(), farm_id, state.database()).await
+        check_user_can_manage_farm(user.id(), farm_id, state.database()).await
     }
 }
 
@@ -205,3 +218,80 @@ where
         Ok(farm)
     }
 }
+
+// ===== Farm Directory filter impls =====
+
+/// Default number of farms returned by the directory `20`
+const fn default_directory_limit() -> u64 {
+    20
+}
+
+/// Default directory page `1`
+const fn default_directory_page() -> u64 {
+    1
+}
+
+/// `farms/directory` query parameters.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FarmDirectoryFilter {
+    /// searches farm name
+    #[serde(default)]
+    pub q: Option<String>,
+    /// filters for farms with a location in the region name
+    #[serde(default)]
+    pub region: Option<String>,
+    /// filters for farms that grow the cultivar name
+    #[serde(default)]
+    pub cultivar: Option<String>,
+    /// filters for verified farms
+    #[serde(default)]
+    pub verified: Option<bool>,
+    /// filters for farms rated at least the minimum rating
+    #[serde(default)]
+    pub min_rating: Option<f64>,
+
+    #[serde(default = "default_directory_page")]
+    pub page: u64,
+    /// maximum number of farms should be returned
+    #[serde(default = "default_directory_limit")]
+    pub limit: u64,
+}
+
+impl FarmDirectoryFilter {
+    /// Farm name search pattern, escapes the `LIKE` wildcards
+    #[must_use]
+    pub fn search_pattern(&self) -> Option<String> {
+        self.q
+            .as_ref()
+            .map(|q| q.clean())
+            .filter(|q| !q.is_empty())
+            .map(|q| {
+                let q = q
+                    .replace('\\', "\\\\")
+                    .replace('%', "\\%")
+                    .replace('_', "\\_");
+                format!("%{q}%")
+            })
+    }
+
+    /// Cleaned region name filter
+    #[must_use]
+    pub fn region(&self) -> Option<String> {
+        self.region.as_ref().map(|region| region.to_titlecase())
+    }
+
+    /// Cleaned cultivar name filter
+    #[must_use]
+    pub fn cultivar(&self) -> Option<String> {
+        self.cultivar
+            .as_ref()
+            .map(|cultivar| cultivar.to_titlecase())
+    }
+
+    /// Returns the directory pagination, the limit is capped at 100 farms
+    #[must_use]
+    pub fn pagination(&self) -> Pagination {
+        Pagination::new(self.page, self.limit.min(100))
+    }
+}
@@ -8,17 +8,18 @@ use axum::{
 use crate::{
     auth::{AdminUser, CurrentUser, FarmerUser},
     endpoint::{EndpointRejection, EndpointResult},
-    files,
+    files::{self, CropQuery, ImageVariants},
     server::state::DatabaseConnection,
+    services::produce::harvest::ArchiveGraceDays,
     settings::FARM_LOGO_UPLOAD_DIR,
     types::ModelID,
     types::{ModelIndex, Pagination},
 };
 
 use super::{
-    forms::{FarmCreateForm, FarmUpdateForm},
-    models::{Farm, FarmList},
-    permissions::FarmOwnershipPermission,
+    forms::{FarmCreateForm, FarmDirectoryFilter, FarmUpdateForm},
+    models::{Farm, FarmCardList, FarmList},
+    permissions::{FarmManagePermission, FarmOwnershipPermission, FarmRestorePermission},
     utils::delete_farm_logo,
 };
 
@@ -34,6 +35,18 @@ pub async fn farm_list(
     Ok(Json(farms))
 }
 
+/// Handles the `GET /farms/directory` route.
+///
+/// The public farm directory, searchable by the farm name.
+#[tracing::instrument(skip(db))]
+pub async fn farm_directory(
+    Query(filter): Query<FarmDirectoryFilter>,
+    State(db): State<DatabaseConnection>,
+) -> EndpointResult<Json<FarmCardList>> {
+    let farms = Farm::directory(filter, db).await?;
+    Ok(Json(farms))
+}
+
 /// Handles the `GET /farms/:farm_id` route.
 #[tracing::instrument(skip(db))]
 pub async fn farm_detail(
@@ -80,6 +93,18 @@ pub async fn farm_delete(
     Ok(StatusCode::NO_CONTENT)
 }
 
+/// Handles the `POST /farms/:farm_id/restore` route.
+#[tracing::instrument(skip(db))]
+pub async fn farm_restore(
+    _: FarmRestorePermission,
+    farm_id: ModelID,
+    State(db): State<DatabaseConnection>,
+    State(grace_days): State<ArchiveGraceDays>,
+) -> EndpointResult<StatusCode> {
+    Farm::restore(farm_id, grace_days, db).await?;
+    Ok(StatusCode::OK)
+}
+
 /// Handles the `GET /farms/:farm_id/locations` route.
 #[tracing::instrument(skip(db))]
 pub async fn farm_location_index(
@@ -91,18 +116,25 @@ pub async fn farm_location_index(
 }
 
 /// Handles the `POST /farms/:farm_id/logo` route.
+///
+/// The logo can be cropped with the `x`, `y`, `width` and `height` query params,
+/// returns the urls of the logo and its square thumbnails.
 #[tracing::instrument(skip(db))]
 pub async fn farm_logo_upload(
-    _: FarmOwnershipPermission,
+    _: FarmManagePermission,
     farm_id: ModelID,
+    Query(crop): Query<CropQuery>,
     State(db): State<DatabaseConnection>,
     multipart: Multipart,
-) -> EndpointResult<Json<String>> {
+) -> EndpointResult<Json<ImageVariants>> {
+    let crop = crop.image_crop()?;
     let (handler, mut uploads) = files::accept_uploads(multipart, 1);
     handler.accept().await?; // Receive logo from the client
     if let Some(file) = uploads.files().await {
-        // Save a logo to the file system
-        let saved_to = file.save_image(FARM_LOGO_UPLOAD_DIR).await?;
+        // Save a logo and its thumbnails to the file system
+        let saved_to = file
+            .save_image_with_thumbnails(FARM_LOGO_UPLOAD_DIR, crop)
+            .await?;
 
         // Save image path to the database
         let (new_logo, old_logo) = Farm::insert_or_delete_logo(farm_id, Some(saved_to), db).await?;
@@ -112,7 +144,10 @@ pub async fn farm_logo_upload(
             tokio::spawn(async move { delete_farm_logo(&old_logo).await });
         }
 
-        Ok(Json(new_logo.unwrap()))
+        Ok(Json(ImageVariants::new(
+            crate::FARM_LOGO_URL,
+            &new_logo.unwrap(),
+        )))
     } else {
         Err(EndpointRejection::BadRequest(
             "Farm logo not received".into(),
@@ -123,7 +158,7 @@ pub async fn farm_logo_upload(
 /// Handles the `DELETE /farms/:farm_id/logo` route.
 #[tracing::instrument(skip(db))]
 pub async fn farm_logo_delete(
-    _: FarmOwnershipPermission,
+    _: FarmManagePermission,
     farm_id: ModelID,
     State(db): State<DatabaseConnection>,
 ) -> EndpointResult<StatusCode> {
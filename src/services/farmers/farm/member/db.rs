@@ -0,0 +1,405 @@
+//! Farm member database impl
+
+use crate::{
+    auth::TokenHash,
+    endpoint::EndpointRejection,
+    error::{ServerError, ServerResult},
+    server::state::DatabaseConnection,
+    services::farmers::farm::utils::{update_user_is_farmer, update_users_is_farmer},
+    types::ModelID,
+};
+
+use super::{
+    forms::{FarmInvitationInsertData, FarmMemberInsertData},
+    FarmInvitation, FarmMember, FarmMemberList, FarmRole,
+};
+
+impl FarmMember {
+    /// Fetches farm members from the database
+    #[tracing::instrument(name = "Fetch FarmMemberList", skip(db))]
+    pub async fn records(farm_id: ModelID, db: DatabaseConnection) -> ServerResult<FarmMemberList> {
+        match sqlx::query!(
+            r#"
+                SELECT member.id AS member_id,
+                    member.farm_id AS member_farm_id,
+                    member.role AS member_role,
+                    member.joined_at AS member_joined_at,
+                    user_.id AS user_id,
+                    user_.first_name AS user_first_name,
+                    user_.last_name AS user_last_name,
+                    user_.identity_verified AS user_identity_verified,
+                    profile.photo AS "user_photo?"
+                FROM services.farm_members member
+                LEFT JOIN accounts.users user_
+                    ON member.user_id = user_.id
+                LEFT JOIN accounts.user_profiles profile
+                    ON user_.id = profile.user_id
+
+                WHERE member.farm_id = $1
+                ORDER BY member.joined_at
+            "#,
+            farm_id.0
+        )
+        .fetch_all(&db.pool)
+        .await
+        {
+            Ok(records) => {
+                let members = records
+                    .into_iter()
+                    .map(|rec| {
+                        Self::from_row(
+                            rec.member_id.into(),
+                            rec.member_farm_id.into(),
+                            &rec.member_role,
+                            rec.member_joined_at,
+                            rec.user_id.into(),
+                            rec.user_first_name,
+                            rec.user_last_name,
+                            rec.user_photo,
+                            rec.user_identity_verified,
+                        )
+                    })
+                    .collect();
+
+                Ok(members)
+            }
+            Err(err) => {
+                tracing::error!("Database error, failed to fetch farm members: {}", err);
+                Err(err.into())
+            }
+        }
+    }
+
+    /// Fetches farm member from the database
+    #[tracing::instrument(name = "Find FarmMember", skip(db))]
+    pub async fn find(id: ModelID, db: DatabaseConnection) -> ServerResult<Option<Self>> {
+        match sqlx::query!(
+            r#"
+                SELECT member.id AS member_id,
+                    member.farm_id AS member_farm_id,
+                    member.role AS member_role,
+                    member.joined_at AS member_joined_at,
+                    user_.id AS user_id,
+                    user_.first_name AS user_first_name,
+                    user_.last_name AS user_last_name,
+                    user_.identity_verified AS user_identity_verified,
+                    profile.photo AS "user_photo?"
+                FROM services.farm_members member
+                LEFT JOIN accounts.users user_
+                    ON member.user_id = user_.id
+                LEFT JOIN accounts.user_profiles profile
+                    ON user_.id = profile.user_id
+
+                WHERE member.id = $1
+            "#,
+            id.0
+        )
+        .fetch_optional(&db.pool)
+        .await
+        {
+            Ok(rec) => Ok(rec.map(|rec| {
+                Self::from_row(
+                    rec.member_id.into(),
+                    rec.member_farm_id.into(),
+                    &rec.member_role,
+                    rec.member_joined_at,
+                    rec.user_id.into(),
+                    rec.user_first_name,
+                    rec.user_last_name,
+                    rec.user_photo,
+                    rec.user_identity_verified,
+                )
+            })),
+            Err(err) => {
+                tracing::error!("Database error, failed to fetch farm member: {}", err);
+                Err(err.into())
+            }
+        }
+    }
+
+    /// Inserts farm member into the database
+    #[tracing::instrument(name = "Insert FarmMember", skip(tx))]
+    pub async fn insert(
+        values: FarmMemberInsertData,
+        tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    ) -> ServerResult<()> {
+        match sqlx::query!(
+            r#"
+                INSERT INTO services.farm_members(
+                    id,
+                    farm_id,
+                    user_id,
+                    role,
+                    joined_at
+                )
+                VALUES($1, $2, $3, $4, $5);
+            "#,
+            values.id.0,
+            values.farm_id.0,
+            values.user_id.0,
+            values.role.as_str(),
+            values.joined_at,
+        )
+        .execute(&mut **tx)
+        .await
+        {
+            Ok(result) => {
+                tracing::trace!(
+                    "Farm member inserted, but transaction not committed: {:?}",
+                    result
+                );
+                Ok(())
+            }
+            Err(err) => {
+                // Handle database constraint error
+                handle_farm_member_database_error(&err)?;
+
+                tracing::error!("Database error, failed to insert farm member: {}", err);
+                Err(err.into())
+            }
+        }
+    }
+
+    /// Updates farm member role in the database
+    #[tracing::instrument(name = "Update FarmMember", skip(db))]
+    pub async fn update(id: ModelID, role: FarmRole, db: DatabaseConnection) -> ServerResult<()> {
+        match sqlx::query!(
+            r#"
+                UPDATE services.farm_members member
+                SET role = $1
+                WHERE member.id = $2
+                    AND member.role <> 'owner';
+            "#,
+            role.as_str(),
+            id.0
+        )
+        .execute(&db.pool)
+        .await
+        {
+            Ok(result) => {
+                tracing::debug!("Farm member updated successfully: {:?}", result);
+                Ok(())
+            }
+            Err(err) => {
+                // Handle database constraint error
+                handle_farm_member_database_error(&err)?;
+
+                tracing::error!("Database error, failed to update farm member: {}", err);
+                Err(err.into())
+            }
+        }
+    }
+
+    /// Deletes farm member from the database
+    #[tracing::instrument(name = "Delete FarmMember", skip(db))]
+    pub async fn delete(id: ModelID, db: DatabaseConnection) -> ServerResult<()> {
+        let mut tx = db.pool.begin().await?;
+        match sqlx::query!(
+            r#"
+                DELETE FROM services.farm_members member
+                WHERE member.id = $1
+                    AND member.role <> 'owner'
+
+                RETURNING member.user_id
+            "#,
+            id.0
+        )
+        .fetch_one(&mut *tx)
+        .await
+        {
+            Ok(rec) => {
+                // The user might no longer be a member of any farm
+                update_users_is_farmer(&[rec.user_id.into()], &mut tx).await?;
+
+                tx.commit().await?;
+                tracing::debug!("Farm member deleted successfully");
+                Ok(())
+            }
+            Err(err) => {
+                // Handle database constraint error
+                handle_farm_member_database_error(&err)?;
+
+                tracing::error!("Database error, failed to delete farm member: {}", err);
+                Err(err.into())
+            }
+        }
+    }
+}
+
+// ===== Farm Invitation impls =====
+
+impl FarmInvitation {
+    /// Inserts farm member invitation into the database
+    ///
+    /// Replaces the previous invitation sent to the same email
+    #[tracing::instrument(name = "Insert FarmInvitation", skip(db, values))]
+    pub async fn insert(
+        values: FarmInvitationInsertData,
+        db: DatabaseConnection,
+    ) -> ServerResult<()> {
+        match sqlx::query!(
+            r#"
+                INSERT INTO services.farm_member_invitations(
+                    id,
+                    farm_id,
+                    invited_by,
+                    email,
+                    role,
+                    token,
+                    created_at
+                )
+                VALUES($1, $2, $3, $4, $5, $6, $7)
+
+                ON CONFLICT ON CONSTRAINT farm_member_invitations_farm_id_email_key
+                DO UPDATE SET invited_by = EXCLUDED.invited_by,
+                    role = EXCLUDED.role,
+                    token = EXCLUDED.token,
+                    created_at = EXCLUDED.created_at;
+            "#,
+            values.id.0,
+            values.farm_id.0,
+            values.invited_by.0,
+            values.email,
+            values.role.as_str(),
+            &values.token[..],
+            values.created_at,
+        )
+        .execute(&db.pool)
+        .await
+        {
+            Ok(result) => {
+                tracing::debug!("Farm invitation inserted successfully: {:?}", result);
+                Ok(())
+            }
+            Err(err) => {
+                // Handle database constraint error
+                handle_farm_member_database_error(&err)?;
+
+                tracing::error!("Database error, failed to insert farm invitation: {}", err);
+                Err(err.into())
+            }
+        }
+    }
+
+    /// Finds farm invitation associated with the token from the database
+    #[tracing::instrument(skip(db, token))]
+    pub async fn find_by_token(
+        token: TokenHash,
+        db: DatabaseConnection,
+    ) -> ServerResult<Option<Self>> {
+        match sqlx::query!(
+            r#"
+                SELECT invitation.id,
+                    invitation.farm_id,
+                    invitation.email,
+                    invitation.role,
+                    invitation.created_at
+                FROM services.farm_member_invitations invitation
+                WHERE invitation.token = $1
+            "#,
+            &token[..]
+        )
+        .fetch_optional(&db.pool)
+        .await
+        {
+            Ok(rec) => Ok(rec.map(|rec| {
+                Self::from_row(
+                    rec.id.into(),
+                    rec.farm_id.into(),
+                    rec.email,
+                    &rec.role,
+                    rec.created_at,
+                )
+            })),
+            Err(err) => {
+                tracing::error!("Database error, failed to find farm invitation: {}", err);
+                Err(err.into())
+            }
+        }
+    }
+
+    /// Adds the user to the farm members and deletes the invitation
+    #[tracing::instrument(name = "Accept FarmInvitation", skip(db))]
+    pub async fn accept(self, user_id: ModelID, db: DatabaseConnection) -> ServerResult<()> {
+        let mut tx = db.pool.begin().await?;
+
+        let member = FarmMemberInsertData::new(self.farm_id, user_id, self.role);
+        FarmMember::insert(member, &mut tx).await?;
+        delete_invitation(self.id, &mut tx).await?;
+        update_user_is_farmer(true, user_id, &mut tx).await?;
+
+        tx.commit().await?;
+        tracing::debug!("Farm invitation accepted successfully");
+        Ok(())
+    }
+
+    /// Deletes farm invitation from the database
+    #[tracing::instrument(name = "Delete FarmInvitation", skip(db))]
+    pub async fn delete(id: ModelID, db: DatabaseConnection) -> ServerResult<()> {
+        let mut tx = db.pool.begin().await?;
+        delete_invitation(id, &mut tx).await?;
+        tx.commit().await?;
+        Ok(())
+    }
+}
+
+/// Deletes farm invitation from the database
+async fn delete_invitation(
+    id: ModelID,
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+) -> ServerResult<()> {
+    match sqlx::query!(
+        r#"
+            DELETE FROM services.farm_member_invitations invitation
+            WHERE invitation.id = $1
+        "#,
+        id.0
+    )
+    .execute(&mut **tx)
+    .await
+    {
+        Ok(result) => {
+            tracing::trace!(
+                "Farm invitation deleted, but transaction not committed: {:?}",
+                result
+            );
+            Ok(())
+        }
+        Err(err) => {
+            tracing::error!("Database error, failed to delete farm invitation: {}", err);
+            Err(err.into())
+        }
+    }
+}
+
+/// Handle farm members database constraints errors
+#[allow(clippy::cognitive_complexity)]
+pub fn handle_farm_member_database_error(err: &sqlx::Error) -> ServerResult<()> {
+    if let sqlx::Error::Database(db_err) = err {
+        // Handle db unique constraints
+        if db_err.is_unique_violation() {
+            tracing::error!("Database error, user already a farm member. {:?}", err);
+            return Err(ServerError::rejection(EndpointRejection::Conflict(
+                "You are already a member of this farm.".into(),
+            )));
+        }
+
+        // Handle db foreign key constraints
+        if db_err.is_foreign_key_violation() {
+            tracing::error!("Database error, farm or user not found. {:?}", err);
+            return Err(ServerError::rejection(EndpointRejection::BadRequest(
+                "Farm or user not found.".into(),
+            )));
+        }
+    }
+
+    // For updates only
+    if matches!(err, &sqlx::Error::RowNotFound) {
+        tracing::error!("Database error, farm member not found. {:?}", err);
+        return Err(ServerError::rejection(EndpointRejection::NotFound(
+            "Farm member not found.".into(),
+        )));
+    }
+
+    Ok(())
+}
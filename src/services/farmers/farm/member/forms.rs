@@ -0,0 +1,207 @@
+//! Farm member forms impls
+
+use axum::{
+    async_trait,
+    extract::{rejection::JsonRejection, FromRequest, FromRequestParts, Json, Request},
+};
+use serde::Deserialize;
+use time::OffsetDateTime;
+
+use crate::{
+    auth::{FarmerUser, Token, TokenHash},
+    endpoint::{
+        validators::{TransformString, ValidateString},
+        EndpointRejection, EndpointResult,
+    },
+    server::state::ServerState,
+    services::farmers::farm::permissions::check_user_owns_farm,
+    types::ModelID,
+};
+
+use super::{FarmMember, FarmRole};
+
+/// Farm member insert cleaned data
+#[derive(Debug, Clone)]
+pub struct FarmMemberInsertData {
+    pub id: ModelID,
+    pub farm_id: ModelID,
+    pub user_id: ModelID,
+    pub role: FarmRole,
+    pub joined_at: OffsetDateTime,
+}
+
+impl FarmMemberInsertData {
+    /// Creates a new farm member insert data
+    #[must_use]
+    pub fn new(farm_id: ModelID, user_id: ModelID, role: FarmRole) -> Self {
+        Self {
+            id: ModelID::new(),
+            farm_id,
+            user_id,
+            role,
+            joined_at: OffsetDateTime::now_utc(),
+        }
+    }
+}
+
+// ===== Farm Member Invite form impls =====
+
+/// Farm member invite form
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FarmMemberInviteForm {
+    pub email: String,
+    pub role: FarmRole,
+}
+
+/// Farm member invitation cleaned data
+#[derive(Debug, Clone)]
+pub struct FarmInvitationInsertData {
+    pub id: ModelID,
+    pub farm_id: ModelID,
+    pub invited_by: ModelID,
+    pub email: String,
+    pub role: FarmRole,
+    pub token: TokenHash,
+    pub created_at: OffsetDateTime,
+}
+
+impl FarmMemberInviteForm {
+    /// Validates invite form inputs
+    fn validate(&mut self) -> EndpointResult<()> {
+        // Clean the data
+        self.clean_data();
+
+        self.email.validate_email()?;
+
+        validate_member_role(self.role)
+    }
+
+    /// Clean form data
+    fn clean_data(&mut self) {
+        self.email = self.email.clean().to_ascii_lowercase();
+    }
+
+    /// Convert `Self` into `FarmInvitationInsertData`
+    ///
+    /// Return (`FarmInvitationInsertData`, token plaintext)
+    #[must_use]
+    pub fn data(self, farm_id: ModelID, user_id: ModelID) -> (FarmInvitationInsertData, String) {
+        let (plaintext, hash) = Token::default().into_parts();
+        let values = FarmInvitationInsertData {
+            id: ModelID::new(),
+            farm_id,
+            invited_by: user_id,
+            email: self.email,
+            role: self.role,
+            token: hash,
+            created_at: OffsetDateTime::now_utc(),
+        };
+        (values, plaintext)
+    }
+
+    ///  Validate a user has the permissions to invite members on this farm
+    async fn authorize_request(
+        state: &ServerState,
+        user: FarmerUser,
+        farm_id: ModelID,
+    ) -> EndpointResult<()> {
+        check_user_owns_farm(user.id(), farm_id, state.database()).await
+    }
+}
+
+#[async_trait]
+impl FromRequest<ServerState> for FarmMemberInviteForm
+where
+    Json<Self>: FromRequest<ServerState, Rejection = JsonRejection>,
+{
+    type Rejection = EndpointRejection;
+
+    async fn from_request(req: Request, state: &ServerState) -> Result<Self, Self::Rejection> {
+        // Extract data
+        let (mut parts, body) = req.into_parts();
+        let user = { FarmerUser::from_parts(&mut parts, state).await? };
+        let farm_id = { ModelID::from_request_parts(&mut parts, state).await? };
+        let Json(mut invite) =
+            Json::<Self>::from_request(Request::from_parts(parts, body), state).await?;
+
+        // Validate form fields
+        invite.validate()?;
+
+        // Authorize request
+        Self::authorize_request(state, user, farm_id).await?;
+
+        Ok(invite)
+    }
+}
+
+// ===== Farm Member Update form impls =====
+
+/// Farm member role update form
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FarmMemberUpdateForm {
+    pub role: FarmRole,
+}
+
+impl FarmMemberUpdateForm {
+    /// Validates member update form inputs
+    fn validate(&self) -> EndpointResult<()> {
+        validate_member_role(self.role)
+    }
+
+    ///  Validate a user has the permissions to update the member role
+    async fn authorize_request(
+        state: &ServerState,
+        user: FarmerUser,
+        member_id: ModelID,
+    ) -> EndpointResult<()> {
+        let Some(member) = FarmMember::find(member_id, state.database()).await? else {
+            return Err(EndpointRejection::NotFound("Farm member not found.".into()));
+        };
+
+        if member.role == FarmRole::Owner {
+            return Err(EndpointRejection::Forbidden(
+                "The farm owner role cannot be changed.".into(),
+            ));
+        }
+
+        check_user_owns_farm(user.id(), member.farm_id, state.database()).await
+    }
+}
+
+#[async_trait]
+impl FromRequest<ServerState> for FarmMemberUpdateForm
+where
+    Json<Self>: FromRequest<ServerState, Rejection = JsonRejection>,
+{
+    type Rejection = EndpointRejection;
+
+    async fn from_request(req: Request, state: &ServerState) -> Result<Self, Self::Rejection> {
+        // Extract data
+        let (mut parts, body) = req.into_parts();
+        let user = { FarmerUser::from_parts(&mut parts, state).await? };
+        let member_id = { ModelID::from_request_parts(&mut parts, state).await? };
+        let Json(member) =
+            Json::<Self>::from_request(Request::from_parts(parts, body), state).await?;
+
+        // Validate form fields
+        member.validate()?;
+
+        // Authorize request
+        Self::authorize_request(state, user, member_id).await?;
+
+        Ok(member)
+    }
+}
+
+/// Validates the role can be given to an invited member,
+/// a farm has only one owner.
+fn validate_member_role(role: FarmRole) -> EndpointResult<()> {
+    if role == FarmRole::Owner {
+        return Err(EndpointRejection::BadRequest(
+            "Member role must be either manager or listing editor.".into(),
+        ));
+    }
+    Ok(())
+}
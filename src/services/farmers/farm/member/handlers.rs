@@ -0,0 +1,144 @@
+//! Farm member http handlers impls
+
+use axum::{
+    extract::{Json, Query, State},
+    http::StatusCode,
+};
+
+use crate::{
+    accounts::emails::EmailModel,
+    auth::{hash_token, CurrentUser, FarmerUser, TokenConfirm},
+    endpoint::{EndpointRejection, EndpointResult},
+    mail::Mail,
+    server::state::DatabaseConnection,
+    services::farmers::farm::{
+        permissions::{FarmManagePermission, FarmMemberDeletePermission},
+        utils::farm_name,
+    },
+    types::ModelID,
+    SERVER_DOMAIN_NAME,
+};
+
+use super::{
+    forms::{FarmMemberInviteForm, FarmMemberUpdateForm},
+    FarmInvitation, FarmMember, FarmMemberList,
+};
+
+/// Handles the `GET /farms/:farm_id/members` route.
+#[tracing::instrument(skip(db))]
+pub async fn farm_member_list(
+    _: FarmManagePermission,
+    farm_id: ModelID,
+    State(db): State<DatabaseConnection>,
+) -> EndpointResult<Json<FarmMemberList>> {
+    let members = FarmMember::records(farm_id, db).await?;
+    Ok(Json(members))
+}
+
+/// Handles the `POST /farms/:farm_id/members` route.
+///
+/// Sends an invitation to join the farm to the email
+#[tracing::instrument(skip(user, db, outlook, form))]
+pub async fn farm_member_invite(
+    user: FarmerUser,
+    farm_id: ModelID,
+    State(db): State<DatabaseConnection>,
+    State(outlook): State<Mail>,
+    form: FarmMemberInviteForm,
+) -> EndpointResult<(StatusCode, &'static str)> {
+    let (values, token) = form.data(farm_id, user.id());
+    let email_address = values.email.clone();
+    let role = values.role.as_str().replace('_', " ");
+    FarmInvitation::insert(values, db.clone()).await?;
+
+    // Send invitation email
+    let farm_name = farm_name(farm_id, db.clone()).await?;
+    let (inviter_name, _) = EmailModel::find_user(user.id(), db).await?;
+    let domain = SERVER_DOMAIN_NAME.get().unwrap();
+    let link = format!("{domain}/farms/invitations/accept?token={token}");
+    let email = outlook.farm_invitation(&inviter_name, &farm_name, &role, &email_address, &link)?;
+    outlook.send(email).await?;
+
+    Ok((StatusCode::CREATED, "Invitation sent successfully"))
+}
+
+/// Handles the `PUT /farms/members/:member_id` route.
+#[tracing::instrument(skip(db, form))]
+pub async fn farm_member_update(
+    _: FarmerUser,
+    member_id: ModelID,
+    State(db): State<DatabaseConnection>,
+    form: FarmMemberUpdateForm,
+) -> EndpointResult<StatusCode> {
+    FarmMember::update(member_id, form.role, db).await?;
+    Ok(StatusCode::OK)
+}
+
+/// Handles the `DELETE /farms/members/:member_id` route.
+#[tracing::instrument(skip(db))]
+pub async fn farm_member_delete(
+    _: FarmMemberDeletePermission,
+    member_id: ModelID,
+    State(db): State<DatabaseConnection>,
+) -> EndpointResult<StatusCode> {
+    FarmMember::delete(member_id, db).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Handles the `POST /farms/invitations/accept?token=...` route.
+#[tracing::instrument(skip(user, db))]
+pub async fn farm_invitation_accept(
+    user: CurrentUser,
+    confirm_token: Option<Query<TokenConfirm>>,
+    State(db): State<DatabaseConnection>,
+) -> EndpointResult<&'static str> {
+    let invitation = find_invitation(confirm_token, db.clone()).await?;
+
+    // Only the invited user can accept the invitation
+    let (_, email_address) = EmailModel::find_user(user.id, db.clone()).await?;
+    if !invitation.email.eq_ignore_ascii_case(&email_address) {
+        return Err(EndpointRejection::BadRequest(
+            "This invitation was sent to a different email address.".into(),
+        ));
+    }
+
+    invitation.accept(user.id, db).await?;
+    Ok("You have joined the farm successfully")
+}
+
+/// Handles the `POST /farms/invitations/decline?token=...` route.
+#[tracing::instrument(skip(db))]
+pub async fn farm_invitation_decline(
+    confirm_token: Option<Query<TokenConfirm>>,
+    State(db): State<DatabaseConnection>,
+) -> EndpointResult<&'static str> {
+    let invitation = find_invitation(confirm_token, db.clone()).await?;
+    FarmInvitation::delete(invitation.id, db).await?;
+    Ok("Farm invitation declined")
+}
+
+/// Finds the invitation matching the token
+async fn find_invitation(
+    confirm_token: Option<Query<TokenConfirm>>,
+    db: DatabaseConnection,
+) -> EndpointResult<FarmInvitation> {
+    static ERR_MSG: &str = "Your farm invitation link is no longer valid.";
+
+    let Some(Query(confirm_token)) = confirm_token else {
+        return Err(EndpointRejection::BadRequest(
+            "Invitation token required!".into(),
+        ));
+    };
+
+    let token_hash = hash_token(confirm_token.token.as_bytes());
+    let Some(invitation) = FarmInvitation::find_by_token(token_hash, db.clone()).await? else {
+        return Err(EndpointRejection::BadRequest(ERR_MSG.into()));
+    };
+
+    if invitation.is_expired() {
+        FarmInvitation::delete(invitation.id, db).await?;
+        return Err(EndpointRejection::BadRequest(ERR_MSG.into()));
+    }
+
+    Ok(invitation)
+}
@@ -0,0 +1,153 @@
+//! Farm member model impls
+
+pub mod db;
+pub mod forms;
+pub mod handlers;
+
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+use time::OffsetDateTime;
+
+use crate::{accounts::user::models::UserIndex, types::ModelID};
+
+/// A `Vec` of farm members
+pub type FarmMemberList = Vec<FarmMember>;
+
+/// The model representing a row in the `farm_members` database table.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FarmMember {
+    pub id: ModelID,
+    pub farm_id: ModelID,
+    pub user: UserIndex,
+    pub role: FarmRole,
+    pub joined_at: OffsetDateTime,
+}
+
+impl FarmMember {
+    /// Creates a new `FarmMember` from the database row
+    #[allow(clippy::too_many_arguments)]
+    #[must_use]
+    pub fn from_row(
+        id: ModelID,
+        farm_id: ModelID,
+        role: &str,
+        joined_at: OffsetDateTime,
+        user_id: ModelID,
+        user_first_name: String,
+        user_last_name: Option<String>,
+        user_photo: Option<String>,
+        user_identity_verified: bool,
+    ) -> Self {
+        Self {
+            id,
+            farm_id,
+            user: UserIndex::from_row(
+                user_id,
+                user_first_name,
+                user_last_name,
+                user_photo,
+                user_identity_verified,
+            ),
+            role: FarmRole::from_row(role),
+            joined_at,
+        }
+    }
+}
+
+/// The role a member has on the farm
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum FarmRole {
+    /// Can do everything a manager can, manage members and delete the farm.
+    Owner,
+    /// Can update the farm details, its locations and harvests.
+    Manager,
+    /// Can only create and update harvests listings.
+    ListingEditor,
+}
+
+impl FarmRole {
+    /// Roles allowed to manage farm members and delete the farm
+    pub const OWNER: &'static [Self] = &[Self::Owner];
+    /// Roles allowed to update the farm and its locations
+    pub const MANAGERS: &'static [Self] = &[Self::Owner, Self::Manager];
+    /// Roles allowed to create and update harvests
+    pub const EDITORS: &'static [Self] = &[Self::Owner, Self::Manager, Self::ListingEditor];
+
+    /// Returns the role name as it stored in the database
+    #[must_use]
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Self::Owner => "owner",
+            Self::Manager => "manager",
+            Self::ListingEditor => "listing_editor",
+        }
+    }
+
+    /// Creates a new `FarmRole` from the database column,
+    /// unknown roles fall back to the least privileged role.
+    #[must_use]
+    pub fn from_row(role: &str) -> Self {
+        match role {
+            "owner" => Self::Owner,
+            "manager" => Self::Manager,
+            "listing_editor" => Self::ListingEditor,
+            unknown => {
+                tracing::error!("Unknown farm role: {unknown:?}");
+                Self::ListingEditor
+            }
+        }
+    }
+
+    /// Converts roles into database column values
+    #[must_use]
+    pub fn to_rows(roles: &[Self]) -> Vec<String> {
+        roles.iter().map(ToString::to_string).collect()
+    }
+}
+
+impl fmt::Display for FarmRole {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// A pending invitation to join a farm
+#[derive(Debug, Clone)]
+pub struct FarmInvitation {
+    pub id: ModelID,
+    pub farm_id: ModelID,
+    pub email: String,
+    pub role: FarmRole,
+    pub created_at: OffsetDateTime,
+}
+
+impl FarmInvitation {
+    /// Creates a new `FarmInvitation` from the database row
+    #[must_use]
+    pub fn from_row(
+        id: ModelID,
+        farm_id: ModelID,
+        email: String,
+        role: &str,
+        created_at: OffsetDateTime,
+    ) -> Self {
+        Self {
+            id,
+            farm_id,
+            email,
+            role: FarmRole::from_row(role),
+            created_at,
+        }
+    }
+
+    /// Checks if the invitation has expired
+    #[must_use]
+    pub fn is_expired(&self) -> bool {
+        let threshold =
+            OffsetDateTime::now_utc() - time::Duration::days(crate::FARM_INVITATION_EXPIRY_DAYS);
+        self.created_at < threshold
+    }
+}
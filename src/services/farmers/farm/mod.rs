@@ -2,8 +2,13 @@
 
 pub mod admin;
 pub mod db;
+pub mod delivery;
+pub mod follow;
 pub mod forms;
 pub mod handlers;
+pub mod member;
 pub mod models;
 pub mod permissions;
+pub mod slug;
+pub mod transfer;
 mod utils;
@@ -2,17 +2,23 @@
 #![allow(dead_code, clippy::missing_const_for_fn)]
 
 use serde::Serialize;
-use time::Date;
+use time::{Date, OffsetDateTime};
 
 use crate::{
     core::accounts::user::models::UserIndex,
-    services::farmers::location::models::{Location, LocationList},
+    services::farmers::{
+        location::models::{Location, LocationList},
+        rating::models::RatingSummary,
+    },
     types::ModelID,
 };
 
 /// A `Vec` of farms
 pub type FarmList = Vec<FarmIndex>;
 
+/// A `Vec` of farm directory cards
+pub type FarmCardList = Vec<FarmCard>;
+
 /// The model representing a row in the `farms` database table.
 ///
 /// Returned by `farm_detail` handler.
@@ -21,11 +27,14 @@ pub type FarmList = Vec<FarmIndex>;
 pub struct Farm {
     pub id: ModelID,
     pub name: String,
+    pub slug: Option<String>,
     pub owner: UserIndex,
     pub logo: Option<String>,
     pub contact_email: Option<String>,
     pub contact_number: Option<String>,
     pub registered_on: Date,
+    pub stats: FarmStats,
+    pub ratings: RatingSummary,
     pub locations: Vec<Location>,
 }
 
@@ -36,25 +45,38 @@ impl Farm {
     pub fn from_row(
         id: ModelID,
         name: String,
+        slug: Option<String>,
         logo: Option<String>,
         contact_email: Option<String>,
         contact_number: Option<String>,
         locations: Vec<Location>,
         registered_on: Date,
+        stats: FarmStats,
+        ratings: RatingSummary,
         owner_id: ModelID,
         owner_first_name: String,
         owner_last_name: Option<String>,
         owner_photo: Option<String>,
+        owner_identity_verified: bool,
     ) -> Self {
         Self {
             id,
             name,
+            slug,
             logo,
             contact_email,
             contact_number,
-            owner: UserIndex::from_row(owner_id, owner_first_name, owner_last_name, owner_photo),
+            owner: UserIndex::from_row(
+                owner_id,
+                owner_first_name,
+                owner_last_name,
+                owner_photo,
+                owner_identity_verified,
+            ),
             locations,
             registered_on,
+            stats,
+            ratings,
         }
     }
 }
@@ -67,6 +89,8 @@ pub struct FarmIndex {
     pub logo: Option<String>,
     pub name: String,
     pub owner: UserIndex,
+    pub stats: FarmStats,
+    pub ratings: RatingSummary,
     pub locations: LocationList,
 }
 
@@ -79,17 +103,106 @@ impl FarmIndex {
         name: String,
         logo: Option<String>,
         locations: LocationList,
+        stats: FarmStats,
+        ratings: RatingSummary,
         owner_id: ModelID,
         owner_first_name: String,
         owner_last_name: Option<String>,
         owner_photo: Option<String>,
+        owner_identity_verified: bool,
     ) -> Self {
         Self {
             id,
             name,
             logo,
-            owner: UserIndex::from_row(owner_id, owner_first_name, owner_last_name, owner_photo),
+            owner: UserIndex::from_row(
+                owner_id,
+                owner_first_name,
+                owner_last_name,
+                owner_photo,
+                owner_identity_verified,
+            ),
+            stats,
+            ratings,
             locations,
         }
     }
 }
+
+/// A lightweight farm returned by `farm_directory` handler.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FarmCard {
+    pub id: ModelID,
+    pub name: String,
+    pub logo: Option<String>,
+    pub verified: bool,
+    pub location_count: i64,
+    pub rating_average: Option<f64>,
+    pub rating_count: i64,
+}
+
+impl FarmCard {
+    /// Creates a new `FarmCard` from the database row
+    #[must_use]
+    pub fn from_row(
+        id: ModelID,
+        name: String,
+        logo: Option<String>,
+        verified: bool,
+        location_count: i64,
+        rating_average: Option<f64>,
+        rating_count: Option<i64>,
+    ) -> Self {
+        Self {
+            id,
+            name,
+            logo,
+            verified,
+            location_count,
+            rating_average: rating_average.map(|average| (average * 10.0).round() / 10.0),
+            rating_count: rating_count.unwrap_or_default(),
+        }
+    }
+}
+
+/// Farm aggregate stats, the harvests and followers aggregates
+/// are read from the `farm_stats` database table.
+///
+/// The ratings aggregates are in the farm [`RatingSummary`].
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FarmStats {
+    pub harvest_count: i64,
+    pub follower_count: i64,
+    pub years_active: Option<i32>,
+    pub verified: bool,
+}
+
+impl FarmStats {
+    /// Creates a new `FarmStats` from the database row
+    #[must_use]
+    pub fn from_row(
+        harvest_count: Option<i64>,
+        follower_count: Option<i64>,
+        founded_at: Option<Date>,
+        verified: bool,
+    ) -> Self {
+        Self {
+            harvest_count: harvest_count.unwrap_or_default(),
+            follower_count: follower_count.unwrap_or_default(),
+            years_active: founded_at.map(years_since),
+            verified,
+        }
+    }
+}
+
+/// Returns the number of full years passed since the date
+fn years_since(date: Date) -> i32 {
+    let today = OffsetDateTime::now_utc().date();
+    let mut years = today.year() - date.year();
+    if (u8::from(today.month()), today.day()) < (u8::from(date.month()), date.day()) {
+        years -= 1;
+    }
+    years.max(0)
+}
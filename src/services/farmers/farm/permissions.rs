@@ -3,12 +3,17 @@
 use axum::{async_trait, extract::FromRequestParts, http::request::Parts};
 
 use crate::{
-    auth::FarmerUser,
+    auth::{CurrentUser, FarmerUser},
     endpoint::{EndpointRejection, EndpointResult},
     server::state::{DatabaseConnection, ServerState},
     types::ModelID,
 };
 
+use super::{
+    delivery::DeliveryOption,
+    member::{FarmMember, FarmRole},
+};
+
 /// Checks if user owns the farm
 #[derive(Debug, Clone)]
 pub struct FarmOwnershipPermission;
@@ -30,30 +35,160 @@ impl FromRequestParts<ServerState> for FarmOwnershipPermission {
     }
 }
 
+/// Checks if user can restore the deleted farm
+///
+/// The farm owner can restore their farm, staff can restore any farm.
+#[derive(Debug, Clone)]
+pub struct FarmRestorePermission;
+
+#[async_trait]
+impl FromRequestParts<ServerState> for FarmRestorePermission {
+    type Rejection = EndpointRejection;
+
+    async fn from_request_parts(
+        parts: &mut Parts,
+        state: &ServerState,
+    ) -> Result<Self, Self::Rejection> {
+        // The owner is no longer a farmer if the farm was their only farm
+        let user = CurrentUser::from_request_parts(parts, state).await?;
+        let farm_id = ModelID::from_request_parts(parts, state).await?;
+
+        if !user.is_staff {
+            check_user_owns_farm(user.id, farm_id, state.database()).await?;
+        }
+
+        Ok(Self)
+    }
+}
+
+/// Checks if user is the farm owner or manager
+#[derive(Debug, Clone)]
+pub struct FarmManagePermission;
+
+#[async_trait]
+impl FromRequestParts<ServerState> for FarmManagePermission {
+    type Rejection = EndpointRejection;
+
+    async fn from_request_parts(
+        parts: &mut Parts,
+        state: &ServerState,
+    ) -> Result<Self, Self::Rejection> {
+        let user = FarmerUser::from_parts(parts, state).await?;
+        let farm_id = ModelID::from_request_parts(parts, state).await?;
+
+        check_user_can_manage_farm(user.id(), farm_id, state.database()).await?;
+
+        Ok(Self)
+    }
+}
+
+/// Checks if user can remove the member from the farm
+///
+/// The farm owner can remove any member except themselves,
+/// other members can only remove themselves.
+#[derive(Debug, Clone)]
+pub struct FarmMemberDeletePermission;
+
+#[async_trait]
+impl FromRequestParts<ServerState> for FarmMemberDeletePermission {
+    type Rejection = EndpointRejection;
+
+    async fn from_request_parts(
+        parts: &mut Parts,
+        state: &ServerState,
+    ) -> Result<Self, Self::Rejection> {
+        let user = FarmerUser::from_parts(parts, state).await?;
+        let member_id = ModelID::from_request_parts(parts, state).await?;
+
+        let Some(member) = FarmMember::find(member_id, state.database()).await? else {
+            return Err(EndpointRejection::NotFound("Farm member not found.".into()));
+        };
+
+        if member.role == FarmRole::Owner {
+            return Err(EndpointRejection::Forbidden(
+                "The farm owner cannot be removed from the farm.".into(),
+            ));
+        }
+
+        if member.user.id != user.id() {
+            check_user_owns_farm(user.id(), member.farm_id, state.database()).await?;
+        }
+
+        Ok(Self)
+    }
+}
+
+/// Checks if user is the owner or manager of the farm
+/// the delivery option belongs to
+#[derive(Debug, Clone)]
+pub struct DeliveryOptionPermission;
+
+#[async_trait]
+impl FromRequestParts<ServerState> for DeliveryOptionPermission {
+    type Rejection = EndpointRejection;
+
+    async fn from_request_parts(
+        parts: &mut Parts,
+        state: &ServerState,
+    ) -> Result<Self, Self::Rejection> {
+        let user = FarmerUser::from_parts(parts, state).await?;
+        let option_id = ModelID::from_request_parts(parts, state).await?;
+
+        let Some(farm_id) = DeliveryOption::farm_id(option_id, state.database()).await? else {
+            return Err(EndpointRejection::NotFound(
+                "Delivery option not found.".into(),
+            ));
+        };
+
+        check_user_can_manage_farm(user.id(), farm_id, state.database()).await?;
+
+        Ok(Self)
+    }
+}
+
 /// Validate user owns the farm
 pub async fn check_user_owns_farm(
     user_id: ModelID,
     farm_id: ModelID,
     db: DatabaseConnection,
+) -> EndpointResult<()> {
+    check_user_farm_role(user_id, farm_id, FarmRole::OWNER, db).await
+}
+
+/// Validate user is the farm owner or manager
+pub async fn check_user_can_manage_farm(
+    user_id: ModelID,
+    farm_id: ModelID,
+    db: DatabaseConnection,
+) -> EndpointResult<()> {
+    check_user_farm_role(user_id, farm_id, FarmRole::MANAGERS, db).await
+}
+
+/// Validate user is a farm member with one of the `roles`
+pub async fn check_user_farm_role(
+    user_id: ModelID,
+    farm_id: ModelID,
+    roles: &[FarmRole],
+    db: DatabaseConnection,
 ) -> EndpointResult<()> {
     match sqlx::query!(
         r#"
-            SELECT first_name
-            FROM accounts.users user_
-            LEFT JOIN services.farms farm
-                ON user_.id = farm.owner_id
+            SELECT member.role
+            FROM services.farm_members member
             WHERE (
-                user_.id = $1
-                AND farm.id = $2
+                member.user_id = $1
+                AND member.farm_id = $2
+                AND member.role = ANY($3)
             )
             "#,
         user_id.0,
-        farm_id.0
+        farm_id.0,
+        &FarmRole::to_rows(roles)[..]
     )
     .fetch_one(&db.pool)
     .await
     {
-        Ok(_user) => Ok(()),
+        Ok(_member) => Ok(()),
         Err(err) => {
             if matches!(err, sqlx::Error::RowNotFound) {
                 Err(EndpointRejection::forbidden())
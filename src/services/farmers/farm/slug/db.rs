@@ -0,0 +1,163 @@
+//! Farm slug database impls
+
+use time::OffsetDateTime;
+
+use crate::{
+    endpoint::EndpointRejection,
+    error::{ServerError, ServerResult},
+    server::state::DatabaseConnection,
+    types::ModelID,
+};
+
+use super::{FarmSlug, SlugLookup};
+
+impl FarmSlug {
+    /// Fetches the farm with the current or a previous slug from the database,
+    /// slugs are matched case-insensitively.
+    #[tracing::instrument(skip(db))]
+    pub async fn find(slug: &str, db: DatabaseConnection) -> ServerResult<Option<SlugLookup>> {
+        match sqlx::query!(
+            r#"
+                SELECT farm.id AS "id!",
+                    farm.slug AS "slug!",
+                    lower(farm.slug) = lower($1) AS "is_current!"
+                FROM services.active_farms farm
+                WHERE lower(farm.slug) = lower($1)
+                    OR farm.id = (
+                        SELECT history.farm_id
+                        FROM services.farm_slug_history history
+                        WHERE history.slug = lower($1)
+                    )
+                ORDER BY 3 DESC
+                LIMIT 1
+            "#,
+            slug
+        )
+        .fetch_optional(&db.pool)
+        .await
+        {
+            Ok(rec) => Ok(rec.map(|rec| {
+                if rec.is_current {
+                    SlugLookup::Current(rec.id.into())
+                } else {
+                    SlugLookup::Renamed(rec.slug)
+                }
+            })),
+            Err(err) => {
+                tracing::error!("Database error, failed to fetch farm slug: {}", err);
+                Err(err.into())
+            }
+        }
+    }
+
+    /// Updates the farm slug in the database,
+    /// the previous slug is kept to redirect to the new one.
+    #[tracing::instrument(skip(db))]
+    pub async fn update(
+        farm_id: ModelID,
+        slug: String,
+        db: DatabaseConnection,
+    ) -> ServerResult<()> {
+        let mut tx = db.pool.begin().await?;
+
+        let previous = match sqlx::query!(
+            r#"
+                SELECT farm.slug
+                FROM services.farms farm
+                WHERE farm.id = $1
+                FOR UPDATE
+            "#,
+            farm_id.0
+        )
+        .fetch_one(&mut *tx)
+        .await
+        {
+            Ok(rec) => rec.slug,
+            Err(err) => {
+                tracing::error!("Database error, failed to fetch farm slug: {}", err);
+                return Err(err.into());
+            }
+        };
+
+        if let Err(err) = sqlx::query!(
+            r#"
+                UPDATE services.farms farm
+                SET slug = $1
+                WHERE farm.id = $2
+            "#,
+            slug,
+            farm_id.0
+        )
+        .execute(&mut *tx)
+        .await
+        {
+            // Handle database constraint error
+            handle_slug_database_error(&err)?;
+
+            tracing::error!("Database error, failed to update farm slug: {}", err);
+            return Err(err.into());
+        }
+
+        // The slug no longer redirects to its previous farm
+        if let Err(err) = sqlx::query!(
+            r#"
+                DELETE FROM services.farm_slug_history history
+                WHERE history.slug = lower($1)
+            "#,
+            slug
+        )
+        .execute(&mut *tx)
+        .await
+        {
+            tracing::error!("Database error, failed to release farm slug: {}", err);
+            return Err(err.into());
+        }
+
+        if let Some(previous) = previous.filter(|previous| !previous.eq_ignore_ascii_case(&slug)) {
+            if let Err(err) = sqlx::query!(
+                r#"
+                    INSERT INTO services.farm_slug_history(
+                        slug,
+                        farm_id,
+                        changed_at
+                    )
+                    VALUES(lower($1), $2, $3)
+                    ON CONFLICT (slug) DO UPDATE
+                        SET farm_id = EXCLUDED.farm_id,
+                            changed_at = EXCLUDED.changed_at
+                "#,
+                previous,
+                farm_id.0,
+                OffsetDateTime::now_utc()
+            )
+            .execute(&mut *tx)
+            .await
+            {
+                tracing::error!(
+                    "Database error, failed to insert farm slug history: {}",
+                    err
+                );
+                return Err(err.into());
+            }
+        }
+
+        tx.commit().await?;
+        tracing::debug!("Farm slug updated successfully");
+        Ok(())
+    }
+}
+
+/// Handle farm slugs database constraints errors
+pub fn handle_slug_database_error(err: &sqlx::Error) -> ServerResult<()> {
+    if let sqlx::Error::Database(db_err) = err {
+        // Handle db unique constraints
+        if db_err.is_unique_violation() && db_err.constraint() == Some("farms_slug_key") {
+            tracing::error!("Database error, farm slug already taken. {:?}", err);
+            return Err(ServerError::rejection(EndpointRejection::Conflict(
+                "This slug is already taken by another farm.".into(),
+            )));
+        }
+    }
+
+    Ok(())
+}
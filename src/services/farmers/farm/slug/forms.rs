@@ -0,0 +1,43 @@
+//! Farm slug forms impls
+
+use axum::{
+    async_trait,
+    extract::{rejection::JsonRejection, FromRequest, Request},
+    Json,
+};
+use serde::Deserialize;
+
+use crate::{
+    endpoint::{validators::ValidateString, EndpointRejection, EndpointResult},
+    server::state::ServerState,
+};
+
+/// Farm slug update form
+#[derive(Debug, Clone, Deserialize)]
+pub struct SlugUpdateForm {
+    pub slug: String,
+}
+
+impl SlugUpdateForm {
+    /// Validates slug update form inputs
+    fn validate(&mut self) -> EndpointResult<()> {
+        // Clean the data
+        self.slug = self.slug.trim().to_ascii_lowercase();
+
+        self.slug.validate_slug()
+    }
+}
+
+#[async_trait]
+impl FromRequest<ServerState> for SlugUpdateForm
+where
+    Json<Self>: FromRequest<ServerState, Rejection = JsonRejection>,
+{
+    type Rejection = EndpointRejection;
+
+    async fn from_request(req: Request, state: &ServerState) -> Result<Self, Self::Rejection> {
+        let Json(mut slug) = Json::<Self>::from_request(req, state).await?;
+        slug.validate()?;
+        Ok(slug)
+    }
+}
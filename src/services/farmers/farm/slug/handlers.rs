@@ -0,0 +1,46 @@
+//! Farm slug http handlers impls
+
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::{IntoResponse, Redirect, Response},
+};
+
+use crate::{
+    endpoint::{EndpointRejection, EndpointResult},
+    server::state::DatabaseConnection,
+    services::farmers::farm::{handlers::farm_detail, permissions::FarmOwnershipPermission},
+    types::ModelID,
+};
+
+use super::{forms::SlugUpdateForm, FarmSlug, SlugLookup};
+
+/// Handles the `PUT /farms/:farm_id/slug` route.
+#[tracing::instrument(skip(db))]
+pub async fn farm_slug_update(
+    _: FarmOwnershipPermission,
+    farm_id: ModelID,
+    State(db): State<DatabaseConnection>,
+    form: SlugUpdateForm,
+) -> EndpointResult<StatusCode> {
+    FarmSlug::update(farm_id, form.slug, db).await?;
+    Ok(StatusCode::OK)
+}
+
+/// Handles the `GET /farms/slug/:slug` route.
+///
+/// Returns the farm detail, previous slugs redirect to the current one.
+#[tracing::instrument(skip(db))]
+pub async fn farm_detail_by_slug(
+    Path(slug): Path<String>,
+    State(db): State<DatabaseConnection>,
+) -> EndpointResult<Response> {
+    match FarmSlug::find(&slug, db.clone()).await? {
+        Some(SlugLookup::Current(farm_id)) => {
+            Ok(farm_detail(farm_id, State(db)).await?.into_response())
+        }
+        // Relative to the requested slug path
+        Some(SlugLookup::Renamed(current)) => Ok(Redirect::temporary(&current).into_response()),
+        None => Err(EndpointRejection::NotFound("Farm not found".into())),
+    }
+}
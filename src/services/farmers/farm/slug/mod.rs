@@ -0,0 +1,21 @@
+//! Farm slug impls
+
+pub mod db;
+pub mod forms;
+pub mod handlers;
+
+use crate::types::ModelID;
+
+/// The model representing the `slug` column in the `farms` database table
+/// and its rename history.
+#[derive(Debug, Clone, Copy)]
+pub struct FarmSlug;
+
+/// The farm found by a slug
+#[derive(Debug, Clone)]
+pub enum SlugLookup {
+    /// The slug is the current slug of the farm
+    Current(ModelID),
+    /// The slug was renamed, holds the farm current slug
+    Renamed(String),
+}
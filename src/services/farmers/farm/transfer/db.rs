@@ -0,0 +1,454 @@
+//! Farm ownership transfer database impl
+
+use time::OffsetDateTime;
+
+use crate::{
+    auth::TokenHash,
+    endpoint::EndpointRejection,
+    error::{ServerError, ServerResult},
+    server::state::DatabaseConnection,
+    services::farmers::farm::{db::handle_farm_database_error, utils::update_users_is_farmer},
+    types::ModelID,
+};
+
+use super::{forms::FarmTransferInsertData, FarmTransfer};
+
+impl FarmTransfer {
+    /// Inserts farm ownership transfer into the database
+    ///
+    /// Replaces the previous pending transfer of the farm
+    #[tracing::instrument(name = "Insert FarmTransfer", skip(db, values))]
+    pub async fn insert(
+        values: FarmTransferInsertData,
+        db: DatabaseConnection,
+    ) -> ServerResult<()> {
+        let mut tx = db.pool.begin().await?;
+        match sqlx::query!(
+            r#"
+                INSERT INTO services.farm_ownership_transfers(
+                    id,
+                    farm_id,
+                    from_user_id,
+                    to_email,
+                    token,
+                    created_at
+                )
+                VALUES($1, $2, $3, $4, $5, $6)
+
+                ON CONFLICT ON CONSTRAINT farm_ownership_transfers_farm_id_key
+                DO UPDATE SET from_user_id = EXCLUDED.from_user_id,
+                    to_email = EXCLUDED.to_email,
+                    token = EXCLUDED.token,
+                    created_at = EXCLUDED.created_at;
+            "#,
+            values.id.0,
+            values.farm_id.0,
+            values.from_user_id.0,
+            values.to_email,
+            &values.token[..],
+            values.created_at,
+        )
+        .execute(&mut *tx)
+        .await
+        {
+            Ok(result) => {
+                tracing::trace!(
+                    "Farm transfer inserted, but transaction not committed: {:?}",
+                    result
+                );
+            }
+            Err(err) => {
+                // Handle database constraint error
+                handle_farm_database_error(&err)?;
+
+                tracing::error!("Database error, failed to insert farm transfer: {}", err);
+                return Err(err.into());
+            }
+        }
+
+        let transfer = Self::from_row(
+            values.id,
+            values.farm_id,
+            values.from_user_id,
+            values.to_email,
+            values.created_at,
+        );
+        let from_user_id = Some(transfer.from_user_id);
+        insert_transfer_log(&transfer, "started", from_user_id, None, &mut tx).await?;
+
+        tx.commit().await?;
+        tracing::debug!("Farm transfer inserted successfully");
+        Ok(())
+    }
+
+    /// Finds farm ownership transfer associated with the token from the database
+    #[tracing::instrument(skip(db, token))]
+    pub async fn find_by_token(
+        token: TokenHash,
+        db: DatabaseConnection,
+    ) -> ServerResult<Option<Self>> {
+        match sqlx::query!(
+            r#"
+                SELECT transfer.id,
+                    transfer.farm_id,
+                    transfer.from_user_id,
+                    transfer.to_email,
+                    transfer.created_at
+                FROM services.farm_ownership_transfers transfer
+                WHERE transfer.token = $1
+            "#,
+            &token[..]
+        )
+        .fetch_optional(&db.pool)
+        .await
+        {
+            Ok(rec) => Ok(rec.map(|rec| {
+                Self::from_row(
+                    rec.id.into(),
+                    rec.farm_id.into(),
+                    rec.from_user_id.into(),
+                    rec.to_email,
+                    rec.created_at,
+                )
+            })),
+            Err(err) => {
+                tracing::error!("Database error, failed to find farm transfer: {}", err);
+                Err(err.into())
+            }
+        }
+    }
+
+    /// Makes the user the farm owner, demotes the previous owner
+    /// to a farm manager and records the transfer.
+    #[tracing::instrument(name = "Accept FarmTransfer", skip(db))]
+    pub async fn accept(self, user_id: ModelID, db: DatabaseConnection) -> ServerResult<()> {
+        let mut tx = db.pool.begin().await?;
+
+        update_farm_owner(self.farm_id, user_id, &mut tx).await?;
+        let previous_owner = demote_farm_owner(self.farm_id, &mut tx).await?;
+        insert_farm_owner(self.farm_id, user_id, &mut tx).await?;
+        delete_transfer(self.id, &mut tx).await?;
+        insert_transfer_log(&self, "completed", previous_owner, Some(user_id), &mut tx).await?;
+
+        update_users_is_farmer(&[user_id], &mut tx).await?;
+
+        tx.commit().await?;
+        tracing::debug!("Farm transfer accepted successfully");
+        Ok(())
+    }
+
+    /// Deletes the farm ownership transfer declined by the receiver
+    /// from the database and records the decline.
+    #[tracing::instrument(name = "Decline FarmTransfer", skip(db))]
+    pub async fn decline(self, db: DatabaseConnection) -> ServerResult<()> {
+        self.close("declined", db).await
+    }
+
+    /// Deletes the expired farm ownership transfer from the database
+    /// and records the expiry.
+    #[tracing::instrument(name = "Expire FarmTransfer", skip(db))]
+    pub async fn expire(self, db: DatabaseConnection) -> ServerResult<()> {
+        self.close("expired", db).await
+    }
+
+    /// Deletes the farm ownership transfer from the database
+    /// and records the `event`.
+    async fn close(self, event: &str, db: DatabaseConnection) -> ServerResult<()> {
+        let mut tx = db.pool.begin().await?;
+        delete_transfer(self.id, &mut tx).await?;
+        let from_user_id = Some(self.from_user_id);
+        insert_transfer_log(&self, event, from_user_id, None, &mut tx).await?;
+        tx.commit().await?;
+        tracing::debug!("Farm transfer {} successfully", event);
+        Ok(())
+    }
+
+    /// Deletes expired farm ownership transfers from the database
+    /// and records their expiry.
+    #[tracing::instrument(skip(db))]
+    pub async fn purge_expired(db: DatabaseConnection) -> ServerResult<()> {
+        let threshold =
+            OffsetDateTime::now_utc() - time::Duration::days(crate::FARM_TRANSFER_EXPIRY_DAYS);
+        let mut tx = db.pool.begin().await?;
+        let transfers: Vec<_> = match sqlx::query!(
+            r#"
+                DELETE FROM services.farm_ownership_transfers transfer
+                WHERE transfer.created_at < $1
+
+                RETURNING transfer.id,
+                    transfer.farm_id,
+                    transfer.from_user_id,
+                    transfer.to_email,
+                    transfer.created_at
+            "#,
+            threshold
+        )
+        .fetch_all(&mut *tx)
+        .await
+        {
+            Ok(records) => records
+                .into_iter()
+                .map(|rec| {
+                    Self::from_row(
+                        rec.id.into(),
+                        rec.farm_id.into(),
+                        rec.from_user_id.into(),
+                        rec.to_email,
+                        rec.created_at,
+                    )
+                })
+                .collect(),
+            Err(err) => {
+                tracing::error!(
+                    "Database error, failed to delete expired farm transfers: {}",
+                    err
+                );
+                return Err(err.into());
+            }
+        };
+
+        for transfer in &transfers {
+            let from_user_id = Some(transfer.from_user_id);
+            insert_transfer_log(transfer, "expired", from_user_id, None, &mut tx).await?;
+        }
+
+        tx.commit().await?;
+        tracing::debug!("Expired farm transfers deleted: {}", transfers.len());
+        Ok(())
+    }
+
+    /// Deletes the farm pending ownership transfer from the database
+    /// and records the cancellation.
+    #[tracing::instrument(name = "Cancel FarmTransfer", skip(db))]
+    pub async fn cancel(farm_id: ModelID, db: DatabaseConnection) -> ServerResult<()> {
+        let mut tx = db.pool.begin().await?;
+        let transfer = match sqlx::query!(
+            r#"
+                DELETE FROM services.farm_ownership_transfers transfer
+                WHERE transfer.farm_id = $1
+
+                RETURNING transfer.id,
+                    transfer.farm_id,
+                    transfer.from_user_id,
+                    transfer.to_email,
+                    transfer.created_at
+            "#,
+            farm_id.0
+        )
+        .fetch_optional(&mut *tx)
+        .await
+        {
+            Ok(Some(rec)) => Self::from_row(
+                rec.id.into(),
+                rec.farm_id.into(),
+                rec.from_user_id.into(),
+                rec.to_email,
+                rec.created_at,
+            ),
+            Ok(None) => {
+                return Err(ServerError::rejection(EndpointRejection::NotFound(
+                    "Farm has no pending ownership transfer.".into(),
+                )))
+            }
+            Err(err) => {
+                tracing::error!("Database error, failed to cancel farm transfer: {}", err);
+                return Err(err.into());
+            }
+        };
+
+        let from_user_id = Some(transfer.from_user_id);
+        insert_transfer_log(&transfer, "cancelled", from_user_id, None, &mut tx).await?;
+
+        tx.commit().await?;
+        tracing::debug!("Farm transfer cancelled successfully");
+        Ok(())
+    }
+}
+
+/// Updates the farm `owner_id` in the database
+async fn update_farm_owner(
+    farm_id: ModelID,
+    user_id: ModelID,
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+) -> ServerResult<()> {
+    match sqlx::query!(
+        r#"
+            UPDATE services.farms farm
+            SET owner_id = $1
+            WHERE farm.id = $2
+                AND farm.deleted = false
+
+            RETURNING farm.id
+        "#,
+        user_id.0,
+        farm_id.0
+    )
+    .fetch_one(&mut **tx)
+    .await
+    {
+        Ok(_rec) => Ok(()),
+        Err(err) => {
+            // Handle database constraint error
+            handle_farm_database_error(&err)?;
+
+            tracing::error!("Database error, failed to update farm owner: {}", err);
+            Err(err.into())
+        }
+    }
+}
+
+/// Demotes the current owner to a farm manager,
+/// returns the previous owner id.
+async fn demote_farm_owner(
+    farm_id: ModelID,
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+) -> ServerResult<Option<ModelID>> {
+    match sqlx::query!(
+        r#"
+            UPDATE services.farm_members member
+            SET role = 'manager'
+            WHERE member.farm_id = $1
+                AND member.role = 'owner'
+
+            RETURNING member.user_id
+        "#,
+        farm_id.0
+    )
+    .fetch_optional(&mut **tx)
+    .await
+    {
+        Ok(rec) => Ok(rec.map(|rec| rec.user_id.into())),
+        Err(err) => {
+            tracing::error!("Database error, failed to demote farm owner: {}", err);
+            Err(err.into())
+        }
+    }
+}
+
+/// Inserts the user as the farm owner member,
+/// an existing member is promoted to owner.
+async fn insert_farm_owner(
+    farm_id: ModelID,
+    user_id: ModelID,
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+) -> ServerResult<()> {
+    match sqlx::query!(
+        r#"
+            INSERT INTO services.farm_members(
+                id,
+                farm_id,
+                user_id,
+                role,
+                joined_at
+            )
+            VALUES($1, $2, $3, 'owner', $4)
+
+            ON CONFLICT (farm_id, user_id)
+            DO UPDATE SET role = EXCLUDED.role;
+        "#,
+        ModelID::new().0,
+        farm_id.0,
+        user_id.0,
+        OffsetDateTime::now_utc(),
+    )
+    .execute(&mut **tx)
+    .await
+    {
+        Ok(result) => {
+            tracing::trace!(
+                "Farm owner inserted, but transaction not committed: {:?}",
+                result
+            );
+            Ok(())
+        }
+        Err(err) => {
+            tracing::error!("Database error, failed to insert farm owner: {}", err);
+            Err(err.into())
+        }
+    }
+}
+
+/// Records the transfer event (`started`, `cancelled`, `declined`, `expired`
+/// or `completed`) in the farm ownership transfer logs
+async fn insert_transfer_log(
+    transfer: &FarmTransfer,
+    event: &str,
+    from_user_id: Option<ModelID>,
+    to_user_id: Option<ModelID>,
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+) -> ServerResult<()> {
+    let logged_at = OffsetDateTime::now_utc();
+    let transferred_at = (event == "completed").then_some(logged_at);
+    match sqlx::query!(
+        r#"
+            INSERT INTO services.farm_ownership_transfer_logs(
+                id,
+                farm_id,
+                event,
+                from_user_id,
+                to_user_id,
+                to_email,
+                initiated_at,
+                transferred_at,
+                logged_at
+            )
+            VALUES($1, $2, $3, $4, $5, $6, $7, $8, $9);
+        "#,
+        ModelID::new().0,
+        transfer.farm_id.0,
+        event,
+        from_user_id.map(|id| id.0),
+        to_user_id.map(|id| id.0),
+        transfer.to_email,
+        transfer.created_at,
+        transferred_at,
+        logged_at,
+    )
+    .execute(&mut **tx)
+    .await
+    {
+        Ok(result) => {
+            tracing::trace!(
+                "Farm transfer log inserted, but transaction not committed: {:?}",
+                result
+            );
+            Ok(())
+        }
+        Err(err) => {
+            tracing::error!(
+                "Database error, failed to insert farm transfer log: {}",
+                err
+            );
+            Err(err.into())
+        }
+    }
+}
+
+/// Deletes farm ownership transfer from the database
+async fn delete_transfer(
+    id: ModelID,
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+) -> ServerResult<()> {
+    match sqlx::query!(
+        r#"
+            DELETE FROM services.farm_ownership_transfers transfer
+            WHERE transfer.id = $1
+        "#,
+        id.0
+    )
+    .execute(&mut **tx)
+    .await
+    {
+        Ok(result) => {
+            tracing::trace!(
+                "Farm transfer deleted, but transaction not committed: {:?}",
+                result
+            );
+            Ok(())
+        }
+        Err(err) => {
+            tracing::error!("Database error, failed to delete farm transfer: {}", err);
+            Err(err.into())
+        }
+    }
+}
@@ -0,0 +1,113 @@
+//! Farm ownership transfer forms impls
+
+use axum::{
+    async_trait,
+    extract::{rejection::JsonRejection, FromRequest, FromRequestParts, Json, Request},
+};
+use serde::Deserialize;
+use time::OffsetDateTime;
+
+use crate::{
+    accounts::emails::EmailModel,
+    auth::{FarmerUser, Token, TokenHash},
+    endpoint::{
+        validators::{TransformString, ValidateString},
+        EndpointRejection, EndpointResult,
+    },
+    server::state::ServerState,
+    services::farmers::farm::permissions::check_user_owns_farm,
+    types::ModelID,
+};
+
+/// Farm ownership transfer form
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FarmTransferForm {
+    pub email: String,
+}
+
+/// Farm ownership transfer cleaned data
+#[derive(Debug, Clone)]
+pub struct FarmTransferInsertData {
+    pub id: ModelID,
+    pub farm_id: ModelID,
+    pub from_user_id: ModelID,
+    pub to_email: String,
+    pub token: TokenHash,
+    pub created_at: OffsetDateTime,
+}
+
+impl FarmTransferForm {
+    /// Validates transfer form inputs
+    fn validate(&mut self) -> EndpointResult<()> {
+        // Clean the data
+        self.clean_data();
+
+        self.email.validate_email()
+    }
+
+    /// Clean form data
+    fn clean_data(&mut self) {
+        self.email = self.email.clean().to_ascii_lowercase();
+    }
+
+    /// Convert `Self` into `FarmTransferInsertData`
+    ///
+    /// Return (`FarmTransferInsertData`, token plaintext)
+    #[must_use]
+    pub fn data(self, farm_id: ModelID, user_id: ModelID) -> (FarmTransferInsertData, String) {
+        let (plaintext, hash) = Token::default().into_parts();
+        let values = FarmTransferInsertData {
+            id: ModelID::new(),
+            farm_id,
+            from_user_id: user_id,
+            to_email: self.email,
+            token: hash,
+            created_at: OffsetDateTime::now_utc(),
+        };
+        (values, plaintext)
+    }
+
+    ///  Validate a user owns the farm and is not transferring it to themselves
+    async fn authorize_request(
+        &self,
+        state: &ServerState,
+        user: FarmerUser,
+        farm_id: ModelID,
+    ) -> EndpointResult<()> {
+        check_user_owns_farm(user.id(), farm_id, state.database()).await?;
+
+        let (_, email_address) = EmailModel::find_user(user.id(), state.database()).await?;
+        if self.email.eq_ignore_ascii_case(&email_address) {
+            return Err(EndpointRejection::BadRequest(
+                "You already own this farm.".into(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl FromRequest<ServerState> for FarmTransferForm
+where
+    Json<Self>: FromRequest<ServerState, Rejection = JsonRejection>,
+{
+    type Rejection = EndpointRejection;
+
+    async fn from_request(req: Request, state: &ServerState) -> Result<Self, Self::Rejection> {
+        // Extract data
+        let (mut parts, body) = req.into_parts();
+        let user = { FarmerUser::from_parts(&mut parts, state).await? };
+        let farm_id = { ModelID::from_request_parts(&mut parts, state).await? };
+        let Json(mut transfer) =
+            Json::<Self>::from_request(Request::from_parts(parts, body), state).await?;
+
+        // Validate form fields
+        transfer.validate()?;
+
+        // Authorize request
+        transfer.authorize_request(state, user, farm_id).await?;
+
+        Ok(transfer)
+    }
+}
@@ -0,0 +1,114 @@
+//! Farm ownership transfer http handlers impls
+
+use axum::{
+    extract::{Query, State},
+    http::StatusCode,
+};
+
+use crate::{
+    accounts::emails::EmailModel,
+    auth::{hash_token, CurrentUser, FarmerUser, TokenConfirm},
+    endpoint::{EndpointRejection, EndpointResult},
+    mail::Mail,
+    server::state::DatabaseConnection,
+    services::farmers::farm::{permissions::FarmOwnershipPermission, utils::farm_name},
+    types::ModelID,
+    SERVER_DOMAIN_NAME,
+};
+
+use super::{forms::FarmTransferForm, FarmTransfer};
+
+/// Handles the `POST /farms/:farm_id/transfer` route.
+///
+/// Sends the farm ownership transfer confirmation to the email
+#[tracing::instrument(skip(user, db, outlook, form))]
+pub async fn farm_transfer_create(
+    user: FarmerUser,
+    farm_id: ModelID,
+    State(db): State<DatabaseConnection>,
+    State(outlook): State<Mail>,
+    form: FarmTransferForm,
+) -> EndpointResult<(StatusCode, &'static str)> {
+    let (values, token) = form.data(farm_id, user.id());
+    let email_address = values.to_email.clone();
+    FarmTransfer::insert(values, db.clone()).await?;
+
+    // Send transfer confirmation email
+    let farm_name = farm_name(farm_id, db.clone()).await?;
+    let (owner_name, _) = EmailModel::find_user(user.id(), db).await?;
+    let domain = SERVER_DOMAIN_NAME.get().unwrap();
+    let link = format!("{domain}/farms/transfer/accept?token={token}");
+    let email = outlook.farm_transfer(&owner_name, &farm_name, &email_address, &link)?;
+    outlook.send(email).await?;
+
+    Ok((StatusCode::CREATED, "Ownership transfer sent successfully"))
+}
+
+/// Handles the `DELETE /farms/:farm_id/transfer` route.
+#[tracing::instrument(skip(db))]
+pub async fn farm_transfer_cancel(
+    _: FarmOwnershipPermission,
+    farm_id: ModelID,
+    State(db): State<DatabaseConnection>,
+) -> EndpointResult<StatusCode> {
+    FarmTransfer::cancel(farm_id, db).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Handles the `POST /farms/transfer/accept?token=...` route.
+#[tracing::instrument(skip(user, db))]
+pub async fn farm_transfer_accept(
+    user: CurrentUser,
+    confirm_token: Option<Query<TokenConfirm>>,
+    State(db): State<DatabaseConnection>,
+) -> EndpointResult<&'static str> {
+    let transfer = find_transfer(confirm_token, db.clone()).await?;
+
+    // Only the receiving user can accept the transfer
+    let (_, email_address) = EmailModel::find_user(user.id, db.clone()).await?;
+    if !transfer.to_email.eq_ignore_ascii_case(&email_address) {
+        return Err(EndpointRejection::BadRequest(
+            "This ownership transfer was sent to a different email address.".into(),
+        ));
+    }
+
+    transfer.accept(user.id, db).await?;
+    Ok("You are now the owner of the farm")
+}
+
+/// Handles the `POST /farms/transfer/decline?token=...` route.
+#[tracing::instrument(skip(db))]
+pub async fn farm_transfer_decline(
+    confirm_token: Option<Query<TokenConfirm>>,
+    State(db): State<DatabaseConnection>,
+) -> EndpointResult<&'static str> {
+    let transfer = find_transfer(confirm_token, db.clone()).await?;
+    transfer.decline(db).await?;
+    Ok("Farm ownership transfer declined")
+}
+
+/// Finds the ownership transfer matching the token
+async fn find_transfer(
+    confirm_token: Option<Query<TokenConfirm>>,
+    db: DatabaseConnection,
+) -> EndpointResult<FarmTransfer> {
+    static ERR_MSG: &str = "Your farm ownership transfer link is no longer valid.";
+
+    let Some(Query(confirm_token)) = confirm_token else {
+        return Err(EndpointRejection::BadRequest(
+            "Transfer token required!".into(),
+        ));
+    };
+
+    let token_hash = hash_token(confirm_token.token.as_bytes());
+    let Some(transfer) = FarmTransfer::find_by_token(token_hash, db.clone()).await? else {
+        return Err(EndpointRejection::BadRequest(ERR_MSG.into()));
+    };
+
+    if transfer.is_expired() {
+        transfer.expire(db).await?;
+        return Err(EndpointRejection::BadRequest(ERR_MSG.into()));
+    }
+
+    Ok(transfer)
+}
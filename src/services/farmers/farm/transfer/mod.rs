@@ -0,0 +1,47 @@
+//! Farm ownership transfer model impls
+
+pub mod db;
+pub mod forms;
+pub mod handlers;
+
+use time::OffsetDateTime;
+
+use crate::types::ModelID;
+
+/// A pending farm ownership transfer
+#[derive(Debug, Clone)]
+pub struct FarmTransfer {
+    pub id: ModelID,
+    pub farm_id: ModelID,
+    pub from_user_id: ModelID,
+    pub to_email: String,
+    pub created_at: OffsetDateTime,
+}
+
+impl FarmTransfer {
+    /// Creates a new `FarmTransfer` from the database row
+    #[must_use]
+    pub const fn from_row(
+        id: ModelID,
+        farm_id: ModelID,
+        from_user_id: ModelID,
+        to_email: String,
+        created_at: OffsetDateTime,
+    ) -> Self {
+        Self {
+            id,
+            farm_id,
+            from_user_id,
+            to_email,
+            created_at,
+        }
+    }
+
+    /// Checks if the transfer has expired
+    #[must_use]
+    pub fn is_expired(&self) -> bool {
+        let threshold =
+            OffsetDateTime::now_utc() - time::Duration::days(crate::FARM_TRANSFER_EXPIRY_DAYS);
+        self.created_at < threshold
+    }
+}
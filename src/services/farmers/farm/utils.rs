@@ -1,5 +1,7 @@
 //! Farm helpers impls
 
+use std::collections::HashMap;
+
 use time::OffsetDateTime;
 
 use crate::{
@@ -7,14 +9,20 @@ use crate::{
     files,
     server::state::DatabaseConnection,
     services::{
-        farmers::location::{db::handle_location_database_error, forms::LocationInsertData},
-        produce::harvest::harvest_max_age,
+        farmers::{
+            location::{
+                db::handle_location_database_error, forms::LocationInsertData,
+                schedule::LocationSchedule,
+            },
+            rating::models::RatingSummary,
+        },
+        produce::harvest::models::{HarvestIndex, HarvestList},
     },
     settings,
     types::ModelID,
 };
 
-use super::db::handle_farm_database_error;
+use super::{db::handle_farm_database_error, models::FarmStats};
 
 /// Insert farm-location into the database
 pub async fn location_insert(
@@ -31,10 +39,18 @@ pub async fn location_insert(
                 region_id,
                 description,
                 coords,
+                timezone,
+                pickup_instructions,
+                access_notes,
                 deleted,
                 created_at
             )
-            VALUES($1, $2, $3, $4, $5, $6, $7, false, $8);
+            VALUES($1, $2, $3, $4, $5, $6, $7,
+                COALESCE($8, (
+                    SELECT country.timezone FROM services.countries country
+                    WHERE country.id = $4
+                ), 'UTC'),
+                $9, $10, false, $11);
         "#,
         location.id.0,
         location.farm_id.0,
@@ -43,6 +59,9 @@ pub async fn location_insert(
         location.region_id.0,
         location.description,
         location.coords,
+        location.timezone,
+        location.pickup_instructions,
+        location.access_notes,
         location.created_at,
     )
     .execute(&mut **tx)
@@ -97,36 +116,65 @@ pub async fn update_user_is_farmer(
     }
 }
 
-/// Fetch farm count belonging to the user
+/// Update members `is_farmer` in the database,
+/// a user is a farmer if they are a member of an active farm.
 ///
 /// # Errors
 ///
 /// Return database error
-pub async fn user_farm_count(
+pub async fn update_users_is_farmer(
+    user_ids: &[ModelID],
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+) -> ServerResult<()> {
+    let user_ids: Vec<_> = user_ids.iter().map(|id| id.0).collect();
+    match sqlx::query!(
+        r#"
+            UPDATE accounts.users user_
+            SET is_farmer = EXISTS(
+                SELECT 1
+                FROM services.farm_members member
+                INNER JOIN services.active_farms farm
+                    ON member.farm_id = farm.id
+                WHERE member.user_id = user_.id
+            )
+            WHERE user_.id = ANY($1);
+       "#,
+        &user_ids[..],
+    )
+    .execute(&mut **tx)
+    .await
+    {
+        Ok(_result) => Ok(()),
+        Err(err) => {
+            tracing::error!("Database error, failed to update users is_farmer: {}", err);
+            Err(err.into())
+        }
+    }
+}
+
+/// Fetch the ids of users who are members of the farm
+///
+/// # Errors
+///
+/// Return database error
+pub async fn farm_member_ids(
     farm_id: ModelID,
     db: DatabaseConnection,
-) -> ServerResult<(Option<ModelID>, i64)> {
+) -> ServerResult<Vec<ModelID>> {
     match sqlx::query!(
         r#"
-            SELECT farm.owner_id AS user_id,
-                COUNT(farm.id) AS "farm_count!"
-            FROM services.active_farms farm
-            WHERE farm.owner_id
-                IN (
-                    SELECT farm.owner_id
-                    FROM services.active_farms farm
-                    WHERE farm.id = $1
-                )
-            GROUP BY farm.owner_id
+            SELECT member.user_id
+            FROM services.farm_members member
+            WHERE member.farm_id = $1
         "#,
         farm_id.0,
     )
-    .fetch_one(&db.pool)
+    .fetch_all(&db.pool)
     .await
     {
-        Ok(rec) => Ok((rec.user_id.map(Into::into), rec.farm_count)),
+        Ok(records) => Ok(records.into_iter().map(|rec| rec.user_id.into()).collect()),
         Err(err) => {
-            tracing::error!("Database error, failed to fetch farm-count: {}", err);
+            tracing::error!("Database error, failed to fetch farm member ids: {}", err);
             Err(err.into())
         }
     }
@@ -134,34 +182,253 @@ pub async fn user_farm_count(
 
 // ==== Farm =====
 
-/// Delete farm from the database
+/// Fetch the farms aggregate stats and ratings summary from the database
 ///
 /// # Errors
 ///
 /// Return database error
-pub async fn delete_farm(
-    farm_id: ModelID,
-    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
-) -> ServerResult<()> {
+pub async fn farm_stats(
+    farm_ids: &[ModelID],
+    db: DatabaseConnection,
+) -> ServerResult<HashMap<ModelID, (FarmStats, RatingSummary)>> {
+    let farm_ids: Vec<_> = farm_ids.iter().map(|id| id.0).collect();
     match sqlx::query!(
         r#"
-            DELETE FROM services.farms farm
+            SELECT farm.id AS "farm_id!",
+                farm.founded_at AS farm_founded_at,
+                farm.verified AS "farm_verified!",
+                stat.rating_average AS "rating_average?",
+                stat.rating_count AS "rating_count?",
+                stat.harvest_count AS "harvest_count?",
+                stat.follower_count AS "follower_count?",
+                stat.grade_1_count AS "grade_1_count?",
+                stat.grade_2_count AS "grade_2_count?",
+                stat.grade_3_count AS "grade_3_count?",
+                stat.grade_4_count AS "grade_4_count?",
+                stat.grade_5_count AS "grade_5_count?"
+            FROM services.active_farms farm
+            LEFT JOIN services.farm_stats stat
+                ON farm.id = stat.farm_id
+            WHERE farm.id = ANY($1)
+        "#,
+        &farm_ids[..],
+    )
+    .fetch_all(&db.pool)
+    .await
+    {
+        Ok(records) => Ok(records
+            .into_iter()
+            .map(|rec| {
+                let stats = FarmStats::from_row(
+                    rec.harvest_count,
+                    rec.follower_count,
+                    rec.farm_founded_at,
+                    rec.farm_verified,
+                );
+                let ratings = RatingSummary::from_row(
+                    rec.rating_average,
+                    rec.rating_count,
+                    [
+                        rec.grade_1_count,
+                        rec.grade_2_count,
+                        rec.grade_3_count,
+                        rec.grade_4_count,
+                        rec.grade_5_count,
+                    ],
+                );
+                (rec.farm_id.into(), (stats, ratings))
+            })
+            .collect()),
+        Err(err) => {
+            tracing::error!("Database error, failed to fetch farm stats: {}", err);
+            Err(err.into())
+        }
+    }
+}
+
+/// Fetch the locations schedule from the database
+///
+/// # Errors
+///
+/// Return database error
+pub async fn location_schedules(
+    location_ids: &[ModelID],
+    db: DatabaseConnection,
+) -> ServerResult<HashMap<ModelID, LocationSchedule>> {
+    let location_ids: Vec<_> = location_ids.iter().map(|id| id.0).collect();
+    match sqlx::query!(
+        r#"
+            SELECT location_.id AS "id!",
+                services.location_schedule(location_.id) AS schedule
+            FROM services.active_locations location_
+            WHERE location_.id = ANY($1)
+        "#,
+        &location_ids[..],
+    )
+    .fetch_all(&db.pool)
+    .await
+    {
+        Ok(records) => Ok(records
+            .into_iter()
+            .map(|rec| (rec.id.into(), LocationSchedule::from_row(rec.schedule)))
+            .collect()),
+        Err(err) => {
+            tracing::error!(
+                "Database error, failed to fetch location schedules: {}",
+                err
+            );
+            Err(err.into())
+        }
+    }
+}
+
+/// Fetch the locations photo gallery from the database,
+/// locations without photos are left out.
+///
+/// # Errors
+///
+/// Return database error
+pub async fn location_galleries(
+    location_ids: &[ModelID],
+    db: DatabaseConnection,
+) -> ServerResult<HashMap<ModelID, Vec<String>>> {
+    let location_ids: Vec<_> = location_ids.iter().map(|id| id.0).collect();
+    match sqlx::query!(
+        r#"
+            SELECT location_.id AS "id!",
+                location_.images AS "images!"
+            FROM services.active_locations location_
+            WHERE location_.id = ANY($1)
+                AND location_.images IS NOT NULL
+        "#,
+        &location_ids[..],
+    )
+    .fetch_all(&db.pool)
+    .await
+    {
+        Ok(records) => Ok(records
+            .into_iter()
+            .map(|rec| (rec.id.into(), rec.images))
+            .collect()),
+        Err(err) => {
+            tracing::error!(
+                "Database error, failed to fetch location galleries: {}",
+                err
+            );
+            Err(err.into())
+        }
+    }
+}
+
+/// Fetch the active harvests available at the locations from the database,
+/// locations without harvests are left out.
+///
+/// # Errors
+///
+/// Return database error
+pub async fn location_harvests(
+    location_ids: &[ModelID],
+    db: DatabaseConnection,
+) -> ServerResult<HashMap<ModelID, HarvestList>> {
+    let location_ids: Vec<_> = location_ids.iter().map(|id| id.0).collect();
+    match sqlx::query!(
+        r#"
+            SELECT harvest.id AS "harvest_id!",
+                harvest.price AS "harvest_price!",
+                harvest.images AS harvest_images,
+                harvest.harvest_date AS "harvest_harvest_date!",
+                cultivar.name AS cultivar_name,
+                cultivar_category.name AS cultivar_category,
+                cultivar.image AS cultivar_image,
+                location_.id AS location_id,
+                location_.place_name AS location_place_name,
+                location_.coords AS location_coords,
+                services.location_open_now(location_.id) AS location_open_now,
+                services.location_open_today(location_.id) AS location_open_today,
+                region.name AS "location_region?",
+                country.name AS "location_country!",
+                farm.id AS farm_id,
+                farm.name AS farm_name,
+                farm.logo AS farm_logo
+            FROM services.active_harvests harvest
+            INNER JOIN services.cultivars cultivar
+                ON harvest.cultivar_id = cultivar.id
+            INNER JOIN services.cultivar_categories cultivar_category
+                ON cultivar.category_id = cultivar_category.id
+            INNER JOIN services.locations location_
+                ON harvest.location_id = location_.id
+            INNER JOIN services.farms farm
+                ON location_.farm_id = farm.id
+            LEFT JOIN services.regions region
+                ON location_.region_id = region.id
+            LEFT JOIN services.countries country
+                ON location_.country_id = country.id
+
+            WHERE harvest.location_id = ANY($1)
+        "#,
+        &location_ids[..],
+    )
+    .fetch_all(&db.pool)
+    .await
+    {
+        Ok(records) => {
+            let mut harvests: HashMap<ModelID, HarvestList> = HashMap::new();
+            for rec in records {
+                harvests
+                    .entry(rec.location_id.into())
+                    .or_default()
+                    .push(HarvestIndex::from_row(
+                        rec.harvest_id.into(),
+                        rec.harvest_price,
+                        rec.harvest_harvest_date,
+                        rec.harvest_images,
+                        rec.cultivar_name,
+                        rec.cultivar_category,
+                        rec.cultivar_image,
+                        rec.location_place_name,
+                        rec.location_region,
+                        rec.location_country,
+                        rec.location_coords,
+                        rec.location_open_now,
+                        rec.location_open_today,
+                        rec.farm_id.into(),
+                        rec.farm_name,
+                        rec.farm_logo,
+                        0.into(), // boost amount not important
+                    ));
+            }
+            Ok(harvests)
+        }
+        Err(err) => {
+            tracing::error!("Database error, failed to fetch location harvests: {}", err);
+            Err(err.into())
+        }
+    }
+}
+
+/// Fetch the farm name from the database
+///
+/// # Errors
+///
+/// Return database error
+pub async fn farm_name(farm_id: ModelID, db: DatabaseConnection) -> ServerResult<String> {
+    match sqlx::query!(
+        r#"
+            SELECT farm.name AS "name!"
+            FROM services.active_farms farm
             WHERE farm.id = $1
         "#,
         farm_id.0
     )
-    .execute(&mut **tx)
+    .fetch_one(&db.pool)
     .await
     {
-        Ok(result) => {
-            tracing::trace!("Farm deleted,  but transaction not committed: {:?}", result);
-            Ok(())
-        }
+        Ok(rec) => Ok(rec.name),
         Err(err) => {
             // Handle database constraint error
             handle_farm_database_error(&err)?;
 
-            tracing::error!("Database error, failed to delete farm: {}", err);
+            tracing::error!("Database error, failed to fetch farm name: {}", err);
             Err(err.into())
         }
     }
@@ -174,17 +441,19 @@ pub async fn delete_farm(
 /// Return database error
 pub async fn archive_farm(
     farm_id: ModelID,
-    deleted_at: OffsetDateTime,
+    archived_at: OffsetDateTime,
     tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
 ) -> ServerResult<()> {
     match sqlx::query!(
         r#"
             UPDATE services.farms farm
                 SET deleted = true,
-                deleted_at = $1
-            WHERE farm.id = $2
+                deleted_at = $1,
+                archived_at = $2
+            WHERE farm.id = $3
         "#,
-        deleted_at.date(),
+        archived_at.date(),
+        archived_at,
         farm_id.0
     )
     .execute(&mut **tx)
@@ -207,58 +476,146 @@ pub async fn archive_farm(
     }
 }
 
-// ===== Location =====
-
-/// Delete farm active locations
+/// Restore farm archived after `cutoff` in the database
+///
+/// Returns the time the farm was archived at,
+/// or `None` if the farm cannot be restored.
 ///
 /// # Errors
 ///
 /// Return database error
-pub async fn delete_farm_locations(
+pub async fn restore_farm(
     farm_id: ModelID,
+    cutoff: OffsetDateTime,
     tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
-) -> ServerResult<u64> {
+) -> ServerResult<Option<OffsetDateTime>> {
     match sqlx::query!(
         r#"
-            WITH location_stats AS(
-                SELECT location_.id AS location_id, COUNT(harvest.id)
-                FROM services.active_farms farm
-                LEFT JOIN services.active_locations location_
-                    ON farm.id = location_.farm_id
-                LEFT JOIN services.harvests harvest
-                    ON location_.id = harvest.location_id
-
-                WHERE farm.id = $1
-                GROUP BY location_.id
-            )
+            UPDATE services.farms farm
+                SET deleted = false,
+                deleted_at = NULL,
+                archived_at = NULL
+            FROM (
+                SELECT old_farm.id, old_farm.archived_at
+                FROM services.farms old_farm
+                WHERE old_farm.id = $1
+                    AND old_farm.deleted = true
+                    AND old_farm.archived_at > $2
+                FOR UPDATE
+            ) archived
+
+            WHERE farm.id = archived.id
+            RETURNING archived.archived_at AS "archived_at!"
+        "#,
+        farm_id.0,
+        cutoff,
+    )
+    .fetch_optional(&mut **tx)
+    .await
+    {
+        Ok(rec) => {
+            tracing::trace!("Farm restored, but transaction not committed");
+            Ok(rec.map(|rec| rec.archived_at))
+        }
+        Err(err) => {
+            // Handle database constraint error
+            handle_farm_database_error(&err)?;
 
-            DELETE FROM services.locations location_
+            tracing::error!("Database error, failed to restore farm: {}", err);
+            Err(err.into())
+        }
+    }
+}
 
-            WHERE location_.id IN(
-                SELECT stat.location_id
-                FROM location_stats stat
-                WHERE stat.count = 0
+/// Delete farms archived on or before `cutoff`
+/// that do not have any locations left
+///
+/// Returns the images of the ratings deleted together with the farms.
+///
+/// # Errors
+///
+/// Return database error
+pub async fn delete_expired_farms(
+    cutoff: OffsetDateTime,
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+) -> ServerResult<Vec<String>> {
+    match sqlx::query!(
+        r#"
+            WITH deleted_farms AS(
+                DELETE FROM services.farms farm
+                WHERE farm.archived_at <= $1
+                    AND NOT EXISTS(
+                        SELECT 1 FROM services.locations location_
+                        WHERE location_.farm_id = farm.id
+                    )
+                RETURNING farm.id
+            )
+
+            SELECT farm_rating.images
+            FROM services.farm_ratings farm_rating
+            WHERE farm_rating.farm_id IN (SELECT id FROM deleted_farms)
+                AND farm_rating.images IS NOT NULL
+        "#,
+        cutoff,
+    )
+    .fetch_all(&mut **tx)
+    .await
+    {
+        Ok(records) => {
+            tracing::trace!(
+                "Expired archived farms deleted, but transaction not committed: {} rating images",
+                records.len()
             );
+            Ok(records
+                .into_iter()
+                .filter_map(|rec| rec.images)
+                .flatten()
+                .collect())
+        }
+        Err(err) => {
+            tracing::error!("Database error, failed to delete expired farms");
+            Err(err.into())
+        }
+    }
+}
+
+/// Finalize farms archived on or before `cutoff`,
+/// the farms can no longer be restored.
+///
+/// # Errors
+///
+/// Return database error
+pub async fn finalize_archived_farms(
+    cutoff: OffsetDateTime,
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+) -> ServerResult<u64> {
+    match sqlx::query!(
+        r#"
+            UPDATE services.farms farm
+            SET archived_at = NULL
+            WHERE farm.archived_at <= $1
         "#,
-        farm_id.0,
+        cutoff,
     )
     .execute(&mut **tx)
     .await
     {
         Ok(result) => {
             tracing::trace!(
-                "Farm active location deleted, but transaction not committed: {:?}",
+                "Archived farms finalized, but transaction not committed: {:?}",
                 result
             );
             Ok(result.rows_affected())
         }
         Err(err) => {
-            tracing::error!("Database error, failed to delete farm locations");
+            tracing::error!("Database error, failed to finalize archived farms");
             Err(err.into())
         }
     }
 }
 
+// ===== Location =====
+
 /// Archive farm active locations
 ///
 /// # Errors
@@ -266,35 +623,22 @@ pub async fn delete_farm_locations(
 /// Return database error
 pub async fn archive_farm_locations(
     farm_id: ModelID,
-    deleted_at: OffsetDateTime,
+    archived_at: OffsetDateTime,
     tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
 ) -> ServerResult<u64> {
     match sqlx::query!(
         r#"
-            WITH location_stats AS(
-                SELECT location_.id AS location_id, COUNT(harvest.id)
-                FROM services.active_farms farm
-                LEFT JOIN services.active_locations location_
-                    ON farm.id = location_.farm_id
-                LEFT JOIN services.harvests harvest
-                    ON location_.id = harvest.location_id
-
-                WHERE farm.id = $1
-                GROUP BY location_.id
-            )
-
             UPDATE services.locations location_
                 SET deleted = TRUE,
-                    deleted_at = $2
+                    deleted_at = $1,
+                    archived_at = $2
 
-            WHERE location_.id IN(
-                SELECT stat.location_id
-                FROM location_stats stat
-                WHERE stat.count > 0
-            );
+            WHERE location_.farm_id = $3
+                AND location_.deleted = false;
         "#,
+        archived_at.date(),
+        archived_at,
         farm_id.0,
-        deleted_at.date(),
     )
     .execute(&mut **tx)
     .await
@@ -313,54 +657,48 @@ pub async fn archive_farm_locations(
     }
 }
 
-// ===== Harvest =====
-
-/// Delete farm active harvests
+/// Restore farm locations archived together with the farm
 ///
 /// # Errors
 ///
 /// Return database error
-pub async fn delete_farm_harvests(
+pub async fn restore_farm_locations(
     farm_id: ModelID,
-    finished_at: OffsetDateTime,
+    archived_at: OffsetDateTime,
     tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
 ) -> ServerResult<u64> {
-    let max_age = harvest_max_age(finished_at)?;
     match sqlx::query!(
         r#"
-            DELETE FROM services.harvests harvest
+            UPDATE services.locations location_
+                SET deleted = false,
+                    deleted_at = NULL,
+                    archived_at = NULL
 
-            WHERE harvest.location_id IN (
-                SELECT location_.id
-                FROM services.active_locations location_
-                WHERE location_.farm_id = $1
-            )
-            AND (
-                harvest.harvest_date > $2 OR
-                harvest.created_at > $3
-            )
+            WHERE location_.farm_id = $1
+                AND location_.archived_at = $2;
         "#,
         farm_id.0,
-        finished_at.date(),
-        max_age,
+        archived_at,
     )
     .execute(&mut **tx)
     .await
     {
         Ok(result) => {
             tracing::trace!(
-                "Farm active harvests deleted, but transaction not committed: {:?}",
+                "Farm locations restored, but transaction not committed: {:?}",
                 result
             );
             Ok(result.rows_affected())
         }
         Err(err) => {
-            tracing::error!("Database error, failed to delete farm harvests");
+            tracing::error!("Database error, failed to restore farm locations");
             Err(err.into())
         }
     }
 }
 
+// ===== Harvest =====
+
 /// Archive farm active harvests
 ///
 /// # Errors
@@ -368,30 +706,26 @@ pub async fn delete_farm_harvests(
 /// Return database error
 pub async fn archive_farm_harvests(
     farm_id: ModelID,
-    finished_at: OffsetDateTime,
+    archived_at: OffsetDateTime,
     tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
 ) -> ServerResult<u64> {
-    let max_age = harvest_max_age(finished_at)?;
     match sqlx::query!(
         r#"
             UPDATE services.harvests harvest
             SET finished = true,
-                images = NULL,
-                finished_at = $1
+                finished_at = $1,
+                archived_at = $2
 
             WHERE harvest.location_id IN (
                 SELECT location_.id
                 FROM services.active_locations location_
-                WHERE location_.farm_id = $2
-            )
-            AND NOT(
-                harvest.harvest_date > $1 OR
-                harvest.created_at > $3
+                WHERE location_.farm_id = $3
             )
+            AND harvest.finished = false
         "#,
-        finished_at.date(),
+        archived_at.date(),
+        archived_at,
         farm_id.0,
-        max_age,
     )
     .execute(&mut **tx)
     .await
@@ -410,74 +744,45 @@ pub async fn archive_farm_harvests(
     }
 }
 
-/// Find farm archived harvest count
+/// Restore farm harvests archived together with the farm
 ///
 /// # Errors
 ///
 /// Return database error
-pub async fn farm_archived_harvest_count(
+pub async fn restore_farm_harvests(
     farm_id: ModelID,
-    db: DatabaseConnection,
-) -> ServerResult<i64> {
+    archived_at: OffsetDateTime,
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+) -> ServerResult<u64> {
     match sqlx::query!(
         r#"
-            SELECT COUNT(harvest.id) AS "harvest_count!"
-            FROM services.harvests harvest
+            UPDATE services.harvests harvest
+            SET finished = false,
+                finished_at = NULL,
+                archived_at = NULL
 
             WHERE harvest.location_id IN (
                 SELECT location_.id
                 FROM services.locations location_
                 WHERE location_.farm_id = $1
             )
-            AND harvest.finished = true;
+            AND harvest.archived_at = $2
         "#,
-        farm_id.0
+        farm_id.0,
+        archived_at,
     )
-    .fetch_one(&db.pool)
+    .execute(&mut **tx)
     .await
     {
-        Ok(rec) => Ok(rec.harvest_count),
-        Err(err) => {
-            tracing::error!(
-                "Database error, failed to fetch farm active harvests count: {}",
-                err
+        Ok(result) => {
+            tracing::trace!(
+                "Farm harvests restored, but transaction not committed: {:?}",
+                result
             );
-            Err(err.into())
+            Ok(result.rows_affected())
         }
-    }
-}
-
-/// Fetch farm active harvests images
-///
-/// # Errors
-///
-/// Return database error
-pub async fn farm_harvest_images(
-    farm_id: ModelID,
-    db: DatabaseConnection,
-) -> ServerResult<Vec<Vec<String>>> {
-    match sqlx::query!(
-        r#"
-            SELECT harvest.images
-            FROM services.active_harvests harvest
-
-            WHERE harvest.location_id IN (
-                SELECT location_.id
-                FROM services.active_locations location_
-                WHERE location_.farm_id = $1
-            )
-        "#,
-        farm_id.0
-    )
-    .fetch_all(&db.pool)
-    .await
-    {
-        Ok(records) => Ok(records.into_iter().filter_map(|rec| rec.images).collect()),
         Err(err) => {
-            tracing::error!(
-                "Database error, failed to fetch farm harvest images: {}",
-                err
-            );
+            tracing::error!("Database error, failed to restore farm harvests");
             Err(err.into())
         }
     }
@@ -489,6 +794,6 @@ pub async fn farm_harvest_images(
 ///
 /// Return io error
 pub async fn delete_farm_logo(file_name: &str) -> ServerResult<()> {
-    let paths = files::saved_paths(settings::FARM_LOGO_UPLOAD_DIR, file_name);
+    let paths = files::saved_paths_with_thumbnails(settings::FARM_LOGO_UPLOAD_DIR, file_name);
     files::delete_files(paths).await
 }
@@ -19,8 +19,13 @@ impl Country {
         match sqlx::query!(
             r#"
                 SELECT  country.id,
-                     country.name
+                     country.name,
+                     country.code,
+                     country.currency,
+                     country.timezone,
+                     country.names
                 FROM services.countries country
+                ORDER BY country.name
             "#
         )
         .fetch_all(&db.pool)
@@ -29,7 +34,16 @@ impl Country {
             Ok(records) => {
                 let countries = records
                     .into_iter()
-                    .map(|rec| Self::from_row(rec.id.into(), rec.name))
+                    .map(|rec| {
+                        Self::from_row(
+                            rec.id.into(),
+                            rec.name,
+                            rec.code,
+                            rec.currency,
+                            rec.timezone,
+                            Some(rec.names),
+                        )
+                    })
                     .collect();
 
                 Ok(countries)
@@ -54,12 +68,20 @@ impl Country {
             r#"
                 INSERT INTO services.countries (
                     id, 
-                    name
+                    name,
+                    code,
+                    currency,
+                    timezone,
+                    names
                 )
-                VALUES ($1, $2);
+                VALUES ($1, $2, $3, $4, $5, $6);
             "#,
             country.id.0,
-            country.name
+            country.name,
+            country.code,
+            country.currency,
+            country.timezone,
+            serde_json::to_value(&country.names).ok(),
         )
         .execute(&db.pool)
         .await
@@ -88,10 +110,20 @@ impl Country {
         match sqlx::query!(
             r#"
                 UPDATE services.countries country
-                SET name = COALESCE($1, country.name)
-                WHERE country.id = $2
+                SET name = COALESCE($1, country.name),
+                    code = COALESCE($2, country.code),
+                    currency = COALESCE($3, country.currency),
+                    timezone = COALESCE($4, country.timezone),
+                    names = COALESCE($5, country.names)
+                WHERE country.id = $6
            "#,
             country.name,
+            country.code,
+            country.currency,
+            country.timezone,
+            country
+                .names
+                .and_then(|names| serde_json::to_value(names).ok()),
             id.0
         )
         .execute(&db.pool)
@@ -12,6 +12,7 @@ use crate::{
         EndpointRejection, EndpointResult,
     },
     server::state::ServerState,
+    services::farmers::location::{schedule::forms::validate_timezone, LocalizedNames},
     types::ModelID,
 };
 
@@ -20,6 +21,10 @@ use crate::{
 #[serde(rename_all = "camelCase")]
 pub struct CountryForm {
     pub name: String,
+    pub code: Option<String>,
+    pub currency: Option<String>,
+    pub timezone: Option<String>,
+    pub names: Option<LocalizedNames>,
 }
 
 /// Country create form cleaned data
@@ -27,6 +32,10 @@ pub struct CountryForm {
 pub struct CountryInsertData {
     pub id: ModelID,
     pub name: String,
+    pub code: Option<String>,
+    pub currency: Option<String>,
+    pub timezone: Option<String>,
+    pub names: LocalizedNames,
 }
 
 impl From<CountryForm> for CountryInsertData {
@@ -34,6 +43,10 @@ impl From<CountryForm> for CountryInsertData {
         Self {
             id: ModelID::new(),
             name: form.name,
+            code: form.code,
+            currency: form.currency,
+            timezone: form.timezone,
+            names: form.names.unwrap_or_default(),
         }
     }
 }
@@ -42,11 +55,21 @@ impl From<CountryForm> for CountryInsertData {
 #[derive(Debug, Clone)]
 pub struct CountryUpdateData {
     pub name: String,
+    pub code: Option<String>,
+    pub currency: Option<String>,
+    pub timezone: Option<String>,
+    pub names: Option<LocalizedNames>,
 }
 
 impl From<CountryForm> for CountryUpdateData {
     fn from(form: CountryForm) -> Self {
-        Self { name: form.name }
+        Self {
+            name: form.name,
+            code: form.code,
+            currency: form.currency,
+            timezone: form.timezone,
+            names: form.names,
+        }
     }
 }
 
@@ -59,12 +82,31 @@ impl CountryForm {
         self.name
             .validate_len(0, 32, "Country name must be at most 32 characters")?;
 
+        if let Some(ref code) = self.code {
+            validate_country_code(code)?;
+        }
+
+        if let Some(ref currency) = self.currency {
+            validate_currency(currency)?;
+        }
+
+        if let Some(ref names) = self.names {
+            validate_names(names)?;
+        }
+
         Ok(())
     }
 
     /// Clean form data
     fn clean_data(&mut self) {
         self.name = self.name.clean().to_titlecase();
+        self.code = self.code.as_ref().map(|code| code.clean().to_uppercase());
+        self.currency = self
+            .currency
+            .as_ref()
+            .map(|code| code.clean().to_uppercase());
+        self.timezone = self.timezone.as_ref().map(|tz| tz.clean());
+        self.names = self.names.take().map(clean_names);
     }
 }
 
@@ -81,7 +123,73 @@ where
 
         // Validate from fields
         country.validate()?;
+        if let Some(ref timezone) = country.timezone {
+            validate_timezone(timezone, state.database()).await?;
+        }
 
         Ok(country)
     }
 }
+
+// ===== Helpers =====
+
+/// Validates the country code is an ISO 3166-1 alpha-2 code, e.g. `NA`
+pub fn validate_country_code(code: &str) -> EndpointResult<()> {
+    if is_country_code(code) {
+        Ok(())
+    } else {
+        Err(EndpointRejection::BadRequest(
+            "Country code must be an ISO 3166-1 alpha-2 code.".into(),
+        ))
+    }
+}
+
+/// Validates the currency is an ISO 4217 code, e.g. `NAD`
+pub fn validate_currency(currency: &str) -> EndpointResult<()> {
+    if currency.len() == 3 && currency.bytes().all(|b| b.is_ascii_uppercase()) {
+        Ok(())
+    } else {
+        Err(EndpointRejection::BadRequest(
+            "Currency must be an ISO 4217 code.".into(),
+        ))
+    }
+}
+
+/// Validates the localized names locales and names
+pub fn validate_names(names: &LocalizedNames) -> EndpointResult<()> {
+    for (locale, name) in names {
+        if !is_locale(locale) {
+            return Err(EndpointRejection::BadRequest(
+                "Localized name locale must be a language code, e.g. `de` or `pt-BR`.".into(),
+            ));
+        }
+        name.validate_len(1, 32, "Localized name must be between 1 and 32 characters")?;
+    }
+    Ok(())
+}
+
+/// Trims the localized names
+#[must_use]
+pub fn clean_names(names: LocalizedNames) -> LocalizedNames {
+    names
+        .into_iter()
+        .map(|(locale, name)| (locale.clean(), name.clean()))
+        .collect()
+}
+
+/// Checks if `code` is an ISO 3166-1 alpha-2 code
+#[must_use]
+pub fn is_country_code(code: &str) -> bool {
+    code.len() == 2 && code.bytes().all(|b| b.is_ascii_uppercase())
+}
+
+/// Checks if `locale` is a language code with an optional region, e.g. `pt-BR`
+fn is_locale(locale: &str) -> bool {
+    let (language, region) = match locale.split_once('-') {
+        Some((language, region)) => (language, Some(region)),
+        None => (locale, None),
+    };
+    (2..=3).contains(&language.len())
+        && language.bytes().all(|b| b.is_ascii_lowercase())
+        && region.is_none_or(is_country_code)
+}
@@ -1,12 +1,13 @@
 //! Location country http handlers impls
 
 use axum::{
-    extract::{Json, State},
+    extract::{Json, Query, State},
     http::StatusCode,
 };
 
 use crate::{
-    auth::AdminUser, endpoint::EndpointResult, server::state::DatabaseConnection, types::ModelID,
+    auth::AdminUser, endpoint::EndpointResult, server::state::DatabaseConnection,
+    services::farmers::location::forms::LocaleQuery, types::ModelID,
 };
 
 use super::{forms::CountryForm, Country, CountryList};
@@ -14,9 +15,15 @@ use super::{forms::CountryForm, Country, CountryList};
 /// Handles the `GET /locations/countries` route.
 #[tracing::instrument(skip(db))]
 pub async fn country_list(
+    locale: Option<Query<LocaleQuery>>,
     State(db): State<DatabaseConnection>,
 ) -> EndpointResult<Json<CountryList>> {
-    let countries = Country::records(db).await?;
+    let lang = locale.unwrap_or_default().0.lang;
+    let countries = Country::records(db)
+        .await?
+        .into_iter()
+        .map(|country| country.localize(lang.as_deref()))
+        .collect();
     Ok(Json(countries))
 }
 
@@ -7,6 +7,8 @@ pub mod handlers;
 use crate::types::ModelID;
 use serde::Serialize;
 
+use super::{try_into_names, LocalizedNames};
+
 /// A `Vec` of country
 pub type CountryList = Vec<Country>;
 
@@ -15,13 +17,43 @@ pub type CountryList = Vec<Country>;
 pub struct Country {
     pub id: ModelID,
     pub name: String,
+    /// ISO 3166-1 alpha-2 code
+    pub code: Option<String>,
+    /// ISO 4217 default currency code
+    pub currency: Option<String>,
+    /// IANA default timezone
+    pub timezone: Option<String>,
+    pub names: LocalizedNames,
 }
 
 impl Country {
     /// Creates a new Location country from the database row
     #[allow(clippy::missing_const_for_fn)]
     #[must_use]
-    pub fn from_row(id: ModelID, name: String) -> Self {
-        Self { id, name }
+    pub fn from_row(
+        id: ModelID,
+        name: String,
+        code: Option<String>,
+        currency: Option<String>,
+        timezone: Option<String>,
+        names: Option<serde_json::Value>,
+    ) -> Self {
+        Self {
+            id,
+            name,
+            code,
+            currency,
+            timezone,
+            names: try_into_names(names),
+        }
+    }
+
+    /// Replaces the country name with its name in `locale`, if it has one
+    #[must_use]
+    pub fn localize(mut self, locale: Option<&str>) -> Self {
+        if let Some(name) = locale.and_then(|locale| self.names.get(locale)) {
+            self.name = name.clone();
+        }
+        self
     }
 }
@@ -6,7 +6,7 @@ use crate::{
     endpoint::EndpointRejection,
     error::{ServerError, ServerResult},
     server::state::DatabaseConnection,
-    services::produce::harvest::{delete_harvest_photos, models::HarvestIndex},
+    services::produce::harvest::{archive_restore_cutoff, models::HarvestIndex, ArchiveGraceDays},
     types::ModelID,
     types::{ModelIdentifier, ModelIndex, Pagination},
 };
@@ -14,9 +14,11 @@ use crate::{
 use super::{
     forms::{LocationInsertData, LocationUpdateData},
     models::{Location, LocationIndex, LocationList},
+    schedule::LocationSchedule,
     utils::{
-        archive_location, archive_location_harvests, delete_location, delete_location_harvests,
-        location_archived_harvest_count, location_harvest_photos,
+        archive_location, archive_location_harvests, delete_expired_locations,
+        delete_location_photos, finalize_archived_locations, restore_location,
+        restore_location_harvests,
     },
 };
 
@@ -87,12 +89,17 @@ impl Location {
         db: DatabaseConnection,
     ) -> ServerResult<Option<Self>> {
         let (offset, limit) = pg.unwrap_or_default().offset_limit();
+        let schedule = LocationSchedule::find(id, db.clone())
+            .await?
+            .unwrap_or_default();
         match sqlx::query!(
             r#"
                 SELECT location_.id AS location_id,
                     location_.place_name AS location_place_name,
                     location_.coords AS location_coords,
                     location_.description AS location_description,
+                    location_.access_notes AS location_access_notes,
+                    location_.images AS location_images,
                     region.name AS "location_region?",
                     country.name AS location_country,
                     farm.id AS farm_id,
@@ -134,15 +141,20 @@ impl Location {
             Ok(records) if records.is_empty() => Ok(None),
             Ok(records) => {
                 let first_rec = &records[0];
-
-                let location_id = first_rec.location_id.into();
-                let farm_id = first_rec.farm_id.into();
-                let farm_name = first_rec.farm_name.clone();
-                let place_name = first_rec.location_place_name.clone();
-                let region = first_rec.location_region.clone();
-                let country = first_rec.location_country.clone();
-                let coords = first_rec.location_coords.clone();
-                let description = first_rec.location_description.clone();
+                let mut location = Self::from_row(
+                    first_rec.location_id.into(),
+                    first_rec.location_place_name.clone(),
+                    first_rec.location_region.clone(),
+                    first_rec.location_country.clone(),
+                    first_rec.location_coords.clone(),
+                    first_rec.location_description.clone(),
+                    first_rec.location_access_notes.clone(),
+                    first_rec.location_images.clone(),
+                    schedule,
+                    first_rec.farm_id.into(),
+                    first_rec.farm_name.clone(),
+                    None,
+                );
 
                 let harvests: Vec<_> = records
                     .into_iter()
@@ -160,6 +172,9 @@ impl Location {
                             rec.location_region,
                             rec.location_country,
                             rec.location_coords,
+                            Some(location.schedule.open_now),
+                            Some(location.schedule.open_today),
+                            rec.farm_id.into(),
                             rec.farm_name,
                             rec.farm_logo,
                             0.into(), // boost amount not important
@@ -167,20 +182,7 @@ impl Location {
                     })
                     .collect();
 
-                let harvests = (!harvests.is_empty()).then_some(harvests);
-
-                let location = Self::from_row(
-                    location_id,
-                    place_name,
-                    region,
-                    country,
-                    coords,
-                    description,
-                    farm_id,
-                    farm_name,
-                    harvests,
-                );
-
+                location.harvests = (!harvests.is_empty()).then_some(harvests);
                 Ok(Some(location))
             }
             Err(err) => {
@@ -206,10 +208,18 @@ impl Location {
                     country_id, 
                     description, 
                     coords,
+                    timezone,
+                    pickup_instructions,
+                    access_notes,
                     deleted,
                     created_at
                 )
-                 VALUES($1, $2, $3, $4, $5, $6, $7, false, $8);
+                 VALUES($1, $2, $3, $4, $5, $6, $7,
+                    COALESCE($8, (
+                        SELECT country.timezone FROM services.countries country
+                        WHERE country.id = $5
+                    ), 'UTC'),
+                    $9, $10, false, $11);
             "#,
             location.id.0,
             location.farm_id.0,
@@ -218,6 +228,9 @@ impl Location {
             location.country_id.0,
             location.description,
             location.coords,
+            location.timezone,
+            location.pickup_instructions,
+            location.access_notes,
             location.created_at,
         )
         .execute(&db.pool)
@@ -251,14 +264,20 @@ impl Location {
                     region_id = $2,
                     country_id = COALESCE($3, location.country_id),
                     description = COALESCE($4, location.description),
-                    coords = $5
-                WHERE location.id = $6;
+                    coords = $5,
+                    timezone = COALESCE($6, location.timezone),
+                    pickup_instructions = COALESCE($7, location.pickup_instructions),
+                    access_notes = COALESCE($8, location.access_notes)
+                WHERE location.id = $9;
             "#,
             location.place_name,
             location.region_id.0,
             location.country_id.0,
             location.description,
             location.coords,
+            location.timezone,
+            location.pickup_instructions,
+            location.access_notes,
             id.0
         )
         .execute(&db.pool)
@@ -280,49 +299,159 @@ impl Location {
 
     /// Deletes farm location from the database
     ///
-    /// Location will ony be deleted if its not the only farm location
-    /// and it does not have any harvests incl archived ones
+    /// Location and its harvests are archived and can be restored
+    /// within the restore grace period, afterwards the location
+    /// will only be deleted if it does not have any harvests incl archived ones
     #[tracing::instrument(name = "Delete Location", skip(db))]
-    #[allow(clippy::cast_sign_loss)]
     pub async fn delete(id: ModelID, db: DatabaseConnection) -> ServerResult<()> {
-        let image_paths = tokio::spawn({
-            let db = db.clone();
-            async move { location_harvest_photos(id, db).await }
-        })
-        .await??;
-
-        let old_archived_harvest_count = tokio::spawn({
-            let db = db.clone();
-            async move { location_archived_harvest_count(id, db).await }
-        })
-        .await??;
+        let mut tx = db.pool.begin().await?;
+
+        let archived_at = OffsetDateTime::now_utc();
+        archive_location_harvests(id, archived_at, &mut tx).await?;
+        archive_location(id, archived_at, &mut tx).await?;
+
+        tx.commit().await?;
+        tracing::debug!("Location::delete transaction committed successfully.");
+
+        Ok(())
+    }
+
+    /// Restores the deleted location and the harvests
+    /// deleted together with it in the database
+    ///
+    /// Location can only be restored within the restore grace period
+    /// of being deleted and while its farm is active.
+    #[tracing::instrument(name = "Restore Location", skip(db))]
+    pub async fn restore(
+        id: ModelID,
+        grace_days: ArchiveGraceDays,
+        db: DatabaseConnection,
+    ) -> ServerResult<()> {
+        let cutoff = archive_restore_cutoff(OffsetDateTime::now_utc(), grace_days)?;
 
         let mut tx = db.pool.begin().await?;
 
-        // Cleanup harvests
-        let finished_at = OffsetDateTime::now_utc();
-        let new_archived_harvest_count =
-            archive_location_harvests(id, finished_at, &mut tx).await?;
-        _ = delete_location_harvests(id, finished_at, &mut tx).await?;
+        let Some(archived_at) = restore_location(id, cutoff, &mut tx).await? else {
+            return Err(ServerError::rejection(EndpointRejection::NotFound(
+                "Location not found or can no longer be restored.".into(),
+            )));
+        };
+        restore_location_harvests(id, archived_at, &mut tx).await?;
 
-        let archived_count = old_archived_harvest_count as u64 + new_archived_harvest_count;
+        tx.commit().await?;
+        tracing::debug!("Location::restore transaction committed successfully.");
 
-        // Delete or archive location
-        if archived_count == 0 {
-            delete_location(id, &mut tx).await?;
-        } else {
-            archive_location(id, &mut tx).await?;
-        }
+        Ok(())
+    }
+
+    /// Finalizes locations archived for longer than the restore grace period
+    ///
+    /// Locations that do not have any harvests incl archived ones are deleted.
+    /// Must run after the archived harvests are purged.
+    #[tracing::instrument(name = "Purge archived Locations", skip(db))]
+    pub async fn purge_archived(
+        grace_days: ArchiveGraceDays,
+        db: DatabaseConnection,
+    ) -> ServerResult<()> {
+        let cutoff = archive_restore_cutoff(OffsetDateTime::now_utc(), grace_days)?;
 
+        let mut tx = db.pool.begin().await?;
+        let mut images = delete_expired_locations(cutoff, &mut tx).await?;
+        images.extend(finalize_archived_locations(cutoff, &mut tx).await?);
         tx.commit().await?;
-        tracing::debug!("Location::delete transaction committed successfully.");
+        tracing::debug!("Location::purge_archived, transaction committed successfully.");
 
-        // Delete active harvest images
-        tokio::spawn(async move { delete_harvest_photos(image_paths.into_iter().flatten()).await });
+        // Delete archived location images
+        if delete_location_photos(images.into_iter()).await.is_err() {
+            tracing::error!("Io error, failed to delete archived location images, but locations were purged successfully.");
+        }
 
         Ok(())
     }
 
+    /// Inserts location image-paths into the database
+    /// Returns paths of old images
+    #[tracing::instrument(name = "Database::location-insert-image", skip(db))]
+    pub async fn insert_photos(
+        id: ModelID,
+        paths: Vec<String>,
+        db: DatabaseConnection,
+    ) -> ServerResult<Option<Vec<String>>> {
+        match sqlx::query!(
+            r#"
+                UPDATE services.locations location_
+                SET images = $1
+                WHERE location_.id = $2
+
+                RETURNING (
+                    SELECT location_.images
+                    FROM services.locations location_
+                    WHERE  location_.id = $2
+                ) AS old_images
+           "#,
+            &paths[..],
+            id.0
+        )
+        .fetch_one(&db.pool)
+        .await
+        {
+            Ok(rec) => {
+                tracing::debug!("Location image-paths inserted successfully");
+                Ok(rec.old_images)
+            }
+            Err(err) => {
+                // Handle database constraint error
+                handle_location_database_error(&err)?;
+
+                tracing::error!(
+                    "Database error, failed to insert location image-paths: {}",
+                    err
+                );
+                Err(err.into())
+            }
+        }
+    }
+
+    /// Deletes location image-paths from the database
+    #[tracing::instrument(name = "Database::location-delete-image", skip(db))]
+    pub async fn delete_photos(id: ModelID, db: DatabaseConnection) -> ServerResult<()> {
+        match sqlx::query!(
+            r#"
+                UPDATE services.locations location_
+                SET images = NULL
+                WHERE location_.id = $1
+
+                RETURNING (
+                    SELECT location_.images
+                    FROM services.locations location_
+                    WHERE  location_.id = $1
+                ) AS images
+           "#,
+            id.0
+        )
+        .fetch_one(&db.pool)
+        .await
+        {
+            Ok(rec) => {
+                tracing::debug!("Location image-paths deleted successfully");
+
+                // Delete images from the file system
+                if let Some(images) = rec.images {
+                    tokio::spawn(async move { delete_location_photos(images.into_iter()).await });
+                }
+
+                Ok(())
+            }
+            Err(err) => {
+                // Handle database constraint error
+                handle_location_database_error(&err)?;
+
+                tracing::error!("Database error, failed to delete image-paths: {}", err);
+                Err(err.into())
+            }
+        }
+    }
+
     /// Fetches country regions from the database
     pub async fn regions(country_id: ModelID, db: DatabaseConnection) -> ServerResult<ModelIndex> {
         match sqlx::query!(
@@ -14,12 +14,16 @@ use crate::{
         validators::{TransformString, ValidateString},
         EndpointRejection, EndpointResult,
     },
-    server::state::ServerState,
-    services::farmers::farm::permissions::check_user_owns_farm,
+    server::state::{DatabaseConnection, ServerState},
+    services::farmers::farm::permissions::check_user_can_manage_farm,
     types::ModelID,
 };
 
-use super::permissions::check_user_owns_location;
+use super::{
+    permissions::check_user_can_manage_location,
+    region::{boundary::validate_coords, Region},
+    schedule::forms::validate_timezone,
+};
 
 /// Embedded location create form,
 /// this form is embedded in `FarmCreateForm`.
@@ -34,6 +38,9 @@ pub struct LocationEmbeddedForm {
     pub country_id: String,
     pub description: Option<String>,
     pub coords: Option<Point>,
+    pub timezone: Option<String>,
+    pub pickup_instructions: Option<String>,
+    pub access_notes: Option<String>,
 }
 
 impl LocationEmbeddedForm {
@@ -48,6 +55,10 @@ impl LocationEmbeddedForm {
         self.place_name
             .validate_len(0, 64, "Place name must be at most 64 characters")?;
 
+        if let Some(ref coords) = self.coords {
+            validate_coords(coords)?;
+        }
+
         if let Some(ref desc) = self.description {
             desc.validate_len(
                 0,
@@ -56,6 +67,18 @@ impl LocationEmbeddedForm {
             )?;
         }
 
+        if let Some(ref instructions) = self.pickup_instructions {
+            instructions.validate_len(
+                0,
+                512,
+                "Pickup instructions must be at most 512 characters",
+            )?;
+        }
+
+        if let Some(ref notes) = self.access_notes {
+            notes.validate_len(0, 512, "Access notes must be at most 512 characters")?;
+        }
+
         Ok(())
     }
 
@@ -63,6 +86,12 @@ impl LocationEmbeddedForm {
     fn clean_data(&mut self) {
         self.place_name = self.place_name.clean().to_titlecase();
         self.description = self.description.as_ref().map(|desc| desc.clean());
+        self.timezone = self.timezone.as_ref().map(|tz| tz.clean());
+        self.pickup_instructions = self
+            .pickup_instructions
+            .as_ref()
+            .map(|instructions| instructions.clean());
+        self.access_notes = self.access_notes.as_ref().map(|notes| notes.clean());
     }
 
     /// Converts `Self` into `LocationInsertData`
@@ -77,6 +106,9 @@ impl LocationEmbeddedForm {
             country_id: ModelID::from_str_unchecked(&self.country_id),
             description: self.description,
             coords: serde_json::to_value(self.coords).ok(),
+            timezone: self.timezone,
+            pickup_instructions: self.pickup_instructions,
+            access_notes: self.access_notes,
             created_at: OffsetDateTime::now_utc().date(),
         }
     }
@@ -94,6 +126,9 @@ pub struct LocationCreateForm {
     pub country_id: String,
     pub description: Option<String>,
     pub coords: Option<Point>,
+    pub timezone: Option<String>,
+    pub pickup_instructions: Option<String>,
+    pub access_notes: Option<String>,
 }
 
 /// Location create cleaned data
@@ -106,6 +141,10 @@ pub struct LocationInsertData {
     pub country_id: ModelID,
     pub description: Option<String>,
     pub coords: Option<serde_json::Value>,
+    /// Falls back to the country timezone, or `UTC` if the country has none
+    pub timezone: Option<String>,
+    pub pickup_instructions: Option<String>,
+    pub access_notes: Option<String>,
     pub created_at: Date,
 }
 
@@ -121,6 +160,10 @@ impl LocationCreateForm {
         self.place_name
             .validate_len(0, 64, "Place name must be at most 64 characters")?;
 
+        if let Some(ref coords) = self.coords {
+            validate_coords(coords)?;
+        }
+
         if let Some(ref desc) = self.description {
             desc.validate_len(
                 0,
@@ -129,6 +172,18 @@ impl LocationCreateForm {
             )?;
         }
 
+        if let Some(ref instructions) = self.pickup_instructions {
+            instructions.validate_len(
+                0,
+                512,
+                "Pickup instructions must be at most 512 characters",
+            )?;
+        }
+
+        if let Some(ref notes) = self.access_notes {
+            notes.validate_len(0, 512, "Access notes must be at most 512 characters")?;
+        }
+
         Ok(())
     }
 
@@ -136,6 +191,12 @@ impl LocationCreateForm {
     fn clean_data(&mut self) {
         self.place_name = self.place_name.clean().to_titlecase();
         self.description = self.description.as_ref().map(|desc| desc.clean());
+        self.timezone = self.timezone.as_ref().map(|tz| tz.clean());
+        self.pickup_instructions = self
+            .pickup_instructions
+            .as_ref()
+            .map(|instructions| instructions.clean());
+        self.access_notes = self.access_notes.as_ref().map(|notes| notes.clean());
     }
 
     /// Convert `Self` into `LocationInsertData`
@@ -150,6 +211,9 @@ impl LocationCreateForm {
             country_id: ModelID::from_str_unchecked(&self.country_id),
             description: self.description,
             coords: serde_json::to_value(self.coords).ok(),
+            timezone: self.timezone,
+            pickup_instructions: self.pickup_instructions,
+            access_notes: self.access_notes,
             created_at: OffsetDateTime::now_utc().date(),
         }
     }
@@ -160,7 +224,7 @@ impl LocationCreateForm {
         farm_id: ModelID,
         state: &ServerState,
     ) -> EndpointResult<()> {
-        check_user_owns_farm(user.id(), farm_id, state.database()).await
+        check_user_can_manage_farm(user.id(), farm_id, state.database()).await
     }
 }
 
@@ -181,6 +245,16 @@ where
 
         // Validate from fields
         location.validate()?;
+        if let Some(ref timezone) = location.timezone {
+            validate_timezone(timezone, state.database()).await?;
+        }
+        validate_location_area(
+            &location.region_id,
+            &location.country_id,
+            location.coords,
+            state.database(),
+        )
+        .await?;
 
         // Authorize the request
         Self::authorize_request(user, farm_id, state).await?;
@@ -200,6 +274,9 @@ pub struct LocationUpdateForm {
     pub country_id: String,
     pub description: Option<String>,
     pub coords: Option<Point>,
+    pub timezone: Option<String>,
+    pub pickup_instructions: Option<String>,
+    pub access_notes: Option<String>,
 }
 
 /// Location update form cleaned data
@@ -210,6 +287,9 @@ pub struct LocationUpdateData {
     pub country_id: ModelID,
     pub description: Option<String>,
     pub coords: Option<serde_json::Value>,
+    pub timezone: Option<String>,
+    pub pickup_instructions: Option<String>,
+    pub access_notes: Option<String>,
 }
 
 impl From<LocationUpdateForm> for LocationUpdateData {
@@ -220,6 +300,9 @@ impl From<LocationUpdateForm> for LocationUpdateData {
             country_id: ModelID::from_str_unchecked(form.country_id),
             description: form.description,
             coords: serde_json::to_value(form.coords).ok(),
+            timezone: form.timezone,
+            pickup_instructions: form.pickup_instructions,
+            access_notes: form.access_notes,
         }
     }
 }
@@ -236,6 +319,10 @@ impl LocationUpdateForm {
         self.place_name
             .validate_len(0, 64, "Place name must be at most 64 characters")?;
 
+        if let Some(ref coords) = self.coords {
+            validate_coords(coords)?;
+        }
+
         if let Some(ref desc) = self.description {
             desc.validate_len(
                 0,
@@ -244,6 +331,18 @@ impl LocationUpdateForm {
             )?;
         }
 
+        if let Some(ref instructions) = self.pickup_instructions {
+            instructions.validate_len(
+                0,
+                512,
+                "Pickup instructions must be at most 512 characters",
+            )?;
+        }
+
+        if let Some(ref notes) = self.access_notes {
+            notes.validate_len(0, 512, "Access notes must be at most 512 characters")?;
+        }
+
         Ok(())
     }
 
@@ -251,15 +350,21 @@ impl LocationUpdateForm {
     fn clean_data(&mut self) {
         self.place_name = self.place_name.clean().to_titlecase();
         self.description = self.description.as_ref().map(|desc| desc.clean());
+        self.timezone = self.timezone.as_ref().map(|tz| tz.clean());
+        self.pickup_instructions = self
+            .pickup_instructions
+            .as_ref()
+            .map(|instructions| instructions.clean());
+        self.access_notes = self.access_notes.as_ref().map(|notes| notes.clean());
     }
 
-    ///  Validate a user has the permissions to crate a location on this farm
+    ///  Validate a user has the permissions to update this location
     async fn authorize_request(
         user: FarmerUser,
         location_id: ModelID,
         state: &ServerState,
     ) -> EndpointResult<()> {
-        check_user_owns_location(user.id(), location_id, state.database()).await
+        check_user_can_manage_location(user.id(), location_id, state.database()).await
     }
 }
 
@@ -280,6 +385,16 @@ where
 
         // Validate form fields
         location.validate()?;
+        if let Some(ref timezone) = location.timezone {
+            validate_timezone(timezone, state.database()).await?;
+        }
+        validate_location_area(
+            &location.region_id,
+            &location.country_id,
+            location.coords,
+            state.database(),
+        )
+        .await?;
 
         // Authorize the request
         Self::authorize_request(user, location_id, state).await?;
@@ -287,3 +402,61 @@ where
         Ok(location)
     }
 }
+
+// ===== Helpers =====
+
+/// Validate the region is in the country
+/// and the coordinates fall inside the region boundary
+///
+/// Regions without a boundary accept any coordinates.
+///
+/// # Errors
+///
+/// Return bad request if the location is outside the region
+pub async fn validate_location_area(
+    region_id: &str,
+    country_id: &str,
+    coords: Option<Point>,
+    db: DatabaseConnection,
+) -> EndpointResult<()> {
+    let region_id = ModelID::from_str_unchecked(region_id);
+    let country_id = ModelID::from_str_unchecked(country_id);
+
+    let Some(area) = Region::find_area(region_id, db.clone()).await? else {
+        return Err(EndpointRejection::BadRequest("Region not found.".into()));
+    };
+
+    if area.country_id != Some(country_id) {
+        return Err(EndpointRejection::BadRequest(
+            "Region not found in the selected country.".into(),
+        ));
+    }
+
+    let (Some(coords), Some(boundary)) = (coords, area.boundary) else {
+        return Ok(());
+    };
+
+    if boundary.contains(&coords) {
+        return Ok(());
+    }
+
+    let msg = match Region::containing(coords, db).await? {
+        Some(suggestion) => format!(
+            "Location coordinates are outside the selected region, they fall in {}, {}.",
+            suggestion.region.name, suggestion.country.name
+        ),
+        None => "Location coordinates are outside the selected region.".to_owned(),
+    };
+    Err(EndpointRejection::BadRequest(msg.into()))
+}
+
+// ===== Locale query impls =====
+
+/// Query parameters selecting the language
+/// countries and regions names are returned in
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct LocaleQuery {
+    /// Locale of the names e.g. `de` or `pt-BR`,
+    /// names without a translation are returned as is.
+    pub lang: Option<String>,
+}
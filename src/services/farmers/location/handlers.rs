@@ -1,14 +1,17 @@
 //! Location http handlers impls
 
 use axum::{
-    extract::{Json, Query, State},
+    extract::{Json, Multipart, Query, State},
     http::StatusCode,
 };
 
 use crate::{
     auth::{AdminUser, FarmerUser},
     endpoint::{EndpointRejection, EndpointResult},
+    files,
     server::state::DatabaseConnection,
+    services::produce::harvest::ArchiveGraceDays,
+    settings::LOCATION_UPLOAD_DIR,
     types::ModelID,
     types::{ModelIndex, Pagination},
 };
@@ -16,7 +19,8 @@ use crate::{
 use super::{
     forms::{LocationCreateForm, LocationUpdateForm},
     models::{Location, LocationList},
-    permissions::LocationDeletePermission,
+    permissions::{LocationDeletePermission, LocationManagePermission, LocationRestorePermission},
+    utils::delete_location_photos,
 };
 
 /// Handles the `GET /locations` route.
@@ -80,6 +84,62 @@ pub async fn location_delete(
     Ok(StatusCode::NO_CONTENT)
 }
 
+/// Handles the `POST /locations/:location_id/restore` route.
+#[tracing::instrument(skip(db))]
+pub async fn location_restore(
+    _: LocationRestorePermission,
+    location_id: ModelID,
+    State(db): State<DatabaseConnection>,
+    State(grace_days): State<ArchiveGraceDays>,
+) -> EndpointResult<StatusCode> {
+    Location::restore(location_id, grace_days, db).await?;
+    Ok(StatusCode::OK)
+}
+
+/// Handles the `POST /locations/:location_id/photos` route.
+///
+/// Replaces the location gallery with the uploaded images
+#[tracing::instrument(skip(db, multipart))]
+pub async fn location_image_uploads(
+    _: LocationManagePermission,
+    location_id: ModelID,
+    State(db): State<DatabaseConnection>,
+    multipart: Multipart,
+) -> EndpointResult<Json<Vec<String>>> {
+    let (handler, mut uploads) = files::accept_uploads(multipart, crate::LOCATION_MAX_IMAGE);
+
+    // Receive images
+    tokio::spawn(async move { handler.accept().await });
+
+    let mut paths = Vec::with_capacity(crate::LOCATION_MAX_IMAGE as usize);
+    while let Some(file) = uploads.files().await {
+        // Save an image to the file system
+        paths.push(format!("{}.jpg", file.id));
+        file.save_image(LOCATION_UPLOAD_DIR).await?;
+    }
+
+    // Save image path to the database
+    // and delete old images if there is some
+    if let Some(old_images) = Location::insert_photos(location_id, paths.clone(), db).await? {
+        tokio::spawn(async move { delete_location_photos(old_images.into_iter()).await });
+    }
+
+    Ok(Json(paths))
+}
+
+/// Handles the `DELETE /locations/:location_id/photos` route.
+///
+/// Deletes all images uploaded for this location
+#[tracing::instrument(skip(db))]
+pub async fn location_image_delete(
+    _: LocationManagePermission,
+    location_id: ModelID,
+    State(db): State<DatabaseConnection>,
+) -> EndpointResult<StatusCode> {
+    Location::delete_photos(location_id, db).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
 /// Handles the `GET /locations/countries/:country_id/regions` route.
 #[tracing::instrument(skip(db))]
 pub async fn region_list(
@@ -0,0 +1,226 @@
+//! Countries and regions bulk import database impl
+
+use crate::{
+    endpoint::EndpointRejection,
+    error::{ServerError, ServerResult},
+    server::state::DatabaseConnection,
+    types::ModelID,
+};
+
+use super::{ImportRecord, ImportSummary, LocationImport};
+
+impl LocationImport {
+    /// Inserts or updates the countries and regions in the database.
+    ///
+    /// Existing countries and regions are matched by their code, or by
+    /// their name if they do not have a code yet. Localized names are merged
+    /// with the existing ones. Nothing is saved if one of the records fails.
+    #[tracing::instrument(name = "Save LocationImport", skip(self, db))]
+    pub async fn save(self, db: DatabaseConnection) -> ServerResult<ImportSummary> {
+        let mut summary = ImportSummary::default();
+        let mut tx = db.pool.begin().await?;
+
+        for country in &self.countries {
+            if upsert_country(country, &mut tx).await? {
+                summary.countries_created += 1;
+            } else {
+                summary.countries_updated += 1;
+            }
+        }
+
+        for region in &self.regions {
+            if upsert_region(region, &mut tx).await? {
+                summary.regions_created += 1;
+            } else {
+                summary.regions_updated += 1;
+            }
+        }
+
+        tx.commit().await?;
+        tracing::debug!("Locations imported successfully: {:?}", summary);
+        Ok(summary)
+    }
+}
+
+/// Inserts or updates the country in the database
+///
+/// Returns `true` if the country was created.
+async fn upsert_country(
+    country: &ImportRecord,
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+) -> ServerResult<bool> {
+    let names = serde_json::to_value(&country.names)?;
+    let updated = match sqlx::query!(
+        r#"
+            UPDATE services.countries country
+            SET code = $1,
+                name = $2,
+                currency = COALESCE($3, country.currency),
+                timezone = COALESCE($4, country.timezone),
+                names = country.names || $5
+            WHERE country.code = $1
+                OR (country.code IS NULL AND country.name = $2)
+        "#,
+        country.code,
+        country.name,
+        country.currency,
+        country.timezone,
+        names,
+    )
+    .execute(&mut **tx)
+    .await
+    {
+        Ok(result) => result.rows_affected() > 0,
+        Err(err) => {
+            handle_import_database_error(&err, &country.code)?;
+
+            tracing::error!("Database error, failed to update imported country: {}", err);
+            return Err(err.into());
+        }
+    };
+
+    if updated {
+        return Ok(false);
+    }
+
+    match sqlx::query!(
+        r#"
+            INSERT INTO services.countries (
+                id,
+                code,
+                name,
+                currency,
+                timezone,
+                names
+            )
+            VALUES ($1, $2, $3, $4, $5, $6);
+        "#,
+        ModelID::new().0,
+        country.code,
+        country.name,
+        country.currency,
+        country.timezone,
+        names,
+    )
+    .execute(&mut **tx)
+    .await
+    {
+        Ok(result) => {
+            tracing::trace!(
+                "Imported country inserted, but transaction not committed: {:?}",
+                result
+            );
+            Ok(true)
+        }
+        Err(err) => {
+            handle_import_database_error(&err, &country.code)?;
+
+            tracing::error!("Database error, failed to insert imported country: {}", err);
+            Err(err.into())
+        }
+    }
+}
+
+/// Inserts or updates the region in the database,
+/// the region country must exist or be part of the import.
+///
+/// Returns `true` if the region was created.
+async fn upsert_region(
+    region: &ImportRecord,
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+) -> ServerResult<bool> {
+    let names = serde_json::to_value(&region.names)?;
+    let updated = match sqlx::query!(
+        r#"
+            UPDATE services.regions region
+            SET code = $1,
+                name = $2,
+                country_id = country.id,
+                names = region.names || $3
+            FROM services.countries country
+            WHERE country.code = split_part($1, '-', 1)
+                AND (region.code = $1
+                    OR (region.code IS NULL
+                        AND region.country_id = country.id
+                        AND region.name = $2))
+        "#,
+        region.code,
+        region.name,
+        names,
+    )
+    .execute(&mut **tx)
+    .await
+    {
+        Ok(result) => result.rows_affected() > 0,
+        Err(err) => {
+            handle_import_database_error(&err, &region.code)?;
+
+            tracing::error!("Database error, failed to update imported region: {}", err);
+            return Err(err.into());
+        }
+    };
+
+    if updated {
+        return Ok(false);
+    }
+
+    match sqlx::query!(
+        r#"
+            INSERT INTO services.regions (
+                id,
+                country_id,
+                code,
+                name,
+                names
+            )
+            SELECT $1, country.id, $2, $3, $4
+            FROM services.countries country
+            WHERE country.code = split_part($2, '-', 1)
+        "#,
+        ModelID::new().0,
+        region.code,
+        region.name,
+        names,
+    )
+    .execute(&mut **tx)
+    .await
+    {
+        Ok(result) if result.rows_affected() == 0 => {
+            Err(ServerError::rejection(EndpointRejection::BadRequest(
+                format!("Region `{}` country not found.", region.code).into(),
+            )))
+        }
+        Ok(result) => {
+            tracing::trace!(
+                "Imported region inserted, but transaction not committed: {:?}",
+                result
+            );
+            Ok(true)
+        }
+        Err(err) => {
+            handle_import_database_error(&err, &region.code)?;
+
+            tracing::error!("Database error, failed to insert imported region: {}", err);
+            Err(err.into())
+        }
+    }
+}
+
+/// Handle countries and regions import database constraints errors
+fn handle_import_database_error(err: &sqlx::Error, code: &str) -> ServerResult<()> {
+    if let sqlx::Error::Database(db_err) = err {
+        // Handle db unique constraints
+        if db_err.is_unique_violation() {
+            tracing::error!(
+                "Database error, `{}` conflicts with an existing name. {:?}",
+                code,
+                err
+            );
+            return Err(ServerError::rejection(EndpointRejection::Conflict(
+                format!("`{code}` name is already used by another country or region.").into(),
+            )));
+        }
+    }
+
+    Ok(())
+}
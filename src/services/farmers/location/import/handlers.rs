@@ -0,0 +1,20 @@
+//! Countries and regions bulk import http handlers impls
+
+use axum::extract::{Json, State};
+
+use crate::{auth::AdminUser, endpoint::EndpointResult, server::state::DatabaseConnection};
+
+use super::{ImportSummary, LocationImport};
+
+/// Handles the `POST /locations/countries/import` route.
+///
+/// Imports countries and regions from a CSV or JSON file.
+#[tracing::instrument(skip(db, import))]
+pub async fn location_import(
+    _: AdminUser,
+    State(db): State<DatabaseConnection>,
+    import: LocationImport,
+) -> EndpointResult<Json<ImportSummary>> {
+    let summary = import.save(db).await?;
+    Ok(Json(summary))
+}
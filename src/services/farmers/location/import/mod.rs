@@ -0,0 +1,332 @@
+//! Countries and regions bulk import impls
+
+pub mod db;
+pub mod handlers;
+
+use std::{
+    collections::{BTreeSet, HashSet},
+    path::Path,
+};
+
+use axum::{
+    async_trait,
+    body::Bytes,
+    extract::{FromRequest, Request},
+    http::header,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    endpoint::{
+        validators::{TransformString, ValidateString},
+        EndpointRejection, EndpointResult,
+    },
+    server::state::{DatabaseConnection, ServerState},
+    services::farmers::location::{
+        country::forms::{clean_names, is_country_code, validate_currency, validate_names},
+        region::forms::is_region_code,
+        schedule::LocationSchedule,
+        LocalizedNames,
+    },
+};
+
+/// File format of the countries and regions import
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImportFormat {
+    Csv,
+    Json,
+}
+
+impl ImportFormat {
+    /// Import format from the request content type
+    #[must_use]
+    pub fn from_content_type(content_type: &str) -> Option<Self> {
+        let mime = content_type.split(';').next().unwrap_or_default().trim();
+        match mime {
+            "text/csv" => Some(Self::Csv),
+            "application/json" => Some(Self::Json),
+            _ => None,
+        }
+    }
+
+    /// Import format from the file extension
+    #[must_use]
+    pub fn from_path(path: &Path) -> Option<Self> {
+        let extension = path.extension()?.to_str()?.to_ascii_lowercase();
+        match extension.as_str() {
+            "csv" => Some(Self::Csv),
+            "json" => Some(Self::Json),
+            _ => None,
+        }
+    }
+}
+
+/// A country or region in the import file.
+///
+/// Records with an ISO 3166-1 code are countries, records
+/// with an ISO 3166-2 code are regions of the country the code starts with.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportRecord {
+    pub code: String,
+    pub name: String,
+    #[serde(default)]
+    pub currency: Option<String>,
+    #[serde(default)]
+    pub timezone: Option<String>,
+    #[serde(default)]
+    pub names: LocalizedNames,
+}
+
+impl ImportRecord {
+    /// Checks if the record is a country
+    #[must_use]
+    pub fn is_country(&self) -> bool {
+        is_country_code(&self.code)
+    }
+
+    /// Validates the record fields
+    fn validate(&mut self) -> Result<(), &'static str> {
+        // Clean the data
+        self.clean_data();
+
+        if !self.is_country() && !is_region_code(&self.code) {
+            return Err("code must be an ISO 3166-1 alpha-2 or ISO 3166-2 code");
+        }
+
+        if self.name.validate_len(1, 32, "").is_err() {
+            return Err("name must be between 1 and 32 characters");
+        }
+
+        if !self.is_country() && (self.currency.is_some() || self.timezone.is_some()) {
+            return Err("only countries can have a currency and timezone");
+        }
+
+        if let Some(ref currency) = self.currency {
+            if validate_currency(currency).is_err() {
+                return Err("currency must be an ISO 4217 code");
+            }
+        }
+
+        if validate_names(&self.names).is_err() {
+            return Err("localized names must be keyed by a language code e.g. `pt-BR`, and be between 1 and 32 characters");
+        }
+
+        Ok(())
+    }
+
+    /// Clean record data, empty optional fields are removed
+    fn clean_data(&mut self) {
+        let clean = |value: &Option<String>| {
+            value
+                .as_ref()
+                .map(|value| value.clean())
+                .filter(|value| !value.is_empty())
+        };
+
+        self.code = self.code.clean().to_uppercase();
+        self.name = self.name.clean().to_titlecase();
+        self.currency = clean(&self.currency).map(|currency| currency.to_uppercase());
+        self.timezone = clean(&self.timezone);
+        self.names = clean_names(std::mem::take(&mut self.names));
+    }
+}
+
+/// A record in the CSV import file, with the columns `code,name,currency,timezone,names`.
+/// The localized names are in the form of `de=Namibia;pt=Namíbia`.
+#[derive(Debug, Clone, Deserialize)]
+struct CsvRecord {
+    code: String,
+    name: String,
+    #[serde(default)]
+    currency: Option<String>,
+    #[serde(default)]
+    timezone: Option<String>,
+    #[serde(default)]
+    names: Option<String>,
+}
+
+impl TryFrom<CsvRecord> for ImportRecord {
+    type Error = &'static str;
+
+    fn try_from(record: CsvRecord) -> Result<Self, Self::Error> {
+        let names = record
+            .names
+            .as_deref()
+            .unwrap_or_default()
+            .split(';')
+            .filter(|name| !name.trim().is_empty())
+            .map(|name| {
+                name.split_once('=')
+                    .map(|(locale, name)| (locale.to_owned(), name.to_owned()))
+                    .ok_or("localized names must be in the form of `de=Namibia;pt=Namíbia`")
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok(Self {
+            code: record.code,
+            name: record.name,
+            currency: record.currency,
+            timezone: record.timezone,
+            names,
+        })
+    }
+}
+
+/// Validated countries and regions to import,
+/// countries are imported first so regions can refer to them.
+#[derive(Debug, Clone, Default)]
+pub struct LocationImport {
+    pub countries: Vec<ImportRecord>,
+    pub regions: Vec<ImportRecord>,
+}
+
+impl LocationImport {
+    /// Parses and validates the import file
+    ///
+    /// # Errors
+    ///
+    /// Return bad request if the file or one of its records is invalid
+    pub fn parse(data: &[u8], format: ImportFormat) -> EndpointResult<Self> {
+        let records = match format {
+            ImportFormat::Csv => parse_csv(data)?,
+            ImportFormat::Json => {
+                serde_json::from_slice::<Vec<ImportRecord>>(data).map_err(|err| {
+                    EndpointRejection::BadRequest(format!("Invalid JSON: {err}").into())
+                })?
+            }
+        };
+
+        if records.is_empty() {
+            return Err(EndpointRejection::BadRequest(
+                "Import file has no countries or regions.".into(),
+            ));
+        }
+
+        let mut import = Self::default();
+        let mut codes = HashSet::new();
+        for (index, mut record) in records.into_iter().enumerate() {
+            let number = index + 1;
+            record.validate().map_err(|msg| {
+                EndpointRejection::BadRequest(format!("Record {number}: {msg}.").into())
+            })?;
+
+            if !codes.insert(record.code.clone()) {
+                return Err(EndpointRejection::BadRequest(
+                    format!("Record {number}: code `{}` is duplicated.", record.code).into(),
+                ));
+            }
+
+            if record.is_country() {
+                import.countries.push(record);
+            } else {
+                import.regions.push(record);
+            }
+        }
+
+        Ok(import)
+    }
+
+    /// Reads, parses and validates the import file at `path`
+    ///
+    /// # Errors
+    ///
+    /// Return bad request if the file or one of its records is invalid
+    pub async fn from_file(path: &Path, db: DatabaseConnection) -> EndpointResult<Self> {
+        let Some(format) = ImportFormat::from_path(path) else {
+            return Err(EndpointRejection::BadRequest(
+                "Import file must be a `.csv` or `.json` file.".into(),
+            ));
+        };
+
+        let data = tokio::fs::read(path).await.map_err(|err| {
+            EndpointRejection::BadRequest(format!("Failed to read import file: {err}").into())
+        })?;
+
+        let import = Self::parse(&data, format)?;
+        import.validate_timezones(db).await?;
+        Ok(import)
+    }
+
+    /// Validates the countries timezones are known IANA timezone names
+    ///
+    /// # Errors
+    ///
+    /// Return bad request if one of the timezones is unknown
+    pub async fn validate_timezones(&self, db: DatabaseConnection) -> EndpointResult<()> {
+        let timezones: BTreeSet<_> = self
+            .countries
+            .iter()
+            .filter_map(|country| country.timezone.as_deref())
+            .collect();
+
+        for timezone in timezones {
+            if !LocationSchedule::timezone_exists(timezone, db.clone()).await? {
+                return Err(EndpointRejection::BadRequest(
+                    format!("Unknown timezone `{timezone}`.").into(),
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl FromRequest<ServerState> for LocationImport {
+    type Rejection = EndpointRejection;
+
+    async fn from_request(req: Request, state: &ServerState) -> Result<Self, Self::Rejection> {
+        let format = req
+            .headers()
+            .get(header::CONTENT_TYPE)
+            .and_then(|value| value.to_str().ok())
+            .and_then(ImportFormat::from_content_type)
+            .ok_or_else(|| {
+                EndpointRejection::UnsupportedMediaType(
+                    "Import file must be `text/csv` or `application/json`.".into(),
+                )
+            })?;
+
+        // Extract data
+        let data = Bytes::from_request(req, state)
+            .await
+            .map_err(|err| EndpointRejection::BadRequest(err.body_text().into()))?;
+
+        // Validate records
+        let import = Self::parse(&data, format)?;
+        import.validate_timezones(state.database()).await?;
+
+        Ok(import)
+    }
+}
+
+/// The number of countries and regions created and updated by the import
+#[derive(Debug, Clone, Copy, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportSummary {
+    pub countries_created: u64,
+    pub countries_updated: u64,
+    pub regions_created: u64,
+    pub regions_updated: u64,
+}
+
+/// Parses the CSV import file records
+fn parse_csv(data: &[u8]) -> EndpointResult<Vec<ImportRecord>> {
+    csv::ReaderBuilder::new()
+        .trim(csv::Trim::All)
+        .from_reader(data)
+        .deserialize::<CsvRecord>()
+        .enumerate()
+        .map(|(index, record)| {
+            let number = index + 1;
+            let record = record.map_err(|err| {
+                EndpointRejection::BadRequest(
+                    format!("Record {number}: invalid CSV, {err}.").into(),
+                )
+            })?;
+            ImportRecord::try_from(record).map_err(|msg| {
+                EndpointRejection::BadRequest(format!("Record {number}: {msg}.").into())
+            })
+        })
+        .collect()
+}
@@ -5,9 +5,12 @@ pub mod country;
 pub mod db;
 pub mod forms;
 pub mod handlers;
+pub mod import;
 pub mod models;
 pub mod permissions;
 pub mod region;
+pub mod schedule;
 mod utils;
 
-pub use models::try_into_point;
+pub use models::{try_into_names, try_into_point, LocalizedNames};
+pub use utils::delete_location_photos;
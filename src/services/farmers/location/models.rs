@@ -1,5 +1,7 @@
 //! Location model impls
 
+use std::collections::BTreeMap;
+
 use geo::Point;
 use serde::Serialize;
 
@@ -8,9 +10,14 @@ use crate::{
     services::produce::harvest::models::HarvestList,
 };
 
+use super::schedule::LocationSchedule;
+
 /// A `Vec` of locations
 pub type LocationList = Vec<LocationIndex>;
 
+/// Country or region names in other languages, keyed by locale e.g. `pt-BR`
+pub type LocalizedNames = BTreeMap<String, String>;
+
 /// The model representing a row in the `locations` database table.
 ///
 /// Returned by `location_detail` handler.
@@ -24,6 +31,9 @@ pub struct Location {
     pub country: String,
     pub coords: Option<Point>,
     pub description: Option<String>,
+    pub access_notes: Option<String>,
+    pub images: Option<Vec<String>>,
+    pub schedule: LocationSchedule,
     pub harvests: Option<HarvestList>,
 }
 
@@ -38,6 +48,9 @@ impl Location {
         country: String,
         coords: Option<serde_json::Value>,
         description: Option<String>,
+        access_notes: Option<String>,
+        images: Option<Vec<String>>,
+        schedule: LocationSchedule,
         farm_id: ModelID,
         farm_name: String,
         harvests: Option<HarvestList>,
@@ -50,6 +63,9 @@ impl Location {
             region,
             coords: try_into_point(coords),
             description,
+            access_notes,
+            images,
+            schedule,
             harvests,
         }
     }
@@ -98,3 +114,11 @@ impl LocationIndex {
 pub fn try_into_point(coords: Option<serde_json::Value>) -> Option<Point> {
     coords.and_then(|value| serde_json::from_value(value).ok())
 }
+
+/// Try convert json value to [`LocalizedNames`],
+#[must_use]
+pub fn try_into_names(names: Option<serde_json::Value>) -> LocalizedNames {
+    names
+        .and_then(|value| serde_json::from_value(value).ok())
+        .unwrap_or_default()
+}
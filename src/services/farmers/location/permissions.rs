@@ -6,9 +6,12 @@ use crate::{
     auth::FarmerUser,
     endpoint::{EndpointRejection, EndpointResult},
     server::state::{DatabaseConnection, ServerState},
+    services::farmers::farm::member::FarmRole,
     types::ModelID,
 };
 
+use super::schedule::LocationHoliday;
+
 /// Checks if user can delete location
 #[derive(Debug, Clone)]
 pub struct LocationDeletePermission;
@@ -24,7 +27,7 @@ impl FromRequestParts<ServerState> for LocationDeletePermission {
         let user = FarmerUser::from_parts(parts, state).await?;
         let location_id = ModelID::from_request_parts(parts, state).await?;
 
-        check_user_owns_location(user.id(), location_id, state.database()).await?;
+        check_user_can_manage_location(user.id(), location_id, state.database()).await?;
         let Some(count) = get_location_count(location_id, state.database()).await? else {
             return Err(EndpointRejection::forbidden());
         };
@@ -39,32 +42,110 @@ impl FromRequestParts<ServerState> for LocationDeletePermission {
     }
 }
 
-/// Validate  user owns a location
-pub async fn check_user_owns_location(
+/// Checks if user can restore the deleted location
+#[derive(Debug, Clone)]
+pub struct LocationRestorePermission;
+
+#[async_trait]
+impl FromRequestParts<ServerState> for LocationRestorePermission {
+    type Rejection = EndpointRejection;
+
+    async fn from_request_parts(
+        parts: &mut Parts,
+        state: &ServerState,
+    ) -> Result<Self, Self::Rejection> {
+        let user = FarmerUser::from_parts(parts, state).await?;
+        let location_id = ModelID::from_request_parts(parts, state).await?;
+
+        check_user_can_manage_location(user.id(), location_id, state.database()).await?;
+
+        Ok(Self)
+    }
+}
+
+/// Checks if user can manage the location photos
+#[derive(Debug, Clone)]
+pub struct LocationManagePermission;
+
+#[async_trait]
+impl FromRequestParts<ServerState> for LocationManagePermission {
+    type Rejection = EndpointRejection;
+
+    async fn from_request_parts(
+        parts: &mut Parts,
+        state: &ServerState,
+    ) -> Result<Self, Self::Rejection> {
+        let user = FarmerUser::from_parts(parts, state).await?;
+        let location_id = ModelID::from_request_parts(parts, state).await?;
+
+        check_user_can_manage_location(user.id(), location_id, state.database()).await?;
+
+        Ok(Self)
+    }
+}
+
+/// Checks if user can delete the location holiday
+#[derive(Debug, Clone)]
+pub struct LocationHolidayDeletePermission;
+
+#[async_trait]
+impl FromRequestParts<ServerState> for LocationHolidayDeletePermission {
+    type Rejection = EndpointRejection;
+
+    async fn from_request_parts(
+        parts: &mut Parts,
+        state: &ServerState,
+    ) -> Result<Self, Self::Rejection> {
+        let user = FarmerUser::from_parts(parts, state).await?;
+        let holiday_id = ModelID::from_request_parts(parts, state).await?;
+
+        let Some(location_id) = LocationHoliday::location_id(holiday_id, state.database()).await?
+        else {
+            return Err(EndpointRejection::NotFound("Holiday not found.".into()));
+        };
+        check_user_can_manage_location(user.id(), location_id, state.database()).await?;
+
+        Ok(Self)
+    }
+}
+
+/// Validate user is the owner or manager of the location's farm
+pub async fn check_user_can_manage_location(
     user_id: ModelID,
     location_id: ModelID,
     db: DatabaseConnection,
+) -> EndpointResult<()> {
+    check_user_location_role(user_id, location_id, FarmRole::MANAGERS, db).await
+}
+
+/// Validate user is a member with one of the `roles`
+/// on the farm the location belongs to
+pub async fn check_user_location_role(
+    user_id: ModelID,
+    location_id: ModelID,
+    roles: &[FarmRole],
+    db: DatabaseConnection,
 ) -> EndpointResult<()> {
     match sqlx::query!(
         r#"
-            SELECT first_name
-            FROM accounts.users user_
-            LEFT JOIN services.farms farm
-                ON user_.id = farm.owner_id
+            SELECT member.role
+            FROM services.farm_members member
             LEFT JOIN services.locations location_
-                ON farm.id = location_.farm_id
+                ON member.farm_id = location_.farm_id
             WHERE (
-                user_.id = $1
+                member.user_id = $1
                 AND location_.id = $2
+                AND member.role = ANY($3)
             )
         "#,
         user_id.0,
-        location_id.0
+        location_id.0,
+        &FarmRole::to_rows(roles)[..]
     )
     .fetch_one(&db.pool)
     .await
     {
-        Ok(_user) => Ok(()),
+        Ok(_member) => Ok(()),
         Err(err) => {
             if matches!(err, sqlx::Error::RowNotFound) {
                 Err(EndpointRejection::forbidden())
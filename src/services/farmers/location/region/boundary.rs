@@ -0,0 +1,185 @@
+//! Region boundary impls
+
+use geo::{Area, BoundingRect, Contains, Coord, LineString, MultiPolygon, Point, Polygon, Rect};
+use serde::Deserialize;
+use serde_json::json;
+
+use crate::endpoint::{EndpointRejection, EndpointResult};
+
+/// Maximum number of positions allowed in a region boundary
+const BOUNDARY_MAX_POSITIONS: usize = 100_000;
+
+/// `GeoJSON` object the region boundary is loaded from
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "type")]
+pub enum GeoJson {
+    FeatureCollection {
+        features: Vec<Self>,
+    },
+    Feature {
+        geometry: Option<Box<Self>>,
+    },
+    Polygon {
+        coordinates: Vec<Vec<Vec<f64>>>,
+    },
+    MultiPolygon {
+        coordinates: Vec<Vec<Vec<Vec<f64>>>>,
+    },
+}
+
+impl GeoJson {
+    /// Collects the polygons rings of the `GeoJSON` object
+    fn into_rings(self, rings: &mut Vec<Vec<Vec<Vec<f64>>>>) {
+        match self {
+            Self::FeatureCollection { features } => {
+                for feature in features {
+                    feature.into_rings(rings);
+                }
+            }
+            Self::Feature { geometry } => {
+                if let Some(geometry) = geometry {
+                    geometry.into_rings(rings);
+                }
+            }
+            Self::Polygon { coordinates } => rings.push(coordinates),
+            Self::MultiPolygon { coordinates } => rings.extend(coordinates),
+        }
+    }
+}
+
+/// The area covered by a region
+#[derive(Debug, Clone, PartialEq)]
+pub struct RegionBoundary(MultiPolygon);
+
+impl RegionBoundary {
+    /// Creates a new `RegionBoundary` from a `GeoJSON` object
+    ///
+    /// # Errors
+    ///
+    /// Return bad request if the `GeoJSON` has no polygons or has invalid coordinates
+    pub fn try_from_geojson(geojson: GeoJson) -> EndpointResult<Self> {
+        let mut polygons = Vec::new();
+        geojson.into_rings(&mut polygons);
+
+        if polygons.is_empty() {
+            return Err(EndpointRejection::BadRequest(
+                "Region boundary must have at least one polygon.".into(),
+            ));
+        }
+
+        let positions: usize = polygons.iter().flatten().map(Vec::len).sum();
+        if positions > BOUNDARY_MAX_POSITIONS {
+            return Err(EndpointRejection::BadRequest(
+                format!("Region boundary must have at most {BOUNDARY_MAX_POSITIONS} positions.")
+                    .into(),
+            ));
+        }
+
+        let polygons = polygons
+            .into_iter()
+            .map(try_into_polygon)
+            .collect::<EndpointResult<Vec<_>>>()?;
+
+        Ok(Self(MultiPolygon::new(polygons)))
+    }
+
+    /// Creates a new `RegionBoundary` from the database column
+    #[must_use]
+    pub fn from_row(boundary: Option<serde_json::Value>) -> Option<Self> {
+        boundary
+            .and_then(|value| serde_json::from_value(value).ok())
+            .and_then(|geojson| Self::try_from_geojson(geojson).ok())
+    }
+
+    /// Returns the boundary as a `GeoJSON` `MultiPolygon` geometry
+    #[must_use]
+    pub fn to_geojson(&self) -> serde_json::Value {
+        let coordinates: Vec<Vec<Vec<[f64; 2]>>> = self
+            .0
+            .iter()
+            .map(|polygon| {
+                std::iter::once(polygon.exterior())
+                    .chain(polygon.interiors())
+                    .map(|ring| ring.coords().map(|coord| [coord.x, coord.y]).collect())
+                    .collect()
+            })
+            .collect();
+
+        json!({ "type": "MultiPolygon", "coordinates": coordinates })
+    }
+
+    /// Returns the smallest rectangle containing the boundary
+    #[must_use]
+    pub fn bounding_rect(&self) -> Option<Rect> {
+        self.0.bounding_rect()
+    }
+
+    /// Returns the boundary area in square degrees, used to rank nested regions
+    #[must_use]
+    pub fn area(&self) -> f64 {
+        self.0.unsigned_area()
+    }
+
+    /// Checks if the point falls inside the boundary
+    #[must_use]
+    pub fn contains(&self, point: &Point) -> bool {
+        self.0.contains(point)
+    }
+}
+
+/// Validate `coords` are valid longitude and latitude
+///
+/// # Errors
+///
+/// Return bad request if the coordinates are out of range
+pub fn validate_coords(coords: &Point) -> EndpointResult<()> {
+    if !(-180.0..=180.0).contains(&coords.x()) || !(-90.0..=90.0).contains(&coords.y()) {
+        return Err(EndpointRejection::BadRequest(
+            "Coordinates must be a valid longitude and latitude.".into(),
+        ));
+    }
+    Ok(())
+}
+
+/// Convert `GeoJSON` polygon rings into a `Polygon`,
+/// the first ring is the exterior and the others are holes.
+fn try_into_polygon(rings: Vec<Vec<Vec<f64>>>) -> EndpointResult<Polygon> {
+    let mut rings = rings.into_iter().map(try_into_ring);
+    let Some(exterior) = rings.next().transpose()? else {
+        return Err(EndpointRejection::BadRequest(
+            "Region boundary polygon must have an exterior ring.".into(),
+        ));
+    };
+    let interiors = rings.collect::<EndpointResult<Vec<_>>>()?;
+
+    Ok(Polygon::new(exterior, interiors))
+}
+
+/// Convert `GeoJSON` positions into a closed ring
+fn try_into_ring(positions: Vec<Vec<f64>>) -> EndpointResult<LineString> {
+    let coords = positions
+        .into_iter()
+        .map(|position| match position[..] {
+            [lng, lat, ..] => {
+                let point = Point::new(lng, lat);
+                validate_coords(&point)?;
+                Ok(Coord::from(point))
+            }
+            _ => Err(EndpointRejection::BadRequest(
+                "Region boundary position must have a longitude and latitude.".into(),
+            )),
+        })
+        .collect::<EndpointResult<Vec<_>>>()?;
+
+    let mut ring = LineString::new(coords);
+    ring.close();
+
+    // A closed ring needs at least three distinct positions
+    if ring.0.len() < 4 {
+        return Err(EndpointRejection::BadRequest(
+            "Region boundary ring must have at least three positions.".into(),
+        ));
+    }
+
+    Ok(ring)
+}
@@ -1,5 +1,7 @@
 //! Cultivar category database impl
 
+use geo::Point;
+
 use crate::{
     endpoint::EndpointRejection,
     error::{ServerError, ServerResult},
@@ -8,8 +10,9 @@ use crate::{
 };
 
 use super::{
+    boundary::RegionBoundary,
     forms::{RegionInsertData, RegionUpdateData},
-    Region, RegionList,
+    Country, Region, RegionArea, RegionList, RegionSuggestion,
 };
 
 impl Region {
@@ -19,8 +22,11 @@ impl Region {
         match sqlx::query!(
             r#"
                 SELECT  region.id,
-                     region.name
+                     region.name,
+                     region.code,
+                     region.names
                 FROM services.regions region
+                ORDER BY region.name
             "#
         )
         .fetch_all(&db.pool)
@@ -29,7 +35,7 @@ impl Region {
             Ok(records) => {
                 let regions = records
                     .into_iter()
-                    .map(|rec| Self::from_row(rec.id.into(), rec.name))
+                    .map(|rec| Self::from_row(rec.id.into(), rec.name, rec.code, Some(rec.names)))
                     .collect();
 
                 Ok(regions)
@@ -49,13 +55,17 @@ impl Region {
                 INSERT INTO services.regions (
                     id,
                     country_id,
-                    name
+                    name,
+                    code,
+                    names
                 )
-                VALUES ($1, $2, $3);
+                VALUES ($1, $2, $3, $4, $5);
             "#,
             region.id.0,
             region.country_id.0,
-            region.name
+            region.name,
+            region.code,
+            serde_json::to_value(&region.names).ok(),
         )
         .execute(&db.pool)
         .await
@@ -85,11 +95,17 @@ impl Region {
             r#"
                 UPDATE services.regions region
                 SET name = COALESCE($1, region.name),
-                    country_id = COALESCE($2, region.country_id)
-                WHERE region.id = $3
+                    country_id = COALESCE($2, region.country_id),
+                    code = COALESCE($3, region.code),
+                    names = COALESCE($4, region.names)
+                WHERE region.id = $5
            "#,
             region.name,
             region.country_id.map(|id| id.0),
+            region.code,
+            region
+                .names
+                .and_then(|names| serde_json::to_value(names).ok()),
             id.0
         )
         .execute(&db.pool)
@@ -137,6 +153,151 @@ impl Region {
     }
 }
 
+impl Region {
+    /// Fetches the region country and boundary from the database
+    #[tracing::instrument(name = "Find RegionArea", skip(db))]
+    pub async fn find_area(
+        id: ModelID,
+        db: DatabaseConnection,
+    ) -> ServerResult<Option<RegionArea>> {
+        match sqlx::query!(
+            r#"
+                SELECT region.country_id,
+                    region.boundary
+                FROM services.regions region
+                WHERE region.id = $1
+            "#,
+            id.0
+        )
+        .fetch_optional(&db.pool)
+        .await
+        {
+            Ok(rec) => Ok(rec.map(|rec| RegionArea {
+                country_id: rec.country_id.map(ModelID),
+                boundary: RegionBoundary::from_row(rec.boundary),
+            })),
+            Err(err) => {
+                tracing::error!("Database error, failed to fetch region area: {}", err);
+                Err(err.into())
+            }
+        }
+    }
+
+    /// Fetches the region the point falls in from the database,
+    /// the smallest region is returned if the point falls in nested regions.
+    #[tracing::instrument(name = "Find RegionSuggestion", skip(db))]
+    pub async fn containing(
+        point: Point,
+        db: DatabaseConnection,
+    ) -> ServerResult<Option<RegionSuggestion>> {
+        match sqlx::query!(
+            r#"
+                SELECT region.id AS region_id,
+                    region.name AS region_name,
+                    region.code AS region_code,
+                    region.names AS region_names,
+                    region.boundary AS "region_boundary!",
+                    country.id AS country_id,
+                    country.name AS country_name,
+                    country.code AS country_code,
+                    country.currency AS country_currency,
+                    country.timezone AS country_timezone,
+                    country.names AS country_names
+                FROM services.regions region
+                INNER JOIN services.countries country
+                    ON region.country_id = country.id
+
+                WHERE region.boundary IS NOT NULL
+                    AND $1 BETWEEN region.min_lng AND region.max_lng
+                    AND $2 BETWEEN region.min_lat AND region.max_lat
+            "#,
+            point.x(),
+            point.y()
+        )
+        .fetch_all(&db.pool)
+        .await
+        {
+            Ok(records) => {
+                let suggestion = records
+                    .into_iter()
+                    .filter_map(|rec| {
+                        let boundary = RegionBoundary::from_row(Some(rec.region_boundary))?;
+                        boundary.contains(&point).then(|| {
+                            let suggestion = RegionSuggestion {
+                                region: Self::from_row(
+                                    rec.region_id.into(),
+                                    rec.region_name,
+                                    rec.region_code,
+                                    Some(rec.region_names),
+                                ),
+                                country: Country::from_row(
+                                    rec.country_id.into(),
+                                    rec.country_name,
+                                    rec.country_code,
+                                    rec.country_currency,
+                                    rec.country_timezone,
+                                    Some(rec.country_names),
+                                ),
+                            };
+                            (boundary.area(), suggestion)
+                        })
+                    })
+                    .min_by(|(area, _), (other, _)| area.total_cmp(other))
+                    .map(|(_, suggestion)| suggestion);
+
+                Ok(suggestion)
+            }
+            Err(err) => {
+                tracing::error!("Database error, failed to fetch region at point: {}", err);
+                Err(err.into())
+            }
+        }
+    }
+
+    /// Updates the region boundary in the database,
+    /// the boundary is cleared if `boundary` is `None`
+    #[tracing::instrument(name = "Update Region boundary", skip(db, boundary))]
+    pub async fn update_boundary(
+        id: ModelID,
+        boundary: Option<RegionBoundary>,
+        db: DatabaseConnection,
+    ) -> ServerResult<()> {
+        let bounding_rect = boundary.as_ref().and_then(RegionBoundary::bounding_rect);
+        match sqlx::query!(
+            r#"
+                UPDATE services.regions region
+                SET boundary = $1,
+                    min_lng = $2,
+                    min_lat = $3,
+                    max_lng = $4,
+                    max_lat = $5
+                WHERE region.id = $6
+           "#,
+            boundary.as_ref().map(RegionBoundary::to_geojson),
+            bounding_rect.map(|rect| rect.min().x),
+            bounding_rect.map(|rect| rect.min().y),
+            bounding_rect.map(|rect| rect.max().x),
+            bounding_rect.map(|rect| rect.max().y),
+            id.0
+        )
+        .execute(&db.pool)
+        .await
+        {
+            Ok(result) if result.rows_affected() == 0 => Err(ServerError::rejection(
+                EndpointRejection::NotFound("Region not found.".into()),
+            )),
+            Ok(result) => {
+                tracing::debug!("Region boundary updated successfully: {:?}", result);
+                Ok(())
+            }
+            Err(err) => {
+                tracing::error!("Database error, failed to update region boundary: {}", err);
+                Err(err.into())
+            }
+        }
+    }
+}
+
 /// Handle regions database constraints errors
 #[allow(clippy::cognitive_complexity)]
 fn handle_region_database_error(err: &sqlx::Error) -> ServerResult<()> {
@@ -4,6 +4,7 @@ use axum::{
     async_trait,
     extract::{rejection::JsonRejection, FromRequest, Json, Request},
 };
+use geo::Point;
 use serde::Deserialize;
 
 use crate::{
@@ -12,15 +13,23 @@ use crate::{
         EndpointRejection, EndpointResult,
     },
     server::state::ServerState,
+    services::farmers::location::{
+        country::forms::{clean_names, is_country_code, validate_names},
+        LocalizedNames,
+    },
     types::ModelID,
 };
 
+use super::boundary::{validate_coords, GeoJson, RegionBoundary};
+
 /// Region create form
 #[derive(Debug, Clone, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct RegionForm {
     pub name: String,
     pub country_id: String,
+    pub code: Option<String>,
+    pub names: Option<LocalizedNames>,
 }
 
 /// Region create form cleaned data
@@ -29,6 +38,8 @@ pub struct RegionInsertData {
     pub id: ModelID,
     pub country_id: ModelID,
     pub name: String,
+    pub code: Option<String>,
+    pub names: LocalizedNames,
 }
 
 impl From<RegionForm> for RegionInsertData {
@@ -37,6 +48,8 @@ impl From<RegionForm> for RegionInsertData {
             id: ModelID::new(),
             country_id: ModelID::from_str_unchecked(&form.country_id),
             name: form.name,
+            code: form.code,
+            names: form.names.unwrap_or_default(),
         }
     }
 }
@@ -46,6 +59,8 @@ impl From<RegionForm> for RegionInsertData {
 pub struct RegionUpdateData {
     pub name: Option<String>,
     pub country_id: Option<ModelID>,
+    pub code: Option<String>,
+    pub names: Option<LocalizedNames>,
 }
 
 impl From<RegionForm> for RegionUpdateData {
@@ -53,6 +68,8 @@ impl From<RegionForm> for RegionUpdateData {
         Self {
             name: Some(form.name),
             country_id: Some(ModelID::from_str_unchecked(&form.country_id)),
+            code: form.code,
+            names: form.names,
         }
     }
 }
@@ -68,12 +85,22 @@ impl RegionForm {
 
         self.country_id.validate_id("Invalid country id")?;
 
+        if let Some(ref code) = self.code {
+            validate_region_code(code)?;
+        }
+
+        if let Some(ref names) = self.names {
+            validate_names(names)?;
+        }
+
         Ok(())
     }
 
     /// Clean form data
     fn clean_data(&mut self) {
         self.name = self.name.clean().to_titlecase();
+        self.code = self.code.as_ref().map(|code| code.clean().to_uppercase());
+        self.names = self.names.take().map(clean_names);
     }
 }
 
@@ -94,3 +121,74 @@ where
         Ok(region)
     }
 }
+
+/// Validates the region code is an ISO 3166-2 code, e.g. `NA-KH`
+pub fn validate_region_code(code: &str) -> EndpointResult<()> {
+    if is_region_code(code) {
+        Ok(())
+    } else {
+        Err(EndpointRejection::BadRequest(
+            "Region code must be an ISO 3166-2 code.".into(),
+        ))
+    }
+}
+
+/// Checks if `code` is an ISO 3166-2 code, the country code
+/// followed by up to three letters or digits
+#[must_use]
+pub fn is_region_code(code: &str) -> bool {
+    code.split_once('-').is_some_and(|(country, subdivision)| {
+        is_country_code(country)
+            && (1..=3).contains(&subdivision.len())
+            && subdivision
+                .bytes()
+                .all(|b| b.is_ascii_uppercase() || b.is_ascii_digit())
+    })
+}
+
+// ===== Region Boundary form impls =====
+
+/// Region boundary form, accepts a `GeoJSON` `Polygon`, `MultiPolygon`,
+/// `Feature` or `FeatureCollection`
+#[derive(Debug, Clone)]
+pub struct RegionBoundaryForm {
+    pub boundary: RegionBoundary,
+}
+
+#[async_trait]
+impl FromRequest<ServerState> for RegionBoundaryForm
+where
+    Json<GeoJson>: FromRequest<ServerState, Rejection = JsonRejection>,
+{
+    type Rejection = EndpointRejection;
+
+    async fn from_request(req: Request, state: &ServerState) -> Result<Self, Self::Rejection> {
+        // Extract data
+        let Json(geojson) = Json::<GeoJson>::from_request(req, state).await?;
+
+        // Validate form fields
+        let boundary = RegionBoundary::try_from_geojson(geojson)?;
+
+        Ok(Self { boundary })
+    }
+}
+
+/// The coordinates to find the region for
+#[derive(Debug, Clone, Deserialize)]
+pub struct RegionLookup {
+    pub lat: f64,
+    pub lng: f64,
+}
+
+impl RegionLookup {
+    /// Validates and returns the lookup coordinates
+    ///
+    /// # Errors
+    ///
+    /// Return bad request if the coordinates are out of range
+    pub fn point(&self) -> EndpointResult<Point> {
+        let point = Point::new(self.lng, self.lat);
+        validate_coords(&point)?;
+        Ok(point)
+    }
+}
@@ -1,20 +1,35 @@
 //! Location region http handlers impls
 
 use axum::{
-    extract::{Json, State},
+    extract::{Json, Query, State},
     http::StatusCode,
 };
 
 use crate::{
-    auth::AdminUser, endpoint::EndpointResult, server::state::DatabaseConnection, types::ModelID,
+    auth::AdminUser,
+    endpoint::{EndpointRejection, EndpointResult},
+    server::state::DatabaseConnection,
+    services::farmers::location::forms::LocaleQuery,
+    types::ModelID,
 };
 
-use super::{forms::RegionForm, Region, RegionList};
+use super::{
+    forms::{RegionBoundaryForm, RegionForm, RegionLookup},
+    Region, RegionList, RegionSuggestion,
+};
 
 /// Handles the `GET /locations/countries/:country_id/regions` route.
 #[tracing::instrument(skip(db))]
-pub async fn region_list(State(db): State<DatabaseConnection>) -> EndpointResult<Json<RegionList>> {
-    let regions = Region::records(db).await?;
+pub async fn region_list(
+    locale: Option<Query<LocaleQuery>>,
+    State(db): State<DatabaseConnection>,
+) -> EndpointResult<Json<RegionList>> {
+    let lang = locale.unwrap_or_default().0.lang;
+    let regions = Region::records(db)
+        .await?
+        .into_iter()
+        .map(|region| region.localize(lang.as_deref()))
+        .collect();
     Ok(Json(regions))
 }
 
@@ -51,3 +66,46 @@ pub async fn region_delete(
     Region::delete(region_id, db).await?;
     Ok(StatusCode::NO_CONTENT)
 }
+
+/// Handles the `PUT /locations/countries/regions/:region_id/boundary` route.
+///
+/// Replaces the region boundary with the `GeoJSON` polygons.
+#[tracing::instrument(skip(db, form))]
+pub async fn region_boundary_update(
+    _: AdminUser,
+    region_id: ModelID,
+    State(db): State<DatabaseConnection>,
+    form: RegionBoundaryForm,
+) -> EndpointResult<StatusCode> {
+    Region::update_boundary(region_id, Some(form.boundary), db).await?;
+    Ok(StatusCode::OK)
+}
+
+/// Handles the `DELETE /locations/countries/regions/:region_id/boundary` route.
+#[tracing::instrument(skip(db))]
+pub async fn region_boundary_delete(
+    _: AdminUser,
+    region_id: ModelID,
+    State(db): State<DatabaseConnection>,
+) -> EndpointResult<StatusCode> {
+    Region::update_boundary(region_id, None, db).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Handles the `GET /locations/countries/regions/suggest` route.
+///
+/// Return the region and country the `lat` and `lng` coordinates fall in.
+#[tracing::instrument(skip(db))]
+pub async fn region_suggest(
+    Query(lookup): Query<RegionLookup>,
+    State(db): State<DatabaseConnection>,
+) -> EndpointResult<Json<RegionSuggestion>> {
+    Region::containing(lookup.point()?, db).await?.map_or_else(
+        || {
+            Err(EndpointRejection::NotFound(
+                "No region found at these coordinates.".into(),
+            ))
+        },
+        |suggestion| Ok(Json(suggestion)),
+    )
+}
@@ -1,5 +1,6 @@
 //! Location region model impls
 
+pub mod boundary;
 pub mod db;
 pub mod forms;
 pub mod handlers;
@@ -7,6 +8,9 @@ pub mod handlers;
 use crate::types::ModelID;
 use serde::Serialize;
 
+use super::{country::Country, try_into_names, LocalizedNames};
+use boundary::RegionBoundary;
+
 /// A `Vec` of regions
 pub type RegionList = Vec<Region>;
 
@@ -15,13 +19,50 @@ pub type RegionList = Vec<Region>;
 pub struct Region {
     pub id: ModelID,
     pub name: String,
+    /// ISO 3166-2 code
+    pub code: Option<String>,
+    pub names: LocalizedNames,
 }
 
 impl Region {
     /// Creates a new Location region from the database row
     #[allow(clippy::missing_const_for_fn)]
     #[must_use]
-    pub fn from_row(id: ModelID, name: String) -> Self {
-        Self { id, name }
+    pub fn from_row(
+        id: ModelID,
+        name: String,
+        code: Option<String>,
+        names: Option<serde_json::Value>,
+    ) -> Self {
+        Self {
+            id,
+            name,
+            code,
+            names: try_into_names(names),
+        }
     }
+
+    /// Replaces the region name with its name in `locale`, if it has one
+    #[must_use]
+    pub fn localize(mut self, locale: Option<&str>) -> Self {
+        if let Some(name) = locale.and_then(|locale| self.names.get(locale)) {
+            self.name = name.clone();
+        }
+        self
+    }
+}
+
+/// The country and boundary of a region,
+/// used to validate the locations placed in the region.
+#[derive(Debug, Clone)]
+pub struct RegionArea {
+    pub country_id: Option<ModelID>,
+    pub boundary: Option<RegionBoundary>,
+}
+
+/// The region the coordinates fall in
+#[derive(Debug, Clone, Serialize)]
+pub struct RegionSuggestion {
+    pub region: Region,
+    pub country: Country,
 }
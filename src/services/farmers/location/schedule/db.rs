@@ -0,0 +1,248 @@
+//! Location schedule database impl
+
+use crate::{
+    endpoint::EndpointRejection,
+    error::{ServerError, ServerResult},
+    server::state::DatabaseConnection,
+    types::ModelID,
+};
+
+use super::{
+    forms::{LocationHolidayInsertData, OpeningHoursInsertData},
+    LocationHoliday, LocationSchedule,
+};
+
+impl LocationSchedule {
+    /// Fetches the location schedule from the database
+    #[tracing::instrument(name = "Find LocationSchedule", skip(db))]
+    pub async fn find(location_id: ModelID, db: DatabaseConnection) -> ServerResult<Option<Self>> {
+        match sqlx::query!(
+            r#"
+                SELECT services.location_schedule(location_.id) AS schedule
+                FROM services.active_locations location_
+                WHERE location_.id = $1
+            "#,
+            location_id.0
+        )
+        .fetch_optional(&db.pool)
+        .await
+        {
+            Ok(rec) => Ok(rec.map(|rec| Self::from_row(rec.schedule))),
+            Err(err) => {
+                tracing::error!("Database error, failed to fetch location schedule: {}", err);
+                Err(err.into())
+            }
+        }
+    }
+
+    /// Replaces the location weekly opening hours in the database
+    #[tracing::instrument(name = "Update OpeningHours", skip(db, values))]
+    pub async fn update_hours(
+        location_id: ModelID,
+        values: Vec<OpeningHoursInsertData>,
+        db: DatabaseConnection,
+    ) -> ServerResult<()> {
+        let mut tx = db.pool.begin().await?;
+
+        match sqlx::query!(
+            r#"
+                DELETE FROM services.location_opening_hours hours
+                WHERE hours.location_id = $1
+            "#,
+            location_id.0
+        )
+        .execute(&mut *tx)
+        .await
+        {
+            Ok(result) => {
+                tracing::trace!(
+                    "Opening hours deleted, but transaction not committed: {:?}",
+                    result
+                );
+            }
+            Err(err) => {
+                tracing::error!("Database error, failed to delete opening hours: {}", err);
+                return Err(err.into());
+            }
+        }
+
+        for hours in values {
+            if let Err(err) = sqlx::query!(
+                r#"
+                    INSERT INTO services.location_opening_hours(
+                        id,
+                        location_id,
+                        weekday,
+                        opens_at,
+                        closes_at
+                    )
+                    VALUES($1, $2, $3, $4, $5);
+                "#,
+                hours.id.0,
+                hours.location_id.0,
+                hours.weekday,
+                hours.opens_at,
+                hours.closes_at,
+            )
+            .execute(&mut *tx)
+            .await
+            {
+                // Handle database constraint error
+                handle_schedule_database_error(&err)?;
+
+                tracing::error!("Database error, failed to insert opening hours: {}", err);
+                return Err(err.into());
+            }
+        }
+
+        tx.commit().await?;
+        tracing::debug!("Opening hours updated successfully");
+        Ok(())
+    }
+
+    /// Checks if the timezone is a known IANA timezone name
+    #[tracing::instrument(skip(db))]
+    pub async fn timezone_exists(timezone: &str, db: DatabaseConnection) -> ServerResult<bool> {
+        match sqlx::query!(
+            r#"
+                SELECT EXISTS(
+                    SELECT 1 FROM pg_timezone_names tz
+                    WHERE tz.name = $1
+                ) AS "exists!"
+            "#,
+            timezone
+        )
+        .fetch_one(&db.pool)
+        .await
+        {
+            Ok(rec) => Ok(rec.exists),
+            Err(err) => {
+                tracing::error!("Database error, failed to check timezone exists: {}", err);
+                Err(err.into())
+            }
+        }
+    }
+}
+
+impl LocationHoliday {
+    /// Inserts location holiday into the database
+    ///
+    /// Replaces the holiday on the same date
+    #[tracing::instrument(name = "Insert LocationHoliday", skip(db, values))]
+    pub async fn insert(
+        values: LocationHolidayInsertData,
+        db: DatabaseConnection,
+    ) -> ServerResult<ModelID> {
+        match sqlx::query!(
+            r#"
+                INSERT INTO services.location_holidays(
+                    id,
+                    location_id,
+                    date,
+                    name,
+                    opens_at,
+                    closes_at
+                )
+                VALUES($1, $2, $3, $4, $5, $6)
+
+                ON CONFLICT ON CONSTRAINT location_holidays_location_id_date_key
+                DO UPDATE SET name = EXCLUDED.name,
+                    opens_at = EXCLUDED.opens_at,
+                    closes_at = EXCLUDED.closes_at
+
+                RETURNING id;
+            "#,
+            values.id.0,
+            values.location_id.0,
+            values.date,
+            values.name,
+            values.opens_at,
+            values.closes_at,
+        )
+        .fetch_one(&db.pool)
+        .await
+        {
+            Ok(rec) => {
+                tracing::debug!("Location holiday inserted successfully");
+                Ok(rec.id.into())
+            }
+            Err(err) => {
+                // Handle database constraint error
+                handle_schedule_database_error(&err)?;
+
+                tracing::error!("Database error, failed to insert location holiday: {}", err);
+                Err(err.into())
+            }
+        }
+    }
+
+    /// Deletes location holiday from the database
+    #[tracing::instrument(name = "Delete LocationHoliday", skip(db))]
+    pub async fn delete(id: ModelID, db: DatabaseConnection) -> ServerResult<()> {
+        match sqlx::query!(
+            r#"
+                DELETE FROM services.location_holidays holiday
+                WHERE holiday.id = $1
+            "#,
+            id.0
+        )
+        .execute(&db.pool)
+        .await
+        {
+            Ok(result) => {
+                tracing::debug!("Location holiday deleted successfully: {:?}", result);
+                Ok(())
+            }
+            Err(err) => {
+                tracing::error!("Database error, failed to delete location holiday: {}", err);
+                Err(err.into())
+            }
+        }
+    }
+
+    /// Fetches the location id the holiday belongs to
+    #[tracing::instrument(skip(db))]
+    pub async fn location_id(id: ModelID, db: DatabaseConnection) -> ServerResult<Option<ModelID>> {
+        match sqlx::query!(
+            r#"
+                SELECT holiday.location_id
+                FROM services.location_holidays holiday
+                WHERE holiday.id = $1
+            "#,
+            id.0
+        )
+        .fetch_optional(&db.pool)
+        .await
+        {
+            Ok(rec) => Ok(rec.map(|rec| rec.location_id.into())),
+            Err(err) => {
+                tracing::error!("Database error, failed to fetch holiday location: {}", err);
+                Err(err.into())
+            }
+        }
+    }
+}
+
+/// Handle location schedule database constraints errors
+#[allow(clippy::cognitive_complexity)]
+pub fn handle_schedule_database_error(err: &sqlx::Error) -> ServerResult<()> {
+    if let sqlx::Error::Database(db_err) = err {
+        // Handle db foreign key constraints
+        if db_err.is_foreign_key_violation() {
+            tracing::error!("Database error, location not found. {:?}", err);
+            return Err(ServerError::rejection(EndpointRejection::BadRequest(
+                "Location not found.".into(),
+            )));
+        }
+
+        // Handle db check constraints
+        if db_err.is_check_violation() {
+            tracing::error!("Database error, invalid opening hours. {:?}", err);
+            return Err(ServerError::rejection(EndpointRejection::BadRequest(
+                "Closing time must differ from the opening time.".into(),
+            )));
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,282 @@
+//! Location schedule forms impls
+
+use axum::{
+    async_trait,
+    extract::{rejection::JsonRejection, FromRequest, FromRequestParts, Json, Request},
+};
+use serde::Deserialize;
+use time::{Date, OffsetDateTime, Time};
+
+use crate::{
+    auth::FarmerUser,
+    endpoint::{
+        validators::{TransformString, ValidateString},
+        EndpointRejection, EndpointResult,
+    },
+    server::state::{DatabaseConnection, ServerState},
+    services::farmers::location::permissions::check_user_can_manage_location,
+    types::ModelID,
+};
+
+use super::LocationSchedule;
+
+/// Maximum number of opening hours a location can have per week
+const OPENING_HOURS_MAX: usize = 28;
+
+/// Number of minutes in a day
+const DAY_MINUTES: u32 = 24 * 60;
+
+/// Number of minutes in a week
+const WEEK_MINUTES: u32 = 7 * DAY_MINUTES;
+
+/// Location opening hours form,
+/// replaces all the location weekly opening hours.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OpeningHoursForm {
+    pub opening_hours: Vec<OpeningHoursEntry>,
+}
+
+/// Location opening hours for a single weekday
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OpeningHoursEntry {
+    pub weekday: u8,
+    pub opens_at: String,
+    pub closes_at: String,
+}
+
+/// Location opening hours cleaned data
+#[derive(Debug, Clone)]
+pub struct OpeningHoursInsertData {
+    pub id: ModelID,
+    pub location_id: ModelID,
+    pub weekday: i16,
+    pub opens_at: Time,
+    pub closes_at: Time,
+}
+
+impl OpeningHoursForm {
+    /// Validates opening hours form inputs
+    fn validate(&self) -> EndpointResult<()> {
+        if self.opening_hours.len() > OPENING_HOURS_MAX {
+            return Err(EndpointRejection::BadRequest(
+                format!("A location can have at most {OPENING_HOURS_MAX} opening hours.").into(),
+            ));
+        }
+
+        let mut hours = Vec::with_capacity(self.opening_hours.len());
+        for entry in &self.opening_hours {
+            if !(1..=7).contains(&entry.weekday) {
+                return Err(EndpointRejection::BadRequest(
+                    "Weekday must be between 1 (Monday) and 7 (Sunday).".into(),
+                ));
+            }
+            let (opens_at, closes_at) = validate_hours(&entry.opens_at, &entry.closes_at)?;
+            hours.push(week_minutes(entry.weekday, opens_at, closes_at));
+        }
+
+        // Opening hours must not overlap, including
+        // the hours open past midnight into the next day
+        hours.sort_unstable();
+        let overlaps = hours.windows(2).any(|pair| pair[1].0 < pair[0].1)
+            || hours
+                .first()
+                .zip(hours.last())
+                .is_some_and(|(first, last)| last.1 > WEEK_MINUTES + first.0);
+        if overlaps {
+            return Err(EndpointRejection::BadRequest(
+                "Opening hours must not overlap.".into(),
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Convert `Self` into a `Vec` of `OpeningHoursInsertData`
+    #[must_use]
+    pub fn data(self, location_id: ModelID) -> Vec<OpeningHoursInsertData> {
+        self.opening_hours
+            .into_iter()
+            .map(|entry| OpeningHoursInsertData {
+                id: ModelID::new(),
+                location_id,
+                weekday: i16::from(entry.weekday),
+                opens_at: parse_time(&entry.opens_at).unwrap_or(Time::MIDNIGHT),
+                closes_at: parse_time(&entry.closes_at).unwrap_or(Time::MIDNIGHT),
+            })
+            .collect()
+    }
+}
+
+#[async_trait]
+impl FromRequest<ServerState> for OpeningHoursForm
+where
+    Json<Self>: FromRequest<ServerState, Rejection = JsonRejection>,
+{
+    type Rejection = EndpointRejection;
+
+    async fn from_request(req: Request, state: &ServerState) -> Result<Self, Self::Rejection> {
+        // Extract data
+        let (mut parts, body) = req.into_parts();
+        let user = { FarmerUser::from_parts(&mut parts, state).await? };
+        let location_id = { ModelID::from_request_parts(&mut parts, state).await? };
+        let Json(hours) =
+            Json::<Self>::from_request(Request::from_parts(parts, body), state).await?;
+
+        // Validate form fields
+        hours.validate()?;
+
+        // Authorize the request
+        check_user_can_manage_location(user.id(), location_id, state.database()).await?;
+
+        Ok(hours)
+    }
+}
+
+// ===== Location Holiday form impls =====
+
+/// Location holiday form
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LocationHolidayForm {
+    pub date: Date,
+    pub name: Option<String>,
+    pub opens_at: Option<String>,
+    pub closes_at: Option<String>,
+}
+
+/// Location holiday cleaned data
+#[derive(Debug, Clone)]
+pub struct LocationHolidayInsertData {
+    pub id: ModelID,
+    pub location_id: ModelID,
+    pub date: Date,
+    pub name: Option<String>,
+    pub opens_at: Option<Time>,
+    pub closes_at: Option<Time>,
+}
+
+impl LocationHolidayForm {
+    /// Validates holiday form inputs
+    fn validate(&mut self) -> EndpointResult<()> {
+        // Clean the data
+        self.clean_data();
+
+        // Allow a day behind for locations in timezones behind UTC
+        let yesterday = OffsetDateTime::now_utc().date().previous_day();
+        if yesterday.is_some_and(|yesterday| self.date < yesterday) {
+            return Err(EndpointRejection::BadRequest(
+                "Holiday date must not be in the past.".into(),
+            ));
+        }
+
+        if let Some(ref name) = self.name {
+            name.validate_len(0, 64, "Holiday name must be at most 64 characters")?;
+        }
+
+        match (&self.opens_at, &self.closes_at) {
+            (None, None) => Ok(()),
+            (Some(opens_at), Some(closes_at)) => validate_hours(opens_at, closes_at).map(|_| ()),
+            _ => Err(EndpointRejection::BadRequest(
+                "Holiday opening and closing time must be provided together.".into(),
+            )),
+        }
+    }
+
+    /// Clean form data
+    fn clean_data(&mut self) {
+        self.name = self.name.as_ref().map(|name| name.clean());
+    }
+
+    /// Convert `Self` into `LocationHolidayInsertData`
+    #[must_use]
+    pub fn data(self, location_id: ModelID) -> LocationHolidayInsertData {
+        LocationHolidayInsertData {
+            id: ModelID::new(),
+            location_id,
+            date: self.date,
+            name: self.name,
+            opens_at: self.opens_at.as_deref().and_then(parse_time),
+            closes_at: self.closes_at.as_deref().and_then(parse_time),
+        }
+    }
+}
+
+#[async_trait]
+impl FromRequest<ServerState> for LocationHolidayForm
+where
+    Json<Self>: FromRequest<ServerState, Rejection = JsonRejection>,
+{
+    type Rejection = EndpointRejection;
+
+    async fn from_request(req: Request, state: &ServerState) -> Result<Self, Self::Rejection> {
+        // Extract data
+        let (mut parts, body) = req.into_parts();
+        let user = { FarmerUser::from_parts(&mut parts, state).await? };
+        let location_id = { ModelID::from_request_parts(&mut parts, state).await? };
+        let Json(mut holiday) =
+            Json::<Self>::from_request(Request::from_parts(parts, body), state).await?;
+
+        // Validate form fields
+        holiday.validate()?;
+
+        // Authorize the request
+        check_user_can_manage_location(user.id(), location_id, state.database()).await?;
+
+        Ok(holiday)
+    }
+}
+
+// ===== Helpers =====
+
+/// Validates the location timezone is a known IANA timezone name
+pub async fn validate_timezone(timezone: &str, db: DatabaseConnection) -> EndpointResult<()> {
+    if LocationSchedule::timezone_exists(timezone, db).await? {
+        Ok(())
+    } else {
+        Err(EndpointRejection::BadRequest(
+            "Invalid location timezone.".into(),
+        ))
+    }
+}
+
+/// Validates opening and closing time, a closing time
+/// before the opening time means the hours wrap past midnight.
+fn validate_hours(opens_at: &str, closes_at: &str) -> EndpointResult<(Time, Time)> {
+    let (Some(opens), Some(closes)) = (parse_time(opens_at), parse_time(closes_at)) else {
+        return Err(EndpointRejection::BadRequest(
+            "Opening hours time must be in the format HH:MM.".into(),
+        ));
+    };
+
+    if closes == opens {
+        return Err(EndpointRejection::BadRequest(
+            "Closing time must differ from the opening time.".into(),
+        ));
+    }
+
+    Ok((opens, closes))
+}
+
+/// Returns the opening hours as minutes since the start of the week (Monday 00:00),
+/// the hours wrapping past midnight close on the next day.
+fn week_minutes(weekday: u8, opens_at: Time, closes_at: Time) -> (u32, u32) {
+    let minutes = |time: Time| u32::from(time.hour()) * 60 + u32::from(time.minute());
+    let day_start = u32::from(weekday - 1) * DAY_MINUTES;
+    let opens = day_start + minutes(opens_at);
+    let mut closes = day_start + minutes(closes_at);
+    if closes_at < opens_at {
+        closes += DAY_MINUTES;
+    }
+    (opens, closes)
+}
+
+/// Parses time in the format `HH:MM`
+fn parse_time(value: &str) -> Option<Time> {
+    let (hour, minute) = value.trim().split_once(':')?;
+    if hour.len() != 2 || minute.len() != 2 {
+        return None;
+    }
+    Time::from_hms(hour.parse().ok()?, minute.parse().ok()?, 0).ok()
+}
@@ -0,0 +1,66 @@
+//! Location schedule http handlers impls
+
+use axum::{
+    extract::{Json, State},
+    http::StatusCode,
+};
+
+use crate::{
+    auth::FarmerUser,
+    endpoint::{EndpointRejection, EndpointResult},
+    server::state::DatabaseConnection,
+    services::farmers::location::permissions::LocationHolidayDeletePermission,
+    types::ModelID,
+};
+
+use super::{
+    forms::{LocationHolidayForm, OpeningHoursForm},
+    LocationHoliday, LocationSchedule,
+};
+
+/// Handles the `GET /locations/:location_id/schedule` route.
+#[tracing::instrument(skip(db))]
+pub async fn location_schedule(
+    location_id: ModelID,
+    State(db): State<DatabaseConnection>,
+) -> EndpointResult<Json<LocationSchedule>> {
+    LocationSchedule::find(location_id, db).await?.map_or_else(
+        || Err(EndpointRejection::NotFound("Location not found.".into())),
+        |schedule| Ok(Json(schedule)),
+    )
+}
+
+/// Handles the `PUT /locations/:location_id/hours` route.
+#[tracing::instrument(skip(db, form))]
+pub async fn location_hours_update(
+    _: FarmerUser,
+    location_id: ModelID,
+    State(db): State<DatabaseConnection>,
+    form: OpeningHoursForm,
+) -> EndpointResult<StatusCode> {
+    LocationSchedule::update_hours(location_id, form.data(location_id), db).await?;
+    Ok(StatusCode::OK)
+}
+
+/// Handles the `POST /locations/:location_id/holidays` route.
+#[tracing::instrument(skip(db, form))]
+pub async fn location_holiday_create(
+    _: FarmerUser,
+    location_id: ModelID,
+    State(db): State<DatabaseConnection>,
+    form: LocationHolidayForm,
+) -> EndpointResult<StatusCode> {
+    LocationHoliday::insert(form.data(location_id), db).await?;
+    Ok(StatusCode::CREATED)
+}
+
+/// Handles the `DELETE /locations/holidays/:holiday_id` route.
+#[tracing::instrument(skip(db))]
+pub async fn location_holiday_delete(
+    _: LocationHolidayDeletePermission,
+    holiday_id: ModelID,
+    State(db): State<DatabaseConnection>,
+) -> EndpointResult<StatusCode> {
+    LocationHoliday::delete(holiday_id, db).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
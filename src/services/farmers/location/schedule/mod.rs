@@ -0,0 +1,59 @@
+//! Location schedule model impls
+
+pub mod db;
+pub mod forms;
+pub mod handlers;
+
+use serde::{Deserialize, Serialize};
+use time::Date;
+
+use crate::types::ModelID;
+
+/// Location opening hours, upcoming holidays and pickup instructions.
+///
+/// Built by the `services.location_schedule` database function,
+/// `open_now` and `open_today` are computed in the location's timezone.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LocationSchedule {
+    pub timezone: String,
+    pub pickup_instructions: Option<String>,
+    pub open_now: bool,
+    pub open_today: bool,
+    pub opening_hours: Vec<OpeningHours>,
+    pub holidays: Vec<LocationHoliday>,
+}
+
+impl LocationSchedule {
+    /// Creates a new `LocationSchedule` from the database column
+    #[must_use]
+    pub fn from_row(value: Option<serde_json::Value>) -> Self {
+        value
+            .and_then(|value| serde_json::from_value(value).ok())
+            .unwrap_or_default()
+    }
+}
+
+/// Location weekly opening hours,
+/// `weekday` is the ISO day of the week: 1 = Monday ... 7 = Sunday,
+/// `closes_at` before `opens_at` means the hours wrap past midnight.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OpeningHours {
+    pub id: ModelID,
+    pub weekday: u8,
+    pub opens_at: String,
+    pub closes_at: String,
+}
+
+/// A holiday exception replacing the location weekly opening hours,
+/// the location is closed the whole day if it has no opening hours.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LocationHoliday {
+    pub id: ModelID,
+    pub date: Date,
+    pub name: Option<String>,
+    pub opens_at: Option<String>,
+    pub closes_at: Option<String>,
+}
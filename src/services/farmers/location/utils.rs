@@ -1,28 +1,33 @@
 //! Location helpers impls
 
+use std::path::PathBuf;
+
 use time::OffsetDateTime;
 
-use crate::{
-    error::ServerResult, server::state::DatabaseConnection,
-    services::produce::harvest::harvest_max_age, types::ModelID,
-};
+use crate::{error::ServerResult, files, settings::LOCATION_UPLOAD_DIR, types::ModelID};
 
 use super::db::handle_location_database_error;
 
-/// Delete location from the database
+/// Archive location from the database
 ///
 /// # Errors
 ///
 /// Return database error
-pub async fn delete_location(
+pub async fn archive_location(
     location_id: ModelID,
+    archived_at: OffsetDateTime,
     tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
 ) -> ServerResult<()> {
     match sqlx::query!(
         r#"
-            DELETE FROM services.locations location_
-            WHERE location_.id = $1
+            UPDATE services.locations location_
+            SET deleted = true,
+                deleted_at = $1,
+                archived_at = $2
+            WHERE location_.id = $3
         "#,
+        archived_at.date(),
+        archived_at,
         location_id.0
     )
     .execute(&mut **tx)
@@ -30,7 +35,7 @@ pub async fn delete_location(
     {
         Ok(result) => {
             tracing::trace!(
-                "Location deleted, but transaction not committed: {:?}",
+                "Location archived, but transaction not committed: {:?}",
                 result
             );
             Ok(())
@@ -39,199 +44,259 @@ pub async fn delete_location(
             // Handle database constraint error
             handle_location_database_error(&err)?;
 
-            tracing::debug!("Database error, failed to delete location: {}", err);
+            tracing::error!("Database error, failed to archive location: {}", err);
             Err(err.into())
         }
     }
 }
 
-/// Archive location from the database
+/// Archive location active harvests
 ///
 /// # Errors
 ///
 /// Return database error
-pub async fn archive_location(
+pub async fn archive_location_harvests(
     location_id: ModelID,
+    archived_at: OffsetDateTime,
     tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
-) -> ServerResult<()> {
+) -> ServerResult<u64> {
     match sqlx::query!(
         r#"
-            UPDATE services.locations location_
-            SET deleted = true,
-                deleted_at = $1
-            WHERE location_.id = $2
+            UPDATE services.harvests harvest
+            SET finished = true,
+                finished_at = $1,
+                archived_at = $2
+
+            WHERE harvest.location_id = $3
+                AND harvest.finished = false
         "#,
-        OffsetDateTime::now_utc().date(),
-        location_id.0
+        archived_at.date(),
+        archived_at,
+        location_id.0,
     )
     .execute(&mut **tx)
     .await
     {
         Ok(result) => {
             tracing::trace!(
-                "Location archived, but transaction not committed: {:?}",
+                "Location active harvests archived, but transaction not committed: {:?}",
                 result
             );
-            Ok(())
+            Ok(result.rows_affected())
         }
         Err(err) => {
-            // Handle database constraint error
-            handle_location_database_error(&err)?;
-
-            tracing::error!("Database error, failed to archive location: {}", err);
+            tracing::error!("Database error, failed to archive location harvests");
             Err(err.into())
         }
     }
 }
 
-/// Fetch location active harvests images
+/// Restore location archived after `cutoff` in the database,
+/// the location farm must be active.
+///
+/// Returns the time the location was archived at,
+/// or `None` if the location cannot be restored.
 ///
 /// # Errors
 ///
 /// Return database error
-pub async fn location_harvest_photos(
+pub async fn restore_location(
     location_id: ModelID,
-    db: DatabaseConnection,
-) -> ServerResult<Vec<Vec<String>>> {
+    cutoff: OffsetDateTime,
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+) -> ServerResult<Option<OffsetDateTime>> {
     match sqlx::query!(
         r#"
-            SELECT harvest.images
-            FROM services.active_harvests harvest
-            WHERE harvest.location_id = $1
+            UPDATE services.locations location_
+            SET deleted = false,
+                deleted_at = NULL,
+                archived_at = NULL
+            FROM (
+                SELECT old_location.id, old_location.archived_at
+                FROM services.locations old_location
+                INNER JOIN services.active_farms farm
+                    ON old_location.farm_id = farm.id
+                WHERE old_location.id = $1
+                    AND old_location.deleted = true
+                    AND old_location.archived_at > $2
+                FOR UPDATE OF old_location
+            ) archived
+
+            WHERE location_.id = archived.id
+            RETURNING archived.archived_at AS "archived_at!"
         "#,
-        location_id.0
+        location_id.0,
+        cutoff,
     )
-    .fetch_all(&db.pool)
+    .fetch_optional(&mut **tx)
     .await
     {
-        Ok(records) => Ok(records.into_iter().filter_map(|rec| rec.images).collect()),
+        Ok(rec) => {
+            tracing::trace!("Location restored, but transaction not committed");
+            Ok(rec.map(|rec| rec.archived_at))
+        }
         Err(err) => {
-            tracing::error!(
-                "Database error, failed to fetch location harvest images: {}",
-                err
-            );
+            // Handle database constraint error
+            handle_location_database_error(&err)?;
+
+            tracing::error!("Database error, failed to restore location: {}", err);
             Err(err.into())
         }
     }
 }
 
-/// Find location archived harvest count
+/// Restore location harvests archived together with the location
 ///
 /// # Errors
 ///
 /// Return database error
-pub async fn location_archived_harvest_count(
+pub async fn restore_location_harvests(
     location_id: ModelID,
-    db: DatabaseConnection,
-) -> ServerResult<i64> {
+    archived_at: OffsetDateTime,
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+) -> ServerResult<u64> {
     match sqlx::query!(
         r#"
-            SELECT COUNT(harvest.id) AS "harvest_count!"
-            FROM services.locations location_
-            LEFT JOIN services.harvests harvest
-                ON location_.id = harvest.location_id
-            
-            WHERE location_.id = $1 AND harvest.finished = true;
+            UPDATE services.harvests harvest
+            SET finished = false,
+                finished_at = NULL,
+                archived_at = NULL
+
+            WHERE harvest.location_id = $1
+                AND harvest.archived_at = $2
         "#,
-        location_id.0
+        location_id.0,
+        archived_at,
     )
-    .fetch_one(&db.pool)
+    .execute(&mut **tx)
     .await
     {
-        Ok(rec) => Ok(rec.harvest_count),
-        Err(err) => {
-            tracing::error!(
-                "Database error, failed to fetch location active harvests count: {}",
-                err
+        Ok(result) => {
+            tracing::trace!(
+                "Location harvests restored, but transaction not committed: {:?}",
+                result
             );
+            Ok(result.rows_affected())
+        }
+        Err(err) => {
+            tracing::error!("Database error, failed to restore location harvests");
             Err(err.into())
         }
     }
 }
 
-/// Delete location active harvests
+/// Delete locations archived on or before `cutoff`
+/// that do not have any harvests incl archived ones
+///
+/// Returns the images of the deleted locations.
 ///
 /// # Errors
 ///
 /// Return database error
-pub async fn delete_location_harvests(
-    location_id: ModelID,
-    finished_at: OffsetDateTime,
+pub async fn delete_expired_locations(
+    cutoff: OffsetDateTime,
     tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
-) -> ServerResult<u64> {
-    let max_age = harvest_max_age(finished_at)?;
+) -> ServerResult<Vec<String>> {
     match sqlx::query!(
         r#"
-            DELETE FROM services.harvests harvest
-            
-            WHERE harvest.location_id = $1
-                AND (
-                    harvest.harvest_date > $2 OR 
-                    harvest.created_at > $3
+            DELETE FROM services.locations location_
+            WHERE location_.archived_at <= $1
+                AND NOT EXISTS(
+                    SELECT 1 FROM services.harvests harvest
+                    WHERE harvest.location_id = location_.id
                 )
+            RETURNING location_.images
         "#,
-        location_id.0,
-        finished_at.date(),
-        max_age,
+        cutoff,
     )
-    .execute(&mut **tx)
+    .fetch_all(&mut **tx)
     .await
     {
-        Ok(result) => {
+        Ok(records) => {
             tracing::trace!(
-                "Location active harvests deleted, but transaction not committed: {:?}",
-                result
+                "Expired archived locations deleted, but transaction not committed: {} rows",
+                records.len()
             );
-            Ok(result.rows_affected())
+            Ok(records
+                .into_iter()
+                .filter_map(|rec| rec.images)
+                .flatten()
+                .collect())
         }
         Err(err) => {
-            tracing::error!("Database error, failed to delete location harvests");
+            tracing::error!("Database error, failed to delete expired locations");
             Err(err.into())
         }
     }
 }
 
-/// Archive location active harvests
+/// Finalize locations archived on or before `cutoff`,
+/// the locations can no longer be restored and their images are removed.
+///
+/// Returns the images of the finalized locations.
 ///
 /// # Errors
 ///
 /// Return database error
-pub async fn archive_location_harvests(
-    location_id: ModelID,
-    finished_at: OffsetDateTime,
+pub async fn finalize_archived_locations(
+    cutoff: OffsetDateTime,
     tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
-) -> ServerResult<u64> {
-    let max_age = harvest_max_age(finished_at)?;
+) -> ServerResult<Vec<String>> {
     match sqlx::query!(
         r#"
-            UPDATE services.harvests harvest
-            SET finished = true,
-                images = NULL,
-                finished_at = $1
-
-            WHERE harvest.location_id = $2
-                AND NOT(
-                    harvest.harvest_date > $1 OR 
-                    harvest.created_at > $3
-                )
+            UPDATE services.locations location_
+            SET archived_at = NULL,
+                images = NULL
+            FROM (
+                SELECT old_location.id, old_location.images
+                FROM services.locations old_location
+                WHERE old_location.archived_at <= $1
+                FOR UPDATE
+            ) archived
+            WHERE location_.id = archived.id
+            RETURNING archived.images
         "#,
-        finished_at.date(),
-        location_id.0,
-        max_age,
+        cutoff,
     )
-    .execute(&mut **tx)
+    .fetch_all(&mut **tx)
     .await
     {
-        Ok(result) => {
+        Ok(records) => {
             tracing::trace!(
-                "Location active harvests archived, but transaction not committed: {:?}",
-                result
+                "Archived locations finalized, but transaction not committed: {} rows",
+                records.len()
             );
-            Ok(result.rows_affected())
+            Ok(records
+                .into_iter()
+                .filter_map(|rec| rec.images)
+                .flatten()
+                .collect())
         }
         Err(err) => {
-            tracing::error!("Database error, failed to archive location harvests");
+            tracing::error!("Database error, failed to finalize archived locations");
             Err(err.into())
         }
     }
 }
+
+/// Delete location images from file system
+///
+/// # Errors
+///
+/// Return an error if failed to delete files
+pub async fn delete_location_photos<P>(paths: P) -> ServerResult<()>
+where
+    P: Iterator<Item = String> + Send,
+{
+    let all_paths: Vec<PathBuf> = paths
+        .flat_map(|file| {
+            crate::IMAGE_OUTPUT_FORMATS.map(|ext| {
+                PathBuf::from(LOCATION_UPLOAD_DIR)
+                    .join(&file)
+                    .with_extension(ext.extensions_str()[0])
+            })
+        })
+        .collect();
+
+    files::delete_files(all_paths).await
+}
@@ -1,5 +1,7 @@
 //! `FarmRating` database impl
 
+use time::OffsetDateTime;
+
 use crate::{
     endpoint::EndpointRejection,
     error::{ServerError, ServerResult},
@@ -8,10 +10,20 @@ use crate::{
 };
 
 use super::{
-    forms::{FarmRatingInsertData, FarmRatingUpdateData},
-    models::{FarmRating, FarmRatingList},
+    delete_rating_photos,
+    forms::{
+        FarmRatingFilter, FarmRatingInsertData, FarmRatingReportInsertData, FarmRatingUpdateData,
+        RatingReplyInsertData, RatingReplyUpdateData, RatingSort,
+    },
+    models::{
+        FarmRating, FarmRatingList, RatingReply, RatingReplyList, RatingSummary, ReportedRating,
+        ReportedRatingList,
+    },
 };
 
+/// Number of the most recent and most helpful ratings in the rating summary
+const RATING_SUMMARY_HIGHLIGHTS: u64 = 3;
+
 impl FarmRating {
     /// Fetches all farm-rating records from the database
     #[tracing::instrument(name = "Fetch FarmRatingList", skip(db))]
@@ -22,6 +34,9 @@ impl FarmRating {
                 SELECT farm_rating.id AS farm_rating_id,
                     farm_rating.grade AS farm_rating_grade,
                     farm_rating.comment AS farm_rating_comment,
+                    farm_rating.images AS farm_rating_images,
+                    farm_rating.verified_buyer AS farm_rating_verified_buyer,
+                    farm_rating.helpful_count AS farm_rating_helpful_count,
                     farm_rating.updated_at AS "farm_rating_updated_at?",
                     farm_rating.created_at AS farm_rating_created_at,
                     farm.id AS "farm_id!",
@@ -29,6 +44,7 @@ impl FarmRating {
                     user_.id AS user_id,
                     user_.first_name AS user_first_name,
                     user_.last_name AS user_last_name,
+                    user_.identity_verified AS user_identity_verified,
                     user_profile.photo AS user_photo
                 FROM services.farm_ratings farm_rating
                 LEFT JOIN services.active_farms farm
@@ -38,6 +54,7 @@ impl FarmRating {
                 LEFT JOIN accounts.user_profiles user_profile
                     On farm_rating.author_id = user_profile.user_id
 
+                WHERE farm_rating.reply_to IS NULL
                 ORDER BY farm_rating.created_at
                 LIMIT $1
                 OFFSET $2
@@ -49,13 +66,16 @@ impl FarmRating {
         .await
         {
             Ok(records) => {
-                let farm_ratings: Vec<_> = records
+                let mut farm_ratings: Vec<_> = records
                     .into_iter()
                     .map(|rec| {
                         Self::from_row(
                             rec.farm_rating_id.into(),
                             rec.farm_rating_grade.unwrap_or_default(),
                             rec.farm_rating_comment,
+                            rec.farm_rating_images,
+                            rec.farm_rating_verified_buyer,
+                            rec.farm_rating_helpful_count,
                             rec.farm_rating_updated_at
                                 .unwrap_or(rec.farm_rating_created_at),
                             rec.farm_id.into(),
@@ -64,10 +84,12 @@ impl FarmRating {
                             rec.user_first_name,
                             rec.user_last_name,
                             rec.user_photo,
+                            rec.user_identity_verified,
                         )
                     })
                     .collect();
 
+                Self::attach_replies(&mut farm_ratings, db).await?;
                 Ok(farm_ratings)
             }
             Err(err) => {
@@ -85,6 +107,9 @@ impl FarmRating {
                 SELECT farm_rating.id AS farm_rating_id,
                     farm_rating.grade AS farm_rating_grade,
                     farm_rating.comment AS farm_rating_comment,
+                    farm_rating.images AS farm_rating_images,
+                    farm_rating.verified_buyer AS farm_rating_verified_buyer,
+                    farm_rating.helpful_count AS farm_rating_helpful_count,
                     farm_rating.updated_at AS "farm_rating_updated_at?",
                     farm_rating.created_at AS farm_rating_created_at,
                     farm.id AS "farm_id!",
@@ -92,6 +117,7 @@ impl FarmRating {
                     user_.id AS user_id,
                     user_.first_name AS user_first_name,
                     user_.last_name AS user_last_name,
+                    user_.identity_verified AS user_identity_verified,
                     user_profile.photo AS user_photo
                 FROM services.farm_ratings farm_rating
                 LEFT JOIN services.active_farms farm
@@ -101,7 +127,9 @@ impl FarmRating {
                 LEFT JOIN accounts.user_profiles user_profile
                     On farm_rating.author_id = user_profile.user_id
 
-                WHERE farm_rating.id = $1;
+                WHERE farm_rating.id = $1
+                    AND farm_rating.reply_to IS NULL
+                    AND farm_rating.hidden_at IS NULL;
             "#,
             id.0,
         )
@@ -109,10 +137,13 @@ impl FarmRating {
         .await
         {
             Ok(rec) => {
-                let farm_rating = Self::from_row(
+                let mut farm_rating = Self::from_row(
                     rec.farm_rating_id.into(),
                     rec.farm_rating_grade.unwrap_or_default(),
                     rec.farm_rating_comment,
+                    rec.farm_rating_images,
+                    rec.farm_rating_verified_buyer,
+                    rec.farm_rating_helpful_count,
                     rec.farm_rating_updated_at
                         .unwrap_or(rec.farm_rating_created_at),
                     rec.farm_id.into(),
@@ -121,8 +152,10 @@ impl FarmRating {
                     rec.user_first_name,
                     rec.user_last_name,
                     rec.user_photo,
+                    rec.user_identity_verified,
                 );
 
+                Self::attach_replies(std::slice::from_mut(&mut farm_rating), db).await?;
                 Ok(Some(farm_rating))
             }
             Err(err) => {
@@ -150,15 +183,18 @@ impl FarmRating {
                     farm_id, 
                     grade, 
                     comment, 
+                    images,
+                    verified_buyer,
                     created_at
                 )
-                VALUES($1, $2, $3, $4, $5, $6)
+                VALUES($1, $2, $3, $4, $5, $6, services.farm_verified_buyer($2, $3), $7)
             "#,
             farm_rating.id.0,
             farm_rating.user_id.0,
             farm_rating.farm_id.0,
             i32::from(farm_rating.grade),
             farm_rating.comment,
+            (!farm_rating.images.is_empty()).then_some(&farm_rating.images[..]),
             farm_rating.created_at
         )
         .execute(&db.pool)
@@ -190,8 +226,11 @@ impl FarmRating {
                 UPDATE services.farm_ratings farm_rating
                 SET grade = COALESCE($1, farm_rating.grade),
                     comment = $2,
+                    verified_buyer = farm_rating.verified_buyer
+                        OR services.farm_verified_buyer(farm_rating.author_id, farm_rating.farm_id),
                     updated_at = $3
                     WHERE farm_rating.id = $4
+                        AND farm_rating.reply_to IS NULL
             "#,
             i32::from(farm_rating.grade),
             farm_rating.comment,
@@ -215,21 +254,25 @@ impl FarmRating {
         }
     }
 
-    /// Deletes farm-rating from the database
+    /// Deletes farm-rating and its images from the database
     #[tracing::instrument(name = "Delete FarmRating", skip(db))]
     pub async fn delete(id: ModelID, db: DatabaseConnection) -> ServerResult<()> {
         match sqlx::query!(
             r#"
                 DELETE FROM services.farm_ratings farm_rating
                     WHERE farm_rating.id = $1
+                RETURNING farm_rating.images
             "#,
             id.0
         )
-        .execute(&db.pool)
+        .fetch_optional(&db.pool)
         .await
         {
-            Ok(result) => {
-                tracing::debug!("Farm-rating deleted successfully: {:?}", result);
+            Ok(rec) => {
+                tracing::debug!("Farm-rating deleted successfully");
+                if let Some(images) = rec.and_then(|rec| rec.images) {
+                    tokio::spawn(async move { delete_rating_photos(images.into_iter()).await });
+                }
                 Ok(())
             }
             Err(err) => {
@@ -242,10 +285,12 @@ impl FarmRating {
         }
     }
 
-    // Fetch farm's ratings from the database
-    #[allow(dead_code)]
+    /// Fetches farm's ratings from the database,
+    /// filtered and sorted by the `filter`
+    #[tracing::instrument(name = "Fetch farm FarmRatingList", skip(db))]
     pub async fn records_for_farm(
         farm_id: ModelID,
+        filter: &FarmRatingFilter,
         pg: Pagination,
         db: DatabaseConnection,
     ) -> ServerResult<FarmRatingList> {
@@ -255,6 +300,9 @@ impl FarmRating {
                 SELECT farm_rating.id AS farm_rating_id,
                     farm_rating.grade AS farm_rating_grade,
                     farm_rating.comment AS farm_rating_comment,
+                    farm_rating.images AS farm_rating_images,
+                    farm_rating.verified_buyer AS farm_rating_verified_buyer,
+                    farm_rating.helpful_count AS farm_rating_helpful_count,
                     farm_rating.updated_at AS "farm_rating_updated_at?",
                     farm_rating.created_at AS farm_rating_created_at,
                     farm.id AS "farm_id!",
@@ -262,6 +310,7 @@ impl FarmRating {
                     user_.id AS user_id,
                     user_.first_name AS user_first_name,
                     user_.last_name AS user_last_name,
+                    user_.identity_verified AS user_identity_verified,
                     user_profile.photo AS user_photo
                 FROM services.farm_ratings farm_rating
                 LEFT JOIN services.active_farms farm
@@ -272,25 +321,38 @@ impl FarmRating {
                     On farm_rating.author_id = user_profile.user_id
                 
                 WHERE farm.id = $1
-                ORDER BY farm_rating.created_at
+                    AND farm_rating.reply_to IS NULL
+                    AND farm_rating.hidden_at IS NULL
+                    AND ($4::integer IS NULL OR farm_rating.grade = $4)
+                ORDER BY
+                    CASE WHEN $5 = 'highest' THEN farm_rating.grade END DESC,
+                    CASE WHEN $5 = 'lowest' THEN farm_rating.grade END,
+                    CASE WHEN $5 = 'helpful' THEN farm_rating.helpful_count END DESC,
+                    CASE WHEN $5 = 'recent' THEN farm_rating.created_at END DESC,
+                    farm_rating.created_at
                 LIMIT $2
                 OFFSET $3
             "#,
             farm_id.0,
             limit,
-            offset
+            offset,
+            filter.grade(),
+            filter.sort.as_str(),
         )
         .fetch_all(&db.pool)
         .await
         {
             Ok(records) => {
-                let farm_ratings: Vec<_> = records
+                let mut farm_ratings: Vec<_> = records
                     .into_iter()
                     .map(|rec| {
                         Self::from_row(
                             rec.farm_rating_id.into(),
                             rec.farm_rating_grade.unwrap_or_default(),
                             rec.farm_rating_comment,
+                            rec.farm_rating_images,
+                            rec.farm_rating_verified_buyer,
+                            rec.farm_rating_helpful_count,
                             rec.farm_rating_updated_at
                                 .unwrap_or(rec.farm_rating_created_at),
                             rec.farm_id.into(),
@@ -299,10 +361,12 @@ impl FarmRating {
                             rec.user_first_name,
                             rec.user_last_name,
                             rec.user_photo,
+                            rec.user_identity_verified,
                         )
                     })
                     .collect();
 
+                Self::attach_replies(&mut farm_ratings, db).await?;
                 Ok(farm_ratings)
             }
             Err(err) => {
@@ -311,12 +375,603 @@ impl FarmRating {
             }
         }
     }
+
+    /// Inserts user helpful vote on the farm-rating into the database
+    #[tracing::instrument(name = "Insert FarmRating vote", skip(db))]
+    pub async fn vote(
+        id: ModelID,
+        user_id: ModelID,
+        created_at: OffsetDateTime,
+        db: DatabaseConnection,
+    ) -> ServerResult<()> {
+        match sqlx::query!(
+            r#"
+                INSERT INTO services.farm_rating_votes(
+                    rating_id,
+                    user_id,
+                    created_at
+                )
+                VALUES($1, $2, $3)
+                ON CONFLICT (rating_id, user_id) DO NOTHING;
+            "#,
+            id.0,
+            user_id.0,
+            created_at,
+        )
+        .execute(&db.pool)
+        .await
+        {
+            Ok(result) => {
+                tracing::debug!("Farm-rating vote inserted successfully: {:?}", result);
+                Ok(())
+            }
+            Err(err) => {
+                tracing::error!("Database error, failed to insert farm-rating vote: {}", err);
+                Err(err.into())
+            }
+        }
+    }
+
+    /// Deletes user helpful vote on the farm-rating from the database
+    #[tracing::instrument(name = "Delete FarmRating vote", skip(db))]
+    pub async fn unvote(id: ModelID, user_id: ModelID, db: DatabaseConnection) -> ServerResult<()> {
+        match sqlx::query!(
+            r#"
+                DELETE FROM services.farm_rating_votes vote
+                WHERE vote.rating_id = $1
+                    AND vote.user_id = $2
+            "#,
+            id.0,
+            user_id.0
+        )
+        .execute(&db.pool)
+        .await
+        {
+            Ok(result) if result.rows_affected() == 0 => Err(ServerError::rejection(
+                EndpointRejection::NotFound("You have not voted on this rating.".into()),
+            )),
+            Ok(result) => {
+                tracing::debug!("Farm-rating vote deleted successfully: {:?}", result);
+                Ok(())
+            }
+            Err(err) => {
+                tracing::error!("Database error, failed to delete farm-rating vote: {}", err);
+                Err(err.into())
+            }
+        }
+    }
+
+    /// Inserts user report on the farm-rating into the database,
+    /// the rating is hidden once it was reported `RATING_REPORTS_TO_HIDE` times
+    /// since it was last reviewed.
+    #[tracing::instrument(name = "Insert FarmRating report", skip(db, report))]
+    pub async fn report(
+        report: FarmRatingReportInsertData,
+        db: DatabaseConnection,
+    ) -> ServerResult<()> {
+        let mut tx = db.pool.begin().await?;
+
+        match sqlx::query!(
+            r#"
+                INSERT INTO services.farm_rating_reports(
+                    id,
+                    rating_id,
+                    user_id,
+                    reason,
+                    created_at
+                )
+                VALUES($1, $2, $3, $4, $5)
+                ON CONFLICT (rating_id, user_id) DO NOTHING;
+            "#,
+            report.id.0,
+            report.rating_id.0,
+            report.user_id.0,
+            report.reason,
+            report.created_at,
+        )
+        .execute(&mut *tx)
+        .await
+        {
+            Ok(result) if result.rows_affected() == 0 => {
+                return Err(ServerError::rejection(EndpointRejection::Conflict(
+                    "You have already reported this rating.".into(),
+                )));
+            }
+            Ok(result) => {
+                tracing::trace!(
+                    "Farm-rating report inserted, but transaction not committed: {:?}",
+                    result
+                );
+            }
+            Err(err) => {
+                // Handle database constraint error
+                handle_farm_rating_database_error(&err)?;
+
+                tracing::error!(
+                    "Database error, failed to insert farm-rating report: {}",
+                    err
+                );
+                return Err(err.into());
+            }
+        }
+
+        match sqlx::query!(
+            r#"
+                UPDATE services.farm_ratings farm_rating
+                SET hidden_at = $2
+                WHERE farm_rating.id = $1
+                    AND farm_rating.hidden_at IS NULL
+                    AND (
+                        SELECT COUNT(report.id)
+                        FROM services.farm_rating_reports report
+                        WHERE report.rating_id = farm_rating.id
+                            AND report.created_at > COALESCE(farm_rating.reviewed_at, '-infinity')
+                    ) >= $3
+            "#,
+            report.rating_id.0,
+            report.created_at,
+            crate::RATING_REPORTS_TO_HIDE,
+        )
+        .execute(&mut *tx)
+        .await
+        {
+            Ok(result) => {
+                tracing::trace!(
+                    "Reported farm-rating hidden, but transaction not committed: {:?}",
+                    result
+                );
+            }
+            Err(err) => {
+                tracing::error!(
+                    "Database error, failed to hide reported farm-rating: {}",
+                    err
+                );
+                return Err(err.into());
+            }
+        }
+
+        tx.commit().await?;
+        tracing::debug!("FarmRating::report, transaction committed successfully.");
+
+        Ok(())
+    }
+
+    /// Dismisses the farm-rating reports and makes the rating visible again,
+    /// only the reports received afterwards can hide the rating.
+    #[tracing::instrument(name = "Restore reported FarmRating", skip(db))]
+    pub async fn restore_reported(
+        id: ModelID,
+        reviewed_at: OffsetDateTime,
+        db: DatabaseConnection,
+    ) -> ServerResult<()> {
+        match sqlx::query!(
+            r#"
+                UPDATE services.farm_ratings farm_rating
+                SET hidden_at = NULL,
+                    reviewed_at = $2
+                WHERE farm_rating.id = $1
+                    AND farm_rating.reply_to IS NULL
+            "#,
+            id.0,
+            reviewed_at,
+        )
+        .execute(&db.pool)
+        .await
+        {
+            Ok(result) if result.rows_affected() == 0 => Err(ServerError::rejection(
+                EndpointRejection::NotFound("Farm rating not found.".into()),
+            )),
+            Ok(result) => {
+                tracing::debug!("Reported farm-rating restored successfully: {:?}", result);
+                Ok(())
+            }
+            Err(err) => {
+                tracing::error!(
+                    "Database error, failed to restore reported farm-rating: {}",
+                    err
+                );
+                Err(err.into())
+            }
+        }
+    }
+
+    /// Fetches the ratings reported since they were last reviewed from the database,
+    /// hidden ratings first.
+    #[tracing::instrument(name = "Fetch ReportedRatingList", skip(db))]
+    pub async fn reported_records(
+        pg: Pagination,
+        db: DatabaseConnection,
+    ) -> ServerResult<ReportedRatingList> {
+        let (offset, limit) = pg.offset_limit();
+        match sqlx::query!(
+            r#"
+                SELECT farm_rating.id AS farm_rating_id,
+                    farm_rating.grade AS farm_rating_grade,
+                    farm_rating.comment AS farm_rating_comment,
+                    farm_rating.images AS farm_rating_images,
+                    farm_rating.verified_buyer AS farm_rating_verified_buyer,
+                    farm_rating.helpful_count AS farm_rating_helpful_count,
+                    farm_rating.updated_at AS "farm_rating_updated_at?",
+                    farm_rating.created_at AS farm_rating_created_at,
+                    farm_rating.hidden_at AS "farm_rating_hidden_at?",
+                    farm.id AS farm_id,
+                    farm.name AS farm_name,
+                    user_.id AS user_id,
+                    user_.first_name AS user_first_name,
+                    user_.last_name AS user_last_name,
+                    user_.identity_verified AS user_identity_verified,
+                    user_profile.photo AS "user_photo?",
+                    COUNT(report.id) AS "report_count!",
+                    array_agg(report.reason ORDER BY report.created_at) AS "report_reasons!"
+                FROM services.farm_ratings farm_rating
+                INNER JOIN services.farm_rating_reports report
+                    ON farm_rating.id = report.rating_id
+                    AND report.created_at > COALESCE(farm_rating.reviewed_at, '-infinity')
+                INNER JOIN services.farms farm
+                    ON farm_rating.farm_id = farm.id
+                INNER JOIN accounts.users user_
+                    ON farm_rating.author_id = user_.id
+                LEFT JOIN accounts.user_profiles user_profile
+                    ON farm_rating.author_id = user_profile.user_id
+
+                GROUP BY farm_rating.id, farm.id, user_.id, user_profile.photo
+                ORDER BY farm_rating.hidden_at IS NULL,
+                    COUNT(report.id) DESC,
+                    MIN(report.created_at)
+                LIMIT $1
+                OFFSET $2
+            "#,
+            limit,
+            offset
+        )
+        .fetch_all(&db.pool)
+        .await
+        {
+            Ok(records) => {
+                let reported = records
+                    .into_iter()
+                    .map(|rec| ReportedRating {
+                        rating: Self::from_row(
+                            rec.farm_rating_id.into(),
+                            rec.farm_rating_grade.unwrap_or_default(),
+                            rec.farm_rating_comment,
+                            rec.farm_rating_images,
+                            rec.farm_rating_verified_buyer,
+                            rec.farm_rating_helpful_count,
+                            rec.farm_rating_updated_at
+                                .unwrap_or(rec.farm_rating_created_at),
+                            rec.farm_id.into(),
+                            rec.farm_name,
+                            rec.user_id.into(),
+                            rec.user_first_name,
+                            rec.user_last_name,
+                            rec.user_photo,
+                            rec.user_identity_verified,
+                        ),
+                        hidden_at: rec.farm_rating_hidden_at,
+                        report_count: rec.report_count,
+                        reasons: rec.report_reasons,
+                    })
+                    .collect();
+
+                Ok(reported)
+            }
+            Err(err) => {
+                tracing::error!(
+                    "Database error, failed to fetch reported farm-ratings: {}",
+                    err
+                );
+                Err(err.into())
+            }
+        }
+    }
+
+    /// Fetches the replies to the ratings from the database
+    /// and nests them under the ratings
+    async fn attach_replies(ratings: &mut [Self], db: DatabaseConnection) -> ServerResult<()> {
+        if ratings.is_empty() {
+            return Ok(());
+        }
+        let rating_ids: Vec<_> = ratings.iter().map(|rating| rating.id).collect();
+        let replies = RatingReply::records(&rating_ids, db).await?;
+        let mut threads = RatingReply::into_threads(&rating_ids, replies);
+        for rating in ratings {
+            rating.replies = threads.remove(&rating.id).unwrap_or_default();
+        }
+        Ok(())
+    }
+}
+
+impl RatingReply {
+    /// Fetches the replies to the ratings, incl replies to replies,
+    /// from the database in chronological order
+    #[tracing::instrument(name = "Fetch RatingReplyList", skip(db))]
+    pub async fn records(
+        rating_ids: &[ModelID],
+        db: DatabaseConnection,
+    ) -> ServerResult<RatingReplyList> {
+        let rating_ids: Vec<_> = rating_ids.iter().map(|id| id.0).collect();
+        match sqlx::query!(
+            r#"
+                WITH RECURSIVE thread AS (
+                    SELECT reply.id,
+                        reply.reply_to,
+                        reply.farm_id,
+                        reply.author_id,
+                        reply.comment,
+                        reply.updated_at,
+                        reply.created_at
+                    FROM services.farm_ratings reply
+                    WHERE reply.reply_to = ANY($1)
+
+                    UNION ALL
+
+                    SELECT reply.id,
+                        reply.reply_to,
+                        reply.farm_id,
+                        reply.author_id,
+                        reply.comment,
+                        reply.updated_at,
+                        reply.created_at
+                    FROM services.farm_ratings reply
+                    INNER JOIN thread
+                        ON reply.reply_to = thread.id
+                )
+
+                SELECT thread.id AS "reply_id!",
+                    thread.reply_to AS "reply_to!",
+                    thread.comment AS reply_comment,
+                    thread.updated_at AS "reply_updated_at?",
+                    thread.created_at AS "reply_created_at!",
+                    COALESCE(thread.author_id = farm.owner_id, false) AS "from_farm!",
+                    user_.id AS user_id,
+                    user_.first_name AS user_first_name,
+                    user_.last_name AS user_last_name,
+                    user_.identity_verified AS user_identity_verified,
+                    user_profile.photo AS "user_photo?"
+                FROM thread
+                LEFT JOIN services.farms farm
+                    ON thread.farm_id = farm.id
+                INNER JOIN accounts.users user_
+                    ON thread.author_id = user_.id
+                LEFT JOIN accounts.user_profiles user_profile
+                    ON thread.author_id = user_profile.user_id
+
+                ORDER BY thread.created_at
+            "#,
+            &rating_ids[..]
+        )
+        .fetch_all(&db.pool)
+        .await
+        {
+            Ok(records) => {
+                let replies = records
+                    .into_iter()
+                    .map(|rec| {
+                        Self::from_row(
+                            rec.reply_id.into(),
+                            rec.reply_comment,
+                            rec.from_farm,
+                            rec.reply_to.into(),
+                            rec.reply_updated_at.unwrap_or(rec.reply_created_at),
+                            rec.user_id.into(),
+                            rec.user_first_name,
+                            rec.user_last_name,
+                            rec.user_photo,
+                            rec.user_identity_verified,
+                        )
+                    })
+                    .collect();
+
+                Ok(replies)
+            }
+            Err(err) => {
+                tracing::error!("Database error, failed to fetch rating replies: {}", err);
+                Err(err.into())
+            }
+        }
+    }
+
+    /// Inserts rating reply into the database
+    #[tracing::instrument(name = "Insert RatingReply", skip(db, reply))]
+    pub async fn insert(
+        reply: RatingReplyInsertData,
+        db: DatabaseConnection,
+    ) -> ServerResult<ModelID> {
+        match sqlx::query!(
+            r#"
+                INSERT INTO services.farm_ratings(
+                    id,
+                    author_id,
+                    farm_id,
+                    comment,
+                    reply_to,
+                    created_at
+                )
+                SELECT $1, $2, parent.farm_id, $3, parent.id, $4
+                FROM services.farm_ratings parent
+                WHERE parent.id = $5
+            "#,
+            reply.id.0,
+            reply.user_id.0,
+            reply.comment,
+            reply.created_at,
+            reply.reply_to.0,
+        )
+        .execute(&db.pool)
+        .await
+        {
+            Ok(result) if result.rows_affected() == 0 => Err(ServerError::rejection(
+                EndpointRejection::NotFound("Farm rating not found.".into()),
+            )),
+            Ok(result) => {
+                tracing::debug!("Rating reply inserted successfully: {:?}", result);
+                Ok(reply.id)
+            }
+            Err(err) => {
+                // Handle database constraint error
+                handle_farm_rating_database_error(&err)?;
+
+                tracing::error!("Database error, failed to insert rating reply: {}", err);
+                Err(err.into())
+            }
+        }
+    }
+
+    /// Updates rating reply in the database
+    #[tracing::instrument(name = "Update RatingReply", skip(db, reply))]
+    pub async fn update(
+        id: ModelID,
+        reply: RatingReplyUpdateData,
+        db: DatabaseConnection,
+    ) -> ServerResult<()> {
+        match sqlx::query!(
+            r#"
+                UPDATE services.farm_ratings reply
+                SET comment = $1,
+                    updated_at = $2
+                WHERE reply.id = $3
+                    AND reply.reply_to IS NOT NULL
+            "#,
+            reply.comment,
+            reply.updated_at,
+            id.0,
+        )
+        .execute(&db.pool)
+        .await
+        {
+            Ok(result) => {
+                tracing::debug!("Rating reply updated successfully: {:?}", result);
+                Ok(())
+            }
+            Err(err) => {
+                // Handle database constraint error
+                handle_farm_rating_database_error(&err)?;
+
+                tracing::error!("Database error, failed to update rating reply: {}", err);
+                Err(err.into())
+            }
+        }
+    }
+
+    /// Deletes rating reply and the replies to it from the database
+    #[tracing::instrument(name = "Delete RatingReply", skip(db))]
+    pub async fn delete(id: ModelID, db: DatabaseConnection) -> ServerResult<()> {
+        match sqlx::query!(
+            r#"
+                DELETE FROM services.farm_ratings reply
+                WHERE reply.id = $1
+                    AND reply.reply_to IS NOT NULL
+            "#,
+            id.0
+        )
+        .execute(&db.pool)
+        .await
+        {
+            Ok(result) => {
+                tracing::debug!("Rating reply deleted successfully: {:?}", result);
+                Ok(())
+            }
+            Err(err) => {
+                tracing::error!("Database error, failed to delete rating reply: {}", err);
+                Err(err.into())
+            }
+        }
+    }
+}
+
+impl RatingSummary {
+    /// Fetches farm ratings summary incl the most recent
+    /// and most helpful ratings from the database
+    #[tracing::instrument(name = "Find RatingSummary", skip(db))]
+    pub async fn find(farm_id: ModelID, db: DatabaseConnection) -> ServerResult<Option<Self>> {
+        match sqlx::query!(
+            r#"
+                SELECT stat.rating_average AS "rating_average?",
+                    stat.rating_count AS "rating_count?",
+                    stat.grade_1_count AS "grade_1_count?",
+                    stat.grade_2_count AS "grade_2_count?",
+                    stat.grade_3_count AS "grade_3_count?",
+                    stat.grade_4_count AS "grade_4_count?",
+                    stat.grade_5_count AS "grade_5_count?"
+                FROM services.active_farms farm
+                LEFT JOIN services.farm_stats stat
+                    ON farm.id = stat.farm_id
+
+                WHERE farm.id = $1
+            "#,
+            farm_id.0
+        )
+        .fetch_optional(&db.pool)
+        .await
+        {
+            Ok(Some(rec)) => {
+                let mut summary = Self::from_row(
+                    rec.rating_average,
+                    rec.rating_count,
+                    [
+                        rec.grade_1_count,
+                        rec.grade_2_count,
+                        rec.grade_3_count,
+                        rec.grade_4_count,
+                        rec.grade_5_count,
+                    ],
+                );
+                summary.load_highlights(farm_id, db).await?;
+                Ok(Some(summary))
+            }
+            Ok(None) => Ok(None),
+            Err(err) => {
+                tracing::error!("Database error, failed to fetch rating summary: {}", err);
+                Err(err.into())
+            }
+        }
+    }
+
+    /// Fetches the farm most recent and most helpful ratings from the database
+    pub async fn load_highlights(
+        &mut self,
+        farm_id: ModelID,
+        db: DatabaseConnection,
+    ) -> ServerResult<()> {
+        if self.count == 0 {
+            return Ok(());
+        }
+
+        let pg = Pagination::new(1, RATING_SUMMARY_HIGHLIGHTS);
+        self.recent = FarmRating::records_for_farm(
+            farm_id,
+            &FarmRatingFilter::sorted(RatingSort::Recent),
+            pg.clone(),
+            db.clone(),
+        )
+        .await?;
+        self.most_helpful = FarmRating::records_for_farm(
+            farm_id,
+            &FarmRatingFilter::sorted(RatingSort::Helpful),
+            pg,
+            db,
+        )
+        .await?;
+
+        Ok(())
+    }
 }
 
 /// Handle harvest database constraints errors
 #[allow(clippy::cognitive_complexity)]
 fn handle_farm_rating_database_error(err: &sqlx::Error) -> ServerResult<()> {
     if let sqlx::Error::Database(db_err) = err {
+        // Handle db unique constraints
+        if db_err.is_unique_violation()
+            && db_err.constraint() == Some("farm_ratings_author_id_farm_id_key")
+        {
+            tracing::error!("Database error, user already rated the farm. {:?}", err);
+            return Err(ServerError::rejection(EndpointRejection::Conflict(
+                "You have already rated this farm, update your rating instead.".into(),
+            )));
+        }
+
         // Handle db foreign key constraints
         if db_err.is_foreign_key_violation() {
             if let Some(constraint) = db_err.constraint() {
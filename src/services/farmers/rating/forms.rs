@@ -2,7 +2,8 @@
 
 use axum::{
     async_trait,
-    extract::{rejection::JsonRejection, FromRequest, FromRequestParts, Json, Request},
+    extract::{rejection::JsonRejection, FromRequest, FromRequestParts, Json, Multipart, Request},
+    http::header,
 };
 use serde::Deserialize;
 use time::OffsetDateTime;
@@ -13,20 +14,30 @@ use crate::{
         validators::{TransformString, ValidateString},
         EndpointRejection, EndpointResult,
     },
+    files::UploadedFile,
     server::state::ServerState,
     types::ModelID,
 };
 
-use super::permissions::check_user_owns_rating;
+use super::permissions::{
+    check_user_can_give_feedback, check_user_can_reply, check_user_owns_rating,
+    check_user_owns_reply,
+};
 
-use helpers::validate_rating_grade;
+use helpers::{validate_rating_grade, validate_reply_comment, validate_report_reason};
 
 /// Farm rating create form
+///
+/// Accepts a json body, or a `multipart/form-data` body
+/// with the `grade` and `comment` fields and the rating photos.
 #[derive(Debug, Clone, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct FarmRatingCreateForm {
     pub grade: u8,
     pub comment: String,
+    /// Photos of what the author received, only accepted via multipart
+    #[serde(skip)]
+    pub photos: Vec<UploadedFile>,
 }
 
 /// Farm rating cleaned data
@@ -37,6 +48,7 @@ pub struct FarmRatingInsertData {
     pub user_id: ModelID,
     pub grade: u8,
     pub comment: String,
+    pub images: Vec<String>,
     pub created_at: OffsetDateTime,
 }
 
@@ -59,19 +71,79 @@ impl FarmRatingCreateForm {
         self.comment = self.comment.clean();
     }
 
-    /// Convert `Self` into `FarmRatingInsertData`
-    #[allow(dead_code)]
+    /// Convert `Self` into `FarmRatingInsertData`,
+    /// `images` are the paths the rating photos were saved to.
     #[must_use]
-    pub fn data(self, farm_id: ModelID, user_id: ModelID) -> FarmRatingInsertData {
+    pub fn data(
+        self,
+        farm_id: ModelID,
+        user_id: ModelID,
+        images: Vec<String>,
+    ) -> FarmRatingInsertData {
         FarmRatingInsertData {
             id: ModelID::new(),
             farm_id,
             user_id,
             grade: self.grade,
             comment: self.comment,
+            images,
             created_at: OffsetDateTime::now_utc(),
         }
     }
+
+    /// Parses the rating fields and photos from the multipart body
+    async fn from_multipart(mut multipart: Multipart) -> EndpointResult<Self> {
+        let mut grade = None;
+        let mut comment = String::new();
+        let mut photos = Vec::new();
+
+        while let Some(field) = multipart
+            .next_field()
+            .await
+            .map_err(|err| EndpointRejection::BadRequest(err.to_string().into()))?
+        {
+            if field.file_name().is_some() {
+                if photos.len() == usize::from(crate::RATING_MAX_IMAGE) {
+                    return Err(EndpointRejection::BadRequest(
+                        format!(
+                            "Rating can have at most {} photos.",
+                            crate::RATING_MAX_IMAGE
+                        )
+                        .into(),
+                    ));
+                }
+                photos.push(UploadedFile::try_from_field(field).await?);
+                continue;
+            }
+
+            let name = field.name().map(ToOwned::to_owned);
+            let text = field
+                .text()
+                .await
+                .map_err(|err| EndpointRejection::BadRequest(err.to_string().into()))?;
+            match name.as_deref() {
+                Some("grade") => {
+                    grade = Some(text.trim().parse().map_err(|_| {
+                        EndpointRejection::BadRequest("Rating grade must be between 1 and 5".into())
+                    })?);
+                }
+                Some("comment") => comment = text,
+                _ => {}
+            }
+        }
+
+        let Some(grade) = grade else {
+            return Err(EndpointRejection::BadRequest(
+                "Rating grade is required.".into(),
+            ));
+        };
+
+        Ok(Self {
+            grade,
+            comment,
+            photos,
+        })
+    }
 }
 
 #[async_trait]
@@ -83,15 +155,105 @@ where
 
     async fn from_request(req: Request, state: &ServerState) -> Result<Self, Self::Rejection> {
         // Extract data
-        let Json(mut rating) = Json::<Self>::from_request(req, state).await?;
+        let (mut parts, body) = req.into_parts();
+        let user = { CurrentUser::from_parts(&mut parts, state).await? };
+        let farm_id = { ModelID::from_request_parts(&mut parts, state).await? };
+        let is_multipart = parts
+            .headers
+            .get(header::CONTENT_TYPE)
+            .and_then(|content_type| content_type.to_str().ok())
+            .is_some_and(|content_type| content_type.starts_with("multipart/form-data"));
+        let req = Request::from_parts(parts, body);
+
+        let mut rating = if is_multipart {
+            let multipart = Multipart::from_request(req, state)
+                .await
+                .map_err(|err| EndpointRejection::BadRequest(err.body_text().into()))?;
+            Self::from_multipart(multipart).await?
+        } else {
+            let Json(rating) = Json::<Self>::from_request(req, state).await?;
+            rating
+        };
 
         // Validate form fields
         rating.validate()?;
 
+        // Authorize request
+        state
+            .rating_policy()
+            .check(user.id, farm_id, state.database())
+            .await?;
+
         Ok(rating)
     }
 }
 
+// ===== FarmRating Filter impls ======
+
+/// Farm ratings filter and sort order
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FarmRatingFilter {
+    /// Only return ratings with this grade
+    pub grade: Option<u8>,
+    #[serde(default)]
+    pub sort: RatingSort,
+}
+
+impl FarmRatingFilter {
+    /// Creates a new `FarmRatingFilter` sorting by `sort`
+    #[must_use]
+    pub const fn sorted(sort: RatingSort) -> Self {
+        Self { grade: None, sort }
+    }
+
+    /// Validates the grade filter is between 1 and 5
+    ///
+    /// # Errors
+    ///
+    /// Return bad request if the grade is out of range
+    pub fn validate(&self) -> EndpointResult<()> {
+        self.grade.map_or(Ok(()), validate_rating_grade)
+    }
+
+    /// Returns the grade filter as it stored in the database
+    #[must_use]
+    pub fn grade(&self) -> Option<i32> {
+        self.grade.map(i32::from)
+    }
+}
+
+/// Farm ratings sort order
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum RatingSort {
+    /// Oldest ratings first
+    #[default]
+    Oldest,
+    /// Newest ratings first
+    Recent,
+    /// Highest grade first
+    Highest,
+    /// Lowest grade first
+    Lowest,
+    /// Most helpful ratings first, by the number of helpful votes
+    Helpful,
+}
+
+impl RatingSort {
+    /// Returns the sort order name used by the database queries
+    #[must_use]
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Self::Oldest => "oldest",
+            Self::Recent => "recent",
+            Self::Highest => "highest",
+            Self::Lowest => "lowest",
+            Self::Helpful => "helpful",
+        }
+    }
+}
+
 // ===== FarmRating Update form impls ======
 
 /// Farm rating update form
@@ -174,11 +336,234 @@ where
     }
 }
 
+// ===== RatingReply Create form impls ======
+
+/// Rating reply create form
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RatingReplyCreateForm {
+    pub comment: String,
+}
+
+/// Rating reply cleaned data
+#[derive(Debug, Clone)]
+pub struct RatingReplyInsertData {
+    pub id: ModelID,
+    pub reply_to: ModelID,
+    pub user_id: ModelID,
+    pub comment: String,
+    pub created_at: OffsetDateTime,
+}
+
+impl RatingReplyCreateForm {
+    /// Validates rating reply form inputs
+    fn validate(&mut self) -> EndpointResult<()> {
+        // Clean the data
+        self.comment = self.comment.clean();
+
+        validate_reply_comment(&self.comment)
+    }
+
+    /// Convert `Self` into `RatingReplyInsertData`
+    #[must_use]
+    pub fn data(self, reply_to: ModelID, user_id: ModelID) -> RatingReplyInsertData {
+        RatingReplyInsertData {
+            id: ModelID::new(),
+            reply_to,
+            user_id,
+            comment: self.comment,
+            created_at: OffsetDateTime::now_utc(),
+        }
+    }
+}
+
+#[async_trait]
+impl FromRequest<ServerState> for RatingReplyCreateForm
+where
+    Json<Self>: FromRequest<ServerState, Rejection = JsonRejection>,
+{
+    type Rejection = EndpointRejection;
+
+    async fn from_request(req: Request, state: &ServerState) -> Result<Self, Self::Rejection> {
+        // Extract data
+        let (mut parts, body) = req.into_parts();
+        let user = { CurrentUser::from_parts(&mut parts, state).await? };
+        let reply_to = { ModelID::from_request_parts(&mut parts, state).await? };
+        let Json(mut reply) =
+            Json::<Self>::from_request(Request::from_parts(parts, body), state).await?;
+
+        // Validate form fields
+        reply.validate()?;
+
+        // Authorize request
+        check_user_can_reply(user.id, reply_to, state.database()).await?;
+
+        Ok(reply)
+    }
+}
+
+// ===== RatingReply Update form impls ======
+
+/// Rating reply update form
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RatingReplyUpdateForm {
+    pub comment: String,
+}
+
+/// Rating reply update cleaned data
+#[derive(Debug, Clone)]
+pub struct RatingReplyUpdateData {
+    pub comment: String,
+    pub updated_at: OffsetDateTime,
+}
+
+impl From<RatingReplyUpdateForm> for RatingReplyUpdateData {
+    fn from(form: RatingReplyUpdateForm) -> Self {
+        Self {
+            comment: form.comment,
+            updated_at: OffsetDateTime::now_utc(),
+        }
+    }
+}
+
+impl RatingReplyUpdateForm {
+    /// Validates rating reply form inputs
+    fn validate(&mut self) -> EndpointResult<()> {
+        // Clean the data
+        self.comment = self.comment.clean();
+
+        validate_reply_comment(&self.comment)
+    }
+}
+
+#[async_trait]
+impl FromRequest<ServerState> for RatingReplyUpdateForm
+where
+    Json<Self>: FromRequest<ServerState, Rejection = JsonRejection>,
+{
+    type Rejection = EndpointRejection;
+
+    async fn from_request(req: Request, state: &ServerState) -> Result<Self, Self::Rejection> {
+        // Extract data
+        let (mut parts, body) = req.into_parts();
+        let user = { CurrentUser::from_parts(&mut parts, state).await? };
+        let reply_id = { ModelID::from_request_parts(&mut parts, state).await? };
+        let Json(mut reply) =
+            Json::<Self>::from_request(Request::from_parts(parts, body), state).await?;
+
+        // Validate form fields
+        reply.validate()?;
+
+        // Authorize request
+        check_user_owns_reply(user.id, reply_id, state.database()).await?;
+
+        Ok(reply)
+    }
+}
+
+// ===== FarmRating Report form impls ======
+
+/// Farm rating report form
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FarmRatingReportForm {
+    pub reason: String,
+}
+
+/// Farm rating report cleaned data
+#[derive(Debug, Clone)]
+pub struct FarmRatingReportInsertData {
+    pub id: ModelID,
+    pub rating_id: ModelID,
+    pub user_id: ModelID,
+    pub reason: String,
+    pub created_at: OffsetDateTime,
+}
+
+impl FarmRatingReportForm {
+    /// Validates farm rating report form inputs
+    fn validate(&mut self) -> EndpointResult<()> {
+        // Clean the data
+        self.reason = self.reason.clean();
+
+        validate_report_reason(&self.reason)
+    }
+
+    /// Convert `Self` into `FarmRatingReportInsertData`
+    #[must_use]
+    pub fn data(self, rating_id: ModelID, user_id: ModelID) -> FarmRatingReportInsertData {
+        FarmRatingReportInsertData {
+            id: ModelID::new(),
+            rating_id,
+            user_id,
+            reason: self.reason,
+            created_at: OffsetDateTime::now_utc(),
+        }
+    }
+}
+
+#[async_trait]
+impl FromRequest<ServerState> for FarmRatingReportForm
+where
+    Json<Self>: FromRequest<ServerState, Rejection = JsonRejection>,
+{
+    type Rejection = EndpointRejection;
+
+    async fn from_request(req: Request, state: &ServerState) -> Result<Self, Self::Rejection> {
+        // Extract data
+        let (mut parts, body) = req.into_parts();
+        let user = { CurrentUser::from_parts(&mut parts, state).await? };
+        let rating_id = { ModelID::from_request_parts(&mut parts, state).await? };
+        let Json(mut report) =
+            Json::<Self>::from_request(Request::from_parts(parts, body), state).await?;
+
+        // Validate form fields
+        report.validate()?;
+
+        // Authorize request
+        check_user_can_give_feedback(user.id, rating_id, state.database()).await?;
+
+        Ok(report)
+    }
+}
+
+// ===== FarmRating Review form impls ======
+
+/// Staff decision on a reported farm rating
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum RatingReviewDecision {
+    /// The reports are dismissed and the rating is visible again
+    Restore,
+    /// The rating is deleted
+    Remove,
+}
+
+/// Farm rating review form
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RatingReviewForm {
+    pub decision: RatingReviewDecision,
+}
+
 // ===== Helpers =====
 
 mod helpers {
     use crate::endpoint::{EndpointRejection, EndpointResult};
 
+    use crate::endpoint::validators::ValidateString;
+
+    /// Validate reply `comment` is not empty.
+    pub fn validate_reply_comment(comment: &str) -> EndpointResult<()> {
+        comment.validate_len(1, 512, "Reply must be between 1 and 512 characters")
+    }
+
+    /// Validate report `reason` is not empty.
+    pub fn validate_report_reason(reason: &str) -> EndpointResult<()> {
+        reason.validate_len(1, 256, "Report reason must be between 1 and 256 characters")
+    }
+
     /// Validate rating `grade` is between 1 and 5.
     pub fn validate_rating_grade(grade: u8) -> EndpointResult<()> {
         if !(1..=5).contains(&grade) {
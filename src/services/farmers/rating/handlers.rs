@@ -4,6 +4,7 @@ use axum::{
     extract::{Json, Query, State},
     http::StatusCode,
 };
+use time::OffsetDateTime;
 
 use crate::{
     auth::{AdminUser, CurrentUser},
@@ -14,9 +15,15 @@ use crate::{
 };
 
 use super::{
-    forms::{FarmRatingCreateForm, FarmRatingUpdateForm},
-    models::{FarmRating, FarmRatingList},
-    permissions::FarmRatingOwnershipPermission,
+    forms::{
+        FarmRatingCreateForm, FarmRatingFilter, FarmRatingReportForm, FarmRatingUpdateForm,
+        RatingReplyCreateForm, RatingReplyUpdateForm, RatingReviewDecision, RatingReviewForm,
+    },
+    models::{FarmRating, FarmRatingList, RatingReply, RatingSummary, ReportedRatingList},
+    permissions::{
+        FarmRatingOwnershipPermission, FarmRatingVotePermission, RatingReplyDeletePermission,
+    },
+    utils::{delete_rating_photos, save_rating_photos},
 };
 
 /// Handles the `GET /farms/ratings` route.
@@ -34,17 +41,35 @@ pub async fn farm_rating_list(
 }
 
 /// Handles the `GET /farms/:farm_id/ratings` route.
+///
+/// Ratings can be filtered by `grade` and sorted by `sort`,
+/// one of `oldest`, `recent`, `highest`, `lowest` or `helpful`.
 #[tracing::instrument(skip(db))]
 pub async fn farm_ratings(
     farm_id: ModelID,
+    filter: Option<Query<FarmRatingFilter>>,
     pg: Option<Query<Pagination>>,
     State(db): State<DatabaseConnection>,
 ) -> EndpointResult<Json<FarmRatingList>> {
+    let filter = filter.unwrap_or_default().0;
+    filter.validate()?;
     let pagination = pg.unwrap_or_default().0;
-    let farm_ratings = FarmRating::records_for_farm(farm_id, pagination, db).await?;
+    let farm_ratings = FarmRating::records_for_farm(farm_id, &filter, pagination, db).await?;
     Ok(Json(farm_ratings))
 }
 
+/// Handles the `GET /farms/:farm_id/ratings/summary` route.
+#[tracing::instrument(skip(db))]
+pub async fn farm_rating_summary(
+    farm_id: ModelID,
+    State(db): State<DatabaseConnection>,
+) -> EndpointResult<Json<RatingSummary>> {
+    RatingSummary::find(farm_id, db).await?.map_or_else(
+        || Err(EndpointRejection::NotFound("Farm not found".into())),
+        |summary| Ok(Json(summary)),
+    )
+}
+
 /// Handles the `GET /farms/ratings/rating_id` route.
 #[tracing::instrument(skip(db))]
 pub async fn farm_rating_detail(
@@ -58,14 +83,25 @@ pub async fn farm_rating_detail(
 }
 
 /// Handles the `POST /farms/:farm_id/ratings` route.
+///
+/// The user must be allowed to rate the farm by the server rating policy,
+/// photos can be attached to the rating via `multipart/form-data`.
 #[tracing::instrument(skip(db, user, form))]
 pub async fn farm_rating_create(
     user: CurrentUser,
     farm_id: ModelID,
     State(db): State<DatabaseConnection>,
-    form: FarmRatingCreateForm,
+    mut form: FarmRatingCreateForm,
 ) -> EndpointResult<StatusCode> {
-    FarmRating::insert(form.data(farm_id, user.id), db).await?;
+    // Save the rating photos to the file system
+    let images = save_rating_photos(std::mem::take(&mut form.photos)).await?;
+
+    // Delete the saved photos if the rating could not be saved
+    if let Err(err) = FarmRating::insert(form.data(farm_id, user.id, images.clone()), db).await {
+        tokio::spawn(async move { delete_rating_photos(images.into_iter()).await });
+        return Err(err.into());
+    }
+
     Ok(StatusCode::CREATED)
 }
 
@@ -90,3 +126,115 @@ pub async fn farm_rating_delete(
     FarmRating::delete(rating_id, db).await?;
     Ok(StatusCode::NO_CONTENT)
 }
+
+/// Handles the `POST /farms/ratings/:rating_id/replies` route.
+///
+/// Replies to a rating or to a reply.
+#[tracing::instrument(skip(db, user, form))]
+pub async fn rating_reply_create(
+    user: CurrentUser,
+    rating_id: ModelID,
+    State(db): State<DatabaseConnection>,
+    form: RatingReplyCreateForm,
+) -> EndpointResult<StatusCode> {
+    RatingReply::insert(form.data(rating_id, user.id), db).await?;
+    Ok(StatusCode::CREATED)
+}
+
+/// Handles the `PUT /farms/ratings/replies/:reply_id` route.
+#[tracing::instrument(skip(db, form))]
+pub async fn rating_reply_update(
+    reply_id: ModelID,
+    State(db): State<DatabaseConnection>,
+    form: RatingReplyUpdateForm,
+) -> EndpointResult<StatusCode> {
+    RatingReply::update(reply_id, form.into(), db).await?;
+    Ok(StatusCode::OK)
+}
+
+/// Handles the `DELETE /farms/ratings/replies/:reply_id` route.
+#[tracing::instrument(skip(db))]
+pub async fn rating_reply_delete(
+    _: RatingReplyDeletePermission,
+    reply_id: ModelID,
+    State(db): State<DatabaseConnection>,
+) -> EndpointResult<StatusCode> {
+    RatingReply::delete(reply_id, db).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Handles the `POST /farms/ratings/:rating_id/helpful` route.
+///
+/// Marks the rating as helpful, voting again has no effect.
+#[tracing::instrument(skip(db, user))]
+pub async fn farm_rating_vote(
+    user: CurrentUser,
+    _: FarmRatingVotePermission,
+    rating_id: ModelID,
+    State(db): State<DatabaseConnection>,
+) -> EndpointResult<StatusCode> {
+    FarmRating::vote(rating_id, user.id, OffsetDateTime::now_utc(), db).await?;
+    Ok(StatusCode::CREATED)
+}
+
+/// Handles the `DELETE /farms/ratings/:rating_id/helpful` route.
+#[tracing::instrument(skip(db, user))]
+pub async fn farm_rating_unvote(
+    user: CurrentUser,
+    rating_id: ModelID,
+    State(db): State<DatabaseConnection>,
+) -> EndpointResult<StatusCode> {
+    FarmRating::unvote(rating_id, user.id, db).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Handles the `POST /farms/ratings/:rating_id/reports` route.
+///
+/// The rating is hidden until staff review it
+/// once it was reported by enough users.
+#[tracing::instrument(skip(db, user, form))]
+pub async fn farm_rating_report(
+    user: CurrentUser,
+    rating_id: ModelID,
+    State(db): State<DatabaseConnection>,
+    form: FarmRatingReportForm,
+) -> EndpointResult<StatusCode> {
+    FarmRating::report(form.data(rating_id, user.id), db).await?;
+    Ok(StatusCode::CREATED)
+}
+
+/// Handles the `GET /farms/ratings/reports` route.
+///
+/// Return the ratings waiting for staff review, hidden ratings first.
+#[tracing::instrument(skip(db))]
+pub async fn farm_rating_reported_list(
+    _: AdminUser,
+    pg: Option<Query<Pagination>>,
+    State(db): State<DatabaseConnection>,
+) -> EndpointResult<Json<ReportedRatingList>> {
+    let pagination = pg.unwrap_or_default().0;
+    let reported = FarmRating::reported_records(pagination, db).await?;
+    Ok(Json(reported))
+}
+
+/// Handles the `POST /farms/ratings/:rating_id/review` route.
+///
+/// Restores the reported rating or removes it.
+#[tracing::instrument(skip(db))]
+pub async fn farm_rating_review(
+    _: AdminUser,
+    rating_id: ModelID,
+    State(db): State<DatabaseConnection>,
+    Json(form): Json<RatingReviewForm>,
+) -> EndpointResult<StatusCode> {
+    match form.decision {
+        RatingReviewDecision::Restore => {
+            FarmRating::restore_reported(rating_id, OffsetDateTime::now_utc(), db).await?;
+            Ok(StatusCode::OK)
+        }
+        RatingReviewDecision::Remove => {
+            FarmRating::delete(rating_id, db).await?;
+            Ok(StatusCode::NO_CONTENT)
+        }
+    }
+}
@@ -5,3 +5,7 @@ pub mod forms;
 pub mod handlers;
 pub mod models;
 pub mod permissions;
+pub mod policy;
+mod utils;
+
+pub use utils::delete_rating_photos;
@@ -1,5 +1,7 @@
 //! Farm rating model impls
 
+use std::collections::{BTreeMap, HashMap};
+
 use serde::Serialize;
 use time::OffsetDateTime;
 
@@ -17,32 +19,165 @@ pub struct FarmRating {
     pub id: ModelID,
     pub grade: u8,
     pub comment: Option<String>,
+    /// Photos of what the author received
+    pub images: Option<Vec<String>>,
     pub farm: ModelIdentifier,
     pub author: UserIndex,
+    /// Whether the author had a conversation with the farm owner
+    pub verified_buyer: bool,
+    /// Number of users who found the rating helpful
+    pub helpful_count: i64,
     /// The date on which the rating was last updated at and
     /// if is not set is the rating creation date
     pub update_at: OffsetDateTime,
+    /// Replies to the rating in chronological order
+    pub replies: RatingReplyList,
+}
+
+/// Farm ratings summary
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RatingSummary {
+    pub average: Option<f64>,
+    pub count: i64,
+    /// Number of ratings per grade, from grade 1 to 5
+    pub histogram: BTreeMap<u8, i64>,
+    /// The most recent ratings, only set on the farm detail
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub recent: FarmRatingList,
+    /// The most helpful ratings, only set on the farm detail
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub most_helpful: FarmRatingList,
+}
+
+impl RatingSummary {
+    /// Creates a new `RatingSummary` from the database row,
+    /// `grade_counts` are the number of ratings for grades 1 to 5.
+    #[must_use]
+    pub fn from_row(
+        average: Option<f64>,
+        count: Option<i64>,
+        grade_counts: [Option<i64>; 5],
+    ) -> Self {
+        Self {
+            average: average.map(|average| (average * 10.0).round() / 10.0),
+            count: count.unwrap_or_default(),
+            histogram: (1..=5)
+                .zip(grade_counts)
+                .map(|(grade, count)| (grade, count.unwrap_or_default()))
+                .collect(),
+            recent: Vec::new(),
+            most_helpful: Vec::new(),
+        }
+    }
 }
 
-// // `FarmRating` reply
-// #[derive(Debug, Clone, Serialize)]
-// pub struct RatingReply {
-//     pub id: ModelID,
-//     pub comment: String,
-//     pub author: UserIndex,
-//     pub reply_to: ModelID,
-//     pub created_at: OffsetDateTime,
-// }
-// WITH RECURSIVE ctename AS (
-//     SELECT empno, ename
-//     FROM emp
-//     WHERE empno = 7566
-//  UNION ALL
-//     SELECT emp.empno, emp.ename
-//     FROM emp
-//        JOIN ctename ON emp.mgr = ctename.empno
-// )
-// SELECT * FROM ctename;
+/// A `Vec` of reported farm ratings
+pub type ReportedRatingList = Vec<ReportedRating>;
+
+/// A farm rating reported by users, waiting for staff review
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReportedRating {
+    pub rating: FarmRating,
+    /// The date on which the rating was hidden,
+    /// `None` if the rating is still visible.
+    pub hidden_at: Option<OffsetDateTime>,
+    /// Number of reports since the rating was last reviewed
+    pub report_count: i64,
+    /// The reports reasons in chronological order
+    pub reasons: Vec<String>,
+}
+
+/// A `Vec` of farm rating replies
+pub type RatingReplyList = Vec<RatingReply>;
+
+/// A reply to a `FarmRating` or to another reply,
+/// it is a row in the `farm_ratings` database table with `reply_to` set.
+#[derive(Debug, Clone, Serialize)]
+pub struct RatingReply {
+    pub id: ModelID,
+    pub comment: String,
+    pub author: UserIndex,
+    /// Whether the reply was written by the farm owner
+    pub from_farm: bool,
+    pub reply_to: ModelID,
+    /// The date on which the reply was last updated at and
+    /// if is not set is the reply creation date
+    pub update_at: OffsetDateTime,
+    /// Replies to this reply in chronological order
+    pub replies: RatingReplyList,
+}
+
+impl RatingReply {
+    /// Creates a new `RatingReply` from the database row
+    #[allow(clippy::too_many_arguments)]
+    #[must_use]
+    pub fn from_row(
+        id: ModelID,
+        comment: Option<String>,
+        from_farm: bool,
+        reply_to: ModelID,
+        update_at: OffsetDateTime,
+        user_id: ModelID,
+        user_first_name: String,
+        user_last_name: Option<String>,
+        user_photo: Option<String>,
+        user_identity_verified: bool,
+    ) -> Self {
+        Self {
+            id,
+            comment: comment.unwrap_or_default(),
+            author: UserIndex::from_row(
+                user_id,
+                user_first_name,
+                user_last_name,
+                user_photo,
+                user_identity_verified,
+            ),
+            from_farm,
+            reply_to,
+            update_at,
+            replies: Vec::new(),
+        }
+    }
+
+    /// Nests the replies under the rating or reply they respond to,
+    /// `replies` must be in chronological order.
+    ///
+    /// Returns the replies grouped by the rating they respond to.
+    #[must_use]
+    pub fn into_threads(
+        rating_ids: &[ModelID],
+        replies: RatingReplyList,
+    ) -> HashMap<ModelID, RatingReplyList> {
+        let mut children: HashMap<ModelID, RatingReplyList> = HashMap::new();
+        for reply in replies {
+            children.entry(reply.reply_to).or_default().push(reply);
+        }
+
+        rating_ids
+            .iter()
+            .filter_map(|rating_id| {
+                let replies = children.remove(rating_id)?;
+                Some((*rating_id, Self::nest(replies, &mut children)))
+            })
+            .collect()
+    }
+
+    /// Attaches the replies to each reply in `replies`
+    fn nest(
+        mut replies: RatingReplyList,
+        children: &mut HashMap<ModelID, RatingReplyList>,
+    ) -> RatingReplyList {
+        for reply in &mut replies {
+            if let Some(nested) = children.remove(&reply.id) {
+                reply.replies = Self::nest(nested, children);
+            }
+        }
+        replies
+    }
+}
 
 impl FarmRating {
     /// Creates a new `FarmRating` from the database row
@@ -56,6 +191,9 @@ impl FarmRating {
         id: ModelID,
         grade: i32,
         comment: Option<String>,
+        images: Option<Vec<String>>,
+        verified_buyer: bool,
+        helpful_count: i64,
         update_at: OffsetDateTime,
         farm_id: ModelID,
         farm_name: String,
@@ -63,14 +201,25 @@ impl FarmRating {
         user_first_name: String,
         user_last_name: Option<String>,
         user_photo: Option<String>,
+        user_identity_verified: bool,
     ) -> Self {
         Self {
             id,
             grade: grade as u8,
             comment,
+            images,
             farm: ModelIdentifier::from_row(farm_id, farm_name),
-            author: UserIndex::from_row(user_id, user_first_name, user_last_name, user_photo),
+            author: UserIndex::from_row(
+                user_id,
+                user_first_name,
+                user_last_name,
+                user_photo,
+                user_identity_verified,
+            ),
+            verified_buyer,
+            helpful_count,
             update_at,
+            replies: Vec::new(),
         }
     }
 }
@@ -30,6 +30,53 @@ impl FromRequestParts<ServerState> for FarmRatingOwnershipPermission {
     }
 }
 
+/// Checks if user can delete the rating reply
+///
+/// Replies can be deleted by their author or by staff,
+/// the replies to the reply are deleted together with it.
+#[derive(Debug, Clone)]
+pub struct RatingReplyDeletePermission;
+
+#[async_trait]
+impl FromRequestParts<ServerState> for RatingReplyDeletePermission {
+    type Rejection = EndpointRejection;
+
+    async fn from_request_parts(
+        parts: &mut Parts,
+        state: &ServerState,
+    ) -> Result<Self, Self::Rejection> {
+        let user = CurrentUser::from_parts(parts, state).await?;
+        let reply_id = ModelID::from_request_parts(parts, state).await?;
+
+        if !user.is_staff {
+            check_user_owns_reply(user.id(), reply_id, state.database()).await?;
+        }
+
+        Ok(Self)
+    }
+}
+
+/// Checks if user can vote on the rating
+#[derive(Debug, Clone)]
+pub struct FarmRatingVotePermission;
+
+#[async_trait]
+impl FromRequestParts<ServerState> for FarmRatingVotePermission {
+    type Rejection = EndpointRejection;
+
+    async fn from_request_parts(
+        parts: &mut Parts,
+        state: &ServerState,
+    ) -> Result<Self, Self::Rejection> {
+        let user = CurrentUser::from_parts(parts, state).await?;
+        let rating_id = ModelID::from_request_parts(parts, state).await?;
+
+        check_user_can_give_feedback(user.id(), rating_id, state.database()).await?;
+
+        Ok(Self)
+    }
+}
+
 /// Validate user owns the rating
 pub async fn check_user_owns_rating(
     user_id: ModelID,
@@ -45,6 +92,7 @@ pub async fn check_user_owns_rating(
             WHERE (
                 user_.id = $1
                 AND farm_rating.id = $2
+                AND farm_rating.reply_to IS NULL
             )
             "#,
         user_id.0,
@@ -64,3 +112,110 @@ pub async fn check_user_owns_rating(
         }
     }
 }
+
+/// Validate user can reply to the rating or reply
+///
+/// Anyone can reply to the ratings of an active farm,
+/// the farm owner replies are marked as coming from the farm.
+pub async fn check_user_can_reply(
+    user_id: ModelID,
+    reply_to: ModelID,
+    db: DatabaseConnection,
+) -> EndpointResult<()> {
+    match sqlx::query!(
+        r#"
+            SELECT EXISTS(
+                SELECT 1
+                FROM services.farm_ratings farm_rating
+                INNER JOIN services.active_farms farm
+                    ON farm_rating.farm_id = farm.id
+                WHERE farm_rating.id = $1
+                    AND farm_rating.hidden_at IS NULL
+            ) AS "exists!"
+        "#,
+        reply_to.0,
+    )
+    .fetch_one(&db.pool)
+    .await
+    {
+        Ok(rec) if rec.exists => Ok(()),
+        Ok(_) => {
+            tracing::trace!("User: {user_id} cannot reply, farm rating: {reply_to} not found");
+            Err(EndpointRejection::NotFound("Farm rating not found.".into()))
+        }
+        Err(err) => {
+            tracing::error!("Database error: {}", err);
+            Err(EndpointRejection::internal_server_error())
+        }
+    }
+}
+
+/// Validate user wrote the rating reply
+pub async fn check_user_owns_reply(
+    user_id: ModelID,
+    reply_id: ModelID,
+    db: DatabaseConnection,
+) -> EndpointResult<()> {
+    match sqlx::query!(
+        r#"
+            SELECT reply.id
+            FROM services.farm_ratings reply
+            WHERE (
+                reply.id = $1
+                AND reply.author_id = $2
+                AND reply.reply_to IS NOT NULL
+            )
+        "#,
+        reply_id.0,
+        user_id.0
+    )
+    .fetch_one(&db.pool)
+    .await
+    {
+        Ok(_reply) => Ok(()),
+        Err(err) => {
+            if matches!(err, sqlx::Error::RowNotFound) {
+                Err(EndpointRejection::forbidden())
+            } else {
+                tracing::error!("Database error: {}", err);
+                Err(EndpointRejection::internal_server_error())
+            }
+        }
+    }
+}
+
+/// Validate user can vote on or report the rating
+///
+/// Users can give feedback on visible ratings of active farms,
+/// except on their own ratings.
+pub async fn check_user_can_give_feedback(
+    user_id: ModelID,
+    rating_id: ModelID,
+    db: DatabaseConnection,
+) -> EndpointResult<()> {
+    match sqlx::query!(
+        r#"
+            SELECT farm_rating.author_id
+            FROM services.farm_ratings farm_rating
+            INNER JOIN services.active_farms farm
+                ON farm_rating.farm_id = farm.id
+            WHERE farm_rating.id = $1
+                AND farm_rating.reply_to IS NULL
+                AND farm_rating.hidden_at IS NULL
+        "#,
+        rating_id.0,
+    )
+    .fetch_optional(&db.pool)
+    .await
+    {
+        Ok(Some(rec)) if rec.author_id == Some(user_id.0) => Err(EndpointRejection::Forbidden(
+            "You cannot give feedback on your own rating.".into(),
+        )),
+        Ok(Some(_)) => Ok(()),
+        Ok(None) => Err(EndpointRejection::NotFound("Farm rating not found.".into())),
+        Err(err) => {
+            tracing::error!("Database error: {}", err);
+            Err(EndpointRejection::internal_server_error())
+        }
+    }
+}
@@ -0,0 +1,154 @@
+//! Farm rating eligibility policy impls
+
+use std::env;
+
+use crate::{
+    endpoint::{EndpointRejection, EndpointResult},
+    server::state::DatabaseConnection,
+    types::ModelID,
+};
+
+/// Who is allowed to rate a farm
+///
+/// Users can always rate a farm only once, the rating can be updated
+/// afterwards; it is enforced by a database unique index.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RatingPolicy {
+    /// The farm owner and members can rate their own farm.
+    pub allow_self_rating: bool,
+    /// Only verified buyers of the farm can rate it.
+    pub verified_buyers_only: bool,
+}
+
+impl RatingPolicy {
+    /// Loads the rating policy from environmental variables,
+    /// unset variables fall back to the default policy.
+    #[must_use]
+    pub fn from_env() -> Self {
+        let default = Self::default();
+        Self {
+            allow_self_rating: env_flag("RATING_ALLOW_SELF_RATING", default.allow_self_rating),
+            verified_buyers_only: env_flag(
+                "RATING_VERIFIED_BUYERS_ONLY",
+                default.verified_buyers_only,
+            ),
+        }
+    }
+
+    /// Validate the user is allowed to rate the farm
+    ///
+    /// # Errors
+    ///
+    /// Return an error describing why the user cannot rate the farm
+    pub async fn check(
+        &self,
+        user_id: ModelID,
+        farm_id: ModelID,
+        db: DatabaseConnection,
+    ) -> EndpointResult<()> {
+        let Some(eligibility) = RatingEligibility::find(user_id, farm_id, db).await? else {
+            return Err(EndpointRejection::NotFound("Farm not found.".into()));
+        };
+
+        if eligibility.is_blocked {
+            return Err(EndpointRejection::Forbidden(
+                "You cannot rate this farm.".into(),
+            ));
+        }
+
+        if !self.allow_self_rating && eligibility.is_member {
+            return Err(EndpointRejection::Forbidden(
+                "You cannot rate your own farm.".into(),
+            ));
+        }
+
+        if eligibility.has_rated {
+            return Err(EndpointRejection::Conflict(
+                "You have already rated this farm, update your rating instead.".into(),
+            ));
+        }
+
+        if self.verified_buyers_only && !eligibility.verified_buyer {
+            return Err(EndpointRejection::Forbidden(
+                "Only verified buyers can rate this farm.".into(),
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+/// The user relationship with the farm they want to rate
+#[derive(Debug, Clone, Copy)]
+#[allow(clippy::struct_excessive_bools)]
+struct RatingEligibility {
+    /// The user owns or is a member of the farm
+    is_member: bool,
+    /// The user already rated the farm
+    has_rated: bool,
+    /// The user had a conversation with the farm owner
+    verified_buyer: bool,
+    /// The farm owner blocked the user
+    is_blocked: bool,
+}
+
+impl RatingEligibility {
+    /// Fetches the user relationship with the farm from the database
+    async fn find(
+        user_id: ModelID,
+        farm_id: ModelID,
+        db: DatabaseConnection,
+    ) -> EndpointResult<Option<Self>> {
+        match sqlx::query!(
+            r#"
+                SELECT farm.owner_id = $1 OR EXISTS(
+                        SELECT 1 FROM services.farm_members member
+                        WHERE member.farm_id = farm.id
+                            AND member.user_id = $1
+                    ) AS "is_member!",
+                    EXISTS(
+                        SELECT 1 FROM services.farm_ratings farm_rating
+                        WHERE farm_rating.farm_id = farm.id
+                            AND farm_rating.author_id = $1
+                            AND farm_rating.reply_to IS NULL
+                    ) AS "has_rated!",
+                    services.farm_verified_buyer($1, farm.id) AS "verified_buyer!",
+                    EXISTS(
+                        SELECT 1 FROM accounts.user_blocks block
+                        WHERE block.user_id = farm.owner_id
+                            AND block.blocked_id = $1
+                    ) AS "is_blocked!"
+                FROM services.active_farms farm
+                WHERE farm.id = $2
+            "#,
+            user_id.0,
+            farm_id.0,
+        )
+        .fetch_optional(&db.pool)
+        .await
+        {
+            Ok(rec) => Ok(rec.map(|rec| Self {
+                is_member: rec.is_member,
+                has_rated: rec.has_rated,
+                verified_buyer: rec.verified_buyer,
+                is_blocked: rec.is_blocked,
+            })),
+            Err(err) => {
+                tracing::error!(
+                    "Database error, failed to fetch rating eligibility: {}",
+                    err
+                );
+                Err(EndpointRejection::internal_server_error())
+            }
+        }
+    }
+}
+
+/// Reads a boolean flag from the environmental variable `key`
+fn env_flag(key: &str, default: bool) -> bool {
+    env::var(key).map_or(default, |value| {
+        value.parse().unwrap_or_else(|_| {
+            panic!("{key} environment variable must be either `true` or `false`.")
+        })
+    })
+}
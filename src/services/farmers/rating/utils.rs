@@ -0,0 +1,51 @@
+//! Farm rating helpers impls
+
+use std::path::PathBuf;
+
+use crate::{
+    endpoint::EndpointResult, error::ServerResult, files, files::UploadedFile,
+    settings::RATING_UPLOAD_DIR,
+};
+
+/// Saves the rating photos to the file system
+///
+/// Returns the saved images paths, the photos already saved
+/// are deleted if one of the photos fails to save.
+///
+/// # Errors
+///
+/// Return bad request if a photo is not a supported image
+pub async fn save_rating_photos(photos: Vec<UploadedFile>) -> EndpointResult<Vec<String>> {
+    let mut paths = Vec::with_capacity(photos.len());
+    for file in photos {
+        let path = format!("{}.jpg", file.id);
+        if let Err(err) = file.save_image(RATING_UPLOAD_DIR).await {
+            tokio::spawn(async move { delete_rating_photos(paths.into_iter()).await });
+            return Err(err);
+        }
+        paths.push(path);
+    }
+    Ok(paths)
+}
+
+/// Delete rating images from file system
+///
+/// # Errors
+///
+/// Return an error if failed to delete files
+pub async fn delete_rating_photos<P>(paths: P) -> ServerResult<()>
+where
+    P: Iterator<Item = String> + Send,
+{
+    let all_paths: Vec<PathBuf> = paths
+        .flat_map(|file| {
+            crate::IMAGE_OUTPUT_FORMATS.map(|ext| {
+                PathBuf::from(RATING_UPLOAD_DIR)
+                    .join(&file)
+                    .with_extension(ext.extensions_str()[0])
+            })
+        })
+        .collect();
+
+    files::delete_files(all_paths).await
+}
@@ -86,10 +86,13 @@ impl Cultivar {
                     harvest.price AS "harvest_price?",
                     harvest.harvest_date AS "harvest_harvest_date?",
                     harvest.images AS harvest_images,
+                    farm.id AS "farm_id?",
                     farm.name AS "farm_name?",
                     farm.logo AS farm_logo,
                     location_.place_name AS "location_place_name?",
                     location_.coords AS location_coords,
+                    services.location_open_now(location_.id) AS location_open_now,
+                    services.location_open_today(location_.id) AS location_open_today,
                     region.name AS "location_region?",
                     country.name AS "location_country?"
                 FROM services.cultivars cultivar
@@ -143,6 +146,9 @@ impl Cultivar {
                             rec.location_region,
                             rec.location_country.unwrap(),
                             rec.location_coords,
+                            rec.location_open_now,
+                            rec.location_open_today,
+                            rec.farm_id.unwrap().into(),
                             rec.farm_name.unwrap(),
                             rec.farm_logo,
                             0.into(), // boost amount not important
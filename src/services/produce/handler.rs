@@ -1,6 +1,6 @@
 //! Harvest feed impls
 
-use std::future;
+use std::{collections::HashSet, future};
 
 use axum::{extract::State, Json};
 use axum_extra::extract::Query;
@@ -9,12 +9,14 @@ use serde::{Deserialize, Serialize};
 // use time::OffsetDateTime;
 
 use crate::{
+    auth::CurrentUser,
     endpoint::{validators::TransformString, EndpointRejection, EndpointResult},
     server::state::DatabaseConnection,
-    types::ModelID,
+    services::farmers::farm::delivery::{DeliveryOption, DeliveryTarget},
+    types::{ModelID, Pagination},
 };
 
-use super::harvest::models::{Harvest, HarvestList};
+use super::harvest::models::{Harvest, HarvestIndex, HarvestList};
 
 /// Handles the `GET /harvests/feed` route.
 #[tracing::instrument(skip(db))]
@@ -22,9 +24,8 @@ pub async fn harvest_feed(
     filters: Query<HarvestFilter>,
     State(db): State<DatabaseConnection>,
 ) -> EndpointResult<Json<HarvestFeed>> {
-    let cultivars = filters.cultivars();
-    let regions = filters.regions();
     let skip_id = filters.offset_id();
+    let matcher = filters.matcher(db.clone()).await?;
 
     let mut harvests: Vec<_> = Harvest::stream(&db)
         .await
@@ -36,19 +37,7 @@ pub async fn harvest_feed(
             future::ready(skip_id.map_or_else(|| Ok(false), |id| Ok(harvest.id != id)))
         })
         // Filters
-        .try_filter(|harvest| {
-            // Filters harvests, if no filters are available
-            // the all harvests will pass the filter, otherwise
-            // only those matched the filters will pass.
-            future::ready(match (cultivars.is_empty(), regions.is_empty()) {
-                (true, true) => true,
-                (false, false) => {
-                    cultivars.contains(&harvest.name) && regions.contains(&harvest.region)
-                }
-                (false, true) => cultivars.contains(&harvest.name),
-                (true, false) => regions.contains(&harvest.region),
-            })
-        })
+        .try_filter(|harvest| future::ready(matcher.matches(harvest)))
         // Limit
         .take(filters.limit + 1)
         .try_collect()
@@ -75,6 +64,20 @@ pub async fn harvest_feed(
     Ok(Json(HarvestFeed { harvests, offset }))
 }
 
+/// Handles the `GET /produce/following` route.
+///
+/// Active harvests of the farms the user follows, newest first.
+#[tracing::instrument(skip(user, db))]
+pub async fn harvest_following_feed(
+    user: CurrentUser,
+    pg: Option<Query<Pagination>>,
+    State(db): State<DatabaseConnection>,
+) -> EndpointResult<Json<HarvestList>> {
+    let pagination = pg.unwrap_or_default().0;
+    let harvests = Harvest::following(user.id, pagination, db).await?;
+    Ok(Json(harvests))
+}
+
 /// Harvests feed
 #[derive(Debug, Clone, Serialize)]
 #[serde(rename_all = "camelCase")]
@@ -96,6 +99,18 @@ pub struct HarvestFilter {
     /// filters for region name
     #[serde(default)]
     pub region: Vec<String>,
+    /// filters for harvests at locations open today
+    #[serde(default)]
+    pub open_today: bool,
+    /// filters for harvests delivered to the buyer latitude
+    #[serde(default)]
+    pub lat: Option<f64>,
+    /// filters for harvests delivered to the buyer longitude
+    #[serde(default)]
+    pub lng: Option<f64>,
+    /// filters for harvests delivered to the buyer region id
+    #[serde(default)]
+    pub delivery_region: Option<ModelID>,
 
     /// `skip_id` - position in the result set.
     /// query's harvests starting from this harvest_id.
@@ -113,6 +128,7 @@ const fn default_harvests_len_limit() -> usize {
 
 impl HarvestFilter {
     /// `skip_id` - position in the result set.
+    #[must_use]
     pub fn offset_id(&self) -> Option<ModelID> {
         self.offset
             .as_ref()
@@ -120,12 +136,86 @@ impl HarvestFilter {
     }
 
     /// Cleaned cultivar name filters
+    #[must_use]
     pub fn cultivars(&self) -> Vec<String> {
         self.cultivar.iter().map(|c| c.to_titlecase()).collect()
     }
 
+    /// Buyer location the harvests must be delivered to
+    #[must_use]
+    pub fn delivery_target(&self) -> Option<DeliveryTarget> {
+        let target = DeliveryTarget {
+            lat: self.lat,
+            lng: self.lng,
+            delivery_region: self.delivery_region,
+        };
+        (!target.is_empty()).then_some(target)
+    }
+
     /// Cleaned region name filters
+    #[must_use]
     pub fn regions(&self) -> Vec<Option<String>> {
         self.region.iter().map(|r| Some(r.to_titlecase())).collect()
     }
+
+    /// Resolves the filters into a matcher, fetching the farms
+    /// delivering to the buyer location if one was provided.
+    ///
+    /// # Errors
+    ///
+    /// Return database error
+    pub async fn matcher(&self, db: DatabaseConnection) -> EndpointResult<HarvestMatcher> {
+        let delivering_farms = match self.delivery_target() {
+            Some(target) => Some(DeliveryOption::farms_delivering_to(&target, db).await?),
+            None => None,
+        };
+        Ok(HarvestMatcher {
+            cultivars: self.cultivars(),
+            regions: self.regions(),
+            open_today: self.open_today,
+            delivering_farms,
+        })
+    }
+}
+
+/// The resolved `harvests/feed` filters
+#[derive(Debug, Clone)]
+pub struct HarvestMatcher {
+    cultivars: Vec<String>,
+    regions: Vec<Option<String>>,
+    open_today: bool,
+    delivering_farms: Option<HashSet<ModelID>>,
+}
+
+impl HarvestMatcher {
+    /// Checks if the harvest passes the filters
+    #[must_use]
+    pub fn matches(&self, harvest: &HarvestIndex) -> bool {
+        self.matches_any(
+            std::slice::from_ref(&harvest.name),
+            &harvest.region,
+            harvest.farm_id,
+            harvest.open_today,
+        )
+    }
+
+    /// Checks if a location selling `cultivars` passes the filters,
+    /// if no filters are available everything passes the filters.
+    #[must_use]
+    pub fn matches_any(
+        &self,
+        cultivars: &[String],
+        region: &Option<String>,
+        farm_id: ModelID,
+        open_today: bool,
+    ) -> bool {
+        let cultivar_matched =
+            self.cultivars.is_empty() || cultivars.iter().any(|c| self.cultivars.contains(c));
+        let region_matched = self.regions.is_empty() || self.regions.contains(region);
+        let delivers = self
+            .delivering_farms
+            .as_ref()
+            .is_none_or(|farm_ids| farm_ids.contains(&farm_id));
+        cultivar_matched && region_matched && delivers && (!self.open_today || open_today)
+    }
 }
@@ -44,6 +44,7 @@ impl HarvestAdmin {
         farm_owner_first_name: String,
         farm_owner_last_name: Option<String>,
         farm_owner_photo: Option<String>,
+        farm_owner_identity_verified: bool,
         price: serde_json::Value,
         r#type: Option<String>,
         description: Option<String>,
@@ -58,6 +59,9 @@ impl HarvestAdmin {
         region: Option<String>,
         country: String,
         coords: Option<serde_json::Value>,
+        location_access_notes: Option<String>,
+        location_images: Option<Vec<String>>,
+        location_schedule: Option<serde_json::Value>,
     ) -> Self {
         Self {
             id,
@@ -69,6 +73,7 @@ impl HarvestAdmin {
                 farm_owner_first_name,
                 farm_owner_last_name,
                 farm_owner_photo,
+                farm_owner_identity_verified,
             ),
             price: Price::from_row(price),
             r#type,
@@ -79,7 +84,16 @@ impl HarvestAdmin {
             updated_at,
             finished_at,
             created_at,
-            location: HarvestLocation::from_row(location_id, place_name, region, country, coords),
+            location: HarvestLocation::from_row(
+                location_id,
+                place_name,
+                region,
+                country,
+                coords,
+                location_access_notes,
+                location_images,
+                location_schedule,
+            ),
         }
     }
 }
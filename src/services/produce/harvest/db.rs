@@ -1,6 +1,7 @@
 //! Harvest database impl
 
 use futures_util::stream::{Stream, TryStreamExt};
+use time::OffsetDateTime;
 
 use crate::{
     endpoint::EndpointRejection,
@@ -13,7 +14,11 @@ use crate::{
 use super::{
     forms::{HarvestInsertData, HarvestUpdateData},
     models::{Harvest, HarvestIndex, HarvestList},
-    utils::{delete_harvest_photos, delete_or_archive_harvest, find_delete_harvest},
+    utils::{
+        archive_harvest, archive_restore_cutoff, can_delete_harvest, delete_harvest_photos,
+        delete_harvests, expired_archived_harvests, finalize_archived_harvests, restore_harvest,
+        ArchiveGraceDays,
+    },
 };
 
 impl Harvest {
@@ -34,10 +39,13 @@ impl Harvest {
                     cultivar.name AS cultivar_name,
                     cultivar_category.name AS cultivar_category,
                     cultivar.image AS cultivar_image, 
+                    farm.id AS farm_id,
                     farm.name AS farm_name,
                     farm.logo AS farm_logo,
                     location_.place_name AS location_place_name,
                     location_.coords AS location_coords,
+                    services.location_open_now(location_.id) AS location_open_now,
+                    services.location_open_today(location_.id) AS location_open_today,
                     region.name AS "location_region?",
                     country.name AS location_country,
                     subscription.amount AS "boost_amount?",
@@ -77,6 +85,9 @@ impl Harvest {
                 rec.location_region,
                 rec.location_country,
                 rec.location_coords,
+                rec.location_open_now,
+                rec.location_open_today,
+                rec.farm_id.into(),
                 rec.farm_name,
                 rec.farm_logo,
                 calc_boost_amount(rec.boost_amount, rec.subscription_expires_at, today),
@@ -99,10 +110,13 @@ impl Harvest {
                     cultivar.name AS cultivar_name,
                     cultivar_category.name AS cultivar_category,
                     cultivar.image AS cultivar_image, 
+                    farm.id AS farm_id,
                     farm.name AS farm_name,
                     farm.logo AS farm_logo,
                     location_.place_name AS location_place_name,
                     location_.coords AS location_coords,
+                    services.location_open_now(location_.id) AS location_open_now,
+                    services.location_open_today(location_.id) AS location_open_today,
                     region.name AS "location_region?",
                     country.name AS location_country,
                     subscription.amount AS "boost_amount?",
@@ -151,6 +165,9 @@ impl Harvest {
                             rec.location_region,
                             rec.location_country,
                             rec.location_coords,
+                            rec.location_open_now,
+                            rec.location_open_today,
+                            rec.farm_id.into(),
                             rec.farm_name,
                             rec.farm_logo,
                             calc_boost_amount(rec.boost_amount, rec.subscription_expires_at, today),
@@ -168,6 +185,108 @@ impl Harvest {
         }
     }
 
+    /// Fetches harvest records of the farms the user follows from the database,
+    /// newest harvests first.
+    #[tracing::instrument(name = "Fetch Following HarvestList", skip(db))]
+    pub async fn following(
+        user_id: ModelID,
+        pg: Pagination,
+        db: DatabaseConnection,
+    ) -> ServerResult<HarvestList> {
+        //NB! Don't forget to select harvests from services.active_harvests
+        let (offset, limit) = pg.offset_limit();
+        match sqlx::query!(
+            r#"
+                SELECT harvest.id AS "harvest_id!",
+                    harvest.cultivar_id,
+                    harvest.price AS "harvest_price!",
+                    harvest.harvest_date AS "harvest_harvest_date!",
+                    harvest.images AS harvest_images,
+                    cultivar.name AS cultivar_name,
+                    cultivar_category.name AS cultivar_category,
+                    cultivar.image AS cultivar_image, 
+                    farm.id AS farm_id,
+                    farm.name AS farm_name,
+                    farm.logo AS farm_logo,
+                    location_.place_name AS location_place_name,
+                    location_.coords AS location_coords,
+                    services.location_open_now(location_.id) AS location_open_now,
+                    services.location_open_today(location_.id) AS location_open_today,
+                    region.name AS "location_region?",
+                    country.name AS location_country,
+                    subscription.amount AS "boost_amount?",
+                    subscription.expires_at AS "subscription_expires_at?"
+                FROM services.active_harvests harvest
+                LEFT JOIN services.cultivars cultivar
+                    ON harvest.cultivar_id = cultivar.id
+                LEFT JOIN services.cultivar_categories cultivar_category
+                    ON cultivar.category_id = cultivar_category.id
+                LEFT JOIN services.locations location_
+                    ON harvest.location_id = location_.id
+                LEFT JOIN services.farms farm
+                    ON location_.farm_id = farm.id
+                INNER JOIN accounts.farm_follows follow
+                    ON farm.id = follow.farm_id
+                    AND follow.user_id = $1
+                LEFT JOIN services.regions region
+                    ON location_.region_id = region.id
+                LEFT JOIN services.countries country
+                    ON location_.country_id = country.id
+
+                LEFT JOIN features.harvest_subscriptions subscription
+                    ON harvest.id  = subscription.harvest_id
+
+                ORDER BY harvest.created_at DESC
+                LIMIT $2
+                OFFSET $3;
+            "#,
+            user_id.0,
+            limit,
+            offset
+        )
+        .fetch_all(&db.pool)
+        .await
+        {
+            Ok(records) => {
+                let today = time::OffsetDateTime::now_utc().date();
+                let harvests = records
+                    .into_iter()
+                    .map(|rec| {
+                        HarvestIndex::from_row(
+                            rec.harvest_id.into(),
+                            rec.harvest_price,
+                            rec.harvest_harvest_date,
+                            rec.harvest_images,
+                            rec.cultivar_name,
+                            rec.cultivar_category,
+                            rec.cultivar_image,
+                            rec.location_place_name,
+                            rec.location_region,
+                            rec.location_country,
+                            rec.location_coords,
+                            rec.location_open_now,
+                            rec.location_open_today,
+                            rec.farm_id.into(),
+                            rec.farm_name,
+                            rec.farm_logo,
+                            calc_boost_amount(rec.boost_amount, rec.subscription_expires_at, today),
+                        )
+                    })
+                    .collect();
+
+                Ok(harvests)
+            }
+
+            Err(err) => {
+                tracing::error!(
+                    "Database error, failed to fetch following harvests: {}",
+                    err
+                );
+                Err(err.into())
+            }
+        }
+    }
+
     /// Fetches harvest detail from the database
     #[tracing::instrument(name = "Find Harvest", skip(db))]
     pub async fn find(id: ModelID, db: DatabaseConnection) -> ServerResult<Option<Self>> {
@@ -193,11 +312,15 @@ impl Harvest {
                     location_.id AS location_id,
                     location_.place_name AS location_place_name,
                     location_.coords AS location_coords,
+                    location_.access_notes AS location_access_notes,
+                    location_.images AS location_images,
+                    services.location_schedule(location_.id) AS location_schedule,
                     region.name AS "location_region?",
                     country.name AS location_country,
                     user_.id AS farm_owner_id,
                     user_.first_name AS farm_owner_first_name,
                     user_.last_name AS farm_owner_last_name,
+                    user_.identity_verified AS farm_owner_identity_verified,
                     profile.photo AS farm_owner_photo
                 FROM services.active_harvests harvest
                 LEFT JOIN services.cultivars cultivar
@@ -242,6 +365,9 @@ impl Harvest {
                     rec.location_region,
                     rec.location_country,
                     rec.location_coords,
+                    rec.location_access_notes,
+                    rec.location_images,
+                    rec.location_schedule,
                     rec.farm_id.into(),
                     rec.farm_name,
                     rec.farm_logo,
@@ -251,6 +377,7 @@ impl Harvest {
                     rec.farm_owner_first_name,
                     rec.farm_owner_last_name,
                     rec.farm_owner_photo,
+                    rec.farm_owner_identity_verified,
                 );
 
                 Ok(Some(harvest))
@@ -361,23 +488,76 @@ impl Harvest {
 
     /// Deletes harvest from the database
     ///
-    /// Harvest will only be deleted if it has not stayed on
-    /// the platform for at least `HARVEST_MAX_AGE_TO_ARCHIVE` days
+    /// Harvest is archived and can be restored within
+    /// the restore grace period, afterwards it will only be deleted
+    /// if it has not stayed on the platform for at least
+    /// `HARVEST_MAX_AGE_TO_ARCHIVE` days
     #[tracing::instrument(name = "Delete Harvest", skip(db))]
     pub async fn delete(id: ModelID, db: DatabaseConnection) -> ServerResult<()> {
-        let mut harvest = find_delete_harvest(id, db.clone()).await?;
-        let images = harvest.images.take();
+        archive_harvest(id, OffsetDateTime::now_utc(), db).await
+    }
 
-        delete_or_archive_harvest(harvest, db).await?;
+    /// Restores the deleted harvest in the database
+    ///
+    /// Harvest can only be restored within the restore grace period
+    /// of being deleted and while its location is active.
+    #[tracing::instrument(name = "Restore Harvest", skip(db))]
+    pub async fn restore(
+        id: ModelID,
+        grace_days: ArchiveGraceDays,
+        db: DatabaseConnection,
+    ) -> ServerResult<()> {
+        let cutoff = archive_restore_cutoff(OffsetDateTime::now_utc(), grace_days)?;
+        if restore_harvest(id, cutoff, db).await? == 0 {
+            return Err(ServerError::rejection(EndpointRejection::NotFound(
+                "Harvest not found or can no longer be restored.".into(),
+            )));
+        }
+        Ok(())
+    }
+
+    /// Finalizes harvests archived for longer than the restore grace period
+    ///
+    /// Harvests that have not stayed on the platform for at least
+    /// `HARVEST_MAX_AGE_TO_ARCHIVE` days are deleted,
+    /// the others are kept without their images.
+    #[tracing::instrument(name = "Purge archived Harvests", skip(db))]
+    pub async fn purge_archived(
+        grace_days: ArchiveGraceDays,
+        db: DatabaseConnection,
+    ) -> ServerResult<()> {
+        let cutoff = archive_restore_cutoff(OffsetDateTime::now_utc(), grace_days)?;
+        let harvests = expired_archived_harvests(cutoff, db.clone()).await?;
+        if harvests.is_empty() {
+            return Ok(());
+        }
 
-        // Delete harvest images
-        if let Some(image_paths) = images {
-            let paths = image_paths.clone();
-            if delete_harvest_photos(paths.into_iter()).await.is_err() {
-                tracing::error!("Io error, failed to delete harvest images at: {image_paths:?}, but harvest was deleted successfully.");
+        let mut delete_ids = Vec::new();
+        let mut archive_ids = Vec::new();
+        for harvest in &harvests {
+            if can_delete_harvest(
+                harvest.harvest_date,
+                harvest.created_at,
+                harvest.archived_at,
+            )? {
+                delete_ids.push(harvest.id);
+            } else {
+                archive_ids.push(harvest.id);
             }
         }
 
+        let mut tx = db.pool.begin().await?;
+        delete_harvests(&delete_ids, &mut tx).await?;
+        finalize_archived_harvests(&archive_ids, &mut tx).await?;
+        tx.commit().await?;
+        tracing::debug!("Harvest::purge_archived, transaction committed successfully.");
+
+        // Delete archived harvest images
+        let image_paths = harvests.into_iter().filter_map(|harvest| harvest.images);
+        if delete_harvest_photos(image_paths.flatten()).await.is_err() {
+            tracing::error!("Io error, failed to delete archived harvest images, but harvests were purged successfully.");
+        }
+
         Ok(())
     }
 
@@ -14,7 +14,7 @@ use crate::{
         EndpointRejection, EndpointResult,
     },
     server::state::ServerState,
-    services::farmers::location::permissions::check_user_owns_location,
+    services::farmers::{farm::member::FarmRole, location::permissions::check_user_location_role},
     types::{price::Price, ModelID},
 };
 
@@ -102,8 +102,9 @@ impl HarvestCreateForm {
         user: FarmerUser,
         location_id: ModelID,
     ) -> EndpointResult<()> {
-        // Validate the location belongs to the users's farm
-        check_user_owns_location(user.id(), location_id, state.database()).await
+        // Validate the location belongs to a farm the user can list harvests on
+        let db = state.database();
+        check_user_location_role(user.id(), location_id, FarmRole::EDITORS, db).await
     }
 }
 
@@ -9,7 +9,12 @@ use crate::{
     auth::FarmerUser,
     endpoint::{EndpointRejection, EndpointResult},
     files,
+    mail::Mail,
     server::state::DatabaseConnection,
+    services::farmers::farm::{
+        delivery::{DeliveryOption, DeliveryTarget},
+        follow::notify_followers,
+    },
     settings::HARVEST_UPLOAD_DIR,
     types::{ModelID, Pagination},
 };
@@ -17,8 +22,8 @@ use crate::{
 use super::{
     forms::{HarvestCreateForm, HarvestUpdateForm},
     models::{Harvest, HarvestList},
-    permissions::HarvestOwnershipPermission,
-    utils::delete_harvest_photos,
+    permissions::{HarvestManagePermission, HarvestOwnershipPermission},
+    utils::{delete_harvest_photos, ArchiveGraceDays},
 };
 
 /// Handles the `GET /harvests` route.
@@ -32,16 +37,29 @@ pub async fn harvest_list(
     Ok(Json(harvests))
 }
 
-/// Handles the `GET /harvests/:harvest_id` route.
+/// Handles the `GET /harvests/:harvest_id?lat=...&lng=...&deliveryRegion=...` route.
+///
+/// Estimates the delivery fee if the buyer location is provided.
 #[tracing::instrument(skip(db))]
 pub async fn harvest_detail(
     harvest_id: ModelID,
+    Query(target): Query<DeliveryTarget>,
     State(db): State<DatabaseConnection>,
 ) -> EndpointResult<Json<Harvest>> {
-    Harvest::find(harvest_id, db).await?.map_or_else(
-        || Err(EndpointRejection::NotFound("Harvest not found.".into())),
-        |harvest| Ok(Json(harvest)),
-    )
+    let Some(mut harvest) = Harvest::find(harvest_id, db.clone()).await? else {
+        return Err(EndpointRejection::NotFound("Harvest not found.".into()));
+    };
+
+    harvest.delivery_options = DeliveryOption::records(harvest.farm.id, db).await?;
+    if !target.is_empty() {
+        harvest.delivery_estimate = DeliveryOption::cheapest_estimate(
+            &harvest.delivery_options,
+            &target,
+            harvest.location.coords,
+        );
+    }
+
+    Ok(Json(harvest))
 }
 
 /// Handles the `POST /harvests` route.
@@ -49,9 +67,14 @@ pub async fn harvest_detail(
 pub async fn harvest_create(
     _: FarmerUser,
     State(db): State<DatabaseConnection>,
+    State(outlook): State<Mail>,
     form: HarvestCreateForm,
 ) -> EndpointResult<StatusCode> {
-    Harvest::insert(form.into(), db).await?;
+    let harvest_id = Harvest::insert(form.into(), db.clone()).await?;
+
+    // Notify the farm followers in the background
+    tokio::spawn(async move { notify_followers(harvest_id, db, outlook).await });
+
     Ok(StatusCode::CREATED)
 }
 
@@ -70,7 +93,7 @@ pub async fn harvest_update(
 /// Handles the `DELETE /harvests/:harvest_id` route.
 #[tracing::instrument(skip(db))]
 pub async fn harvest_delete(
-    _: HarvestOwnershipPermission,
+    _: HarvestManagePermission,
     harvest_id: ModelID,
     State(db): State<DatabaseConnection>,
 ) -> EndpointResult<StatusCode> {
@@ -78,6 +101,18 @@ pub async fn harvest_delete(
     Ok(StatusCode::NO_CONTENT)
 }
 
+/// Handles the `POST /harvests/:harvest_id/restore` route.
+#[tracing::instrument(skip(db))]
+pub async fn harvest_restore(
+    _: HarvestManagePermission,
+    harvest_id: ModelID,
+    State(db): State<DatabaseConnection>,
+    State(grace_days): State<ArchiveGraceDays>,
+) -> EndpointResult<StatusCode> {
+    Harvest::restore(harvest_id, grace_days, db).await?;
+    Ok(StatusCode::OK)
+}
+
 /// Handles the `POST /harvests/:harvest_id/photos` route.
 #[tracing::instrument(skip(db, multipart))]
 #[allow(clippy::redundant_closure)]
@@ -8,4 +8,4 @@ pub mod models;
 pub mod permissions;
 mod utils;
 
-pub use utils::{delete_harvest_photos, harvest_max_age};
+pub use utils::{archive_restore_cutoff, delete_harvest_photos, harvest_max_age, ArchiveGraceDays};
@@ -8,7 +8,10 @@ use time::{Date, OffsetDateTime};
 use crate::{
     core::types::{price::Price, ModelID},
     core::{accounts::user::models::UserIndex, types::ModelIdentifier},
-    services::farmers::location,
+    services::farmers::{
+        farm::delivery::{DeliveryEstimate, DeliveryOptionList},
+        location::{self, schedule::LocationSchedule},
+    },
 };
 
 /// A `Vec` of harvests
@@ -34,6 +37,9 @@ pub struct Harvest {
     pub harvest_date: Date,
     pub created_at: Date,
     pub location: HarvestLocation,
+    pub delivery_options: DeliveryOptionList,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub delivery_estimate: Option<DeliveryEstimate>,
 }
 
 impl Harvest {
@@ -57,6 +63,9 @@ impl Harvest {
         region: Option<String>,
         country: String,
         coords: Option<serde_json::Value>,
+        location_access_notes: Option<String>,
+        location_images: Option<Vec<String>>,
+        location_schedule: Option<serde_json::Value>,
         farm_id: ModelID,
         farm_name: String,
         farm_logo: Option<String>,
@@ -66,6 +75,7 @@ impl Harvest {
         farm_owner_first_name: String,
         farm_owner_last_name: Option<String>,
         farm_owner_photo: Option<String>,
+        farm_owner_identity_verified: bool,
     ) -> Self {
         Self {
             id,
@@ -84,6 +94,7 @@ impl Harvest {
                 farm_owner_first_name,
                 farm_owner_last_name,
                 farm_owner_photo,
+                farm_owner_identity_verified,
             ),
             price: Price::from_row(price),
             r#type,
@@ -92,7 +103,18 @@ impl Harvest {
             images,
             harvest_date,
             created_at: created_at.date(),
-            location: HarvestLocation::from_row(location_id, place_name, region, country, coords),
+            location: HarvestLocation::from_row(
+                location_id,
+                place_name,
+                region,
+                country,
+                coords,
+                location_access_notes,
+                location_images,
+                location_schedule,
+            ),
+            delivery_options: DeliveryOptionList::new(),
+            delivery_estimate: None,
         }
     }
 }
@@ -104,6 +126,7 @@ pub struct HarvestIndex {
     pub id: ModelID,
     pub name: String,
     pub category: String,
+    pub farm_id: ModelID,
     pub farm_name: String,
     pub farm_logo: Option<String>,
     pub price: Price,
@@ -114,6 +137,8 @@ pub struct HarvestIndex {
     pub region: Option<String>,
     pub country: String,
     pub coords: Option<Point>,
+    pub open_now: bool,
+    pub open_today: bool,
 
     // This field is for internal use only; it is not sent to the users.
     // it is used for ordering
@@ -137,6 +162,9 @@ impl HarvestIndex {
         region: Option<String>,
         country: String,
         coords: Option<serde_json::Value>,
+        open_now: Option<bool>,
+        open_today: Option<bool>,
+        farm_id: ModelID,
         farm_name: String,
         farm_logo: Option<String>,
         boost_amount: rust_decimal::Decimal,
@@ -145,6 +173,7 @@ impl HarvestIndex {
             id,
             category: cultivar_category,
             name: cultivar_name,
+            farm_id,
             farm_name,
             farm_logo,
             price: Price::from_row(price),
@@ -155,6 +184,8 @@ impl HarvestIndex {
             region,
             place_name,
             coords: location::try_into_point(coords),
+            open_now: open_now.unwrap_or_default(),
+            open_today: open_today.unwrap_or_default(),
             boost_amount,
         }
     }
@@ -169,17 +200,24 @@ pub struct HarvestLocation {
     pub region: Option<String>,
     pub country: String,
     pub coords: Option<Point>,
+    pub access_notes: Option<String>,
+    pub images: Option<Vec<String>>,
+    pub schedule: LocationSchedule,
 }
 
 impl HarvestLocation {
     /// Creates a new `HarvestLocation` from the database row
     #[must_use]
+    #[allow(clippy::too_many_arguments)]
     pub fn from_row(
         id: ModelID,
         place_name: String,
         region: Option<String>,
         country: String,
         coords: Option<serde_json::Value>,
+        access_notes: Option<String>,
+        images: Option<Vec<String>>,
+        schedule: Option<serde_json::Value>,
     ) -> Self {
         Self {
             id,
@@ -187,6 +225,9 @@ impl HarvestLocation {
             region,
             country,
             coords: location::try_into_point(coords),
+            access_notes,
+            images,
+            schedule: LocationSchedule::from_row(schedule),
         }
     }
 }
@@ -6,10 +6,11 @@ use crate::{
     auth::FarmerUser,
     endpoint::{EndpointRejection, EndpointResult},
     server::state::{DatabaseConnection, ServerState},
+    services::farmers::farm::member::FarmRole,
     types::ModelID,
 };
 
-/// Checks if user owns the harvest
+/// Checks if user is allowed to edit the harvest
 #[derive(Debug, Clone)]
 pub struct HarvestOwnershipPermission;
 
@@ -24,44 +25,68 @@ impl FromRequestParts<ServerState> for HarvestOwnershipPermission {
         let user = FarmerUser::from_parts(parts, state).await?;
         let harvest_id = ModelID::from_request_parts(parts, state).await?;
 
-        check_user_owns_harvest(user.id(), harvest_id, state.database()).await?;
+        check_user_harvest_role(user.id(), harvest_id, FarmRole::EDITORS, state.database()).await?;
 
         Ok(Self)
     }
 }
 
-/// Validate user owns the harvest
+/// Checks if user is allowed to delete or restore the harvest
+#[derive(Debug, Clone)]
+pub struct HarvestManagePermission;
+
+#[async_trait]
+impl FromRequestParts<ServerState> for HarvestManagePermission {
+    type Rejection = EndpointRejection;
+
+    async fn from_request_parts(
+        parts: &mut Parts,
+        state: &ServerState,
+    ) -> Result<Self, Self::Rejection> {
+        let user = FarmerUser::from_parts(parts, state).await?;
+        let harvest_id = ModelID::from_request_parts(parts, state).await?;
+
+        check_user_harvest_role(user.id(), harvest_id, FarmRole::MANAGERS, state.database())
+            .await?;
+
+        Ok(Self)
+    }
+}
+
+/// Validate user is a member with one of the `roles`
+/// on the farm the harvest belongs to
 ///
 /// # Errors
 ///
 /// Return an error if user and harvest cannot be found.
-pub async fn check_user_owns_harvest(
+pub async fn check_user_harvest_role(
     user_id: ModelID,
     harvest_id: ModelID,
+    roles: &[FarmRole],
     db: DatabaseConnection,
 ) -> EndpointResult<()> {
     match sqlx::query!(
         r#"
-            SELECT first_name
-            FROM accounts.users user_
-            LEFT JOIN services.farms farm
-                ON user_.id = farm.owner_id
+            SELECT member.role
+            FROM services.farm_members member
             LEFT JOIN services.locations location_
-                ON farm.id = location_.farm_id
+                ON member.farm_id = location_.farm_id
             LEFT JOIN services.harvests harvest
             ON location_.id = harvest.location_id
             WHERE (
-                user_.id = $1
+                member.user_id = $1
                 AND harvest.id = $2
+                AND member.role = ANY($3)
             )
             "#,
         user_id.0,
-        harvest_id.0
+        harvest_id.0,
+        &FarmRole::to_rows(roles)[..]
     )
     .fetch_one(&db.pool)
     .await
     {
-        Ok(_user) => Ok(()),
+        Ok(_member) => Ok(()),
         Err(err) => {
             if matches!(err, sqlx::Error::RowNotFound) {
                 Err(EndpointRejection::forbidden())
@@ -86,28 +111,28 @@ pub async fn check_user_can_update_harvest(
 ) -> EndpointResult<()> {
     match sqlx::query!(
         r#"
-            SELECT first_name
-            FROM accounts.users user_
-            LEFT JOIN services.farms farm
-                ON user_.id = farm.owner_id
+            SELECT member.role
+            FROM services.farm_members member
             LEFT JOIN services.locations location_
-                ON farm.id = location_.farm_id
+                ON member.farm_id = location_.farm_id
             LEFT JOIN services.harvests harvest
             ON location_.id = harvest.location_id
             WHERE (
-                user_.id = $1
+                member.user_id = $1
                 AND location_.id = $2
                 AND harvest.id = $3
+                AND member.role = ANY($4)
             )
             "#,
         user_id.0,
         location_id.0,
-        harvest_id.0
+        harvest_id.0,
+        &FarmRole::to_rows(FarmRole::EDITORS)[..]
     )
     .fetch_one(&db.pool)
     .await
     {
-        Ok(_user) => Ok(()),
+        Ok(_member) => Ok(()),
         Err(err) => {
             if matches!(err, sqlx::Error::RowNotFound) {
                 Err(EndpointRejection::forbidden())
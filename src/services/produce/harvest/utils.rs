@@ -1,10 +1,11 @@
 //! Harvest helpers impls
 
-use std::path::PathBuf;
+use std::{env, path::PathBuf};
 
 use time::{Date, Duration, OffsetDateTime};
 
 use crate::{
+    endpoint::EndpointRejection,
     error::{ServerError, ServerResult},
     files,
     server::state::DatabaseConnection,
@@ -14,132 +15,222 @@ use crate::{
 
 use super::db::handle_harvest_database_error;
 
-/// find harvest from the database for deletion
+/// Archive harvest in the database
+///
+/// The harvest keeps its images until the restore grace period passes.
 ///
 /// # Errors
 ///
 /// Return database error
-pub async fn find_delete_harvest(
+pub async fn archive_harvest(
     harvest_id: ModelID,
+    archived_at: OffsetDateTime,
     db: DatabaseConnection,
-) -> ServerResult<DeleteHarvest> {
+) -> ServerResult<()> {
     match sqlx::query!(
         r#"
-            SELECT harvest.id,
-                harvest.harvest_date,
-                harvest.created_at,
-                harvest.images
-            FROM services.harvests harvest
-            WHERE harvest.id = $1;
+            UPDATE services.harvests harvest
+            SET finished = true,
+                finished_at = $1,
+                archived_at = $2
+            WHERE harvest.id = $3
+                AND harvest.finished = false
         "#,
+        archived_at.date(),
+        archived_at,
         harvest_id.0
     )
-    .fetch_one(&db.pool)
+    .execute(&db.pool)
     .await
     {
-        Ok(rec) => Ok(DeleteHarvest {
-            id: rec.id.into(),
-            harvest_date: rec.harvest_date,
-            created_at: rec.created_at,
-            images: rec.images,
-        }),
+        Ok(result) if result.rows_affected() == 0 => Err(ServerError::rejection(
+            EndpointRejection::NotFound("Harvest not found.".into()),
+        )),
+        Ok(result) => {
+            tracing::debug!("Harvest archived successfully: {:?}", result);
+            Ok(())
+        }
         Err(err) => {
-            tracing::error!(
-                "Database error, failed to fetch harvest for deletion: {}",
-                err
-            );
+            // Handle database constraint error
+            handle_harvest_database_error(&err)?;
+
+            tracing::error!("Database error, failed to archive harvest: {}", err);
             Err(err.into())
         }
     }
 }
 
-/// A minimal harvest used for deletion
-#[derive(Debug, Clone)]
-pub struct DeleteHarvest {
-    pub id: ModelID,
-    pub harvest_date: Date,
-    pub created_at: OffsetDateTime,
-    pub images: Option<Vec<String>>,
-}
-
-/// Delete harvest from the database
+/// Restore harvest archived after `cutoff` in the database,
+/// the harvest location must be active.
 ///
 /// # Errors
 ///
 /// Return database error
-async fn delete_harvest(harvest_id: ModelID, db: DatabaseConnection) -> ServerResult<()> {
+pub async fn restore_harvest(
+    harvest_id: ModelID,
+    cutoff: OffsetDateTime,
+    db: DatabaseConnection,
+) -> ServerResult<u64> {
     match sqlx::query!(
         r#"
-            DELETE FROM services.harvests harvest
+            UPDATE services.harvests harvest
+            SET finished = false,
+                finished_at = NULL,
+                archived_at = NULL
             WHERE harvest.id = $1
+                AND harvest.archived_at > $2
+                AND harvest.location_id IN (
+                    SELECT location_.id
+                    FROM services.active_locations location_
+                    INNER JOIN services.active_farms farm
+                        ON location_.farm_id = farm.id
+                )
         "#,
-        harvest_id.0
+        harvest_id.0,
+        cutoff,
     )
     .execute(&db.pool)
     .await
     {
         Ok(result) => {
-            tracing::debug!("Harvest deleted successfully: {:?}", result);
-            Ok(())
+            tracing::debug!("Harvest restored successfully: {:?}", result);
+            Ok(result.rows_affected())
         }
         Err(err) => {
             // Handle database constraint error
             handle_harvest_database_error(&err)?;
 
-            tracing::error!("Database error, failed to delete harvest: {}", err);
+            tracing::error!("Database error, failed to restore harvest: {}", err);
             Err(err.into())
         }
     }
 }
 
-/// Archive harvest in the database
+/// A minimal archived harvest used to finalize the archive
+#[derive(Debug, Clone)]
+pub struct ArchivedHarvest {
+    pub id: ModelID,
+    pub harvest_date: Date,
+    pub created_at: OffsetDateTime,
+    pub archived_at: OffsetDateTime,
+    pub images: Option<Vec<String>>,
+}
+
+/// Fetch harvests archived on or before `cutoff` from the database
 ///
 /// # Errors
 ///
 /// Return database error
-async fn archive_harvest(harvest_id: ModelID, db: DatabaseConnection) -> ServerResult<()> {
+pub async fn expired_archived_harvests(
+    cutoff: OffsetDateTime,
+    db: DatabaseConnection,
+) -> ServerResult<Vec<ArchivedHarvest>> {
     match sqlx::query!(
         r#"
-            UPDATE services.harvests harvest
-            SET finished = true,
-                images = NULL,
-                finished_at = $1
-            WHERE harvest.id = $2
+            SELECT harvest.id,
+                harvest.harvest_date,
+                harvest.created_at,
+                harvest.archived_at AS "archived_at!",
+                harvest.images
+            FROM services.harvests harvest
+            WHERE harvest.archived_at <= $1;
         "#,
-        OffsetDateTime::now_utc().date(),
-        harvest_id.0
+        cutoff
     )
-    .execute(&db.pool)
+    .fetch_all(&db.pool)
+    .await
+    {
+        Ok(records) => Ok(records
+            .into_iter()
+            .map(|rec| ArchivedHarvest {
+                id: rec.id.into(),
+                harvest_date: rec.harvest_date,
+                created_at: rec.created_at,
+                archived_at: rec.archived_at,
+                images: rec.images,
+            })
+            .collect()),
+        Err(err) => {
+            tracing::error!(
+                "Database error, failed to fetch expired archived harvests: {}",
+                err
+            );
+            Err(err.into())
+        }
+    }
+}
+
+/// Delete harvests from the database
+///
+/// # Errors
+///
+/// Return database error
+pub async fn delete_harvests(
+    harvest_ids: &[ModelID],
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+) -> ServerResult<u64> {
+    let harvest_ids: Vec<_> = harvest_ids.iter().map(|id| id.0).collect();
+    match sqlx::query!(
+        r#"
+            DELETE FROM services.harvests harvest
+            WHERE harvest.id = ANY($1)
+        "#,
+        &harvest_ids[..]
+    )
+    .execute(&mut **tx)
     .await
     {
         Ok(result) => {
-            tracing::debug!("Harvest archived successfully: {:?}", result);
-            Ok(())
+            tracing::trace!(
+                "Harvests deleted, but transaction not committed: {:?}",
+                result
+            );
+            Ok(result.rows_affected())
         }
         Err(err) => {
-            // Handle database constraint error
-            handle_harvest_database_error(&err)?;
-
-            tracing::error!("Database error, failed to archive harvest: {}", err);
+            tracing::error!("Database error, failed to delete harvests: {}", err);
             Err(err.into())
         }
     }
 }
 
-/// Deletes or archives the harvest
+/// Finalize archived harvests in the database,
+/// the harvests lose their images and can no longer be restored.
 ///
 /// # Errors
 ///
 /// Return database error
-pub async fn delete_or_archive_harvest(
-    harvest: DeleteHarvest,
-    db: DatabaseConnection,
-) -> ServerResult<()> {
-    let finished_at = OffsetDateTime::now_utc();
-    if can_delete_harvest(harvest.harvest_date, harvest.created_at, finished_at)? {
-        delete_harvest(harvest.id, db).await
-    } else {
-        archive_harvest(harvest.id, db).await
+pub async fn finalize_archived_harvests(
+    harvest_ids: &[ModelID],
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+) -> ServerResult<u64> {
+    let harvest_ids: Vec<_> = harvest_ids.iter().map(|id| id.0).collect();
+    match sqlx::query!(
+        r#"
+            UPDATE services.harvests harvest
+            SET images = NULL,
+                archived_at = NULL
+            WHERE harvest.id = ANY($1)
+        "#,
+        &harvest_ids[..]
+    )
+    .execute(&mut **tx)
+    .await
+    {
+        Ok(result) => {
+            tracing::trace!(
+                "Archived harvests finalized, but transaction not committed: {:?}",
+                result
+            );
+            Ok(result.rows_affected())
+        }
+        Err(err) => {
+            tracing::error!(
+                "Database error, failed to finalize archived harvests: {}",
+                err
+            );
+            Err(err.into())
+        }
     }
 }
 
@@ -149,7 +240,7 @@ pub async fn delete_or_archive_harvest(
 /// # Errors
 ///
 /// Return an error if failed to calculate harvest max age
-fn can_delete_harvest(
+pub fn can_delete_harvest(
     harvest_date: Date,
     created_at: OffsetDateTime,
     finished_at: OffsetDateTime,
@@ -174,6 +265,49 @@ pub fn harvest_max_age(finished_at: OffsetDateTime) -> ServerResult<OffsetDateTi
         })
 }
 
+/// Number of days a deleted farm, location or harvest can be restored
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ArchiveGraceDays(pub i64);
+
+impl Default for ArchiveGraceDays {
+    fn default() -> Self {
+        Self(crate::ARCHIVE_RESTORE_GRACE_DAYS)
+    }
+}
+
+impl ArchiveGraceDays {
+    /// Loads the restore grace period from the `ARCHIVE_RESTORE_GRACE_DAYS`
+    /// environmental variable, falls back to the default if unset.
+    #[must_use]
+    pub fn from_env() -> Self {
+        env::var("ARCHIVE_RESTORE_GRACE_DAYS").map_or_else(
+            |_| Self::default(),
+            |value| {
+                Self(value.parse().unwrap_or_else(|_| {
+                    panic!(
+                        "ARCHIVE_RESTORE_GRACE_DAYS environment variable must be a number of days."
+                    )
+                }))
+            },
+        )
+    }
+}
+
+/// Calculate the time before which archived rows can no longer be restored
+///
+/// # Errors
+///
+/// Return an error if failed to calculate the cutoff
+pub fn archive_restore_cutoff(
+    now: OffsetDateTime,
+    grace_days: ArchiveGraceDays,
+) -> ServerResult<OffsetDateTime> {
+    now.checked_sub(Duration::days(grace_days.0))
+        .ok_or_else(|| {
+            ServerError::new("Archive restore error, failed calculate restore grace cutoff.")
+        })
+}
+
 /// Delete harvest images from file system
 ///
 /// # Errors
@@ -0,0 +1,205 @@
+//! Map view database impl
+
+use crate::{error::ServerResult, server::state::DatabaseConnection};
+
+use super::{
+    models::{MapHarvest, MapHarvestList, MapLocation, MapLocationList},
+    BoundingBox,
+};
+
+impl MapHarvest {
+    /// Fetches at most `limit` active harvests with coordinates from the database,
+    /// inside the `bbox` and in the `regions` if provided.
+    #[tracing::instrument(name = "Fetch MapHarvestList", skip(db))]
+    pub async fn records(
+        bbox: Option<BoundingBox>,
+        regions: &[String],
+        limit: i64,
+        db: DatabaseConnection,
+    ) -> ServerResult<MapHarvestList> {
+        match sqlx::query!(
+            r#"
+                SELECT harvest.id AS "harvest_id!",
+                    harvest.price AS "harvest_price!",
+                    harvest.harvest_date AS "harvest_harvest_date!",
+                    harvest.images AS harvest_images,
+                    cultivar.name AS cultivar_name,
+                    cultivar_category.name AS cultivar_category,
+                    cultivar.image AS cultivar_image,
+                    farm.id AS farm_id,
+                    farm.name AS farm_name,
+                    farm.logo AS farm_logo,
+                    location_.place_name AS location_place_name,
+                    location_.coords AS location_coords,
+                    services.location_open_now(location_.id) AS location_open_now,
+                    services.location_open_today(location_.id) AS location_open_today,
+                    region.name AS "location_region?",
+                    country.name AS location_country
+                FROM services.active_harvests harvest
+                LEFT JOIN services.cultivars cultivar
+                    ON harvest.cultivar_id = cultivar.id
+                LEFT JOIN services.cultivar_categories cultivar_category
+                    ON cultivar.category_id = cultivar_category.id
+                LEFT JOIN services.locations location_
+                    ON harvest.location_id = location_.id
+                LEFT JOIN services.farms farm
+                    ON location_.farm_id = farm.id
+                LEFT JOIN services.regions region
+                    ON location_.region_id = region.id
+                LEFT JOIN services.countries country
+                    ON location_.country_id = country.id
+
+                LEFT JOIN features.harvest_subscriptions subscription
+                    ON harvest.id = subscription.harvest_id
+
+                WHERE location_.coords IS NOT NULL
+                    AND (cardinality($5::text[]) = 0 OR region.name = ANY($5))
+                    AND ($1::double precision IS NULL OR (
+                        (location_.coords->>'y')::double precision BETWEEN $2 AND $4
+                        AND CASE
+                            WHEN $1 <= $3 THEN
+                                (location_.coords->>'x')::double precision BETWEEN $1 AND $3
+                            -- Crosses the antimeridian
+                            ELSE (location_.coords->>'x')::double precision >= $1
+                                OR (location_.coords->>'x')::double precision <= $3
+                        END
+                    ))
+                ORDER BY subscription.amount DESC NULLS LAST,
+                    greatest(AGE(harvest.harvest_date), -AGE(harvest.harvest_date))
+                LIMIT $6
+            "#,
+            bbox.map(|bbox| bbox.min.x()),
+            bbox.map(|bbox| bbox.min.y()),
+            bbox.map(|bbox| bbox.max.x()),
+            bbox.map(|bbox| bbox.max.y()),
+            regions,
+            limit,
+        )
+        .fetch_all(&db.pool)
+        .await
+        {
+            Ok(records) => {
+                let harvests = records
+                    .into_iter()
+                    .map(|rec| {
+                        Self::from_row(
+                            rec.harvest_id.into(),
+                            rec.harvest_price,
+                            rec.harvest_harvest_date,
+                            rec.harvest_images,
+                            rec.cultivar_name,
+                            rec.cultivar_category,
+                            rec.cultivar_image,
+                            rec.location_place_name,
+                            rec.location_region,
+                            rec.location_country,
+                            rec.location_coords,
+                            rec.location_open_now,
+                            rec.location_open_today,
+                            rec.farm_id.into(),
+                            rec.farm_name,
+                            rec.farm_logo,
+                        )
+                    })
+                    .collect();
+
+                Ok(harvests)
+            }
+            Err(err) => {
+                tracing::error!("Database error, failed to fetch map harvests: {}", err);
+                Err(err.into())
+            }
+        }
+    }
+}
+
+impl MapLocation {
+    /// Fetches active locations with coordinates from the database,
+    /// inside the `bbox` and in the `regions` if provided.
+    #[tracing::instrument(name = "Fetch MapLocationList", skip(db))]
+    pub async fn records(
+        bbox: Option<BoundingBox>,
+        regions: &[String],
+        db: DatabaseConnection,
+    ) -> ServerResult<MapLocationList> {
+        match sqlx::query!(
+            r#"
+                SELECT location_.id AS "location_id!",
+                    location_.place_name AS "location_place_name!",
+                    location_.coords AS location_coords,
+                    services.location_open_now(location_.id) AS location_open_now,
+                    services.location_open_today(location_.id) AS location_open_today,
+                    ARRAY(
+                        SELECT DISTINCT cultivar.name
+                        FROM services.active_harvests harvest
+                        INNER JOIN services.cultivars cultivar
+                            ON harvest.cultivar_id = cultivar.id
+                        WHERE harvest.location_id = location_.id
+                    ) AS "location_cultivars!",
+                    region.name AS "location_region?",
+                    country.name AS location_country,
+                    farm.id AS "farm_id!",
+                    farm.name AS "farm_name!",
+                    farm.logo AS farm_logo
+                FROM services.active_locations location_
+                INNER JOIN services.active_farms farm
+                    ON location_.farm_id = farm.id
+                LEFT JOIN services.regions region
+                    ON location_.region_id = region.id
+                LEFT JOIN services.countries country
+                    ON location_.country_id = country.id
+
+                WHERE location_.coords IS NOT NULL
+                    AND (cardinality($5::text[]) = 0 OR region.name = ANY($5))
+                    AND ($1::double precision IS NULL OR (
+                        (location_.coords->>'y')::double precision BETWEEN $2 AND $4
+                        AND CASE
+                            WHEN $1 <= $3 THEN
+                                (location_.coords->>'x')::double precision BETWEEN $1 AND $3
+                            -- Crosses the antimeridian
+                            ELSE (location_.coords->>'x')::double precision >= $1
+                                OR (location_.coords->>'x')::double precision <= $3
+                        END
+                    ))
+                ORDER BY location_.place_name
+                LIMIT $6
+            "#,
+            bbox.map(|bbox| bbox.min.x()),
+            bbox.map(|bbox| bbox.min.y()),
+            bbox.map(|bbox| bbox.max.x()),
+            bbox.map(|bbox| bbox.max.y()),
+            regions,
+            crate::MAP_LOCATIONS_MAX,
+        )
+        .fetch_all(&db.pool)
+        .await
+        {
+            Ok(records) => {
+                let locations = records
+                    .into_iter()
+                    .map(|rec| {
+                        Self::from_row(
+                            rec.location_id.into(),
+                            rec.location_place_name,
+                            rec.location_region,
+                            rec.location_country,
+                            rec.location_coords,
+                            rec.location_open_now,
+                            rec.location_open_today,
+                            rec.location_cultivars,
+                            rec.farm_id.into(),
+                            rec.farm_name,
+                            rec.farm_logo,
+                        )
+                    })
+                    .collect();
+
+                Ok(locations)
+            }
+            Err(err) => {
+                tracing::error!("Database error, failed to fetch map locations: {}", err);
+                Err(err.into())
+            }
+        }
+    }
+}
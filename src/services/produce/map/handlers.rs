@@ -0,0 +1,85 @@
+//! Map view http handlers impls
+
+use axum::extract::State;
+use axum_extra::extract::Query;
+
+use crate::{
+    endpoint::EndpointResult, server::state::DatabaseConnection, services::produce::HarvestFilter,
+};
+
+use super::{
+    models::{MapHarvest, MapLocation},
+    Feature, FeatureCollection, MapQuery,
+};
+
+/// Handles the `GET /produce/geojson` route.
+///
+/// Active harvests matching the feed filters as a `GeoJSON` `FeatureCollection`.
+#[tracing::instrument(skip(db))]
+pub async fn harvest_geojson(
+    filters: Query<HarvestFilter>,
+    map: Query<MapQuery>,
+    State(db): State<DatabaseConnection>,
+) -> EndpointResult<FeatureCollection> {
+    let bbox = map.bounding_box()?;
+    let matcher = filters.matcher(db.clone()).await?;
+
+    let regions: Vec<_> = filters.regions().into_iter().flatten().collect();
+
+    // The bbox and regions are filtered in the database
+    let features = MapHarvest::records(bbox, &regions, crate::MAP_HARVESTS_MAX, db)
+        .await?
+        .into_iter()
+        .filter_map(|harvest| {
+            let coords = harvest.coords?;
+            matcher
+                .matches_any(
+                    std::slice::from_ref(&harvest.name),
+                    &harvest.region,
+                    harvest.farm_id,
+                    harvest.open_today,
+                )
+                .then(|| Feature::point(harvest.id, coords, &harvest))
+        })
+        .collect();
+
+    Ok(FeatureCollection {
+        features: map.cluster(features),
+    })
+}
+
+/// Handles the `GET /locations/geojson` route.
+///
+/// Active locations matching the feed filters as a `GeoJSON` `FeatureCollection`.
+#[tracing::instrument(skip(db))]
+pub async fn location_geojson(
+    filters: Query<HarvestFilter>,
+    map: Query<MapQuery>,
+    State(db): State<DatabaseConnection>,
+) -> EndpointResult<FeatureCollection> {
+    let bbox = map.bounding_box()?;
+    let matcher = filters.matcher(db.clone()).await?;
+
+    let regions: Vec<_> = filters.regions().into_iter().flatten().collect();
+
+    // The bbox and regions are filtered in the database
+    let features = MapLocation::records(bbox, &regions, db)
+        .await?
+        .into_iter()
+        .filter_map(|location| {
+            let coords = location.coords?;
+            matcher
+                .matches_any(
+                    &location.cultivars,
+                    &location.region,
+                    location.farm_id,
+                    location.open_today,
+                )
+                .then(|| Feature::point(location.id, coords, &location))
+        })
+        .collect();
+
+    Ok(FeatureCollection {
+        features: map.cluster(features),
+    })
+}
@@ -0,0 +1,235 @@
+//! Harvests and locations map view impls
+
+pub mod db;
+pub mod handlers;
+pub mod models;
+
+use std::{collections::BTreeMap, f64::consts::PI};
+
+use axum::{
+    http::{header, HeaderValue},
+    response::{IntoResponse, Response},
+    Json,
+};
+use geo::Point;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    endpoint::{EndpointRejection, EndpointResult},
+    services::farmers::location::region::boundary::validate_coords,
+    types::ModelID,
+};
+
+/// `GeoJSON` media type
+const GEOJSON_CONTENT_TYPE: &str = "application/geo+json";
+/// Size in pixels of a map tile
+const MAP_TILE_SIZE: f64 = 256.0;
+/// Web mercator maximum latitude
+const MERCATOR_MAX_LAT: f64 = 85.051_128_78;
+
+/// A `Vec` of map features
+pub type FeatureList = Vec<Feature>;
+
+/// A `GeoJSON` `FeatureCollection` returned by the map handlers.
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "type")]
+pub struct FeatureCollection {
+    pub features: FeatureList,
+}
+
+impl IntoResponse for FeatureCollection {
+    fn into_response(self) -> Response {
+        (
+            [(
+                header::CONTENT_TYPE,
+                HeaderValue::from_static(GEOJSON_CONTENT_TYPE),
+            )],
+            Json(self),
+        )
+            .into_response()
+    }
+}
+
+/// A `GeoJSON` `Feature` on the map, either a location,
+/// a harvest or a cluster of them.
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "type")]
+pub struct Feature {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<ModelID>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bbox: Option<[f64; 4]>,
+    pub geometry: Geometry,
+    pub properties: serde_json::Value,
+}
+
+impl Feature {
+    /// Creates a new point `Feature`, the `coords` are
+    /// removed from properties since they are in the geometry.
+    #[must_use]
+    pub fn point<P: Serialize>(id: ModelID, coords: Point, properties: &P) -> Self {
+        let mut properties = serde_json::to_value(properties).unwrap_or_default();
+        if let Some(properties) = properties.as_object_mut() {
+            properties.remove("coords");
+        }
+        Self {
+            id: Some(id),
+            bbox: None,
+            geometry: Geometry::Point {
+                coordinates: [coords.x(), coords.y()],
+            },
+            properties,
+        }
+    }
+
+    /// Creates a cluster `Feature` placed at the center of its members
+    #[allow(clippy::cast_precision_loss)]
+    #[must_use]
+    fn cluster(members: &[Self], zoom: u8) -> Self {
+        let count = members.len() as f64;
+        let mut bbox = [f64::MAX, f64::MAX, f64::MIN, f64::MIN];
+        let (mut lng, mut lat) = (0.0, 0.0);
+        for member in members {
+            let [x, y] = member.geometry.coords();
+            lng += x;
+            lat += y;
+            bbox = [
+                bbox[0].min(x),
+                bbox[1].min(y),
+                bbox[2].max(x),
+                bbox[3].max(y),
+            ];
+        }
+
+        Self {
+            id: None,
+            bbox: Some(bbox),
+            geometry: Geometry::Point {
+                coordinates: [lng / count, lat / count],
+            },
+            properties: serde_json::json!({
+                "cluster": true,
+                "pointCount": members.len(),
+                "expansionZoom": zoom + 1,
+            }),
+        }
+    }
+}
+
+/// A `GeoJSON` geometry object
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "type")]
+pub enum Geometry {
+    Point { coordinates: [f64; 2] },
+}
+
+impl Geometry {
+    /// The geometry longitude and latitude
+    #[must_use]
+    pub const fn coords(&self) -> [f64; 2] {
+        match self {
+            Self::Point { coordinates } => *coordinates,
+        }
+    }
+}
+
+/// Map view query parameters, used together with the feed filters.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MapQuery {
+    /// The visible area, `minLng,minLat,maxLng,maxLat`
+    #[serde(default)]
+    pub bbox: Option<String>,
+    /// The map zoom level, features are clustered below `MAP_CLUSTER_MAX_ZOOM`
+    #[serde(default)]
+    pub zoom: Option<u8>,
+}
+
+impl MapQuery {
+    /// Parsed visible area
+    ///
+    /// # Errors
+    ///
+    /// Return bad request if the bbox is invalid
+    pub fn bounding_box(&self) -> EndpointResult<Option<BoundingBox>> {
+        self.bbox.as_deref().map(BoundingBox::parse).transpose()
+    }
+
+    /// Group features close to each other at the requested zoom level,
+    /// features are not clustered if the zoom level is not provided.
+    #[must_use]
+    pub fn cluster(&self, features: FeatureList) -> FeatureList {
+        match self.zoom {
+            Some(zoom) if zoom < crate::MAP_CLUSTER_MAX_ZOOM => cluster_features(features, zoom),
+            _ => features,
+        }
+    }
+}
+
+/// A rectangle area on the map, may cross the antimeridian.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BoundingBox {
+    min: Point,
+    max: Point,
+}
+
+impl BoundingBox {
+    /// Parses a bbox in the form of `minLng,minLat,maxLng,maxLat`
+    ///
+    /// # Errors
+    ///
+    /// Return bad request if the bbox is invalid
+    pub fn parse(bbox: &str) -> EndpointResult<Self> {
+        let invalid = || {
+            EndpointRejection::BadRequest(
+                "bbox must be in the form of `minLng,minLat,maxLng,maxLat`.".into(),
+            )
+        };
+
+        let values = bbox
+            .split(',')
+            .map(|value| value.trim().parse::<f64>().map_err(|_| invalid()))
+            .collect::<EndpointResult<Vec<_>>>()?;
+        let [min_lng, min_lat, max_lng, max_lat] = values[..] else {
+            return Err(invalid());
+        };
+
+        let (min, max) = (Point::new(min_lng, min_lat), Point::new(max_lng, max_lat));
+        validate_coords(&min)?;
+        validate_coords(&max)?;
+        if min_lat > max_lat {
+            return Err(invalid());
+        }
+
+        Ok(Self { min, max })
+    }
+}
+
+/// Group features falling in the same grid cell at `zoom` level,
+/// the cell size is `MAP_CLUSTER_RADIUS` pixels.
+#[allow(clippy::cast_possible_truncation)]
+fn cluster_features(features: FeatureList, zoom: u8) -> FeatureList {
+    let cells = f64::from(1_u32 << zoom) * MAP_TILE_SIZE / crate::MAP_CLUSTER_RADIUS;
+
+    let mut grid: BTreeMap<(i64, i64), FeatureList> = BTreeMap::new();
+    for feature in features {
+        let [lng, lat] = feature.geometry.coords();
+        // Web mercator projection of the coordinates in the range 0..=1
+        let x = lng / 360.0 + 0.5;
+        let sin = lat
+            .clamp(-MERCATOR_MAX_LAT, MERCATOR_MAX_LAT)
+            .to_radians()
+            .sin();
+        let y = 0.5 - ((1.0 + sin) / (1.0 - sin)).ln() / (4.0 * PI);
+
+        let cell = ((x * cells).floor() as i64, (y * cells).floor() as i64);
+        grid.entry(cell).or_default().push(feature);
+    }
+
+    grid.into_values()
+        .filter_map(|mut members| match members.len() {
+            1 => members.pop(),
+            _ => Some(Feature::cluster(&members, zoom)),
+        })
+        .collect()
+}
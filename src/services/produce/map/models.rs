@@ -0,0 +1,131 @@
+//! Map view model impls
+
+use geo::Point;
+use serde::Serialize;
+use time::Date;
+
+use crate::{
+    services::farmers::location,
+    types::{price::Price, ModelID},
+};
+
+/// A `Vec` of map harvests
+pub type MapHarvestList = Vec<MapHarvest>;
+
+/// A `Vec` of map locations
+pub type MapLocationList = Vec<MapLocation>;
+
+/// An active harvest shown on the map, returned by `harvest_geojson` handler.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MapHarvest {
+    pub id: ModelID,
+    pub name: String,
+    pub category: String,
+    pub farm_id: ModelID,
+    pub farm_name: String,
+    pub farm_logo: Option<String>,
+    pub price: Price,
+    pub images: Option<Vec<String>>,
+    pub cultivar_image: Option<String>,
+    pub harvest_date: Date,
+    pub place_name: String,
+    pub region: Option<String>,
+    pub country: String,
+    pub coords: Option<Point>,
+    pub open_now: bool,
+    pub open_today: bool,
+}
+
+impl MapHarvest {
+    /// Creates a new `MapHarvest` from the database row
+    #[allow(clippy::too_many_arguments)]
+    #[must_use]
+    pub fn from_row(
+        id: ModelID,
+        price: serde_json::Value,
+        harvest_date: Date,
+        images: Option<Vec<String>>,
+        cultivar_name: String,
+        cultivar_category: String,
+        cultivar_image: Option<String>,
+        place_name: String,
+        region: Option<String>,
+        country: String,
+        coords: Option<serde_json::Value>,
+        open_now: Option<bool>,
+        open_today: Option<bool>,
+        farm_id: ModelID,
+        farm_name: String,
+        farm_logo: Option<String>,
+    ) -> Self {
+        Self {
+            id,
+            name: cultivar_name,
+            category: cultivar_category,
+            farm_id,
+            farm_name,
+            farm_logo,
+            price: Price::from_row(price),
+            images,
+            cultivar_image,
+            harvest_date,
+            place_name,
+            region,
+            country,
+            coords: location::try_into_point(coords),
+            open_now: open_now.unwrap_or_default(),
+            open_today: open_today.unwrap_or_default(),
+        }
+    }
+}
+
+/// An active location shown on the map, returned by `location_geojson` handler.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MapLocation {
+    pub id: ModelID,
+    pub place_name: String,
+    pub farm_id: ModelID,
+    pub farm_name: String,
+    pub farm_logo: Option<String>,
+    pub region: Option<String>,
+    pub country: String,
+    pub coords: Option<Point>,
+    pub open_now: bool,
+    pub open_today: bool,
+    pub cultivars: Vec<String>,
+}
+
+impl MapLocation {
+    /// Creates a new `MapLocation` from the database row
+    #[allow(clippy::too_many_arguments)]
+    #[must_use]
+    pub fn from_row(
+        id: ModelID,
+        place_name: String,
+        region: Option<String>,
+        country: String,
+        coords: Option<serde_json::Value>,
+        open_now: Option<bool>,
+        open_today: Option<bool>,
+        cultivars: Vec<String>,
+        farm_id: ModelID,
+        farm_name: String,
+        farm_logo: Option<String>,
+    ) -> Self {
+        Self {
+            id,
+            place_name,
+            farm_id,
+            farm_name,
+            farm_logo,
+            region,
+            country,
+            coords: location::try_into_point(coords),
+            open_now: open_now.unwrap_or_default(),
+            open_today: open_today.unwrap_or_default(),
+            cultivars,
+        }
+    }
+}
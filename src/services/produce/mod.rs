@@ -3,5 +3,6 @@
 pub mod cultivar;
 mod handler;
 pub mod harvest;
+pub mod map;
 
-pub use handler::harvest_feed;
+pub use handler::{harvest_feed, harvest_following_feed, HarvestFilter, HarvestMatcher};
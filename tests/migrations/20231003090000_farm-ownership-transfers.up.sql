@@ -0,0 +1,27 @@
+-- Add up migration script here
+
+-- Pending farm ownership transfers sent by email,
+-- a farm can only have one pending transfer.
+CREATE TABLE IF NOT EXISTS services.farm_ownership_transfers(
+    id uuid PRIMARY KEY,
+    farm_id uuid REFERENCES services.farms (id) ON DELETE CASCADE UNIQUE NOT NULL,
+    from_user_id uuid REFERENCES accounts.users (id) ON DELETE CASCADE NOT NULL,
+    to_email text NOT NULL,
+    token bytea UNIQUE NOT NULL, -- token hash
+    created_at timestamptz NOT NULL
+);
+
+-- Farm ownership transfers audit log, records every transfer
+-- started, cancelled, declined, expired and completed.
+CREATE TABLE IF NOT EXISTS services.farm_ownership_transfer_logs(
+    id uuid PRIMARY KEY,
+    farm_id uuid REFERENCES services.farms (id) ON DELETE CASCADE NOT NULL,
+    event text NOT NULL
+        CHECK (event IN ('started', 'cancelled', 'declined', 'expired', 'completed')),
+    from_user_id uuid REFERENCES accounts.users (id) ON DELETE SET NULL,
+    to_user_id uuid REFERENCES accounts.users (id) ON DELETE SET NULL,
+    to_email text NOT NULL,
+    initiated_at timestamptz NOT NULL,
+    transferred_at timestamptz, -- set when the transfer is completed
+    logged_at timestamptz NOT NULL
+);
@@ -0,0 +1,23 @@
+-- Add up migration script here
+
+-- Farm delivery options,
+-- `pickup`: buyers collect the harvest at the farm's locations.
+-- `radius`: delivery within `radius_km` of the location.
+-- `regions`: delivery into the listed regions.
+CREATE TABLE IF NOT EXISTS services.farm_delivery_options(
+    id uuid PRIMARY KEY,
+    farm_id uuid REFERENCES services.farms (id) ON DELETE CASCADE NOT NULL,
+    kind text NOT NULL CHECK (kind IN ('pickup', 'radius', 'regions')),
+    location_id uuid REFERENCES services.locations (id) ON DELETE CASCADE,
+    radius_km double precision CHECK (radius_km > 0),
+    region_ids uuid[] NOT NULL DEFAULT '{}',
+    fee_type text NOT NULL CHECK (fee_type IN ('flat', 'per_km')),
+    fee decimal NOT NULL CHECK (fee >= 0),
+    minimum_order decimal NOT NULL CHECK (minimum_order >= 0),
+    created_at timestamptz NOT NULL,
+    CHECK (kind <> 'radius' OR (location_id IS NOT NULL AND radius_km IS NOT NULL)),
+    CHECK (kind <> 'regions' OR cardinality(region_ids) > 0)
+);
+
+CREATE INDEX IF NOT EXISTS farm_delivery_options_farm_id_idx
+    ON services.farm_delivery_options (farm_id);
@@ -0,0 +1,40 @@
+-- Add up migration script here
+
+-- The time the farm, location or harvest was archived by a delete.
+-- Rows archived together by the same delete share the same time,
+-- they can be restored until the restore grace period passes,
+-- after which the column is cleared and the archive is final.
+ALTER TABLE services.farms
+    ADD COLUMN IF NOT EXISTS archived_at timestamptz;
+
+ALTER TABLE services.locations
+    ADD COLUMN IF NOT EXISTS archived_at timestamptz;
+
+ALTER TABLE services.harvests
+    ADD COLUMN IF NOT EXISTS archived_at timestamptz;
+
+CREATE INDEX IF NOT EXISTS farms_archived_at_idx
+    ON services.farms (archived_at) WHERE archived_at IS NOT NULL;
+CREATE INDEX IF NOT EXISTS locations_archived_at_idx
+    ON services.locations (archived_at) WHERE archived_at IS NOT NULL;
+CREATE INDEX IF NOT EXISTS harvests_archived_at_idx
+    ON services.harvests (archived_at) WHERE archived_at IS NOT NULL;
+
+CREATE OR REPLACE VIEW services.active_farms AS (
+	SELECT *
+	FROM services.farms farm
+	WHERE farm.deleted = false
+    	AND farm.owner_id IS NOT NULL
+);
+
+CREATE OR REPLACE VIEW services.active_locations AS (
+	SELECT *
+	FROM services.locations location_
+	WHERE location_.deleted = false
+);
+
+CREATE OR REPLACE VIEW services.active_harvests AS (
+	SELECT *
+	FROM services.harvests harvest
+	WHERE harvest.finished = false
+);
@@ -0,0 +1,42 @@
+-- Add down migration script here
+
+CREATE OR REPLACE FUNCTION services.farm_ratings_stats_trigger()
+RETURNS trigger AS $$
+BEGIN
+    IF TG_OP IN ('UPDATE', 'DELETE') THEN
+        PERFORM services.refresh_farm_rating_stats(OLD.farm_id);
+    END IF;
+    IF TG_OP IN ('INSERT', 'UPDATE') THEN
+        PERFORM services.refresh_farm_rating_stats(NEW.farm_id);
+    END IF;
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+DROP FUNCTION IF EXISTS services.farm_rating_helpfulness(uuid);
+DROP FUNCTION IF EXISTS services.adjust_farm_rating_stats(uuid, integer, bigint);
+
+CREATE OR REPLACE FUNCTION services.refresh_farm_rating_stats(target_farm_id uuid)
+RETURNS void AS $$
+    UPDATE services.farm_stats stat
+    SET rating_average = rating_stat.average,
+        rating_count = rating_stat.count,
+        updated_at = now()
+    FROM (
+        SELECT AVG(rating.grade)::double precision AS average,
+            COUNT(rating.id) AS count
+        FROM services.farm_ratings rating
+        WHERE rating.farm_id = target_farm_id
+            AND rating.grade IS NOT NULL
+    ) rating_stat
+    WHERE stat.farm_id = target_farm_id;
+$$ LANGUAGE sql;
+
+DROP INDEX IF EXISTS services.farm_ratings_farm_id_idx;
+
+ALTER TABLE services.farm_stats
+    DROP COLUMN IF EXISTS grade_5_count,
+    DROP COLUMN IF EXISTS grade_4_count,
+    DROP COLUMN IF EXISTS grade_3_count,
+    DROP COLUMN IF EXISTS grade_2_count,
+    DROP COLUMN IF EXISTS grade_1_count;
@@ -0,0 +1,96 @@
+-- Add up migration script here
+
+-- Farm ratings grade histogram
+ALTER TABLE services.farm_stats
+    ADD COLUMN IF NOT EXISTS grade_1_count bigint NOT NULL DEFAULT 0,
+    ADD COLUMN IF NOT EXISTS grade_2_count bigint NOT NULL DEFAULT 0,
+    ADD COLUMN IF NOT EXISTS grade_3_count bigint NOT NULL DEFAULT 0,
+    ADD COLUMN IF NOT EXISTS grade_4_count bigint NOT NULL DEFAULT 0,
+    ADD COLUMN IF NOT EXISTS grade_5_count bigint NOT NULL DEFAULT 0;
+
+CREATE INDEX IF NOT EXISTS farm_ratings_farm_id_idx
+    ON services.farm_ratings (farm_id, created_at) WHERE reply_to IS NULL;
+
+-- ===== FUNCTIONS =====
+
+-- Recompute the farm rating average, count and histogram
+CREATE OR REPLACE FUNCTION services.refresh_farm_rating_stats(target_farm_id uuid)
+RETURNS void AS $$
+    UPDATE services.farm_stats stat
+    SET rating_average = rating_stat.average,
+        rating_count = rating_stat.count,
+        grade_1_count = rating_stat.grade_1_count,
+        grade_2_count = rating_stat.grade_2_count,
+        grade_3_count = rating_stat.grade_3_count,
+        grade_4_count = rating_stat.grade_4_count,
+        grade_5_count = rating_stat.grade_5_count,
+        updated_at = now()
+    FROM (
+        SELECT AVG(rating.grade)::double precision AS average,
+            COUNT(rating.id) AS count,
+            COUNT(rating.id) FILTER (WHERE rating.grade = 1) AS grade_1_count,
+            COUNT(rating.id) FILTER (WHERE rating.grade = 2) AS grade_2_count,
+            COUNT(rating.id) FILTER (WHERE rating.grade = 3) AS grade_3_count,
+            COUNT(rating.id) FILTER (WHERE rating.grade = 4) AS grade_4_count,
+            COUNT(rating.id) FILTER (WHERE rating.grade = 5) AS grade_5_count
+        FROM services.farm_ratings rating
+        WHERE rating.farm_id = target_farm_id
+            AND rating.grade IS NOT NULL
+    ) rating_stat
+    WHERE stat.farm_id = target_farm_id;
+$$ LANGUAGE sql;
+
+-- Add `delta` ratings of `target_grade` to the farm rating stats,
+-- the average is derived from the histogram so no rescan is needed.
+CREATE OR REPLACE FUNCTION services.adjust_farm_rating_stats(
+    target_farm_id uuid,
+    target_grade integer,
+    delta bigint
+)
+RETURNS void AS $$
+    UPDATE services.farm_stats stat
+    SET grade_1_count = stat.grade_1_count + CASE WHEN target_grade = 1 THEN delta ELSE 0 END,
+        grade_2_count = stat.grade_2_count + CASE WHEN target_grade = 2 THEN delta ELSE 0 END,
+        grade_3_count = stat.grade_3_count + CASE WHEN target_grade = 3 THEN delta ELSE 0 END,
+        grade_4_count = stat.grade_4_count + CASE WHEN target_grade = 4 THEN delta ELSE 0 END,
+        grade_5_count = stat.grade_5_count + CASE WHEN target_grade = 5 THEN delta ELSE 0 END,
+        rating_count = stat.rating_count + delta,
+        rating_average = CASE
+            WHEN stat.rating_count + delta > 0 THEN (
+                stat.grade_1_count
+                + 2 * stat.grade_2_count
+                + 3 * stat.grade_3_count
+                + 4 * stat.grade_4_count
+                + 5 * stat.grade_5_count
+                + target_grade * delta
+            )::double precision / (stat.rating_count + delta)
+            ELSE NULL
+        END,
+        updated_at = now()
+    WHERE stat.farm_id = target_farm_id;
+$$ LANGUAGE sql;
+
+-- How helpful the rating is to other users, used to rank the ratings
+CREATE OR REPLACE FUNCTION services.farm_rating_helpfulness(target_rating_id uuid)
+RETURNS bigint AS $$
+    SELECT COUNT(reply.id)
+    FROM services.farm_ratings reply
+    WHERE reply.reply_to = target_rating_id;
+$$ LANGUAGE sql STABLE;
+
+CREATE OR REPLACE FUNCTION services.farm_ratings_stats_trigger()
+RETURNS trigger AS $$
+BEGIN
+    IF TG_OP IN ('UPDATE', 'DELETE') AND OLD.grade IS NOT NULL THEN
+        PERFORM services.adjust_farm_rating_stats(OLD.farm_id, OLD.grade, -1);
+    END IF;
+    IF TG_OP IN ('INSERT', 'UPDATE') AND NEW.grade IS NOT NULL THEN
+        PERFORM services.adjust_farm_rating_stats(NEW.farm_id, NEW.grade, 1);
+    END IF;
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+-- Backfill existing farms stats
+SELECT services.refresh_farm_rating_stats(farm.id)
+FROM services.farms farm;
@@ -0,0 +1,65 @@
+-- Add down migration script here
+
+DROP TRIGGER IF EXISTS farm_rating_votes_count ON services.farm_rating_votes;
+DROP FUNCTION IF EXISTS services.farm_rating_votes_trigger();
+
+CREATE OR REPLACE FUNCTION services.farm_rating_helpfulness(target_rating_id uuid)
+RETURNS bigint AS $$
+    SELECT COUNT(reply.id)
+    FROM services.farm_ratings reply
+    WHERE reply.reply_to = target_rating_id;
+$$ LANGUAGE sql STABLE;
+
+DROP TRIGGER IF EXISTS farm_ratings_stats ON services.farm_ratings;
+CREATE TRIGGER farm_ratings_stats
+AFTER INSERT OR UPDATE OF grade, farm_id OR DELETE ON services.farm_ratings
+FOR EACH ROW EXECUTE FUNCTION services.farm_ratings_stats_trigger();
+
+CREATE OR REPLACE FUNCTION services.farm_ratings_stats_trigger()
+RETURNS trigger AS $$
+BEGIN
+    IF TG_OP IN ('UPDATE', 'DELETE') AND OLD.grade IS NOT NULL THEN
+        PERFORM services.adjust_farm_rating_stats(OLD.farm_id, OLD.grade, -1);
+    END IF;
+    IF TG_OP IN ('INSERT', 'UPDATE') AND NEW.grade IS NOT NULL THEN
+        PERFORM services.adjust_farm_rating_stats(NEW.farm_id, NEW.grade, 1);
+    END IF;
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE OR REPLACE FUNCTION services.refresh_farm_rating_stats(target_farm_id uuid)
+RETURNS void AS $$
+    UPDATE services.farm_stats stat
+    SET rating_average = rating_stat.average,
+        rating_count = rating_stat.count,
+        grade_1_count = rating_stat.grade_1_count,
+        grade_2_count = rating_stat.grade_2_count,
+        grade_3_count = rating_stat.grade_3_count,
+        grade_4_count = rating_stat.grade_4_count,
+        grade_5_count = rating_stat.grade_5_count,
+        updated_at = now()
+    FROM (
+        SELECT AVG(rating.grade)::double precision AS average,
+            COUNT(rating.id) AS count,
+            COUNT(rating.id) FILTER (WHERE rating.grade = 1) AS grade_1_count,
+            COUNT(rating.id) FILTER (WHERE rating.grade = 2) AS grade_2_count,
+            COUNT(rating.id) FILTER (WHERE rating.grade = 3) AS grade_3_count,
+            COUNT(rating.id) FILTER (WHERE rating.grade = 4) AS grade_4_count,
+            COUNT(rating.id) FILTER (WHERE rating.grade = 5) AS grade_5_count
+        FROM services.farm_ratings rating
+        WHERE rating.farm_id = target_farm_id
+            AND rating.grade IS NOT NULL
+    ) rating_stat
+    WHERE stat.farm_id = target_farm_id;
+$$ LANGUAGE sql;
+
+DROP INDEX IF EXISTS services.farm_ratings_hidden_at_idx;
+
+ALTER TABLE services.farm_ratings
+    DROP COLUMN IF EXISTS helpful_count,
+    DROP COLUMN IF EXISTS hidden_at,
+    DROP COLUMN IF EXISTS reviewed_at;
+
+DROP TABLE IF EXISTS services.farm_rating_reports;
+DROP TABLE IF EXISTS services.farm_rating_votes;
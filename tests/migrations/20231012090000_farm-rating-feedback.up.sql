@@ -0,0 +1,103 @@
+-- Add up migration script here
+
+-- Users who found the farm rating helpful
+CREATE TABLE IF NOT EXISTS services.farm_rating_votes(
+    rating_id uuid REFERENCES services.farm_ratings (id) ON DELETE CASCADE NOT NULL,
+    user_id uuid REFERENCES accounts.users (id) ON DELETE CASCADE NOT NULL,
+    created_at timestamptz NOT NULL,
+    PRIMARY KEY(rating_id, user_id)
+);
+
+-- Users reports of abusive farm ratings
+CREATE TABLE IF NOT EXISTS services.farm_rating_reports(
+    id uuid PRIMARY KEY,
+    rating_id uuid REFERENCES services.farm_ratings (id) ON DELETE CASCADE NOT NULL,
+    user_id uuid REFERENCES accounts.users (id) ON DELETE CASCADE NOT NULL,
+    reason text NOT NULL,
+    created_at timestamptz NOT NULL,
+    UNIQUE(rating_id, user_id)
+);
+
+CREATE INDEX IF NOT EXISTS farm_rating_reports_rating_id_idx
+    ON services.farm_rating_reports (rating_id, created_at);
+
+-- hidden_at is set when the rating was reported too many times and waits for staff review,
+-- reviewed_at is the last time staff reviewed the rating reports.
+ALTER TABLE services.farm_ratings
+    ADD COLUMN IF NOT EXISTS helpful_count bigint NOT NULL DEFAULT 0,
+    ADD COLUMN IF NOT EXISTS hidden_at timestamptz,
+    ADD COLUMN IF NOT EXISTS reviewed_at timestamptz;
+
+CREATE INDEX IF NOT EXISTS farm_ratings_hidden_at_idx
+    ON services.farm_ratings (hidden_at) WHERE hidden_at IS NOT NULL;
+
+-- ===== FUNCTIONS =====
+
+-- Hidden ratings are not counted in the farm rating stats
+CREATE OR REPLACE FUNCTION services.refresh_farm_rating_stats(target_farm_id uuid)
+RETURNS void AS $$
+    UPDATE services.farm_stats stat
+    SET rating_average = rating_stat.average,
+        rating_count = rating_stat.count,
+        grade_1_count = rating_stat.grade_1_count,
+        grade_2_count = rating_stat.grade_2_count,
+        grade_3_count = rating_stat.grade_3_count,
+        grade_4_count = rating_stat.grade_4_count,
+        grade_5_count = rating_stat.grade_5_count,
+        updated_at = now()
+    FROM (
+        SELECT AVG(rating.grade)::double precision AS average,
+            COUNT(rating.id) AS count,
+            COUNT(rating.id) FILTER (WHERE rating.grade = 1) AS grade_1_count,
+            COUNT(rating.id) FILTER (WHERE rating.grade = 2) AS grade_2_count,
+            COUNT(rating.id) FILTER (WHERE rating.grade = 3) AS grade_3_count,
+            COUNT(rating.id) FILTER (WHERE rating.grade = 4) AS grade_4_count,
+            COUNT(rating.id) FILTER (WHERE rating.grade = 5) AS grade_5_count
+        FROM services.farm_ratings rating
+        WHERE rating.farm_id = target_farm_id
+            AND rating.grade IS NOT NULL
+            AND rating.hidden_at IS NULL
+    ) rating_stat
+    WHERE stat.farm_id = target_farm_id;
+$$ LANGUAGE sql;
+
+CREATE OR REPLACE FUNCTION services.farm_ratings_stats_trigger()
+RETURNS trigger AS $$
+BEGIN
+    IF TG_OP IN ('UPDATE', 'DELETE') AND OLD.grade IS NOT NULL AND OLD.hidden_at IS NULL THEN
+        PERFORM services.adjust_farm_rating_stats(OLD.farm_id, OLD.grade, -1);
+    END IF;
+    IF TG_OP IN ('INSERT', 'UPDATE') AND NEW.grade IS NOT NULL AND NEW.hidden_at IS NULL THEN
+        PERFORM services.adjust_farm_rating_stats(NEW.farm_id, NEW.grade, 1);
+    END IF;
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+DROP TRIGGER IF EXISTS farm_ratings_stats ON services.farm_ratings;
+CREATE TRIGGER farm_ratings_stats
+AFTER INSERT OR UPDATE OF grade, farm_id, hidden_at OR DELETE ON services.farm_ratings
+FOR EACH ROW EXECUTE FUNCTION services.farm_ratings_stats_trigger();
+
+-- Ratings are ranked by their helpful votes
+DROP FUNCTION IF EXISTS services.farm_rating_helpfulness(uuid);
+
+CREATE OR REPLACE FUNCTION services.farm_rating_votes_trigger()
+RETURNS trigger AS $$
+BEGIN
+    IF TG_OP = 'INSERT' THEN
+        UPDATE services.farm_ratings rating
+        SET helpful_count = rating.helpful_count + 1
+        WHERE rating.id = NEW.rating_id;
+    ELSE
+        UPDATE services.farm_ratings rating
+        SET helpful_count = rating.helpful_count - 1
+        WHERE rating.id = OLD.rating_id;
+    END IF;
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER farm_rating_votes_count
+AFTER INSERT OR DELETE ON services.farm_rating_votes
+FOR EACH ROW EXECUTE FUNCTION services.farm_rating_votes_trigger();
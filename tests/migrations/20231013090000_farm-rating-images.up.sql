@@ -0,0 +1,9 @@
+-- Add up migration script here
+
+-- Photos attached by the author to back the rating,
+-- replies cannot have photos.
+ALTER TABLE services.farm_ratings
+    ADD COLUMN IF NOT EXISTS images text[];
+
+ALTER TABLE services.farm_ratings
+    ADD CONSTRAINT farm_ratings_reply_images_check CHECK (reply_to IS NULL OR images IS NULL);
@@ -0,0 +1,10 @@
+-- Add down migration script here
+
+DROP INDEX IF EXISTS services.regions_bounding_box_idx;
+
+ALTER TABLE services.regions
+    DROP COLUMN IF EXISTS boundary,
+    DROP COLUMN IF EXISTS min_lng,
+    DROP COLUMN IF EXISTS min_lat,
+    DROP COLUMN IF EXISTS max_lng,
+    DROP COLUMN IF EXISTS max_lat;
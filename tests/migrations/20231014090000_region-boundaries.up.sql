@@ -0,0 +1,14 @@
+-- Add up migration script here
+
+-- Region boundary as a GeoJSON MultiPolygon loaded by staff,
+-- the bounding box is used to find the regions a point may fall in.
+ALTER TABLE services.regions
+    ADD COLUMN IF NOT EXISTS boundary jsonb,
+    ADD COLUMN IF NOT EXISTS min_lng double precision,
+    ADD COLUMN IF NOT EXISTS min_lat double precision,
+    ADD COLUMN IF NOT EXISTS max_lng double precision,
+    ADD COLUMN IF NOT EXISTS max_lat double precision;
+
+CREATE INDEX IF NOT EXISTS regions_bounding_box_idx
+    ON services.regions (min_lng, max_lng, min_lat, max_lat)
+    WHERE boundary IS NOT NULL;
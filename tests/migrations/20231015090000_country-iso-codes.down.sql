@@ -0,0 +1,11 @@
+-- Add down migration script here
+
+ALTER TABLE services.regions
+    DROP COLUMN IF EXISTS code,
+    DROP COLUMN IF EXISTS names;
+
+ALTER TABLE services.countries
+    DROP COLUMN IF EXISTS code,
+    DROP COLUMN IF EXISTS currency,
+    DROP COLUMN IF EXISTS timezone,
+    DROP COLUMN IF EXISTS names;
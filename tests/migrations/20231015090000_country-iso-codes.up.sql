@@ -0,0 +1,46 @@
+-- Add up migration script here
+
+-- ISO 3166-1 alpha-2 country codes with the default currency (ISO 4217)
+-- and timezone (IANA), and ISO 3166-2 region codes.
+-- `names` holds the localized names keyed by locale, e.g. {"de": "Namibia"}.
+ALTER TABLE services.countries
+    ADD COLUMN IF NOT EXISTS code text UNIQUE
+        CONSTRAINT countries_code_check CHECK (code ~ '^[A-Z]{2}$'),
+    ADD COLUMN IF NOT EXISTS currency text
+        CONSTRAINT countries_currency_check CHECK (currency ~ '^[A-Z]{3}$'),
+    ADD COLUMN IF NOT EXISTS timezone text,
+    ADD COLUMN IF NOT EXISTS names jsonb NOT NULL DEFAULT '{}'::jsonb;
+
+ALTER TABLE services.regions
+    ADD COLUMN IF NOT EXISTS code text UNIQUE
+        CONSTRAINT regions_code_check CHECK (code ~ '^[A-Z]{2}-[A-Z0-9]{1,3}$'),
+    ADD COLUMN IF NOT EXISTS names jsonb NOT NULL DEFAULT '{}'::jsonb;
+
+
+-- Namibia and its regions
+
+UPDATE services.countries country
+SET code = 'NA',
+    currency = 'NAD',
+    timezone = 'Africa/Windhoek'
+WHERE country.id = '0189c073-51d1-77e9-ae60-f506ede3e22e';
+
+UPDATE services.regions region
+SET code = iso.code
+FROM (
+    VALUES('0189c073-51d0-7f61-8fff-e14088710e6f'::uuid, 'NA-KU'),
+        ('0189c073-51d0-7570-895a-7102c6536b63'::uuid, 'NA-OS'),
+        ('0189c073-51d1-714c-aed1-2d6ad763b1ec'::uuid, 'NA-ON'),
+        ('0189c073-51d1-7b03-8104-e76550250c0a'::uuid, 'NA-OW'),
+        ('0189c073-51d1-76d4-bfdc-e6b23a1812f3'::uuid, 'NA-OT'),
+        ('0189c073-51d1-7663-9ab9-76f9059555f1'::uuid, 'NA-KE'),
+        ('0189c073-51d1-706d-953e-3f2fa546468f'::uuid, 'NA-CA'),
+        ('0189c073-51d2-71e3-9103-20dd6f042ced'::uuid, 'NA-ER'),
+        ('0189c073-51d2-7a4e-8d92-3a5892a42470'::uuid, 'NA-OD'),
+        ('0189c073-51d2-782c-b478-8162580db4c7'::uuid, 'NA-OH'),
+        ('0189c073-51d2-7be2-adbe-9c0b04e4e3fb'::uuid, 'NA-KH'),
+        ('0189c073-51d2-7379-960e-46f99509f124'::uuid, 'NA-HA'),
+        ('0189c073-51d2-7d7b-a9e0-8bcb138908d7'::uuid, 'NA-KA'),
+        ('0189c073-51d2-7bbd-bcd4-38b9201f44a6'::uuid, 'NA-KW')
+) AS iso(id, code)
+WHERE region.id = iso.id;
@@ -0,0 +1,5 @@
+-- Add down migration script here
+
+ALTER TABLE accounts.phones
+    DROP COLUMN IF EXISTS token_attempts,
+    ALTER COLUMN token TYPE text USING NULL;
@@ -0,0 +1,7 @@
+-- Add up migration script here
+
+-- Phone verification codes are stored hashed
+-- and can only be guessed a limited number of times.
+ALTER TABLE accounts.phones
+    ALTER COLUMN token TYPE bytea USING NULL,
+    ADD COLUMN token_attempts smallint NOT NULL DEFAULT 0;
@@ -0,0 +1,12 @@
+-- Add up migration script here
+
+-- Stores password reset code, sent to the user verified phone
+-- on password forgot. Codes are short so they are tied to the user
+-- and can only be guessed a limited number of times.
+CREATE TABLE IF NOT EXISTS auth.password_reset_codes(
+    user_id uuid REFERENCES accounts.users (id) ON DELETE CASCADE,
+    code bytea NOT NULL, -- code hash
+    code_generated_at timestamptz NOT NULL,
+    code_attempts smallint NOT NULL DEFAULT 0,
+    PRIMARY KEY(user_id)
+);
@@ -0,0 +1,22 @@
+-- Add down migration script here
+
+CREATE OR REPLACE VIEW services.active_harvests AS (
+	SELECT *
+	FROM services.harvests harvest
+	WHERE harvest.finished = false
+);
+
+CREATE OR REPLACE VIEW services.active_locations AS (
+	SELECT *
+	FROM services.locations location_
+	WHERE location_.deleted = false
+);
+
+CREATE OR REPLACE VIEW services.active_farms AS (
+	SELECT *
+	FROM services.farms farm
+	WHERE farm.deleted = false
+    	AND farm.owner_id IS NOT NULL
+);
+
+ALTER TABLE accounts.account_delete_requests DROP COLUMN IF EXISTS token;
@@ -0,0 +1,43 @@
+-- Add up migration script here
+
+-- Token of the link sent to the user to cancel the account deletion
+ALTER TABLE accounts.account_delete_requests
+    ADD COLUMN IF NOT EXISTS token bytea UNIQUE;
+
+-- Hide the farms, locations and harvests of deactivated accounts
+CREATE OR REPLACE VIEW services.active_farms AS (
+	SELECT *
+	FROM services.farms farm
+	WHERE farm.deleted = false
+    	AND farm.owner_id IS NOT NULL
+		AND NOT EXISTS(
+			SELECT 1 FROM accounts.account_delete_requests delete_request
+			WHERE delete_request.user_id = farm.owner_id
+		)
+);
+
+CREATE OR REPLACE VIEW services.active_locations AS (
+	SELECT *
+	FROM services.locations location_
+	WHERE location_.deleted = false
+		AND NOT EXISTS(
+			SELECT 1 FROM services.farms farm
+			INNER JOIN accounts.account_delete_requests delete_request
+				ON farm.owner_id = delete_request.user_id
+			WHERE farm.id = location_.farm_id
+		)
+);
+
+CREATE OR REPLACE VIEW services.active_harvests AS (
+	SELECT *
+	FROM services.harvests harvest
+	WHERE harvest.finished = false
+		AND NOT EXISTS(
+			SELECT 1 FROM services.locations location_
+			INNER JOIN services.farms farm
+				ON location_.farm_id = farm.id
+			INNER JOIN accounts.account_delete_requests delete_request
+				ON farm.owner_id = delete_request.user_id
+			WHERE location_.id = harvest.location_id
+		)
+);
@@ -0,0 +1,25 @@
+-- Add up migration script here
+
+-- Identity documents and selfies submitted by users for staff review,
+-- the files are encrypted on the file system and deleted after the review.
+CREATE TABLE IF NOT EXISTS accounts.identity_verifications(
+    id uuid PRIMARY KEY,
+    user_id uuid NOT NULL REFERENCES accounts.users (id) ON DELETE CASCADE,
+    document text,
+    document_type text,
+    selfie text,
+    selfie_type text,
+    status text NOT NULL DEFAULT 'pending' CHECK (status IN ('pending', 'approved', 'rejected')),
+    reject_reason text,
+    submitted_at timestamptz NOT NULL,
+    reviewed_at timestamptz,
+    reviewer_id uuid REFERENCES accounts.users (id) ON DELETE SET NULL
+);
+
+-- A user can only have one submission waiting for review
+CREATE UNIQUE INDEX IF NOT EXISTS identity_verifications_pending_idx
+    ON accounts.identity_verifications (user_id)
+    WHERE status = 'pending';
+
+CREATE INDEX IF NOT EXISTS identity_verifications_user_id_idx
+    ON accounts.identity_verifications (user_id, submitted_at);
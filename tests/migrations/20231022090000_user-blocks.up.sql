@@ -0,0 +1,14 @@
+-- Add up migration script here
+
+-- Users blocked by a user from messaging them, rating their farm
+-- and viewing their profile.
+CREATE TABLE IF NOT EXISTS accounts.user_blocks(
+    user_id uuid NOT NULL REFERENCES accounts.users (id) ON DELETE CASCADE,
+    blocked_id uuid NOT NULL REFERENCES accounts.users (id) ON DELETE CASCADE,
+    blocked_at timestamptz NOT NULL,
+    PRIMARY KEY (user_id, blocked_id),
+    CHECK (user_id <> blocked_id)
+);
+
+CREATE INDEX IF NOT EXISTS user_blocks_blocked_id_idx
+    ON accounts.user_blocks (blocked_id);
@@ -0,0 +1,41 @@
+-- Add up migration script here
+
+-- Public user handles and farm slugs used in shareable links,
+-- unique regardless of case.
+ALTER TABLE accounts.users
+    ADD COLUMN IF NOT EXISTS handle text;
+
+CREATE UNIQUE INDEX IF NOT EXISTS users_handle_key
+    ON accounts.users (lower(handle));
+
+ALTER TABLE services.farms
+    ADD COLUMN IF NOT EXISTS slug text;
+
+CREATE UNIQUE INDEX IF NOT EXISTS farms_slug_key
+    ON services.farms (lower(slug));
+
+-- Previous handles and slugs redirecting to their current owner,
+-- stored in lowercase and released when claimed again.
+CREATE TABLE IF NOT EXISTS accounts.user_handle_history(
+    handle text PRIMARY KEY,
+    user_id uuid NOT NULL REFERENCES accounts.users (id) ON DELETE CASCADE,
+    changed_at timestamptz NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS services.farm_slug_history(
+    slug text PRIMARY KEY,
+    farm_id uuid NOT NULL REFERENCES services.farms (id) ON DELETE CASCADE,
+    changed_at timestamptz NOT NULL
+);
+
+-- Expose the new column in the view
+CREATE OR REPLACE VIEW services.active_farms AS (
+	SELECT *
+	FROM services.farms farm
+	WHERE farm.deleted = false
+    	AND farm.owner_id IS NOT NULL
+		AND NOT EXISTS(
+			SELECT 1 FROM accounts.account_delete_requests delete_request
+			WHERE delete_request.user_id = farm.owner_id
+		)
+);